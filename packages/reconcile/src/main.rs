@@ -0,0 +1,125 @@
+//! Walks all `Balance` accounts of a token on Bentobox and checks that the sum
+//! of their shares equals `Total.amount.base`, plus the vault-side invariant
+//! `Total.amount.elastic == vault amount + strategy balance`.
+//!
+//! Usage: bentobox-reconcile <rpc_url> <bentobox_account> <mint>
+
+use std::{rc::Rc, str::FromStr};
+
+use anchor_client::{
+    solana_client::rpc_filter::{Memcmp, RpcFilterType},
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Keypair},
+    Client, Cluster,
+};
+use anchor_spl::token::TokenAccount;
+use anyhow::{anyhow, Result};
+
+use bentobox::state::{
+    Balance, StrategyData, Total, BALANCE_SEED_PART, STRATEGY_DATA_SEED_PART,
+    TOTAL_KEY_SEED_PART,
+};
+
+/// Offset of `Balance.mint`: discriminator + amount.
+const BALANCE_MINT_OFFSET: usize = 8 + 8;
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 4 {
+        return Err(anyhow!(
+            "usage: bentobox-reconcile <rpc_url> <bentobox_account> <mint>"
+        ));
+    }
+
+    let cluster = Cluster::from_str(&args[1])?;
+    let bentobox_account = Pubkey::from_str(&args[2])?;
+    let mint = Pubkey::from_str(&args[3])?;
+
+    // read-only, payer is never used to sign
+    let client = Client::new_with_options(
+        cluster,
+        Rc::new(Keypair::new()),
+        CommitmentConfig::confirmed(),
+    );
+    let program = client.program(bentobox::ID)?;
+
+    let (total_key, _) = Pubkey::find_program_address(
+        &[TOTAL_KEY_SEED_PART, bentobox_account.as_ref(), mint.as_ref()],
+        &bentobox::ID,
+    );
+    let (strategy_data_key, _) = Pubkey::find_program_address(
+        &[STRATEGY_DATA_SEED_PART, bentobox_account.as_ref(), mint.as_ref()],
+        &bentobox::ID,
+    );
+
+    let total: Total = program.account(total_key)?;
+    let total_base = total.amount.base;
+    let total_elastic = total.amount.elastic;
+    let total_token_account = total.token_account;
+
+    let vault: TokenAccount = program.account(total_token_account)?;
+    let strategy_balance = match program.account::<StrategyData>(strategy_data_key) {
+        Ok(strategy_data) => strategy_data.balance,
+        Err(_) => 0,
+    };
+
+    let balances = program.accounts::<Balance>(vec![RpcFilterType::Memcmp(
+        Memcmp::new_base58_encoded(BALANCE_MINT_OFFSET, mint.as_ref()),
+    )])?;
+
+    let mut shares_sum: u128 = 0;
+    let mut balances_count: usize = 0;
+    for (key, balance) in balances.iter() {
+        // skip balances which belong to another Bentobox
+        let (expected_key, _) = Pubkey::find_program_address(
+            &[
+                BALANCE_SEED_PART,
+                bentobox_account.as_ref(),
+                mint.as_ref(),
+                balance.owner.as_ref(),
+            ],
+            &bentobox::ID,
+        );
+        if expected_key != *key {
+            continue;
+        }
+        shares_sum += u128::from(balance.amount);
+        balances_count += 1;
+    }
+
+    let token_balance = u128::from(vault.amount) + u128::from(strategy_balance);
+
+    println!("token:              {}", mint);
+    println!("balance accounts:   {}", balances_count);
+    println!("sum of shares:      {}", shares_sum);
+    println!("total base:         {}", total_base);
+    println!("vault amount:       {}", vault.amount);
+    println!("strategy balance:   {}", strategy_balance);
+    println!("total elastic:      {}", total_elastic);
+
+    let mut ok = true;
+
+    if shares_sum != total_base {
+        ok = false;
+        println!(
+            "share-sum invariant violated: sum of shares {} != total base {}",
+            shares_sum, total_base
+        );
+    }
+
+    if token_balance < total_elastic {
+        ok = false;
+        println!(
+            "vault invariant violated: shortfall of {}",
+            total_elastic - token_balance
+        );
+    } else if token_balance > total_elastic {
+        println!("skimmable amount:   {}", token_balance - total_elastic);
+    }
+
+    if !ok {
+        std::process::exit(1);
+    }
+
+    println!("invariants hold");
+    Ok(())
+}
@@ -0,0 +1,262 @@
+//! Client side of the strategy interface. Lets any program invoke a strategy program
+//! which implements `strategy_interface` without depending on the strategy crate.
+//! Instructions are dispatched by anchor discriminator, accounts keep the order
+//! of `strategy_interface` structs. Values returned by strategy are read from return data.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::get_return_data, pubkey::Pubkey},
+    Result,
+};
+use common::utils::invoke_signed_instruction;
+
+use crate::errors::ErrorCode;
+use crate::state::ResultAmount;
+
+pub mod accounts {
+    use super::*;
+
+    #[derive(Accounts)]
+    pub struct Skim<'info> {
+        /// CHECK: checked by strategy program.
+        pub strategy_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_vault: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_authority: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub base_strategy_info: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub token_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub authority: AccountInfo<'info>,
+    }
+
+    #[derive(Accounts)]
+    pub struct Harvest<'info> {
+        /// CHECK: checked by strategy program.
+        pub strategy_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub authority: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_vault: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_authority: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub token_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub base_strategy_info: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub cpi_result_account: AccountInfo<'info>,
+    }
+
+    #[derive(Accounts)]
+    pub struct Withdraw<'info> {
+        /// CHECK: checked by strategy program.
+        pub strategy_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_vault: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_authority: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub token_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub authority: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub base_strategy_info: AccountInfo<'info>,
+    }
+
+    #[derive(Accounts)]
+    pub struct Exit<'info> {
+        /// CHECK: checked by strategy program.
+        pub strategy_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_vault: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_authority: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub token_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub authority: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub base_strategy_info: AccountInfo<'info>,
+    }
+
+    #[derive(Accounts)]
+    pub struct HarvestRewards<'info> {
+        /// CHECK: checked by strategy program.
+        pub strategy_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub base_strategy_info: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_vault: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub authority: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_authority: AccountInfo<'info>,
+    }
+
+    #[derive(Accounts)]
+    pub struct SafeHarvest<'info> {
+        /// CHECK: checked by strategy program.
+        pub base_strategy_info: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_vault: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub authority: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_authority: AccountInfo<'info>,
+    }
+
+    #[derive(Accounts)]
+    pub struct Transfer<'info> {
+        /// CHECK: checked by strategy program.
+        pub strategy_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_program: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_account: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub authority: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub bentobox_vault: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_vault: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub strategy_authority: AccountInfo<'info>,
+        /// CHECK: checked by strategy program.
+        pub token_program: AccountInfo<'info>,
+    }
+}
+
+/// Reads value returned by strategy program through return data.
+///
+/// Arguments:
+///
+/// * `program_id` - Pubkey of the strategy program which should have set return data.
+///
+/// Return: `None` if strategy did not return anything.
+pub fn get_strategy_return_data<T: AnchorDeserialize>(program_id: &Pubkey) -> Result<Option<T>> {
+    match get_return_data() {
+        Some((returned_by, data)) => {
+            require_keys_eq!(returned_by, *program_id, ErrorCode::InvalidReturnDataProgram);
+            Ok(Some(T::try_from_slice(&data)?))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Invokes strategy instruction `name` of `ctx.program` with serialized `args`.
+fn invoke_strategy<'info, T: ToAccountMetas + ToAccountInfos<'info>>(
+    ctx: CpiContext<'_, '_, '_, 'info, T>,
+    name: &str,
+    args: Vec<Vec<u8>>,
+) -> Result<()> {
+    let program_id: Pubkey = ctx.program.key();
+
+    invoke_signed_instruction(
+        "global",
+        name,
+        args,
+        program_id,
+        ctx.to_account_metas(None),
+        &ctx.to_account_infos(),
+        ctx.signer_seeds,
+    )
+}
+
+pub fn skim<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::Skim<'info>>,
+    amount: u64,
+) -> Result<()> {
+    invoke_strategy(ctx, "skim", vec![amount.try_to_vec()?])
+}
+
+/// Return: harvest result (+profit or -loss) if strategy returned it.
+pub fn harvest<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::Harvest<'info>>,
+    balance: u64,
+) -> Result<Option<ResultAmount>> {
+    let program_id = ctx.program.key();
+    invoke_strategy(ctx, "harvest", vec![balance.try_to_vec()?])?;
+    get_strategy_return_data(&program_id)
+}
+
+/// Return: the amount withdrawn to strategy vault if strategy returned it.
+pub fn withdraw<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::Withdraw<'info>>,
+    amount: u64,
+) -> Result<Option<u64>> {
+    let program_id = ctx.program.key();
+    invoke_strategy(ctx, "withdraw", vec![amount.try_to_vec()?])?;
+    get_strategy_return_data(&program_id)
+}
+
+/// Return: the amount withdrawn to strategy vault if strategy returned it.
+pub fn exit<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::Exit<'info>>,
+) -> Result<Option<u64>> {
+    let program_id = ctx.program.key();
+    invoke_strategy(ctx, "exit", vec![])?;
+    get_strategy_return_data(&program_id)
+}
+
+pub fn harvest_rewards<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::HarvestRewards<'info>>,
+) -> Result<()> {
+    invoke_strategy(ctx, "harvest_rewards", vec![])
+}
+
+pub fn safe_harvest<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::SafeHarvest<'info>>,
+    max_balance: u64,
+) -> Result<()> {
+    invoke_strategy(ctx, "safe_harvest", vec![max_balance.try_to_vec()?])
+}
+
+pub fn transfer<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::Transfer<'info>>,
+    amount: u64,
+) -> Result<()> {
+    invoke_strategy(ctx, "transfer", vec![amount.try_to_vec()?])
+}
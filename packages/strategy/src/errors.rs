@@ -6,4 +6,6 @@ pub enum ErrorCode {
     EmptyAccountsListForAfterExit,
     #[msg("Error while casting AccountInfo to BaseStrategyInfo.")]
     WrongConvertionFromAccountInfoToBaseStrategyInfo,
+    #[msg("Return data was set by other program than strategy.")]
+    InvalidReturnDataProgram,
 }
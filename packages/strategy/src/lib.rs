@@ -1,3 +1,4 @@
+pub mod cpi;
 pub mod errors;
 pub mod state;
 pub mod strategy_interface;
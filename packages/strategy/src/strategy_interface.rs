@@ -131,10 +131,12 @@ pub struct Harvest<'info> {
     #[account(rent_exempt = enforce,
               constraint = base_strategy_info.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
     pub base_strategy_info: UncheckedAccount<'info>,
+    /// Optional account for storing harvest result, strategy program id is passed if absent.
+    /// Harvest result is returned via return data, the account is kept for BentoBox built with `cpi-result-account`.
     /// CHECK: on owner and on rent_exempt enforce.
     #[account(mut, 
               rent_exempt = enforce,
-              constraint = cpi_result_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+              constraint = cpi_result_account.key() == strategy_program.key() || cpi_result_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
     pub cpi_result_account: UncheckedAccount<'info>,
 }
 
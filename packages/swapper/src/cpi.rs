@@ -0,0 +1,136 @@
+//! Client side of the swapper interface. Lets any program invoke a swapper program
+//! which implements `swapper_interface` without depending on the swapper crate.
+//! Instructions are dispatched by anchor discriminator, accounts keep the order
+//! of `swapper_interface` structs. Quotes are read from return data.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::get_return_data, pubkey::Pubkey},
+    Result,
+};
+use common::utils::invoke_signed_instruction;
+
+use crate::errors::ErrorCode;
+
+pub mod accounts {
+    use super::*;
+
+    #[derive(Accounts)]
+    pub struct Swap<'info> {
+        /// CHECK: checked by swapper program.
+        pub source_token_account: AccountInfo<'info>,
+        /// CHECK: checked by swapper program.
+        pub destination_token_account: AccountInfo<'info>,
+        /// CHECK: checked by swapper program.
+        pub swap_program: AccountInfo<'info>,
+        /// CHECK: checked by swapper program.
+        pub token_program: AccountInfo<'info>,
+        /// CHECK: checked by swapper program.
+        pub authority: AccountInfo<'info>,
+    }
+
+    #[derive(Accounts)]
+    pub struct GetAmountIn<'info> {
+        /// CHECK: checked by swapper program.
+        pub authority: AccountInfo<'info>,
+    }
+
+    #[derive(Accounts)]
+    pub struct GetAmountOut<'info> {
+        /// CHECK: checked by swapper program.
+        pub authority: AccountInfo<'info>,
+    }
+
+    #[derive(Accounts)]
+    pub struct GetSpotPrice<'info> {
+        /// CHECK: checked by swapper program.
+        pub authority: AccountInfo<'info>,
+    }
+}
+
+pub fn swap<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::Swap<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let program_id: Pubkey = ctx.program.key();
+
+    invoke_signed_instruction(
+        "global",
+        "swap",
+        vec![amount_in.try_to_vec()?, minimum_amount_out.try_to_vec()?],
+        program_id,
+        ctx.to_account_metas(None),
+        &ctx.to_account_infos(),
+        ctx.signer_seeds,
+    )
+}
+
+/// Quotes amount of source token needed to receive `amount_out` of destination token.
+/// Pool accounts required by swapper are passed as remaining accounts.
+pub fn get_amount_in<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetAmountIn<'info>>,
+    amount_out: u64,
+) -> Result<u64> {
+    let program_id: Pubkey = ctx.program.key();
+
+    invoke_signed_instruction(
+        "global",
+        "get_amount_in",
+        vec![amount_out.try_to_vec()?],
+        program_id,
+        ctx.to_account_metas(None),
+        &ctx.to_account_infos(),
+        ctx.signer_seeds,
+    )?;
+
+    get_swapper_return_data(&program_id)
+}
+
+/// Quotes amount of destination token received for `amount_in` of source token.
+/// Pool accounts required by swapper are passed as remaining accounts.
+pub fn get_amount_out<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetAmountOut<'info>>,
+    amount_in: u64,
+) -> Result<u64> {
+    let program_id: Pubkey = ctx.program.key();
+
+    invoke_signed_instruction(
+        "global",
+        "get_amount_out",
+        vec![amount_in.try_to_vec()?],
+        program_id,
+        ctx.to_account_metas(None),
+        &ctx.to_account_infos(),
+        ctx.signer_seeds,
+    )?;
+
+    get_swapper_return_data(&program_id)
+}
+
+/// Quotes spot price of the pool, destination amount per `SWAP_PRICE_PRECISION` source amount.
+/// Pool accounts required by swapper are passed as remaining accounts.
+pub fn get_spot_price<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::GetSpotPrice<'info>>,
+) -> Result<u64> {
+    let program_id: Pubkey = ctx.program.key();
+
+    invoke_signed_instruction(
+        "global",
+        "get_spot_price",
+        vec![],
+        program_id,
+        ctx.to_account_metas(None),
+        &ctx.to_account_infos(),
+        ctx.signer_seeds,
+    )?;
+
+    get_swapper_return_data(&program_id)
+}
+
+/// Reads quote returned by swapper program through return data.
+fn get_swapper_return_data(program_id: &Pubkey) -> Result<u64> {
+    let (returned_by, data) = get_return_data().ok_or(ErrorCode::SwapperReturnDataMissing)?;
+    require_keys_eq!(returned_by, *program_id, ErrorCode::InvalidReturnDataProgram);
+    Ok(u64::try_from_slice(&data)?)
+}
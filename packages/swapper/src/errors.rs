@@ -6,4 +6,18 @@ pub enum ErrorCode {
     InvalidTokenSourceOwner,
     #[msg("Invalid token destination account or token destination program account.")]
     InvalidTokenDestinationOwner,
+    #[msg("Swapper did not return quote via return data.")]
+    SwapperReturnDataMissing,
+    #[msg("Return data was set by another program.")]
+    InvalidReturnDataProgram,
+    #[msg("Swap output is less than minimum amount out.")]
+    SwapAmountOutTooLow,
+    #[msg("Swap consumed more source tokens than amount in.")]
+    SwapAmountInExceeded,
+    #[msg("Source token balance increased during swap.")]
+    SwapSourceBalanceIncreased,
+    #[msg("Destination token balance decreased during swap.")]
+    SwapDestinationBalanceDecreased,
+    #[msg("Pool reserves are empty, spot price is undefined.")]
+    EmptyPoolReserves,
 }
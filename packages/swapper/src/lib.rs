@@ -1,2 +1,4 @@
+pub mod cpi;
 pub mod errors;
 pub mod swapper_interface;
+pub mod utils;
@@ -29,6 +29,7 @@ pub mod swapper_interface {
 }
 
 /// Accounts for a get_amount_in instruction.
+/// Pool accounts required by swapper are passed as remaining accounts.
 #[derive(Accounts, Clone)]
 pub struct GetAmountIn<'info> {
     #[account(mut)]
@@ -36,12 +37,21 @@ pub struct GetAmountIn<'info> {
 }
 
 /// Accounts for a get_amount_out instruction.
+/// Pool accounts required by swapper are passed as remaining accounts.
 #[derive(Accounts, Clone)]
 pub struct GetAmountOut<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 }
 
+/// Accounts for a get_spot_price instruction.
+/// Pool accounts required by swapper are passed as remaining accounts.
+#[derive(Accounts, Clone)]
+pub struct GetSpotPrice<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
 #[derive(Accounts, Clone)]
 pub struct Swap<'info> {
     /// Base Account to swap INTO.Must be the SOURCE token. Cauldron_authority must be owner.
@@ -65,14 +75,23 @@ pub struct Swap<'info> {
 //** Swapper implementation: override the following functions: */
 // #[interface]
 pub trait Swapper<'info> {
+    /// Returns amount of source token needed to receive `amount_out` of destination token.
+    /// Quote is returned to the caller via return data.
     fn get_amount_in<'a>(
         ctx: Context<'_, '_, '_, 'a, GetAmountIn<'a>>,
         amount_out: u64,
-    ) -> Result<()>;
+    ) -> Result<u64>;
+    /// Returns amount of destination token received for `amount_in` of source token.
+    /// Quote is returned to the caller via return data.
     fn get_amount_out<'a>(
         ctx: Context<'_, '_, '_, 'a, GetAmountOut<'a>>,
-        amount_int: u64,
-    ) -> Result<()>;
+        amount_in: u64,
+    ) -> Result<u64>;
+
+    /// Returns spot price of the pool from its reserves, destination amount per
+    /// `SWAP_PRICE_PRECISION` source amount, fees and price impact excluded.
+    /// Price is returned to the caller via return data.
+    fn get_spot_price<'a>(ctx: Context<'_, '_, '_, 'a, GetSpotPrice<'a>>) -> Result<u64>;
 
     fn swap<'a>(
         ctx: Context<'_, '_, '_, 'a, Swap<'a>>,
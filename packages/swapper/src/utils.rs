@@ -0,0 +1,108 @@
+//! Balance-delta verification shared by swapper programs. Swappers don't trust
+//! the DEX instruction result and check token balances around the swap.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor::amount;
+
+use crate::errors::ErrorCode;
+use common::errors::ErrorCode as CommonErrorCode;
+
+/// Precision of effective swap price, price is destination amount per 1e9 source amount.
+pub const SWAP_PRICE_PRECISION: u128 = 1_000_000_000;
+
+/// Token balances of swap source and destination accounts captured before the swap.
+pub struct SwapBalances {
+    pub source_amount: u64,
+    pub destination_amount: u64,
+}
+
+/// Verified result of the swap.
+pub struct SwapResult {
+    /// Source tokens consumed by the swap.
+    pub amount_in: u64,
+    /// Destination tokens received by the swap.
+    pub amount_out: u64,
+    /// Destination amount per `SWAP_PRICE_PRECISION` source amount.
+    pub price: u64,
+}
+
+impl SwapBalances {
+    pub fn load(source: &AccountInfo, destination: &AccountInfo) -> Result<Self> {
+        Ok(SwapBalances {
+            source_amount: amount(source)?,
+            destination_amount: amount(destination)?,
+        })
+    }
+
+    /// Compares balances after the swap with captured ones.
+    ///
+    /// Arguments:
+    ///
+    /// * `source` - Swap source token account.
+    /// * `destination` - Swap destination token account.
+    /// * `amount_in` - Max amount of source tokens swap can consume.
+    /// * `minimum_amount_out` - Min amount of destination tokens swap should return.
+    pub fn verify(
+        &self,
+        source: &AccountInfo,
+        destination: &AccountInfo,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> Result<SwapResult> {
+        let consumed = self
+            .source_amount
+            .checked_sub(amount(source)?)
+            .ok_or(ErrorCode::SwapSourceBalanceIncreased)?;
+        let received = amount(destination)?
+            .checked_sub(self.destination_amount)
+            .ok_or(ErrorCode::SwapDestinationBalanceDecreased)?;
+
+        require!(consumed <= amount_in, ErrorCode::SwapAmountInExceeded);
+        require!(
+            received >= minimum_amount_out,
+            ErrorCode::SwapAmountOutTooLow
+        );
+
+        Ok(SwapResult {
+            amount_in: consumed,
+            amount_out: received,
+            price: effective_price(consumed, received)?,
+        })
+    }
+}
+
+/// Returns destination amount per `SWAP_PRICE_PRECISION` source amount, 0 for empty swap.
+pub fn effective_price(amount_in: u64, amount_out: u64) -> Result<u64> {
+    if amount_in == 0 {
+        return Ok(0);
+    }
+
+    let price = (amount_out as u128)
+        .checked_mul(SWAP_PRICE_PRECISION)
+        .ok_or(CommonErrorCode::WrongIntegerMultiplication)?
+        .checked_div(amount_in as u128)
+        .ok_or(CommonErrorCode::WrongIntegerDivision)?;
+
+    Ok(u64::try_from(price).unwrap_or(u64::MAX))
+}
+
+/// Returns spot price of the pool, destination amount per `SWAP_PRICE_PRECISION` source amount.
+///
+/// Arguments:
+///
+/// * `reserve_in` - Pool reserve of source token.
+/// * `reserve_out` - Pool reserve of destination token.
+pub fn spot_price(reserve_in: u128, reserve_out: u128) -> Result<u64> {
+    require!(
+        reserve_in > 0 && reserve_out > 0,
+        ErrorCode::EmptyPoolReserves
+    );
+
+    let price = reserve_out
+        .checked_mul(SWAP_PRICE_PRECISION)
+        .ok_or(CommonErrorCode::WrongIntegerMultiplication)?
+        .checked_div(reserve_in)
+        .ok_or(CommonErrorCode::WrongIntegerDivision)?;
+
+    Ok(u64::try_from(price).unwrap_or(u64::MAX))
+}
@@ -1,12 +1,11 @@
 use crate::{error::ErrorCode, state::*, utils::SPLFlashLoan};
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::sysvar};
 use anchor_spl::token::{Mint, SetAuthority, Token, TokenAccount, Transfer};
 use spl_token_lending::{math::Decimal, state::Reserve};
 
 use crate::state::{BaseExit, BaseSafeHarvest};
 
-use common::utils::calculate_end_byte_to_serialize;
-use common::constants::{DISCRIMINATOR_BYTES, STRATEGY_VAULT_SEED_PART};
+use common::constants::STRATEGY_VAULT_SEED_PART;
 use common::errors::ErrorCode as CommonErrorCode;
 #[derive(Accounts)]
 pub struct Conversion<'info> {
@@ -47,6 +46,29 @@ pub struct SetStrategyDelay<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct MigrateBentoBox<'info> {
+    /// Bentobox account in legacy layout.
+    /// CHECK: layout checked in instruction.
+    #[account(mut)]
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// Payer of rent for the new account size.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPerformanceFee<'info> {
+    /// Bentobox account.
+    #[account(mut, has_one = authority)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// BentoBox authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 #[instruction(new_authority: Pubkey)]
 pub struct TransferAuthority<'info> {
@@ -121,6 +143,161 @@ pub struct WhitelistMasterContract<'info> {
     pub bentobox_account: Box<Account<'info, BentoBox>>,
 }
 
+#[derive(Accounts)]
+pub struct RevokeMasterContractApprovals<'info> {
+    /// Master Contract PDA account which stores approvals epoch.
+    #[account(mut,
+              seeds = [WHITELISTED_MASTER_CONTRACT_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       master_contract_account.key().as_ref()],
+              bump)]
+    pub master_contract_whitelisted: Box<Account<'info, MasterContractWhitelisted>>,
+    /// CHECK: used only for whitelisted account seeds.
+    pub master_contract_account: UncheckedAccount<'info>,
+    /// BentoBox authority account.
+    pub authority: Signer<'info>,
+    /// Bentobox account.
+    #[account(has_one = authority)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterStrategyProgram<'info> {
+    /// Strategy program PDA account to store whitelisted information.
+    #[account(init,
+              seeds = [WHITELISTED_STRATEGY_PROGRAM_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       strategy_program.key().as_ref()],
+              bump,
+              payer = authority,
+              space = StrategyProgramWhitelisted::SIZE)]
+    pub strategy_program_whitelisted: Box<Account<'info, StrategyProgramWhitelisted>>,
+    /// Bentobox account.
+    #[account(has_one = authority)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// CHECK: If this account is strategy program executable
+    #[account(executable)]
+    pub strategy_program: UncheckedAccount<'info>,
+    /// BentoBox authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WhitelistStrategyProgram<'info> {
+    /// Strategy program PDA account that store whitelisted information.
+    #[account(mut,
+              seeds = [WHITELISTED_STRATEGY_PROGRAM_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       strategy_program.key().as_ref()],
+              bump)]
+    pub strategy_program_whitelisted: Box<Account<'info, StrategyProgramWhitelisted>>,
+    /// CHECK: If this account is strategy program executable
+    #[account(executable)]
+    pub strategy_program: UncheckedAccount<'info>,
+    /// BentoBox authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Bentobox account.
+    #[account(has_one = authority)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+}
+
+#[derive(Accounts)]
+#[instruction(keeper: Pubkey)]
+pub struct CreateStrategyKeeper<'info> {
+    /// Keeper PDA account to store keeper information for token.
+    #[account(init,
+              seeds = [STRATEGY_KEEPER_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       mint.key().as_ref(),
+                       keeper.as_ref()],
+              bump,
+              payer = authority,
+              space = StrategyKeeper::SIZE)]
+    pub strategy_keeper: Box<Account<'info, StrategyKeeper>>,
+    /// Bentobox account.
+    #[account(has_one = authority)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// Mint token account.
+    pub mint: Box<Account<'info, Mint>>,
+    /// BentoBox authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetStrategyKeeper<'info> {
+    /// Keeper PDA account that store keeper information for token.
+    #[account(mut,
+              seeds = [STRATEGY_KEEPER_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       mint.key().as_ref(),
+                       strategy_keeper.keeper.as_ref()],
+              bump)]
+    pub strategy_keeper: Box<Account<'info, StrategyKeeper>>,
+    /// Bentobox account.
+    #[account(has_one = authority)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// Mint token account.
+    pub mint: Box<Account<'info, Mint>>,
+    /// BentoBox authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMasterContractWhitelisted<'info> {
+    /// Master Contract whitelisted account in legacy layout.
+    /// CHECK: layout checked in instruction.
+    #[account(mut,
+              seeds = [WHITELISTED_MASTER_CONTRACT_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       master_contract_account.key().as_ref()],
+              bump)]
+    pub master_contract_whitelisted: UncheckedAccount<'info>,
+    /// Master Contract account.
+    /// CHECK: only used as seed of `master_contract_whitelisted`.
+    pub master_contract_account: UncheckedAccount<'info>,
+    /// Bentobox account.
+    /// CHECK: only used as seed of `master_contract_whitelisted`.
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// Payer of rent for the new account size.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct MigrateMasterContractApproved<'info> {
+    /// Master Contract approval account in legacy layout.
+    /// CHECK: layout checked in instruction.
+    #[account(mut,
+              seeds = [APPROVED_MASTER_CONTRACT_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       master_contract_account.key().as_ref(),
+                       user.as_ref()],
+              bump)]
+    pub master_contract_approved: UncheckedAccount<'info>,
+    /// Master Contract account.
+    /// CHECK: only used as seed of `master_contract_approved`.
+    pub master_contract_account: UncheckedAccount<'info>,
+    /// Bentobox account.
+    /// CHECK: only used as seed of `master_contract_approved`.
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// Payer of rent for the new account size.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct CreateApproveMasterContract<'info> {
     /// Master Contract PDA account to store user approval information.
@@ -224,6 +401,75 @@ pub struct ApproveMasterContract<'info> {
     pub bentobox_account: Box<Account<'info, BentoBox>>,
 }
 
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct ApproveMasterContractWithPermit<'info> {
+    /// Master Contract PDA account to store user approval information.
+    #[account(init_if_needed,
+              seeds = [APPROVED_MASTER_CONTRACT_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       master_contract_account.key().as_ref(),
+                       user.as_ref()],
+              bump,
+              payer = payer,
+              space = MasterContractApproved::SIZE)]
+    pub master_contract_approved: Box<Account<'info, MasterContractApproved>>,
+    /// PDA account which stores user permit nonce.
+    #[account(init_if_needed,
+              seeds = [PERMIT_NONCE_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       user.as_ref()],
+              bump,
+              payer = payer,
+              space = PermitNonce::SIZE)]
+    pub permit_nonce: Box<Account<'info, PermitNonce>>,
+    /// Master Contract PDA account that store whitelised information.
+    #[account(seeds = [WHITELISTED_MASTER_CONTRACT_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       master_contract_account.key().as_ref()],
+              bump,
+              constraint = master_contract_whitelisted.whitelisted == true @ ErrorCode::MasterContractNotWhitelisted)]
+    pub master_contract_whitelisted: Box<Account<'info, MasterContractWhitelisted>>,
+    /// CHECK: If this account is master contract executable
+    #[account(executable)]
+    pub master_contract_program: UncheckedAccount<'info>,
+    /// CHECK: If this account belongs to master contract executable
+    #[account(rent_exempt = enforce,
+              constraint = master_contract_account.owner == &master_contract_program.key() @ ErrorCode::MasterContractAccountOwnerDoesNotMatchProgram)]
+    pub master_contract_account: UncheckedAccount<'info>,
+    /// Instructions sysvar account, used to find ed25519 permit verification.
+    /// CHECK: account address.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    /// Relayer which sends the permit and pays for the accounts.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+    /// Bentobox account.
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseMasterContractApproval<'info> {
+    /// Master Contract PDA account which stores user approval information.
+    #[account(mut,
+              seeds = [APPROVED_MASTER_CONTRACT_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       master_contract_account.key().as_ref(),
+                       authority.key().as_ref()],
+              bump,
+              close = authority)]
+    pub master_contract_approved: Box<Account<'info, MasterContractApproved>>,
+    /// CHECK: used only for approval account seeds.
+    pub master_contract_account: UncheckedAccount<'info>,
+    /// User which approved master contract.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Bentobox account.
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+}
+
 #[derive(Accounts)]
 pub struct CreateVault<'info> {
     /// Bentobox pda total account by token. 
@@ -294,6 +540,51 @@ pub struct CreateBalance<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct CloseBalance<'info> {
+    /// Balance account.
+    #[account(mut,
+              seeds = [BALANCE_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       mint.key().as_ref(),
+                       authority.key().as_ref()],
+              bump,
+              constraint = balance.amount == 0 @ ErrorCode::BalanceIsNotEmpty,
+              close = authority)]
+    pub balance: Box<Account<'info, Balance>>,
+    /// Bentobox account.
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// Owner of balance account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Mint token account.
+    pub mint: Box<Account<'info, Mint>>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct MigrateBalance<'info> {
+    /// Balance account in legacy layout.
+    /// CHECK: layout checked in instruction.
+    #[account(mut,
+              seeds = [BALANCE_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       mint.key().as_ref(),
+                       owner.as_ref()],
+              bump)]
+    pub balance: UncheckedAccount<'info>,
+    /// Bentobox account.
+    /// CHECK: only used as seed of `balance`.
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// Payer of rent for the new account size.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// Mint token account.
+    pub mint: Box<Account<'info, Mint>>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 #[instruction(to: Pubkey, amount: u64, share: u64)]
 pub struct Deposit<'info> {
@@ -370,7 +661,6 @@ pub struct Withdraw<'info> {
                      bentobox_account.key().as_ref(),
                      bentobox_vault.mint.as_ref()],
              bump,
-             constraint = bentobox_vault.amount >= amount @ ErrorCode::WithdrawAmountToHigh,
              constraint = bentobox_vault.owner == vault_authority.key() @ ErrorCode::WithdrawTokenAccountInvalidAuthority,
              constraint = bentobox_vault.mint == to.mint  @ CommonErrorCode::IncompatibleTokenAccounts,
              constraint = total_data.load()?.token_account == bentobox_vault.key() @ ErrorCode::InvalidTotalTokenAccount)]
@@ -410,6 +700,9 @@ pub struct Withdraw<'info> {
     // master_contract_approved
     // > Only needed if authority is master contract
     // master_contract_account
+    // > Only needed if bentobox vault is short
+    // strategy_data, bentobox_program, strategy_program, strategy_account, strategy_vault,
+    // strategy_authority, base_strategy_info, remaining accounts of strategy
 }
 
 impl<'info> Withdraw<'info> {
@@ -458,6 +751,33 @@ pub struct TransferInternal<'info> {
     // master_contract_account
 }
 
+#[derive(Accounts)]
+pub struct Reconcile<'info> {
+    /// Bentobox token account.
+    #[account(seeds = [TOTAL_VAULT_KEY_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       mint.key().as_ref()],
+              bump)]
+    pub bentobox_vault: Box<Account<'info, TokenAccount>>,
+    /// Total account which stores all Bentobox amount by token.
+    #[account(seeds = [TOTAL_KEY_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       mint.key().as_ref()],
+              bump,
+              constraint = total_data.load()?.token_account == bentobox_vault.key() @ ErrorCode::InvalidTotalTokenAccount)]
+    pub total_data: AccountLoader<'info, Total>,
+    /// Strategy data account which stores base data for strategy.
+    #[account(seeds = [STRATEGY_DATA_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       mint.key().as_ref()],
+              bump)]
+    pub strategy_data: Box<Account<'info, StrategyData>>,
+    /// Mint token account.
+    pub mint: Box<Account<'info, Mint>>,
+    /// Bentobox account.
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+}
+
 #[derive(Accounts)]
 #[instruction(amount: u64)]
 pub struct FlashLoan<'info> {
@@ -575,6 +895,28 @@ pub struct CreateStrategyData<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct MigrateStrategyData<'info> {
+    /// Strategy data account for token in legacy layout.
+    /// CHECK: layout checked in instruction.
+    #[account(mut,
+              seeds = [STRATEGY_DATA_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       mint.key().as_ref()],
+              bump)]
+    pub strategy_data: UncheckedAccount<'info>,
+    /// Payer of rent for the new account size.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// Mint accoount for token.
+    pub mint: Box<Account<'info, Mint>>,
+    /// Bentobox account, should be migrated before strategy data as it counts pending strategies.
+    #[account(mut)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct SetStrategyTargetPercentage<'info> {
     // Strategy data account for token.
@@ -594,18 +936,146 @@ pub struct SetStrategyTargetPercentage<'info> {
     pub authority: Signer<'info>,
 } 
 
+#[derive(Accounts)]
+pub struct SetStrategyWithdrawOrder<'info> {
+    // Strategy data account for token.
+    #[account(mut, 
+              seeds = [STRATEGY_DATA_SEED_PART.as_ref(),
+              bentobox_account.key().as_ref(),
+              mint.key().as_ref()],
+              bump,)]
+    pub strategy_data: Box<Account<'info, StrategyData>>,
+    /// Bentobox account.
+    #[account(has_one = authority)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// Mint token account.
+    pub mint: Box<Account<'info, Mint>>,
+    /// Bentobox authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+} 
+
+#[derive(Accounts)]
+pub struct SetStrategyLossLimits<'info> {
+    // Strategy data account for token.
+    #[account(mut, 
+              seeds = [STRATEGY_DATA_SEED_PART.as_ref(),
+              bentobox_account.key().as_ref(),
+              mint.key().as_ref()],
+              bump,)]
+    pub strategy_data: Box<Account<'info, StrategyData>>,
+    /// Bentobox account.
+    #[account(has_one = authority)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// Mint token account.
+    pub mint: Box<Account<'info, Mint>>,
+    /// Bentobox authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+} 
+
+#[derive(Accounts)]
+pub struct SetKeeperReward<'info> {
+    // Strategy data account for token.
+    #[account(mut, 
+              seeds = [STRATEGY_DATA_SEED_PART.as_ref(),
+              bentobox_account.key().as_ref(),
+              mint.key().as_ref()],
+              bump,)]
+    pub strategy_data: Box<Account<'info, StrategyData>>,
+    /// Bentobox account.
+    #[account(has_one = authority)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// Mint token account.
+    pub mint: Box<Account<'info, Mint>>,
+    /// Bentobox authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct SetStrategy<'info> {
     /// Strategy program account.
+    /// CHECK: should be executable and whitelisted in BentoBox.
+    #[account(executable)]
+    pub strategy_program: UncheckedAccount<'info>,
+    /// Strategy program PDA account that store whitelisted information.
+    #[account(seeds = [WHITELISTED_STRATEGY_PROGRAM_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       strategy_program.key().as_ref()],
+              bump,
+              constraint = strategy_program_whitelisted.whitelisted @ ErrorCode::StrategyProgramNotWhitelisted)]
+    pub strategy_program_whitelisted: Box<Account<'info, StrategyProgramWhitelisted>>,
+    #[account(mut, has_one = authority)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
     /// CHECK: account checked in CPI.
+    #[account(rent_exempt = enforce, 
+              constraint = strategy_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub strategy_account: UncheckedAccount<'info>,
+    /// Strategy data account which stores base data for strategy.  
+    #[account(mut, 
+              seeds = [STRATEGY_DATA_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       bentobox_vault.mint.as_ref()],
+              bump,)]
+    pub strategy_data: Box<Account<'info, StrategyData>>,
+    /// Bentobox token account.
+    #[account(seeds = [TOTAL_VAULT_KEY_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       bentobox_vault.mint.as_ref()],
+              bump)]
+    pub bentobox_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelPendingStrategy<'info> {
+    // Strategy data account for token.
+    #[account(mut, 
+              seeds = [STRATEGY_DATA_SEED_PART.as_ref(),
+              bentobox_account.key().as_ref(),
+              mint.key().as_ref()],
+              bump,)]
+    pub strategy_data: Box<Account<'info, StrategyData>>,
+    /// Bentobox account.
+    #[account(mut, has_one = authority)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// Mint token account.
+    pub mint: Box<Account<'info, Mint>>,
+    /// Bentobox authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetPendingStrategy<'info> {
+    // Strategy data account for token.
+    #[account(seeds = [STRATEGY_DATA_SEED_PART.as_ref(),
+              bentobox_account.key().as_ref(),
+              mint.key().as_ref()],
+              bump,)]
+    pub strategy_data: Box<Account<'info, StrategyData>>,
+    /// Bentobox account.
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// Mint token account.
+    pub mint: Box<Account<'info, Mint>>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveStrategy<'info> {
+    /// Strategy program account.
+    /// CHECK: should be executable.
+    #[account(executable)]
     pub strategy_program: UncheckedAccount<'info>,
     /// Bentobox program account.
     /// CHECK: account checked in CPI.
     pub bentobox_program: UncheckedAccount<'info>,
     #[account(has_one = authority)]
     pub bentobox_account: Box<Account<'info, BentoBox>>,
-    /// CHECK: account checked in CPI.
-    #[account(rent_exempt = enforce, 
+    /// Strategy account.
+    /// CHECK: strategy should be added for token.
+    #[account(mut,
               constraint = strategy_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
     pub strategy_account: UncheckedAccount<'info>,
     /// Strategy data account which stores base data for strategy.  
@@ -618,14 +1088,20 @@ pub struct SetStrategy<'info> {
     /// Bentobox token account.
     #[account(mut, 
               seeds = [TOTAL_VAULT_KEY_SEED_PART.as_ref(),
-                      bentobox_account.key().as_ref(),
-                      bentobox_vault.mint.as_ref()],
+                       bentobox_account.key().as_ref(),
+                       bentobox_vault.mint.as_ref()],
               bump,
               constraint = bentobox_vault.owner == bentobox_authority.key() @ CommonErrorCode::BentoboxVaultInvalidAuthority,
               constraint = total_data.load()?.token_account == bentobox_vault.key() @ ErrorCode::InvalidTotalTokenAccount)]
     pub bentobox_vault: Box<Account<'info, TokenAccount>>,
     /// Strategy token account.
-    #[account(mut)]
+    #[account(mut,
+              seeds = [STRATEGY_VAULT_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref(),
+                       bentobox_vault.mint.as_ref()],
+              bump,
+              seeds::program = strategy_program.key(),
+              constraint = strategy_vault.owner == strategy_authority.key() @ CommonErrorCode::StrategyVaultInvalidAuthority,)]
     pub strategy_vault: Box<Account<'info, TokenAccount>>,
     /// Total account which stores all Bentobox amount by token.
     #[account(mut, 
@@ -641,7 +1117,7 @@ pub struct SetStrategy<'info> {
     pub base_strategy_info: UncheckedAccount<'info>,
     /// Bentobox token authority pda account.
     /// CHECK: account checked in CPI.
-    #[account(mut)]
+    #[account(mut, seeds = [BENTOBOX_SEED_PART, bentobox_account.key().as_ref()], bump)]
     pub bentobox_authority: UncheckedAccount<'info>,
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -652,7 +1128,7 @@ pub struct SetStrategy<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> SetStrategy<'info> {
+impl<'info> RemoveStrategy<'info> {
     /// Helper function to create `BaseExit` accounts.
     pub fn create_base_exit_accounts(&self) -> Result<BaseExit<'info>> {
         let mut bentobox_authority = self.bentobox_authority.to_account_info();
@@ -678,16 +1154,25 @@ impl<'info> SetStrategy<'info> {
 #[derive(Accounts)]
 pub struct Harvest<'info> {
     /// Strategy program account.
-    /// CHECK: account checked in CPI.
+    /// CHECK: should be executable and whitelisted in BentoBox.
+    #[account(executable)]
     pub strategy_program: UncheckedAccount<'info>,
+    /// Strategy program PDA account that store whitelisted information.
+    #[account(seeds = [WHITELISTED_STRATEGY_PROGRAM_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       strategy_program.key().as_ref()],
+              bump,
+              constraint = strategy_program_whitelisted.whitelisted @ ErrorCode::StrategyProgramNotWhitelisted)]
+    pub strategy_program_whitelisted: Box<Account<'info, StrategyProgramWhitelisted>>,
     /// Bentobox program account.
     /// CHECK: account checked in CPI.
     pub bentobox_program: UncheckedAccount<'info>,
     /// Strategy account.
     /// CHECK: strategy should be set and active.
     #[account(mut, 
-              constraint = strategy_data.active_strategy != Pubkey::default() @ ErrorCode::StrategyNotSet,
-              constraint = strategy_data.active_strategy == strategy_account.key() @ ErrorCode::InvalidStrategyAccount)]
+              constraint = !strategy_data.strategies.is_empty() @ ErrorCode::StrategyNotSet,
+              constraint = strategy_data.strategy_index(&strategy_account.key()).is_ok() @ ErrorCode::InvalidStrategyAccount,
+              constraint = strategy_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
     pub strategy_account: UncheckedAccount<'info>,
     /// Strategy token account.
     #[account(mut,
@@ -716,10 +1201,11 @@ pub struct Harvest<'info> {
     pub bentobox_vault: Box<Account<'info, TokenAccount>>,
     /// Bentobox account.
     pub bentobox_account: Box<Account<'info, BentoBox>>,
-    /// Account for storing result from strategy harvest implementation. 
+    /// Account for storing result from strategy harvest implementation.
+    /// Only for strategies which do not return result via return data.
     /// CHECK: account checked in CPI.
     #[account(mut)]
-    pub cpi_result_account: UncheckedAccount<'info>,
+    pub cpi_result_account: Option<UncheckedAccount<'info>>,
     /// Strategy data account which stores base data for strategy.  
     #[account(mut, 
               seeds = [STRATEGY_DATA_SEED_PART.as_ref(),
@@ -741,9 +1227,21 @@ pub struct Harvest<'info> {
     pub system_program: Program<'info, System>,
     /// Token program account.
     pub token_program: Program<'info, Token>,
-    /// Account which holds all base info for strategy.
-    /// CHECK: account checked in CPI.
+    /// Account which holds all base info for strategy, writable as strategy is exited on max loss.
+    /// CHECK: executors.
+    #[account(mut, 
+              rent_exempt = enforce,
+              constraint = base_strategy_info.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
     pub base_strategy_info: UncheckedAccount<'info>,
+    /// Balance account of `fee_to` which receives performance fee shares.
+    /// Only needed if performance fee is set.
+    #[account(mut,
+              seeds = [BALANCE_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       bentobox_vault.mint.as_ref(),
+                       bentobox_account.fee_to.as_ref()],
+              bump)]
+    pub fee_to_balance: Option<Box<Account<'info, Balance>>>,
 }
 
 #[derive(Accounts)]
@@ -751,8 +1249,9 @@ pub struct SafeHarvest<'info> {
     /// Strategy account.
     /// CHECK: strategy should be set and active.
     #[account(mut,
-              constraint = strategy_data.active_strategy != Pubkey::default() @ ErrorCode::StrategyNotSet,
-              constraint = strategy_data.active_strategy == strategy_account.key() @ ErrorCode::InvalidStrategyAccount)]
+              constraint = !strategy_data.strategies.is_empty() @ ErrorCode::StrategyNotSet,
+              constraint = strategy_data.strategy_index(&strategy_account.key()).is_ok() @ ErrorCode::InvalidStrategyAccount,
+              constraint = strategy_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
     pub strategy_account: UncheckedAccount<'info>,
     /// Account which holds all base info for strategy.
     /// CHECK: executors.
@@ -761,8 +1260,16 @@ pub struct SafeHarvest<'info> {
               constraint = base_strategy_info.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
     pub base_strategy_info: UncheckedAccount<'info>,
     /// Strategy program account.
-    /// CHECK: account checked in CPI.
+    /// CHECK: should be executable and whitelisted in BentoBox.
+    #[account(executable)]
     pub strategy_program: UncheckedAccount<'info>,
+    /// Strategy program PDA account that store whitelisted information.
+    #[account(seeds = [WHITELISTED_STRATEGY_PROGRAM_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       strategy_program.key().as_ref()],
+              bump,
+              constraint = strategy_program_whitelisted.whitelisted @ ErrorCode::StrategyProgramNotWhitelisted)]
+    pub strategy_program_whitelisted: Box<Account<'info, StrategyProgramWhitelisted>>,
     /// Bentobox program account.
     /// CHECK: account checked in CPI.
     pub bentobox_program: UncheckedAccount<'info>,
@@ -793,10 +1300,11 @@ pub struct SafeHarvest<'info> {
     pub bentobox_vault: Box<Account<'info, TokenAccount>>,
     /// Bentobox account.
     pub bentobox_account: Box<Account<'info, BentoBox>>,
-    /// Account for storing result from strategy harvest implementation. 
+    /// Account for storing result from strategy harvest implementation.
+    /// Only for strategies which do not return result via return data.
     /// CHECK: account checked in CPI.
     #[account(mut)]
-    pub cpi_result_account: UncheckedAccount<'info>,
+    pub cpi_result_account: Option<UncheckedAccount<'info>>,
     /// Strategy data account which stores base data for strategy.  
     #[account(mut, 
               seeds = [STRATEGY_DATA_SEED_PART.as_ref(),
@@ -818,25 +1326,35 @@ pub struct SafeHarvest<'info> {
     pub system_program: Program<'info, System>,
     /// Token program account.
     pub token_program: Program<'info, Token>,
-    /// Executor info account.
-    /// CHECK: if executor_info belongs to authority and allowed.
-    #[account(rent_exempt = enforce,
-              constraint = executor_info.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)] 
-    pub executor_info: UncheckedAccount<'info>,
+    /// Keeper account of `authority` for token.
+    #[account(seeds = [STRATEGY_KEEPER_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       bentobox_vault.mint.as_ref(),
+                       authority.key().as_ref()],
+              bump,
+              constraint = strategy_keeper.is_keeper @ ErrorCode::UnauthorizedSafeHarvest)]
+    pub strategy_keeper: Box<Account<'info, StrategyKeeper>>,
+    /// Balance account of `fee_to` which receives performance fee shares.
+    /// Only needed if performance fee is set.
+    #[account(mut,
+              seeds = [BALANCE_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       bentobox_vault.mint.as_ref(),
+                       bentobox_account.fee_to.as_ref()],
+              bump)]
+    pub fee_to_balance: Option<Box<Account<'info, Balance>>>,
+    /// Balance account of keeper which receives keeper reward shares.
+    /// Only needed if keeper reward is set.
+    #[account(mut,
+              seeds = [BALANCE_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       bentobox_vault.mint.as_ref(),
+                       authority.key().as_ref()],
+              bump)]
+    pub keeper_balance: Option<Box<Account<'info, Balance>>>,
 }
 
 impl<'info> SafeHarvest<'info> {
-    /// Helper function to check if authority is one of allowed executors. 
-    pub fn only_executors(ctx: &Context<SafeHarvest>) -> Result<()> {
-            let data: &[u8] = &ctx.accounts.executor_info.try_borrow_data()?;
-            let mut executor_info_bytes = &data[DISCRIMINATOR_BYTES..calculate_end_byte_to_serialize(ExecutorInfo::SIZE, false)];
-            let executor_info_decoded = ExecutorInfo::deserialize(& mut executor_info_bytes)?;
-            if executor_info_decoded.user == ctx.accounts.authority.key() && executor_info_decoded.is_executor {
-                return Ok(())
-            }
-        return Err(error!(ErrorCode::UnauthorizedSafeHarvest))
-    }
-
     /// Helper function to create `BaseSafeHarvest` accounts.
     pub fn create_base_safe_harvest_accounts(&self) -> Result<BaseSafeHarvest<'info>> {
         let mut bentobox_authority = self.bentobox_authority.to_account_info();
@@ -856,16 +1374,3 @@ impl<'info> SafeHarvest<'info> {
         })
     }
 }
-
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
-pub struct ExecutorInfo {
-    /// Indicates if user/contract is executor and can call safe harvest instruction.
-    /// Is `true` if user/contract is executor.
-    pub is_executor: bool,
-    pub user: Pubkey,
-}
-
-impl ExecutorInfo {
-    pub const SIZE: usize = 1 + 32;
-}
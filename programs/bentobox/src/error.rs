@@ -116,4 +116,71 @@ pub enum ErrorCode {
 
     #[msg("BentoBox strategy is exited.")]
     StrategyIsExited,
+
+    #[msg("BentoBox: Deposit shares are less than minimum expected.")]
+    DepositShareSlippage,
+
+    #[msg("Balance account can not be closed, it still holds shares.")]
+    BalanceIsNotEmpty,
+
+    // permit
+    #[msg("Approval permit has expired.")]
+    PermitExpired,
+    #[msg("Approval permit nonce mismatch.")]
+    InvalidPermitNonce,
+    #[msg("Previous instruction is not a valid ed25519 verification of the approval permit.")]
+    InvalidPermitSignature,
+
+    #[msg("Master Contract approval was revoked.")]
+    MasterContractApprovalRevoked,
+    #[msg("Strategy program is not whitelisted.")]
+    StrategyProgramNotWhitelisted,
+    #[msg("Strategy did not return result via return data.")]
+    StrategyReturnDataMissing,
+
+    // multi-strategy
+    #[msg("Strategy is already added for token.")]
+    StrategyAlreadyAdded,
+    #[msg("Maximum number of strategies for token is reached.")]
+    TooManyStrategies,
+    #[msg("Withdraw order should contain every strategy of token exactly once.")]
+    InvalidStrategyWithdrawOrder,
+
+    // withdraw from strategy
+    #[msg("Invalid strategy data account.")]
+    InvalidStrategyDataAccount,
+    #[msg("Invalid Bentobox program account.")]
+    InvalidBentoboxProgramAccount,
+    #[msg("Strategy is not the next one in withdraw order.")]
+    StrategyNotNextInWithdrawOrder,
+
+    // loss limits
+    #[msg("Loss limit should not be more than 10000 basis points.")]
+    InvalidLossLimit,
+    #[msg("Strategy loss exceeds loss limit.")]
+    StrategyLossTooHigh,
+
+    // performance fee
+    #[msg("Performance fee should not be more than 10000 basis points.")]
+    InvalidPerformanceFee,
+    #[msg("Balance account of fee recipient is missing.")]
+    FeeToBalanceMissing,
+
+    // keepers
+    #[msg("Keeper reward should not be more than 1000 basis points.")]
+    InvalidKeeperReward,
+    #[msg("Performance fee and keeper reward should not be more than 10000 basis points in total.")]
+    InvalidFeeAndKeeperReward,
+
+    // pending strategy
+    #[msg("Strategy is already pending for token.")]
+    StrategyAlreadyPending,
+    #[msg("Strategy is not pending for token.")]
+    StrategyNotPending,
+    #[msg("Strategy delay can not be shortened while strategy is pending.")]
+    StrategyDelayShortenedWhilePending,
+
+    // migrations
+    #[msg("Account is not in legacy layout or is already migrated.")]
+    InvalidLegacyAccount,
 }
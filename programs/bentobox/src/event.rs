@@ -44,12 +44,13 @@ pub struct LogFlashLoan {
 pub struct LogStrategyQueued {
     pub token: Pubkey,
     pub new_strategy: Pubkey,
-
+    pub strategy_start_date: u64,
 }
 
 #[event]
 pub struct LogStrategyTargetPercentage {
     pub token: Pubkey,
+    pub strategy: Pubkey,
     pub target_percentage: u64,
 }
 
@@ -77,6 +78,34 @@ pub struct LogStrategySet {
     pub new_strategy: Pubkey,
 }
 
+#[event]
+pub struct LogStrategyRemoved {
+    pub token: Pubkey,
+    pub strategy: Pubkey,
+}
+
+#[event]
+pub struct LogStrategyWithdrawOrder {
+    pub token: Pubkey,
+    pub withdraw_order: Vec<Pubkey>,
+}
+
+#[event]
+pub struct LogStrategyLossLimits {
+    pub token: Pubkey,
+    pub max_loss_bps: u64,
+    pub max_cumulative_loss_bps: u64,
+    pub exit_on_max_loss: bool,
+}
+
+#[event]
+pub struct LogStrategyLossLimitExceeded {
+    pub token: Pubkey,
+    pub strategy: Pubkey,
+    pub loss: u64,
+    pub cumulative_loss: u64,
+}
+
 #[event]
 pub struct LogStrategyInvest {
     pub token: Pubkey,
@@ -88,3 +117,88 @@ pub struct LogAuthorityChanged {
     pub authority: Pubkey,
     pub new_authority: Pubkey,
 }
+
+#[event]
+pub struct LogReconcile {
+    pub token: Pubkey,
+    pub vault_amount: u64,
+    pub strategy_balance: u64,
+    pub total_elastic: u128,
+    pub total_base: u128,
+    pub skimmable_amount: u64,
+    pub shortfall_amount: u64,
+}
+
+#[event]
+pub struct LogMasterContractApproval {
+    pub master_contract: Pubkey,
+    pub user: Pubkey,
+    pub approved: bool,
+}
+
+#[event]
+pub struct LogWhitelistMasterContract {
+    pub master_contract: Pubkey,
+    pub whitelisted: bool,
+}
+
+#[event]
+pub struct LogWhitelistStrategyProgram {
+    pub strategy_program: Pubkey,
+    pub whitelisted: bool,
+}
+
+#[event]
+pub struct LogRevokeMasterContractApprovals {
+    pub master_contract: Pubkey,
+    pub approvals_epoch: u64,
+}
+
+#[event]
+pub struct LogPerformanceFee {
+    pub performance_fee_bps: u64,
+    pub fee_to: Pubkey,
+}
+
+#[event]
+pub struct LogStrategyFee {
+    pub token: Pubkey,
+    pub fee_to: Pubkey,
+    pub amount: u64,
+    pub share: u64,
+}
+
+#[event]
+pub struct LogStrategyKeeper {
+    pub token: Pubkey,
+    pub keeper: Pubkey,
+    pub is_keeper: bool,
+}
+
+#[event]
+pub struct LogKeeperReward {
+    pub token: Pubkey,
+    pub keeper_reward_bps: u64,
+}
+
+#[event]
+pub struct LogStrategyKeeperReward {
+    pub token: Pubkey,
+    pub keeper: Pubkey,
+    pub amount: u64,
+    pub share: u64,
+}
+
+#[event]
+pub struct LogStrategyCancelled {
+    pub token: Pubkey,
+    pub strategy: Pubkey,
+}
+
+#[event]
+pub struct LogPendingStrategy {
+    pub token: Pubkey,
+    pub strategy: Pubkey,
+    pub strategy_start_date: u64,
+    pub activatable: bool,
+}
@@ -9,11 +9,12 @@ use anchor_lang::{prelude::*, solana_program::pubkey::Pubkey, Result,solana_prog
 use anchor_spl::token::{self};
 use spl_token::instruction::AuthorityType;
 use std::collections::BTreeMap;
+use std::convert::TryInto;
 
 use spl_token_lending::state::Reserve;
 use utils::flash_loan as spl_flash_loan;
 
-use crate::utils::{base_exit, base_safe_harvest};
+use crate::utils::base_safe_harvest;
 
 use common::errors::ErrorCode as CommonErrorCode;
 use common::rebase::Rebase;
@@ -66,6 +67,9 @@ pub mod bentobox {
         let bentobox_account = &mut ctx.accounts.bentobox_account;
         bentobox_account.authority = ctx.accounts.authority.key();
         bentobox_account.strategy_delay = 0;
+        bentobox_account.performance_fee_bps = 0;
+        bentobox_account.fee_to = ctx.accounts.authority.key();
+        bentobox_account.pending_strategies_count = 0;
         bentobox_account.constants.minimum_share_balance = minimum_share_balance;
         bentobox_account.constants.max_target_percentage = max_target_percentage;
         Ok(())
@@ -75,13 +79,76 @@ pub mod bentobox {
     ///
     /// Arguments:
     ///
-    /// * `delay` - Delay for strategy.
+    /// * `delay` - Delay for strategy. Can not be shortened while any strategy is pending.
     pub fn set_strategy_delay(ctx: Context<SetStrategyDelay>, delay: u64) -> Result<()> {
         let bentobox_account = &mut ctx.accounts.bentobox_account;
+        require!(
+            delay >= bentobox_account.strategy_delay || bentobox_account.pending_strategies_count == 0,
+            ErrorCode::StrategyDelayShortenedWhilePending
+        );
         bentobox_account.strategy_delay = delay;
         Ok(())
     }
 
+    /// Migrates Bentobox account created before performance fee was supported to the current layout.
+    /// Performance fee is not set and `fee_to` is Bentobox owner, as for newly created Bentobox.
+    pub fn migrate_bentobox(ctx: Context<MigrateBentoBox>) -> Result<()> {
+        let bentobox_info = ctx.accounts.bentobox_account.to_account_info();
+        let legacy_data = realloc_legacy_account::<BentoBox>(
+            &bentobox_info,
+            LegacyBentoBox::SIZE,
+            BentoBox::SIZE,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        // legacy size is reserved for `pending_authority` of `Some`, so data can have unused tail
+        let legacy = LegacyBentoBox::deserialize(&mut legacy_data.as_slice())?;
+        let bentobox_account = BentoBox {
+            authority: legacy.authority,
+            pending_authority: legacy.pending_authority,
+            strategy_delay: legacy.strategy_delay,
+            constants: legacy.constants,
+            performance_fee_bps: 0,
+            fee_to: legacy.authority,
+            pending_strategies_count: 0,
+        };
+        write_migrated_account(&bentobox_info, &bentobox_account)
+    }
+
+    /// Sets performance fee taken from strategy profits and its recipient.
+    /// Fee is minted as shares to `Balance` account of `fee_to` on harvest.
+    ///
+    /// Arguments:
+    ///
+    /// * `performance_fee_bps` - Performance fee in basis points, not more than `BPS_DENOMINATOR - MAX_KEEPER_REWARD_BPS`, 0 if no fee is taken.
+    /// * `fee_to`              - Owner of `Balance` account which receives performance fee shares.
+    pub fn set_performance_fee(
+        ctx: Context<SetPerformanceFee>,
+        performance_fee_bps: u64,
+        fee_to: Pubkey,
+    ) -> Result<()> {
+        // leave room for keeper reward of any token
+        require!(
+            performance_fee_bps
+                .checked_add(MAX_KEEPER_REWARD_BPS)
+                .ok_or(CommonErrorCode::WrongIntegerAddition)?
+                <= BPS_DENOMINATOR,
+            ErrorCode::InvalidFeeAndKeeperReward
+        );
+
+        let bentobox_account = &mut ctx.accounts.bentobox_account;
+        bentobox_account.performance_fee_bps = performance_fee_bps;
+        bentobox_account.fee_to = fee_to;
+
+        emit!(LogPerformanceFee {
+            performance_fee_bps,
+            fee_to,
+        });
+
+        Ok(())
+    }
+
     /// Register Master Contract in BentoBox and creates special whitelisted account for it. It is possible to set whitelisted state here.
     ///
     /// Arguments:
@@ -94,6 +161,11 @@ pub mod bentobox {
         let master_contract_info = &mut ctx.accounts.master_contract_whitelisted;
         master_contract_info.master_contract_account = ctx.accounts.master_contract_account.key();
         master_contract_info.whitelisted = whitelisted;
+
+        emit!(LogWhitelistMasterContract {
+            master_contract: master_contract_info.master_contract_account,
+            whitelisted,
+        });
         Ok(())
     }
 
@@ -108,6 +180,159 @@ pub mod bentobox {
     ) -> Result<()> {
         let master_contract_info = &mut ctx.accounts.master_contract_whitelisted;
         master_contract_info.whitelisted = whitelisted;
+
+        emit!(LogWhitelistMasterContract {
+            master_contract: master_contract_info.master_contract_account,
+            whitelisted,
+        });
+        Ok(())
+    }
+
+    /// Emergency revoke of all user approvals of Master Contract.
+    /// Users need to approve Master Contract again to give it access to their funds.
+    pub fn revoke_master_contract_approvals(ctx: Context<RevokeMasterContractApprovals>) -> Result<()> {
+        let master_contract_info = &mut ctx.accounts.master_contract_whitelisted;
+        master_contract_info.approvals_epoch = master_contract_info
+            .approvals_epoch
+            .checked_add(1)
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+
+        emit!(LogRevokeMasterContractApprovals {
+            master_contract: master_contract_info.master_contract_account,
+            approvals_epoch: master_contract_info.approvals_epoch,
+        });
+        Ok(())
+    }
+
+    /// Migrates Master Contract whitelisted account created before approvals epochs to the current layout.
+    /// Account gets approvals epoch 0.
+    pub fn migrate_master_contract_whitelisted(
+        ctx: Context<MigrateMasterContractWhitelisted>,
+    ) -> Result<()> {
+        let whitelisted_info = ctx.accounts.master_contract_whitelisted.to_account_info();
+        let legacy_data = realloc_legacy_account::<MasterContractWhitelisted>(
+            &whitelisted_info,
+            MasterContractWhitelisted::LEGACY_SIZE,
+            MasterContractWhitelisted::SIZE,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        let (master_contract_account, whitelisted) = <(Pubkey, bool)>::try_from_slice(&legacy_data)?;
+        let master_contract_whitelisted = MasterContractWhitelisted {
+            master_contract_account,
+            whitelisted,
+            approvals_epoch: 0,
+        };
+        write_migrated_account(&whitelisted_info, &master_contract_whitelisted)
+    }
+
+    /// Migrates Master Contract approval account created before approvals epochs to the current layout.
+    /// Legacy approvals are made in approvals epoch 0, so they stay revoked if approvals of Master Contract were revoked.
+    ///
+    /// Arguments:
+    ///
+    /// * `user` - address for which approval account was created.
+    pub fn migrate_master_contract_approved(
+        ctx: Context<MigrateMasterContractApproved>,
+        _user: Pubkey,
+    ) -> Result<()> {
+        let approved_info = ctx.accounts.master_contract_approved.to_account_info();
+        let legacy_data = realloc_legacy_account::<MasterContractApproved>(
+            &approved_info,
+            MasterContractApproved::LEGACY_SIZE,
+            MasterContractApproved::SIZE,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        let (master_contract_whitelisted, approved) = <(Pubkey, bool)>::try_from_slice(&legacy_data)?;
+        let master_contract_approved = MasterContractApproved {
+            master_contract_whitelisted,
+            approved,
+            approvals_epoch: 0,
+        };
+        write_migrated_account(&approved_info, &master_contract_approved)
+    }
+
+    /// Register strategy program in BentoBox and creates special whitelisted account for it.
+    /// Only whitelisted strategy programs could be set as strategy and invoked by BentoBox.
+    ///
+    /// Arguments:
+    ///
+    /// * `whitelisted` - True if strategy program should be whitelisted, False othervise.
+    pub fn create_strategy_program_whitelist(
+        ctx: Context<RegisterStrategyProgram>,
+        whitelisted: bool,
+    ) -> Result<()> {
+        let strategy_program_info = &mut ctx.accounts.strategy_program_whitelisted;
+        strategy_program_info.strategy_program = ctx.accounts.strategy_program.key();
+        strategy_program_info.whitelisted = whitelisted;
+
+        emit!(LogWhitelistStrategyProgram {
+            strategy_program: strategy_program_info.strategy_program,
+            whitelisted,
+        });
+        Ok(())
+    }
+
+    /// Setter instruction to change strategy program whitelisted state.
+    ///
+    /// Arguments:
+    ///
+    /// * `whitelisted` - True if strategy program should be whitelisted, False othervise.
+    pub fn whitelist_strategy_program(
+        ctx: Context<WhitelistStrategyProgram>,
+        whitelisted: bool,
+    ) -> Result<()> {
+        let strategy_program_info = &mut ctx.accounts.strategy_program_whitelisted;
+        strategy_program_info.whitelisted = whitelisted;
+
+        emit!(LogWhitelistStrategyProgram {
+            strategy_program: strategy_program_info.strategy_program,
+            whitelisted,
+        });
+        Ok(())
+    }
+
+    /// Registers keeper for token in BentoBox and creates special keeper account for it.
+    /// Only keepers of the token are allowed to call `safe_harvest`.
+    ///
+    /// Arguments:
+    ///
+    /// * `keeper`    - Keeper address.
+    /// * `is_keeper` - True if keeper is allowed to call `safe_harvest`, False othervise.
+    pub fn create_strategy_keeper(
+        ctx: Context<CreateStrategyKeeper>,
+        keeper: Pubkey,
+        is_keeper: bool,
+    ) -> Result<()> {
+        let strategy_keeper = &mut ctx.accounts.strategy_keeper;
+        strategy_keeper.keeper = keeper;
+        strategy_keeper.is_keeper = is_keeper;
+
+        emit!(LogStrategyKeeper {
+            token: ctx.accounts.mint.key(),
+            keeper,
+            is_keeper,
+        });
+        Ok(())
+    }
+
+    /// Setter instruction to change keeper state for token.
+    ///
+    /// Arguments:
+    ///
+    /// * `is_keeper` - True if keeper is allowed to call `safe_harvest`, False othervise.
+    pub fn set_strategy_keeper(ctx: Context<SetStrategyKeeper>, is_keeper: bool) -> Result<()> {
+        let strategy_keeper = &mut ctx.accounts.strategy_keeper;
+        strategy_keeper.is_keeper = is_keeper;
+
+        emit!(LogStrategyKeeper {
+            token: ctx.accounts.mint.key(),
+            keeper: strategy_keeper.keeper,
+            is_keeper,
+        });
         Ok(())
     }
 
@@ -123,7 +348,7 @@ pub mod bentobox {
         create_contract_approval_internal(
             approved,
             &mut ctx.accounts.master_contract_approved,
-            ctx.accounts.master_contract_whitelisted.key(),
+            &ctx.accounts.master_contract_whitelisted,
         )?;
 
         Ok(())
@@ -136,7 +361,7 @@ pub mod bentobox {
         create_contract_approval_internal(
             approved,
             &mut ctx.accounts.master_contract_approved,
-            ctx.accounts.master_contract_whitelisted.key(),
+            &ctx.accounts.master_contract_whitelisted,
         )?;
 
         Ok(())
@@ -153,6 +378,78 @@ pub mod bentobox {
     ) -> Result<()> {
         let master_contract_approved = &mut ctx.accounts.master_contract_approved;
         master_contract_approved.approved = approved;
+        master_contract_approved.approvals_epoch =
+            ctx.accounts.master_contract_whitelisted.approvals_epoch;
+
+        emit!(LogMasterContractApproval {
+            master_contract: ctx.accounts.master_contract_account.key(),
+            user: ctx.accounts.authority.key(),
+            approved,
+        });
+        Ok(())
+    }
+
+    /// Approves or revokes a Master Contract access to `user` funds with approval signed off-chain.
+    /// Previous instruction in the transaction should be the ed25519 program verification of the
+    /// `APPROVAL_PERMIT_PREFIX` followed by serialized `ApprovalPermit`, signed by `user`.
+    ///
+    /// Arguments:
+    ///
+    /// * `user`     - User which signed the approval permit.
+    /// * `approved` - If True approves access. If False revokes access.
+    /// * `nonce`    - Current permit nonce of the user.
+    /// * `expiry`   - Unix timestamp after which permit is not valid.
+    pub fn set_master_contract_approval_with_permit(
+        ctx: Context<ApproveMasterContractWithPermit>,
+        user: Pubkey,
+        approved: bool,
+        nonce: u64,
+        expiry: i64,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp <= expiry,
+            ErrorCode::PermitExpired
+        );
+
+        let permit_nonce = &mut ctx.accounts.permit_nonce;
+        require!(permit_nonce.nonce == nonce, ErrorCode::InvalidPermitNonce);
+
+        let mut message = APPROVAL_PERMIT_PREFIX.to_vec();
+        ApprovalPermit {
+            bentobox_account: ctx.accounts.bentobox_account.key(),
+            master_contract_account: ctx.accounts.master_contract_account.key(),
+            user,
+            approved,
+            nonce,
+            expiry,
+        }
+        .serialize(&mut message)?;
+
+        verify_ed25519_instruction(&ctx.accounts.instructions, &user, &message)?;
+
+        permit_nonce.nonce = permit_nonce
+            .nonce
+            .checked_add(1)
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+
+        create_contract_approval_internal(
+            approved,
+            &mut ctx.accounts.master_contract_approved,
+            &ctx.accounts.master_contract_whitelisted,
+        )?;
+
+        emit!(LogMasterContractApproval {
+            master_contract: ctx.accounts.master_contract_account.key(),
+            user,
+            approved,
+        });
+
+        Ok(())
+    }
+
+    /// Close master contract approval account and return its rent to the user.
+    /// Closed approval revokes master contract access to user funds.
+    pub fn close_master_contract_approval(_ctx: Context<CloseMasterContractApproval>) -> Result<()> {
         Ok(())
     }
 
@@ -236,10 +533,42 @@ pub mod bentobox {
     /// Arguments:
     ///
     /// * `to` - address for which `Balance` account is created.
-    pub fn create_balance(_ctx: Context<CreateBalance>, _to: Pubkey) -> Result<()> {
+    pub fn create_balance(ctx: Context<CreateBalance>, to: Pubkey) -> Result<()> {
+        let balance = &mut ctx.accounts.balance;
+        balance.mint = ctx.accounts.mint.key();
+        balance.owner = to;
         Ok(())
     }
 
+    /// Close empty balance account and return its rent to the owner.
+    pub fn close_balance(_ctx: Context<CloseBalance>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Migrates balance account created before `mint` and `owner` were stored to the current layout.
+    /// `mint` and `owner` are taken from the balance account seeds, so anyone can migrate.
+    ///
+    /// Arguments:
+    ///
+    /// * `owner` - address for which `Balance` account was created.
+    pub fn migrate_balance(ctx: Context<MigrateBalance>, owner: Pubkey) -> Result<()> {
+        let balance_info = ctx.accounts.balance.to_account_info();
+        let legacy_data = realloc_legacy_account::<Balance>(
+            &balance_info,
+            Balance::LEGACY_SIZE,
+            Balance::SIZE,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        let balance = Balance {
+            amount: u64::try_from_slice(&legacy_data)?,
+            mint: ctx.accounts.mint.key(),
+            owner,
+        };
+        write_migrated_account(&balance_info, &balance)
+    }
+
     /// Deposit an amount of token represented in either `amount` or `share`.
     ///
     /// Arguments:
@@ -247,11 +576,13 @@ pub mod bentobox {
     /// * `to`     - Which account to push the tokens.
     /// * `amount` - Token amount in native representation to deposit.
     /// * `share`  - Token amount represented in shares to deposit. Takes precedence over `amount`.
+    /// * `min_share_out` - Minimum amount of shares which `to` should receive for the deposit.
     pub fn deposit(
         ctx: Context<Deposit>,
         to: Pubkey,
         amount: u64,
         share: u64,
+        min_share_out: u64,
     ) -> Result<AmountShareOut> {
         allowed(
             &ctx.accounts.from.owner,
@@ -310,12 +641,38 @@ pub mod bentobox {
 
         let amount_u128: u128 = amount.into();
 
+        let is_skim = ctx.accounts.bentobox_vault.key() == ctx.accounts.from.key();
+
         require!(
-            ctx.accounts.bentobox_vault.key() != ctx.accounts.from.key()
-                || amount_u128 <= skimmable_amount,
+            !is_skim || amount_u128 <= skimmable_amount,
             ErrorCode::DepositSkimTooMuch
         );
 
+        if !is_skim {
+            // credit only what actually arrived to the vault
+            let vault_amount_before = ctx.accounts.bentobox_vault.amount;
+
+            token::transfer(ctx.accounts.create_transfer_context(), amount_internal)?;
+            ctx.accounts.bentobox_vault.reload()?;
+
+            let received_amount = ctx
+                .accounts
+                .bentobox_vault
+                .amount
+                .checked_sub(vault_amount_before)
+                .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+
+            if received_amount != amount_internal {
+                amount_internal = received_amount;
+                share_internal = total_data_amount.to_base(amount_internal, false)?;
+            }
+        }
+
+        require!(
+            share_internal >= min_share_out,
+            ErrorCode::DepositShareSlippage
+        );
+
         total_data_amount.base = total_data
             .amount
             .base
@@ -345,8 +702,6 @@ pub mod bentobox {
             share: share_internal
         });
 
-        token::transfer(ctx.accounts.create_transfer_context(), amount_internal)?;
-
         Ok(AmountShareOut {
             amount_out: amount_internal,
             share_out: share_internal,
@@ -360,17 +715,30 @@ pub mod bentobox {
     /// * `from`   - Which user to pull the tokens.
     /// * `amount` - Amount of tokens. Either one of `amount` or `share` needs to be supplied.
     /// * `share`  - Like above, but `share` takes precedence over `amount`.
-    pub fn withdraw(
-        ctx: Context<Withdraw>,
+    ///
+    /// If `bentobox_vault` holds less than `amount`, the shortfall is pulled from strategy,
+    /// which accounts are passed as remaining accounts after master contract accounts.
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
         from: Pubkey,
         amount: u64,
         share: u64,
     ) -> Result<AmountShareOut> {
+        let allowed_accounts_count = allowed_accounts_count(
+            &from,
+            &ctx.accounts.authority.key(),
+            &ctx.accounts.bentobox_account.key(),
+            ctx.program_id,
+        )
+        .min(ctx.remaining_accounts.len());
+        let (allowed_accounts, strategy_accounts) =
+            ctx.remaining_accounts.split_at(allowed_accounts_count);
+
         allowed(
             &from,
             &ctx.accounts.authority.key(),
             &ctx.accounts.bentobox_account.key(),
-            &ctx.remaining_accounts,
+            allowed_accounts,
             ctx.program_id,
         )?;
 
@@ -408,6 +776,7 @@ pub mod bentobox {
                 || total_data.amount.base == 0,
             ErrorCode::WithdrawCannotEmpty
         );
+        drop(total_data);
 
         let balance = &mut ctx.accounts.balance;
         balance.amount = balance
@@ -424,6 +793,25 @@ pub mod bentobox {
 
         let authority_seeds = &[BENTOBOX_SEED_PART, bentobox_key.as_ref(), &[_bump]];
 
+        if ctx.accounts.bentobox_vault.amount < amount_internal && !strategy_accounts.is_empty() {
+            withdraw_from_strategies(
+                ctx.program_id,
+                &ctx.accounts.bentobox_account,
+                &ctx.accounts.bentobox_vault,
+                &ctx.accounts.vault_authority,
+                &ctx.accounts.token_program,
+                strategy_accounts,
+                amount_internal - ctx.accounts.bentobox_vault.amount,
+                &[&authority_seeds[..]],
+            )?;
+            ctx.accounts.bentobox_vault.reload()?;
+        }
+
+        require!(
+            ctx.accounts.bentobox_vault.amount >= amount_internal,
+            ErrorCode::WithdrawAmountToHigh
+        );
+
         token::transfer(
             ctx.accounts
                 .create_transfer_context()
@@ -487,6 +875,47 @@ pub mod bentobox {
         Ok(())
     }
 
+    /// Permissionless check of the vault-side invariant
+    /// `total.elastic == vault amount + strategy balance` for a token.
+    ///
+    /// Tokens held above `total.elastic` are reported as skimmable and can be credited
+    /// with a skim deposit, shortfall can not be recovered and is only reported.
+    /// Return: skimmable and shortfall amounts.
+    pub fn reconcile(ctx: Context<Reconcile>) -> Result<ReconcileOut> {
+        let total_data = ctx.accounts.total_data.load()?;
+
+        let token_balance =
+            token_balance_of(&ctx.accounts.bentobox_vault, &ctx.accounts.strategy_data)?;
+
+        let (skimmable_amount, shortfall_amount) = if token_balance >= total_data.amount.elastic {
+            (token_balance - total_data.amount.elastic, 0)
+        } else {
+            (0, total_data.amount.elastic - token_balance)
+        };
+
+        let skimmable_amount: u64 = skimmable_amount
+            .try_into()
+            .map_err(|_| CommonErrorCode::TryIntoConversionError)?;
+        let shortfall_amount: u64 = shortfall_amount
+            .try_into()
+            .map_err(|_| CommonErrorCode::TryIntoConversionError)?;
+
+        emit!(LogReconcile {
+            token: total_data.mint_address,
+            vault_amount: ctx.accounts.bentobox_vault.amount,
+            strategy_balance: ctx.accounts.strategy_data.balance,
+            total_elastic: total_data.amount.elastic,
+            total_base: total_data.amount.base,
+            skimmable_amount,
+            shortfall_amount,
+        });
+
+        Ok(ReconcileOut {
+            skimmable_amount,
+            shortfall_amount,
+        })
+    }
+
     /// Flashloan ability.
     ///
     /// Arguments:
@@ -537,19 +966,31 @@ pub mod bentobox {
         let strategy_data = &mut ctx.accounts.strategy_data;
         strategy_data.strategy_start_date = 0;
         strategy_data.pending_strategy = Pubkey::default();
-        strategy_data.active_strategy = Pubkey::default();
+        strategy_data.strategies = Vec::new();
+        strategy_data.withdraw_order = Vec::new();
 
         Ok(())
     }
 
     /// Sets the target percentage of the strategy for token.
+    /// Sum of target percentages of all token strategies can not exceed `max_target_percentage`.
     /// Only the owner of this contract is allowed to change this.
+    ///
+    /// Arguments:
+    ///
+    /// * `strategy` - Strategy account which target percentage is changed.
+    /// * `target_percentage` - New target percentage of the strategy.
     pub fn set_strategy_target_percentage(
         ctx: Context<SetStrategyTargetPercentage>,
+        strategy: Pubkey,
         target_percentage: u64,
     ) -> Result<()> {
+        let strategy_data = &mut ctx.accounts.strategy_data;
+        let index = strategy_data.strategy_index(&strategy)?;
+        strategy_data.strategies[index].target_percentage = target_percentage;
+
         require!(
-            target_percentage
+            strategy_data.total_target_percentage()?
                 <= ctx
                     .accounts
                     .bentobox_account
@@ -558,105 +999,300 @@ pub mod bentobox {
             ErrorCode::StrategyTargetPercentageTooHigh
         );
 
-        let strategy_data = &mut ctx.accounts.strategy_data;
-        strategy_data.target_percentage = target_percentage;
-
         emit!(LogStrategyTargetPercentage {
             token: ctx.accounts.mint.key(),
+            strategy,
             target_percentage,
         });
 
         Ok(())
     }
 
-    /// Sets the contract address of a new strategy for token.
-    /// Must be called twice with the same arguments.
-    /// A new strategy becomes pending first and can be activated once `STRATEGY_DELAY` is over.
-    pub fn set_strategy<'info>(ctx: Context<'_, '_, '_, 'info, SetStrategy<'info>>) -> Result<()> {
-        let mut base_exit_accounts = ctx.accounts.create_base_exit_accounts()?;
+    /// Sets the order in which strategies of token are withdrawn from when the vault is short.
+    /// Only the owner of this contract is allowed to change this.
+    ///
+    /// Arguments:
+    ///
+    /// * `withdraw_order` - Strategy accounts of token, each exactly once.
+    pub fn set_strategy_withdraw_order(
+        ctx: Context<SetStrategyWithdrawOrder>,
+        withdraw_order: Vec<Pubkey>,
+    ) -> Result<()> {
+        let strategy_data = &mut ctx.accounts.strategy_data;
+
+        require!(
+            withdraw_order.len() == strategy_data.strategies.len()
+                && strategy_data
+                    .strategies
+                    .iter()
+                    .all(|allocation| withdraw_order.contains(&allocation.strategy)),
+            ErrorCode::InvalidStrategyWithdrawOrder
+        );
+        strategy_data.withdraw_order = withdraw_order.clone();
+
+        emit!(LogStrategyWithdrawOrder {
+            token: ctx.accounts.mint.key(),
+            withdraw_order,
+        });
+
+        Ok(())
+    }
+
+    /// Sets the loss limits of token strategies.
+    /// Only the owner of this contract is allowed to change this.
+    ///
+    /// Arguments:
+    ///
+    /// * `max_loss_bps`            - Maximum loss of single harvest in basis points of token total amount, 0 if not limited.
+    /// * `max_cumulative_loss_bps` - Maximum sum of strategy losses in basis points of token total amount, 0 if not limited.
+    /// * `exit_on_max_loss`        - True if strategy should be exited and removed when loss limit is exceeded,
+    ///                               False if harvest should revert.
+    pub fn set_strategy_loss_limits(
+        ctx: Context<SetStrategyLossLimits>,
+        max_loss_bps: u64,
+        max_cumulative_loss_bps: u64,
+        exit_on_max_loss: bool,
+    ) -> Result<()> {
+        require!(
+            max_loss_bps <= BPS_DENOMINATOR && max_cumulative_loss_bps <= BPS_DENOMINATOR,
+            ErrorCode::InvalidLossLimit
+        );
 
         let strategy_data = &mut ctx.accounts.strategy_data;
-        
-        if strategy_data.strategy_start_date == 0
-            || strategy_data.pending_strategy != ctx.accounts.strategy_account.key()
-        {
-            strategy_data.pending_strategy = ctx.accounts.strategy_account.key();
-            strategy_data.strategy_start_date =
-                Clock::get()?.unix_timestamp as u64 + ctx.accounts.bentobox_account.strategy_delay;
-
-            emit!(LogStrategyQueued {
-                token: ctx.accounts.bentobox_vault.mint,
-                new_strategy: ctx.accounts.strategy_account.key(),
-            });
-        } else {
-            require!(
-                strategy_data.strategy_start_date != 0
-                    && strategy_data.strategy_start_date < Clock::get()?.unix_timestamp as u64,
-                ErrorCode::TooEarlyStrategyStartData
-            );
+        strategy_data.max_loss_bps = max_loss_bps;
+        strategy_data.max_cumulative_loss_bps = max_cumulative_loss_bps;
+        strategy_data.exit_on_max_loss = exit_on_max_loss;
 
-            if strategy_data.active_strategy != Pubkey::default() {
-                let bentobox_key = ctx.accounts.bentobox_account.key();
-                let (_vault_authority_address, _bump) = Pubkey::find_program_address(
-                    &[BENTOBOX_SEED_PART, bentobox_key.as_ref()],
-                    &ctx.program_id,
-                );
-                let authority_seeds = &[BENTOBOX_SEED_PART, bentobox_key.as_ref(), &[_bump]];
-
-                let ctx_base_exit = Context::new(
-                    ctx.program_id,
-                    &mut base_exit_accounts,
-                    &ctx.remaining_accounts,
-                    BTreeMap::new(),
-                );
-                let balance_change: i64 = base_exit(
-                    ctx_base_exit,
-                    strategy_data.balance,
-                    &[&authority_seeds[..]],
-                )?;
-
-                let mut total_data = ctx.accounts.total_data.load_mut()?;
-
-                if balance_change > 0 {
-                    let add = balance_change as u64;
-                    total_data.amount.elastic = total_data
-                        .amount
-                        .elastic
-                        .checked_add(add.into())
-                        .ok_or(CommonErrorCode::WrongIntegerAddition)?;
-                    emit!(LogStrategyProfit {
-                        token: ctx.accounts.bentobox_vault.mint,
-                        amount: add,
-                    });
-                } else {
-                    let sub = -balance_change as u64;
-                    total_data.amount.elastic = total_data
-                        .amount
-                        .elastic
-                        .checked_sub(sub.into())
-                        .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
-                    emit!(LogStrategyLoss {
-                        token: ctx.accounts.bentobox_vault.mint,
-                        amount: sub,
-                    });
-                }
-
-                emit!(LogStrategyDivest {
-                    token: ctx.accounts.bentobox_vault.mint,
-                    amount: strategy_data.balance,
-                });
-            }
+        emit!(LogStrategyLossLimits {
+            token: ctx.accounts.mint.key(),
+            max_loss_bps,
+            max_cumulative_loss_bps,
+            exit_on_max_loss,
+        });
 
-            strategy_data.active_strategy = strategy_data.pending_strategy;
-            strategy_data.strategy_start_date = 0;
-            strategy_data.balance = 0;
-            strategy_data.pending_strategy = Pubkey::default();
+        Ok(())
+    }
 
-            emit!(LogStrategySet {
-                token: ctx.accounts.bentobox_vault.mint,
-                new_strategy: ctx.accounts.strategy_program.key.clone(),
-            });
+    /// Sets reward of keeper from profit harvested with `safe_harvest` for token.
+    /// Reward is minted as shares to `Balance` account of keeper.
+    /// Only the owner of this contract is allowed to change this.
+    ///
+    /// Arguments:
+    ///
+    /// * `keeper_reward_bps` - Keeper reward in basis points of harvested profit, not more than `MAX_KEEPER_REWARD_BPS`, 0 if keeper is not rewarded.
+    pub fn set_keeper_reward(ctx: Context<SetKeeperReward>, keeper_reward_bps: u64) -> Result<()> {
+        require!(
+            keeper_reward_bps <= MAX_KEEPER_REWARD_BPS,
+            ErrorCode::InvalidKeeperReward
+        );
+        require!(
+            keeper_reward_bps
+                .checked_add(ctx.accounts.bentobox_account.performance_fee_bps)
+                .ok_or(CommonErrorCode::WrongIntegerAddition)?
+                <= BPS_DENOMINATOR,
+            ErrorCode::InvalidFeeAndKeeperReward
+        );
+
+        ctx.accounts.strategy_data.keeper_reward_bps = keeper_reward_bps;
+
+        emit!(LogKeeperReward {
+            token: ctx.accounts.mint.key(),
+            keeper_reward_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Migrates strategy data account of token created before multiple strategies per token were supported.
+    /// Active strategy keeps its target percentage and balance, pending strategy stays queued
+    /// and is counted in pending strategies of Bentobox.
+    pub fn migrate_strategy_data(ctx: Context<MigrateStrategyData>) -> Result<()> {
+        let strategy_data_info = ctx.accounts.strategy_data.to_account_info();
+        let legacy_data = realloc_legacy_account::<StrategyData>(
+            &strategy_data_info,
+            LegacyStrategyData::SIZE,
+            StrategyData::SIZE,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        let strategy_data = StrategyData::from_legacy(LegacyStrategyData::try_from_slice(&legacy_data)?);
+        if strategy_data.has_pending_strategy() {
+            let bentobox_account = &mut ctx.accounts.bentobox_account;
+            bentobox_account.pending_strategies_count = bentobox_account
+                .pending_strategies_count
+                .checked_add(1)
+                .ok_or(CommonErrorCode::WrongIntegerAddition)?;
         }
+        write_migrated_account(&strategy_data_info, &strategy_data)
+    }
+
+    /// Queues the contract address of a new strategy for token.
+    /// Queued strategy can be activated with `activate_strategy` once `STRATEGY_DELAY` is over
+    /// or cancelled with `cancel_pending_strategy`.
+    /// Only one strategy can be pending for token.
+    pub fn queue_strategy(ctx: Context<SetStrategy>) -> Result<()> {
+        let strategy_data = &mut ctx.accounts.strategy_data;
+        let strategy_key = ctx.accounts.strategy_account.key();
+
+        require!(
+            strategy_data.strategy_index(&strategy_key).is_err(),
+            ErrorCode::StrategyAlreadyAdded
+        );
+        require!(
+            !strategy_data.has_pending_strategy(),
+            ErrorCode::StrategyAlreadyPending
+        );
+
+        strategy_data.pending_strategy = strategy_key;
+        strategy_data.strategy_start_date =
+            Clock::get()?.unix_timestamp as u64 + ctx.accounts.bentobox_account.strategy_delay;
+
+        let bentobox_account = &mut ctx.accounts.bentobox_account;
+        bentobox_account.pending_strategies_count = bentobox_account
+            .pending_strategies_count
+            .checked_add(1)
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+
+        emit!(LogStrategyQueued {
+            token: ctx.accounts.bentobox_vault.mint,
+            new_strategy: strategy_key,
+            strategy_start_date: strategy_data.strategy_start_date,
+        });
+
+        Ok(())
+    }
+
+    /// Activates pending strategy of token once `STRATEGY_DELAY` is over.
+    /// Activated strategy is appended to the end of rebalancing priority and withdraw order
+    /// with zero target percentage.
+    pub fn activate_strategy(ctx: Context<SetStrategy>) -> Result<()> {
+        let strategy_data = &mut ctx.accounts.strategy_data;
+        let strategy_key = ctx.accounts.strategy_account.key();
+
+        require!(
+            strategy_data.has_pending_strategy() && strategy_data.pending_strategy == strategy_key,
+            ErrorCode::StrategyNotPending
+        );
+        require!(
+            strategy_data.strategy_start_date < Clock::get()?.unix_timestamp as u64,
+            ErrorCode::TooEarlyStrategyStartData
+        );
+        require!(
+            strategy_data.strategies.len() < MAX_STRATEGIES_PER_TOKEN,
+            ErrorCode::TooManyStrategies
+        );
+
+        strategy_data.strategies.push(StrategyAllocation {
+            strategy: strategy_key,
+            target_percentage: 0,
+            balance: 0,
+            cumulative_loss: 0,
+        });
+        strategy_data.withdraw_order.push(strategy_key);
+        strategy_data.strategy_start_date = 0;
+        strategy_data.pending_strategy = Pubkey::default();
+
+        let bentobox_account = &mut ctx.accounts.bentobox_account;
+        bentobox_account.pending_strategies_count = bentobox_account
+            .pending_strategies_count
+            .checked_sub(1)
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+
+        emit!(LogStrategySet {
+            token: ctx.accounts.bentobox_vault.mint,
+            new_strategy: strategy_key,
+        });
+
+        Ok(())
+    }
+
+    /// Cancels pending strategy of token.
+    /// Only the owner of this contract is allowed to cancel pending strategy.
+    pub fn cancel_pending_strategy(ctx: Context<CancelPendingStrategy>) -> Result<()> {
+        let strategy_data = &mut ctx.accounts.strategy_data;
+        require!(
+            strategy_data.has_pending_strategy(),
+            ErrorCode::StrategyNotPending
+        );
+
+        let strategy = strategy_data.pending_strategy;
+        strategy_data.strategy_start_date = 0;
+        strategy_data.pending_strategy = Pubkey::default();
+
+        let bentobox_account = &mut ctx.accounts.bentobox_account;
+        bentobox_account.pending_strategies_count = bentobox_account
+            .pending_strategies_count
+            .checked_sub(1)
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+
+        emit!(LogStrategyCancelled {
+            token: ctx.accounts.mint.key(),
+            strategy,
+        });
+
+        Ok(())
+    }
+
+    /// Helper function to inspect pending strategy of token.
+    ///
+    /// Return: Pending strategy, time when it can be activated and if it can be activated now.
+    pub fn get_pending_strategy(ctx: Context<GetPendingStrategy>) -> Result<PendingStrategyOut> {
+        let strategy_data = &ctx.accounts.strategy_data;
+        require!(
+            strategy_data.has_pending_strategy(),
+            ErrorCode::StrategyNotPending
+        );
+
+        let activatable = strategy_data.strategy_start_date < Clock::get()?.unix_timestamp as u64;
+
+        emit!(LogPendingStrategy {
+            token: ctx.accounts.mint.key(),
+            strategy: strategy_data.pending_strategy,
+            strategy_start_date: strategy_data.strategy_start_date,
+            activatable,
+        });
+
+        Ok(PendingStrategyOut {
+            strategy: strategy_data.pending_strategy,
+            strategy_start_date: strategy_data.strategy_start_date,
+            activatable,
+        })
+    }
+
+    /// Removes the strategy of token.
+    /// All funds of the strategy are returned to BentoBox, profit or loss is booked.
+    /// Only the owner of this contract is allowed to remove strategy.
+    pub fn remove_strategy<'info>(
+        ctx: Context<'_, '_, '_, 'info, RemoveStrategy<'info>>,
+    ) -> Result<()> {
+        let mut base_exit_accounts = ctx.accounts.create_base_exit_accounts()?;
+
+        let strategy_data = &mut ctx.accounts.strategy_data;
+        let index = strategy_data.strategy_index(&ctx.accounts.strategy_account.key())?;
+
+        let bentobox_key = ctx.accounts.bentobox_account.key();
+        let (_vault_authority_address, _bump) = Pubkey::find_program_address(
+            &[BENTOBOX_SEED_PART, bentobox_key.as_ref()],
+            &ctx.program_id,
+        );
+        let authority_seeds = &[BENTOBOX_SEED_PART, bentobox_key.as_ref(), &[_bump]];
+
+        let ctx_base_exit = Context::new(
+            ctx.program_id,
+            &mut base_exit_accounts,
+            &ctx.remaining_accounts,
+            BTreeMap::new(),
+        );
+        let mut total_data = ctx.accounts.total_data.load_mut()?;
+
+        exit_strategy(
+            ctx_base_exit,
+            &mut total_data,
+            strategy_data,
+            index,
+            &[&authority_seeds[..]],
+        )?;
 
         Ok(())
     }
@@ -690,7 +1326,9 @@ pub mod bentobox {
             &mut ctx.accounts.strategy_authority,
             &ctx.accounts.token_program,
             &ctx.accounts.base_strategy_info,
-            &mut ctx.accounts.cpi_result_account,
+            &ctx.accounts.cpi_result_account,
+            &mut ctx.accounts.fee_to_balance,
+            &mut None,
             &ctx.remaining_accounts.to_vec(),
             bump,
         )?;
@@ -706,7 +1344,6 @@ pub mod bentobox {
     /// * `rebalance` - Whether BentoBox should rebalance the strategy assets to acheive it's target allocation.
     /// * `max_change_amount` - When rebalancing - the maximum amount that will be deposited to or withdrawn from a strategy to BentoBox.
     /// * `harvest_rewards` - If we want to claim any accrued reward tokens.
-    #[access_control(SafeHarvest::only_executors(&ctx))]
     pub fn safe_harvest<'info>(
         ctx: Context<'_, '_, '_, 'info, SafeHarvest<'info>>,
         max_balance: u64,
@@ -758,7 +1395,9 @@ pub mod bentobox {
             &mut ctx.accounts.strategy_authority,
             &ctx.accounts.token_program,
             &ctx.accounts.base_strategy_info,
-            &mut ctx.accounts.cpi_result_account,
+            &ctx.accounts.cpi_result_account,
+            &mut ctx.accounts.fee_to_balance,
+            &mut ctx.accounts.keeper_balance,
             &ctx.remaining_accounts.to_vec(),
             bump,
         )?;
@@ -2,12 +2,13 @@ use anchor_lang::{prelude::*, solana_program::pubkey::Pubkey, Result};
 use anchor_spl::token::{Token, TokenAccount};
 use std::mem::size_of;
 
-use common::{constants::STRATEGY_VAULT_SEED_PART, errors::ErrorCode};
+use crate::error::ErrorCode;
+use common::constants::STRATEGY_VAULT_SEED_PART;
 use common::rebase::Rebase;
 
 use common::errors::ErrorCode as CommonErrorCode;
 
-use strategy_mock::cpi::accounts::{
+use strategy_package::cpi::accounts::{
     Exit, Harvest, HarvestRewards, SafeHarvest, Skim, Transfer, Withdraw,
 };
 
@@ -26,7 +27,27 @@ pub const WHITELISTED_MASTER_CONTRACT_PART: &[u8] = b"whitelistedmastercontractk
 #[constant]
 pub const APPROVED_MASTER_CONTRACT_PART: &[u8] = b"approvedmastercontractkey";
 #[constant]
+pub const WHITELISTED_STRATEGY_PROGRAM_PART: &[u8] = b"whitelistedstrategyprogramkey";
+#[constant]
+pub const PERMIT_NONCE_SEED_PART: &[u8] = b"permitnoncekey";
+#[constant]
+pub const STRATEGY_KEEPER_SEED_PART: &[u8] = b"bentoboxstrategykeeperkey";
+#[constant]
 pub const REMAINING_ACCOUNTS_COUNT_FOR_ALLOWED: usize = 3;
+#[constant]
+pub const MAX_STRATEGIES_PER_TOKEN: usize = 5;
+#[constant]
+pub const STRATEGY_WITHDRAW_ACCOUNTS_COUNT: usize = 8;
+/// Number of accounts of each strategy in withdraw strategy accounts, not counting strategy remaining accounts.
+#[constant]
+pub const STRATEGY_ACCOUNTS_COUNT: usize = 6;
+#[constant]
+pub const BPS_DENOMINATOR: u64 = 10_000;
+/// Maximal keeper reward of token in basis points, performance fee is limited to the rest of `BPS_DENOMINATOR`.
+#[constant]
+pub const MAX_KEEPER_REWARD_BPS: u64 = 1_000;
+/// Prefix of the message user signs to approve master contract with permit.
+pub const APPROVAL_PERMIT_PREFIX: &[u8] = b"BentoBox: master contract approval";
 
 #[account]
 #[derive(Default)]
@@ -37,9 +58,40 @@ pub struct BentoBox {
     pub pending_authority: Option<Pubkey>,
     pub strategy_delay: u64,
     pub constants: Constants,
+    /// Performance fee taken from strategy profits in basis points.
+    pub performance_fee_bps: u64,
+    /// Account which `Balance` receives performance fee shares.
+    pub fee_to: Pubkey,
+    /// Number of tokens with queued strategy which is not activated or cancelled yet.
+    pub pending_strategies_count: u64,
 }
 
 impl BentoBox {
+    pub const SIZE: usize =
+        8 + 32 + size_of::<Option<Pubkey>>() + 8 + Constants::SIZE + 8 + 32 + 8;
+
+    /// Calculates performance fee amount for strategy `profit`.
+    pub fn performance_fee(&self, profit: u64) -> Result<u64> {
+        let fee = u128::from(profit)
+            .checked_mul(self.performance_fee_bps.into())
+            .ok_or(CommonErrorCode::WrongIntegerMultiplication)?
+            / u128::from(BPS_DENOMINATOR);
+        Ok(fee
+            .try_into()
+            .map_err(|_| CommonErrorCode::TryIntoConversionError)?)
+    }
+}
+
+/// BentoBox layout before performance fee was supported.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyBentoBox {
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub strategy_delay: u64,
+    pub constants: Constants,
+}
+
+impl LegacyBentoBox {
     pub const SIZE: usize = 8 + 32 + size_of::<Option<Pubkey>>() + 8 + Constants::SIZE;
 }
 
@@ -58,25 +110,85 @@ impl Constants {
 pub struct MasterContractWhitelisted {
     pub master_contract_account: Pubkey,
     pub whitelisted: bool,
+    /// Approvals made with other epoch are revoked.
+    pub approvals_epoch: u64,
 }
 
 impl MasterContractWhitelisted {
     pub const SIZE: usize = 8 + // discriminator
     32 + // mastercontract_id
+    1 + // whitelisted
+    8; // approvals_epoch
+    /// Size of account created before `approvals_epoch` was stored.
+    pub const LEGACY_SIZE: usize = 8 + 32 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct StrategyProgramWhitelisted {
+    pub strategy_program: Pubkey,
+    pub whitelisted: bool,
+}
+
+impl StrategyProgramWhitelisted {
+    pub const SIZE: usize = 8 + // discriminator
+    32 + // strategy_program
     1; // whitelisted
 }
 
+#[account]
+#[derive(Default)]
+pub struct StrategyKeeper {
+    pub keeper: Pubkey,
+    /// Indicates if keeper can call `safe_harvest` for token.
+    pub is_keeper: bool,
+}
+
+impl StrategyKeeper {
+    pub const SIZE: usize = 8 + // discriminator
+    32 + // keeper
+    1; // is_keeper
+}
+
 #[account]
 #[derive(Default)]
 pub struct MasterContractApproved {
     pub master_contract_whitelisted: Pubkey,
     pub approved: bool,
+    /// Master contract approvals epoch at the moment of approval.
+    pub approvals_epoch: u64,
 }
 
 impl MasterContractApproved {
     pub const SIZE: usize = 8 + // discriminator
     32 + // master_contract_whitelisted
-    1; // approved
+    1 + // approved
+    8; // approvals_epoch
+    /// Size of account created before `approvals_epoch` was stored.
+    pub const LEGACY_SIZE: usize = 8 + 32 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct PermitNonce {
+    /// Nonce which next approval permit of the user should be signed with.
+    pub nonce: u64,
+}
+
+impl PermitNonce {
+    pub const SIZE: usize = 8 + // discriminator
+    8; // nonce
+}
+
+/// Approval data user signs off-chain, serialized after `APPROVAL_PERMIT_PREFIX`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ApprovalPermit {
+    pub bentobox_account: Pubkey,
+    pub master_contract_account: Pubkey,
+    pub user: Pubkey,
+    pub approved: bool,
+    pub nonce: u64,
+    pub expiry: i64,
 }
 
 #[account(zero_copy(unsafe))]
@@ -100,10 +212,30 @@ impl Total {
 pub struct Balance {
     /// The amount of tokens on BentoBox in shares.
     pub amount: u64,
+    /// Token mint of this balance.
+    pub mint: Pubkey,
+    /// Account which owns this balance.
+    pub owner: Pubkey,
 }
 
 impl Balance {
-    pub const SIZE: usize = 8 + 8;
+    pub const SIZE: usize = 8 + 8 + 32 + 32;
+    /// Size of balance account created before `mint` and `owner` were stored.
+    pub const LEGACY_SIZE: usize = 8 + 8;
+}
+
+/// Strategy data layout before multiple strategies per token were supported.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyStrategyData {
+    pub strategy_start_date: u64,
+    pub target_percentage: u64,
+    pub balance: u64,
+    pub pending_strategy: Pubkey,
+    pub active_strategy: Pubkey,
+}
+
+impl LegacyStrategyData {
+    pub const SIZE: usize = 8 + 8 + 8 + 8 + 32 + 32;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -112,21 +244,193 @@ pub struct AmountShareOut {
     pub share_out: u64,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ReconcileOut {
+    pub skimmable_amount: u64,
+    pub shortfall_amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PendingStrategyOut {
+    pub strategy: Pubkey,
+    /// Time when pending strategy can be activated.
+    pub strategy_start_date: u64,
+    /// True if strategy delay is over and strategy can be activated.
+    pub activatable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone)]
+pub struct StrategyAllocation {
+    /// Strategy account.
+    pub strategy: Pubkey,
+    /// Target percentage of the strategy for `token`.
+    pub target_percentage: u64,
+    /// The balance of the strategy that BentoBox thinks is in there.
+    pub balance: u64,
+    /// Sum of all losses reported by the strategy.
+    pub cumulative_loss: u64,
+}
+
+impl StrategyAllocation {
+    pub const SIZE: usize = 32 + 8 + 8 + 8;
+}
+
 #[account]
 #[derive(Default)]
 pub struct StrategyData {
     /// Time in miliseconds when strategy will start.
     pub strategy_start_date: u64,
-    /// Target percentage of the strategy for `token`.
-    pub target_percentage: u64,
-    /// The balance of the strategy that BentoBox thinks is in there.
+    /// The total balance of all strategies that BentoBox thinks is in there.
     pub balance: u64,
     pub pending_strategy: Pubkey,
-    pub active_strategy: Pubkey,
+    /// Active strategies for `token` in rebalancing priority order.
+    pub strategies: Vec<StrategyAllocation>,
+    /// Order in which strategies are withdrawn from when the vault is short.
+    pub withdraw_order: Vec<Pubkey>,
+    /// Maximum loss of single harvest in basis points of token total amount, 0 if not limited.
+    pub max_loss_bps: u64,
+    /// Maximum sum of strategy losses in basis points of token total amount, 0 if not limited.
+    pub max_cumulative_loss_bps: u64,
+    /// Exit strategy instead of reverting harvest when loss limit is exceeded.
+    pub exit_on_max_loss: bool,
+    /// Reward of keeper from harvested profit in basis points, 0 if not rewarded.
+    pub keeper_reward_bps: u64,
 }
 
 impl StrategyData {
-    pub const SIZE: usize = 8 + 8 + 8 + 8 + 32 + 32;
+    pub const SIZE: usize = 8
+        + 8
+        + 8
+        + 32
+        + 4
+        + MAX_STRATEGIES_PER_TOKEN * StrategyAllocation::SIZE
+        + 4
+        + MAX_STRATEGIES_PER_TOKEN * 32
+        + 8
+        + 8
+        + 1
+        + 8;
+
+    /// Converts single strategy data in legacy layout to the current layout.
+    /// Active strategy becomes the only strategy in rebalancing priority and withdraw order, pending strategy is kept.
+    pub fn from_legacy(legacy: LegacyStrategyData) -> Self {
+        let mut strategy_data = StrategyData {
+            strategy_start_date: legacy.strategy_start_date,
+            balance: legacy.balance,
+            pending_strategy: legacy.pending_strategy,
+            ..Default::default()
+        };
+        if legacy.active_strategy != Pubkey::default() {
+            strategy_data.strategies.push(StrategyAllocation {
+                strategy: legacy.active_strategy,
+                target_percentage: legacy.target_percentage,
+                balance: legacy.balance,
+                cumulative_loss: 0,
+            });
+            strategy_data.withdraw_order.push(legacy.active_strategy);
+        }
+        strategy_data
+    }
+
+    /// Calculates keeper reward amount for strategy `profit`.
+    pub fn keeper_reward(&self, profit: u64) -> Result<u64> {
+        let reward = u128::from(profit)
+            .checked_mul(self.keeper_reward_bps.into())
+            .ok_or(CommonErrorCode::WrongIntegerMultiplication)?
+            / u128::from(BPS_DENOMINATOR);
+        Ok(reward
+            .try_into()
+            .map_err(|_| CommonErrorCode::TryIntoConversionError)?)
+    }
+
+    /// Returns true if there is queued strategy for token.
+    pub fn has_pending_strategy(&self) -> bool {
+        self.strategy_start_date != 0
+    }
+
+    /// Returns position of the strategy in the priority list.
+    pub fn strategy_index(&self, strategy: &Pubkey) -> Result<usize> {
+        require!(!self.strategies.is_empty(), ErrorCode::StrategyNotSet);
+        self.strategies
+            .iter()
+            .position(|allocation| allocation.strategy == *strategy)
+            .ok_or(error!(ErrorCode::InvalidStrategyAccount))
+    }
+
+    /// Sum of target percentages of all strategies.
+    pub fn total_target_percentage(&self) -> Result<u64> {
+        self.strategies
+            .iter()
+            .try_fold(0u64, |sum, allocation| sum.checked_add(allocation.target_percentage))
+            .ok_or(error!(CommonErrorCode::WrongIntegerAddition))
+    }
+
+    /// Calculates target balance of strategy at `index` for `total_elastic`.
+    pub fn target_balance(&self, index: usize, total_elastic: u128) -> Result<u128> {
+        match total_elastic.checked_mul(self.strategies[index].target_percentage.into()) {
+            Some(value) => Ok(value / 100),
+            None => Err(error!(CommonErrorCode::WrongIntegerMultiplication)),
+        }
+    }
+
+    /// Amount of idle tokens which should go to strategies with higher priority
+    /// than strategy at `index` before it can be invested.
+    pub fn reserved_amount(&self, index: usize, total_elastic: u128) -> Result<u128> {
+        let mut reserved: u128 = 0;
+        for i in 0..index {
+            let target_balance = self.target_balance(i, total_elastic)?;
+            reserved = reserved
+                .checked_add(target_balance.saturating_sub(self.strategies[i].balance.into()))
+                .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+        }
+        Ok(reserved)
+    }
+
+    /// Checks if `loss` reported by strategy at `index` exceeds loss limits.
+    pub fn is_loss_limit_exceeded(
+        &self,
+        index: usize,
+        loss: u64,
+        total_elastic: u128,
+    ) -> Result<bool> {
+        let cumulative_loss = self.strategies[index]
+            .cumulative_loss
+            .checked_add(loss)
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+
+        Ok(
+            exceeds_bps(loss, self.max_loss_bps, total_elastic)?
+                || exceeds_bps(cumulative_loss, self.max_cumulative_loss_bps, total_elastic)?,
+        )
+    }
+
+    /// Increases balance of strategy at `index` and total strategies balance.
+    pub fn add_balance(&mut self, index: usize, amount: u64) -> Result<()> {
+        let allocation = &mut self.strategies[index];
+        allocation.balance = allocation
+            .balance
+            .checked_add(amount)
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+        self.balance = self
+            .balance
+            .checked_add(amount)
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+        Ok(())
+    }
+
+    /// Decreases balance of strategy at `index` and total strategies balance.
+    pub fn sub_balance(&mut self, index: usize, amount: u64) -> Result<()> {
+        let allocation = &mut self.strategies[index];
+        allocation.balance = allocation
+            .balance
+            .checked_sub(amount)
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+        self.balance = self
+            .balance
+            .checked_sub(amount)
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+        Ok(())
+    }
 }
 
 #[zero_copy(unsafe)]
@@ -153,8 +457,22 @@ impl From<BentoboxRebase> for Rebase {
     }
 }
 
+/// Checks if `amount` is more than `bps` basis points of `total`. Zero `bps` means no limit.
+fn exceeds_bps(amount: u64, bps: u64, total: u128) -> Result<bool> {
+    if bps == 0 {
+        return Ok(false);
+    }
+    let amount_bps = u128::from(amount)
+        .checked_mul(BPS_DENOMINATOR.into())
+        .ok_or(CommonErrorCode::WrongIntegerMultiplication)?;
+    let limit = total
+        .checked_mul(bps.into())
+        .ok_or(CommonErrorCode::WrongIntegerMultiplication)?;
+    Ok(amount_bps > limit)
+}
+
 /// Calculates the total balance of `token` this contracts holds,
-/// plus the total amount this contract thinks the strategy holds.
+/// plus the total amount this contract thinks the strategies hold.
 pub fn token_balance_of(
     bentobox_vault: &TokenAccount,
     strategy_data: &StrategyData,
@@ -162,7 +480,7 @@ pub fn token_balance_of(
     Ok(bentobox_vault
         .amount
         .checked_add(strategy_data.balance)
-        .ok_or(ErrorCode::WrongIntegerAddition)?
+        .ok_or(CommonErrorCode::WrongIntegerAddition)?
         .into())
 }
 
@@ -258,11 +576,12 @@ pub struct BaseHarvest<'info> {
     /// Token program account.
     pub token_program: Program<'info, Token>,
     /// Account for storing result from strategy harvest implementation.
+    /// Only for strategies which do not return result via return data.
     /// CHECK: on owner and on rent_exempt enforce.
     #[account(mut, 
               rent_exempt = enforce,
               constraint = cpi_result_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
-    pub cpi_result_account: UncheckedAccount<'info>,
+    pub cpi_result_account: Option<UncheckedAccount<'info>>,
 }
 
 impl<'info> BaseHarvest<'info> {
@@ -274,7 +593,13 @@ impl<'info> BaseHarvest<'info> {
             bentobox_account: self.bentobox_account.to_account_info(),
             strategy_account: self.strategy_account.to_account_info(),
             authority: self.authority.to_account_info(),
-            cpi_result_account: self.cpi_result_account.to_account_info(),
+            // strategy program id is passed in place of absent optional account
+            cpi_result_account: self
+                .cpi_result_account
+                .as_ref()
+                .map_or(self.strategy_program.to_account_info(), |account| {
+                    account.to_account_info()
+                }),
             strategy_vault: self.strategy_vault.to_account_info(),
             strategy_authority: self.strategy_authority.to_account_info(),
             token_program: self.token_program.to_account_info(),
@@ -3,13 +3,18 @@ use anchor_lang::context::CpiContext;
 use anchor_lang::solana_program::account_info::AccountInfo;
 use anchor_lang::Result;
 use anchor_lang::{prelude::*, solana_program::pubkey::Pubkey};
-use anchor_lang::{Accounts, ToAccountInfos};
+use anchor_lang::{Accounts, Discriminator, ToAccountInfos};
 
 use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
 
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use strategy_package::cpi::accounts::{Transfer as StrategyTransfer, Withdraw as StrategyWithdraw};
 
-use common::constants::{CAULDRON_SEED_PART, DISCRIMINATOR_BYTES};
+use common::constants::{CAULDRON_SEED_PART, DISCRIMINATOR_BYTES, STRATEGY_VAULT_SEED_PART};
 use common::utils::calculate_end_byte_to_serialize;
 
 // use solana_program::instruction::AccountMeta;
@@ -21,8 +26,9 @@ use std::convert::TryInto;
 // use bentobox_package::{base_harvest, base_skim, base_withdraw};
 // use crate::{base_harvest,base_skim,base_withdraw};
 use common::errors::ErrorCode as CommonErrorCode;
+use common::rebase::Rebase;
 
-use crate::state::{BaseStrategyInfo,ResultAmount};
+use crate::state::BaseStrategyInfo;
 
 pub fn flash_loan<'info>(
     ctx: CpiContext<'_, '_, '_, 'info, SPLFlashLoan<'info>>,
@@ -220,12 +226,22 @@ pub fn allowed<'info>(
             &bentobox_program_id,
         );
 
+        require!(
+            whitelisted_account.whitelisted,
+            ErrorCode::MasterContractNotWhitelisted
+        );
+
         require!(
             approved_account.master_contract_whitelisted == whitelisted_account.key()
                 && expected_approved_account_address == approved_account.key()
                 && approved_account.approved != false,
                 ErrorCode::MasterContractNotApproved
         );
+
+        require!(
+            approved_account.approvals_epoch == whitelisted_account.approvals_epoch,
+            ErrorCode::MasterContractApprovalRevoked
+        );
     }
     Ok(())
 }
@@ -233,15 +249,345 @@ pub fn allowed<'info>(
 pub fn create_contract_approval_internal<'info>(
     approved: bool,
     master_contract_approved: &mut Box<Account<'info, MasterContractApproved>>,
-    master_contract_whitelisted_key: Pubkey,
+    master_contract_whitelisted: &Account<'info, MasterContractWhitelisted>,
 ) -> Result<()> {
     let master_contract_approved = master_contract_approved;
-    master_contract_approved.master_contract_whitelisted = master_contract_whitelisted_key;
+    master_contract_approved.master_contract_whitelisted = master_contract_whitelisted.key();
     master_contract_approved.approved = approved;
+    master_contract_approved.approvals_epoch = master_contract_whitelisted.approvals_epoch;
+
+    Ok(())
+}
+
+/// Checks that instruction right before the current one is the ed25519 program
+/// verification of `message` signed by `signer`, with all data inside that instruction.
+pub fn verify_ed25519_instruction(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    // header: num_signatures (u8) + padding (u8), then 14 bytes of offsets per signature
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+
+    let current_index = load_current_index_checked(instructions)?;
+    require!(current_index > 0, ErrorCode::InvalidPermitSignature);
+
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions)?;
+    require!(
+        ix.program_id == ed25519_program::ID && ix.accounts.is_empty(),
+        ErrorCode::InvalidPermitSignature
+    );
+
+    let data = &ix.data;
+    require!(
+        data.len() >= OFFSETS_START + OFFSETS_SIZE && data[0] == 1,
+        ErrorCode::InvalidPermitSignature
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = OFFSETS_START;
+    let signature_instruction_index = read_u16(offsets + 2);
+    let public_key_offset = read_u16(offsets + 4) as usize;
+    let public_key_instruction_index = read_u16(offsets + 6);
+    let message_data_offset = read_u16(offsets + 8) as usize;
+    let message_data_size = read_u16(offsets + 10) as usize;
+    let message_instruction_index = read_u16(offsets + 12);
+
+    // signature, public key and message should be taken from the ed25519 instruction itself
+    require!(
+        signature_instruction_index == u16::MAX
+            && public_key_instruction_index == u16::MAX
+            && message_instruction_index == u16::MAX,
+        ErrorCode::InvalidPermitSignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidPermitSignature)?;
+    let signed_message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(ErrorCode::InvalidPermitSignature)?;
+
+    require!(
+        public_key == signer.as_ref() && signed_message == message,
+        ErrorCode::InvalidPermitSignature
+    );
 
     Ok(())
 }
 
+/// Returns count of remaining accounts which `allowed` expects for `authority`.
+pub fn allowed_accounts_count(
+    from_owner: &Pubkey,
+    authority: &Pubkey,
+    bentobox_account: &Pubkey,
+    bentobox_program_id: &Pubkey,
+) -> usize {
+    let (_bentobox_address, _) = Pubkey::find_program_address(
+        &[BENTOBOX_SEED_PART, bentobox_account.as_ref()],
+        bentobox_program_id,
+    );
+
+    if from_owner != authority && authority != &_bentobox_address {
+        REMAINING_ACCOUNTS_COUNT_FOR_ALLOWED
+    } else {
+        0
+    }
+}
+
+/// Helper function for withdraw. Pulls `shortfall` from strategies of token to `bentobox_vault`.
+/// Strategies are withdrawn from in withdraw order, skipping strategies without balance,
+/// until `shortfall` is covered or accounts of provided strategies are used up.
+///
+/// Arguments:
+///
+/// * `strategy_accounts` - `strategy_data`, `bentobox_program`, then for each strategy in withdraw order:
+///                         `strategy_program`, `strategy_account`, `strategy_vault`, `strategy_authority`,
+///                         `base_strategy_info`, `strategy_program_whitelisted` and remaining accounts of strategy.
+///                         Remaining accounts of strategy end where `strategy_program` and `strategy_account`
+///                         of the next strategy start.
+/// * `shortfall`         - The amount which is missing in `bentobox_vault`.
+/// * `signer`            - Bentobox pda signature.
+pub fn withdraw_from_strategies<'info>(
+    program_id: &Pubkey,
+    bentobox_account: &Box<Account<'info, BentoBox>>,
+    bentobox_vault: &Box<Account<'info, TokenAccount>>,
+    vault_authority: &UncheckedAccount<'info>,
+    token_program: &Program<'info, Token>,
+    strategy_accounts: &[AccountInfo<'info>],
+    shortfall: u64,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    require!(
+        strategy_accounts.len() >= STRATEGY_WITHDRAW_ACCOUNTS_COUNT,
+        ErrorCode::InvalidRemainingAccountsCount
+    );
+
+    let mut strategy_data = Account::<StrategyData>::try_from(&strategy_accounts[0])?;
+    let (strategy_data_address, _) = Pubkey::find_program_address(
+        &[
+            STRATEGY_DATA_SEED_PART,
+            bentobox_account.key().as_ref(),
+            bentobox_vault.mint.as_ref(),
+        ],
+        program_id,
+    );
+    require!(
+        strategy_data.key() == strategy_data_address,
+        ErrorCode::InvalidStrategyDataAccount
+    );
+    require!(
+        strategy_accounts[1].key() == *program_id,
+        ErrorCode::InvalidBentoboxProgramAccount
+    );
+
+    let withdraw_order: Vec<Pubkey> = strategy_data
+        .withdraw_order
+        .iter()
+        .filter(|strategy| {
+            strategy_data
+                .strategy_index(strategy)
+                .map_or(false, |i| strategy_data.strategies[i].balance > 0)
+        })
+        .cloned()
+        .collect();
+
+    let mut remaining_shortfall = shortfall;
+    let mut start = STRATEGY_WITHDRAW_ACCOUNTS_COUNT - STRATEGY_ACCOUNTS_COUNT;
+
+    for (position, strategy_key) in withdraw_order.iter().enumerate() {
+        if remaining_shortfall == 0 || start == strategy_accounts.len() {
+            break;
+        }
+        require!(
+            strategy_accounts.len() >= start + STRATEGY_ACCOUNTS_COUNT,
+            ErrorCode::InvalidRemainingAccountsCount
+        );
+
+        let strategy_program = &strategy_accounts[start];
+        let strategy_account = &strategy_accounts[start + 1];
+        require!(
+            strategy_account.key == strategy_key,
+            ErrorCode::StrategyNotNextInWithdrawOrder
+        );
+        require!(
+            strategy_account.owner == strategy_program.key,
+            CommonErrorCode::InvalidAccountOwnerStrategyProgram
+        );
+        check_strategy_program_whitelisted(
+            program_id,
+            &bentobox_account.key(),
+            strategy_program.key,
+            &strategy_accounts[start + 5],
+        )?;
+
+        // accounts of the next strategy start with its program followed by its account
+        let end = withdraw_order
+            .get(position + 1)
+            .and_then(|next_strategy| {
+                (start + STRATEGY_ACCOUNTS_COUNT..strategy_accounts.len() - 1)
+                    .find(|&i| strategy_accounts[i + 1].key == next_strategy)
+            })
+            .unwrap_or(strategy_accounts.len());
+
+        let index = strategy_data.strategy_index(strategy_key)?;
+        let amount_in = remaining_shortfall.min(strategy_data.strategies[index].balance);
+
+        let base_strategy_info = &strategy_accounts[start + 4];
+        require!(
+            base_strategy_info.owner == strategy_program.key,
+            CommonErrorCode::InvalidAccountOwnerStrategyProgram
+        );
+        let base_strategy_info_data = BaseStrategyInfo::deserialize(
+            &mut &base_strategy_info.try_borrow_data()?[DISCRIMINATOR_BYTES
+                ..calculate_end_byte_to_serialize(BaseStrategyInfo::SIZE, false)],
+        )?;
+        is_strategy_active(&base_strategy_info_data)?;
+
+        let mut strategy_vault = Account::<TokenAccount>::try_from(&strategy_accounts[start + 2])?;
+        let strategy_authority = &strategy_accounts[start + 3];
+        let (strategy_vault_address, _) = Pubkey::find_program_address(
+            &[
+                STRATEGY_VAULT_SEED_PART,
+                strategy_account.key.as_ref(),
+                bentobox_vault.mint.as_ref(),
+            ],
+            strategy_program.key,
+        );
+        require!(
+            strategy_vault.key() == strategy_vault_address
+                && strategy_vault.owner == strategy_authority.key(),
+            CommonErrorCode::StrategyVaultInvalidAuthority
+        );
+
+        // bentobox authority signs withdraw and transfer with `signer` seeds
+        let withdrawn = strategy_package::cpi::withdraw(
+            CpiContext::new(
+                strategy_program.clone(),
+                StrategyWithdraw {
+                    strategy_program: strategy_program.clone(),
+                    bentobox_program: strategy_accounts[1].clone(),
+                    bentobox_account: bentobox_account.to_account_info(),
+                    strategy_account: strategy_account.clone(),
+                    base_strategy_info: base_strategy_info.clone(),
+                    authority: vault_authority.to_account_info(),
+                    strategy_vault: strategy_vault.to_account_info(),
+                    strategy_authority: strategy_authority.clone(),
+                    token_program: token_program.to_account_info(),
+                },
+            )
+            .with_signer(signer)
+            .with_remaining_accounts(
+                strategy_accounts[start + STRATEGY_ACCOUNTS_COUNT..end].to_vec(),
+            ),
+            amount_in,
+        )?;
+        strategy_vault.reload()?;
+
+        let actual_amount_in = withdrawn_or_vault_amount(withdrawn, &strategy_vault)?;
+        strategy_package::cpi::transfer(
+            CpiContext::new(
+                strategy_program.clone(),
+                StrategyTransfer {
+                    strategy_program: strategy_program.clone(),
+                    bentobox_program: strategy_accounts[1].clone(),
+                    bentobox_account: bentobox_account.to_account_info(),
+                    strategy_vault: strategy_vault.to_account_info(),
+                    bentobox_vault: bentobox_vault.to_account_info(),
+                    strategy_account: strategy_account.clone(),
+                    authority: vault_authority.to_account_info(),
+                    strategy_authority: strategy_authority.clone(),
+                    token_program: token_program.to_account_info(),
+                },
+            )
+            .with_signer(signer),
+            actual_amount_in,
+        )?;
+
+        strategy_data.sub_balance(index, actual_amount_in)?;
+        remaining_shortfall = remaining_shortfall.saturating_sub(actual_amount_in);
+
+        emit!(LogStrategyDivest {
+            token: bentobox_vault.mint,
+            amount: actual_amount_in,
+        });
+
+        start = end;
+    }
+
+    strategy_data.exit(program_id)?;
+
+    Ok(())
+}
+
+/// Helper function exits strategy at `index`, books profit or loss and removes strategy
+/// from rebalancing priority and withdraw order.
+pub fn exit_strategy<'info>(
+    ctx: Context<'_, '_, '_, 'info, BaseExit<'info>>,
+    total_data: &mut Total,
+    strategy_data: &mut StrategyData,
+    index: usize,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    let token = ctx.accounts.bentobox_vault.mint;
+    let strategy_key = strategy_data.strategies[index].strategy;
+    let strategy_balance = strategy_data.strategies[index].balance;
+
+    let balance_change: i64 = base_exit(ctx, strategy_balance, signer)?;
+
+    if balance_change > 0 {
+        let add = balance_change as u64;
+        total_data.amount.elastic = total_data
+            .amount
+            .elastic
+            .checked_add(add.into())
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+        emit!(LogStrategyProfit { token, amount: add });
+    } else if balance_change < 0 {
+        let sub = -balance_change as u64;
+        total_data.amount.elastic = total_data
+            .amount
+            .elastic
+            .checked_sub(sub.into())
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+        emit!(LogStrategyLoss { token, amount: sub });
+    }
+
+    emit!(LogStrategyDivest {
+        token,
+        amount: strategy_balance,
+    });
+
+    strategy_data.sub_balance(index, strategy_balance)?;
+    strategy_data.strategies.remove(index);
+    strategy_data
+        .withdraw_order
+        .retain(|strategy| *strategy != strategy_key);
+
+    emit!(LogStrategyRemoved {
+        token,
+        strategy: strategy_key,
+    });
+
+    Ok(())
+}
+
+/// Helper function mints shares for `amount` of profit to `balance`, tokens stay in BentoBox.
+/// `amount` should not be added to total elastic before.
+fn mint_profit_shares(total_data: &mut Total, amount: u64, balance: &mut Balance) -> Result<u64> {
+    let mut total_data_amount: Rebase = total_data.amount.into();
+    let share = total_data_amount.to_base(amount, false)?;
+    total_data_amount = total_data_amount.add_e_b(amount, share)?;
+    total_data.amount = BentoboxRebase::from(total_data_amount);
+
+    balance.amount = balance
+        .amount
+        .checked_add(share)
+        .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+    Ok(share)
+}
+
 /// Helper function for harvest.
 pub fn harvest_internal<'info>(
     balance: bool,
@@ -258,12 +604,15 @@ pub fn harvest_internal<'info>(
     strategy_authority: &mut UncheckedAccount<'info>,
     token_program: &Program<'info, Token>,
     base_strategy_info: &UncheckedAccount<'info>,
-    cpi_result_account: &mut UncheckedAccount<'info>,
+    cpi_result_account: &Option<UncheckedAccount<'info>>,
+    fee_to_balance: &mut Option<Box<Account<'info, Balance>>>,
+    keeper_balance: &mut Option<Box<Account<'info, Balance>>>,
     remaining_accounts: &[AccountInfo<'info>],
     bump: u8,
 ) -> Result<()> {
     let bentobox_key = bentobox_account.key();
     let bentobox_program_key = bentobox_program.key();
+    let index = strategy_data.strategy_index(&strategy_account.key())?;
 
     let authority_seeds = &[BENTOBOX_SEED_PART, bentobox_key.as_ref(), &[bump]];
     let bentobox_pda_signer = &[&authority_seeds[..]];
@@ -295,7 +644,7 @@ pub fn harvest_internal<'info>(
 
     let balance_change: i64 = base_harvest(
         harvest_ctx,
-        strategy_data.balance,
+        strategy_data.strategies[index].balance,
         bentobox_pda_signer,
         total_data.amount.elastic,
     )?;
@@ -309,12 +658,55 @@ pub fn harvest_internal<'info>(
 
     if balance_change > 0 {
         let add = balance_change as u64;
+        // Performance fee and keeper reward are only taken when there are depositors to share the profit with.
+        // Keeper reward is only taken when harvested by keeper with `Balance` account for the reward.
+        let (fee, keeper_reward) = if total_data.amount.base == 0 {
+            (0, 0)
+        } else if keeper_balance.is_none() {
+            (bentobox_account.performance_fee(add)?, 0)
+        } else {
+            (
+                bentobox_account.performance_fee(add)?,
+                strategy_data.keeper_reward(add)?,
+            )
+        };
+        let depositors_profit = add
+            .checked_sub(fee)
+            .and_then(|profit| profit.checked_sub(keeper_reward))
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
         total_data.amount.elastic = total_data
             .amount
             .elastic
-            .checked_add(add.into())
+            .checked_add(depositors_profit.into())
             .ok_or(CommonErrorCode::WrongIntegerAddition)?;
 
+        if fee > 0 {
+            let fee_to_balance = fee_to_balance
+                .as_mut()
+                .ok_or(ErrorCode::FeeToBalanceMissing)?;
+            let share = mint_profit_shares(total_data, fee, fee_to_balance)?;
+
+            emit!(LogStrategyFee {
+                token: bentobox_vault.mint,
+                fee_to: bentobox_account.fee_to,
+                amount: fee,
+                share,
+            });
+        }
+
+        if keeper_reward > 0 {
+            if let Some(keeper_balance) = keeper_balance.as_mut() {
+                let share = mint_profit_shares(total_data, keeper_reward, keeper_balance)?;
+
+                emit!(LogStrategyKeeperReward {
+                    token: bentobox_vault.mint,
+                    keeper: keeper_balance.owner,
+                    amount: keeper_reward,
+                    share,
+                });
+            }
+        }
+
         emit!(LogStrategyProfit {
             token: bentobox_vault.mint,
             amount: add,
@@ -323,40 +715,88 @@ pub fn harvest_internal<'info>(
         // balanceChange could overflow if it's max negative int128.
         // But tokens with balances that large are not supported by the BentoBox.
         let sub = -balance_change as u64;
+        let loss_limit_exceeded =
+            strategy_data.is_loss_limit_exceeded(index, sub, total_data.amount.elastic)?;
+        require!(
+            !loss_limit_exceeded || strategy_data.exit_on_max_loss,
+            ErrorCode::StrategyLossTooHigh
+        );
+
         total_data.amount.elastic = total_data
             .amount
             .elastic
             .checked_sub(sub.into())
             .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
-        strategy_data.balance = strategy_data
-            .balance
-            .checked_sub(sub)
-            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+        strategy_data.sub_balance(index, sub)?;
+        let allocation = &mut strategy_data.strategies[index];
+        allocation.cumulative_loss = allocation
+            .cumulative_loss
+            .checked_add(sub)
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?;
         emit!(LogStrategyLoss {
             token: bentobox_vault.mint,
             amount: sub,
         });
+
+        if loss_limit_exceeded {
+            emit!(LogStrategyLossLimitExceeded {
+                token: bentobox_vault.mint,
+                strategy: strategy_account.key(),
+                loss: sub,
+                cumulative_loss: strategy_data.strategies[index].cumulative_loss,
+            });
+
+            let mut exit_accounts = BaseExit {
+                strategy_program: strategy_program.clone(),
+                bentobox_program: bentobox_program.clone(),
+                bentobox_account: UncheckedAccount::try_from(bentobox_account.to_account_info()),
+                bentobox_vault: bentobox_vault.clone(),
+                strategy_vault: strategy_vault.clone(),
+                token_program: token_program.clone(),
+                base_strategy_info: base_strategy_info.clone(),
+                authority: bentobox_authority.clone(),
+                strategy_account: strategy_account.clone(),
+                strategy_authority: strategy_authority.clone(),
+            };
+
+            return exit_strategy(
+                Context::new(
+                    &bentobox_program_key,
+                    &mut exit_accounts,
+                    &remaining_accounts,
+                    BTreeMap::new(),
+                ),
+                total_data,
+                strategy_data,
+                index,
+                bentobox_pda_signer,
+            );
+        }
     }
 
     if balance {
-        let target_balance = match total_data
-            .amount
-            .elastic
-            .checked_mul(strategy_data.target_percentage.into())
-        {
-            Some(value) => value / 100,
-            None => return Err(error!(CommonErrorCode::WrongIntegerMultiplication)),
-        };
-        let strategy_data_balance: u128 = strategy_data.balance.into();
+        let target_balance = strategy_data.target_balance(index, total_data.amount.elastic)?;
+        let strategy_data_balance: u128 = strategy_data.strategies[index].balance.into();
 
         if strategy_data_balance < target_balance {
             let mut amount_out = target_balance
-                .checked_sub(strategy_data.balance.into())
+                .checked_sub(strategy_data_balance)
                 .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
 
             if max_change_amount != 0 && amount_out > max_change_amount.into() {
                 amount_out = max_change_amount.into();
             }
+
+            // idle tokens go to strategies with higher priority first
+            let available_amount = u128::from(bentobox_vault.amount).saturating_sub(
+                strategy_data.reserved_amount(index, total_data.amount.elastic)?,
+            );
+            if amount_out > available_amount {
+                amount_out = available_amount;
+            }
+            if amount_out == 0 {
+                return Ok(());
+            }
             // transfer to strategy
             let transfer_ctx = CpiContext::new(
                 token_program.to_account_info(),
@@ -375,10 +815,7 @@ pub fn harvest_internal<'info>(
             token::transfer(transfer_ctx, amount_out_converted)?;
             // end transfer
 
-            strategy_data.balance = strategy_data
-                .balance
-                .checked_add(amount_out_converted)
-                .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+            strategy_data.add_balance(index, amount_out_converted)?;
 
             //skim
             let mut skim_accounts = BaseSkim {
@@ -445,10 +882,7 @@ pub fn harvest_internal<'info>(
                 bentobox_pda_signer,
             )?;
 
-            strategy_data.balance = strategy_data
-                .balance
-                .checked_sub(actual_amount_in)
-                .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+            strategy_data.sub_balance(index, actual_amount_in)?;
 
             emit!(LogStrategyDivest {
                 token: bentobox_vault.mint,
@@ -473,6 +907,31 @@ pub fn is_strategy_active(base_strategy_info: &BaseStrategyInfo) -> Result<()> {
     Ok(())
 }
 
+/// Checks that `strategy_program_whitelisted` is the whitelisted account of `strategy_program` in Bentobox.
+pub fn check_strategy_program_whitelisted(
+    program_id: &Pubkey,
+    bentobox_account: &Pubkey,
+    strategy_program: &Pubkey,
+    strategy_program_whitelisted: &AccountInfo,
+) -> Result<()> {
+    let (strategy_program_whitelisted_address, _) = Pubkey::find_program_address(
+        &[
+            WHITELISTED_STRATEGY_PROGRAM_PART,
+            bentobox_account.as_ref(),
+            strategy_program.as_ref(),
+        ],
+        program_id,
+    );
+    let whitelisted = Account::<StrategyProgramWhitelisted>::try_from(strategy_program_whitelisted)
+        .map_or(false, |account| account.whitelisted);
+
+    require!(
+        strategy_program_whitelisted.key() == strategy_program_whitelisted_address && whitelisted,
+        ErrorCode::StrategyProgramNotWhitelisted
+    );
+    Ok(())
+}
+
 /// Call concrete strategy skim to invest tokens.
 /// Base function for deposit strategy skim through Bentobox.
 /// 
@@ -491,7 +950,7 @@ pub fn base_skim<'info>(
         .create_skim_context()
         .with_signer(signer)
         .with_remaining_accounts(ctx.remaining_accounts.to_vec());
-    strategy_mock::cpi::skim(cpi_ctx, amount)?;
+    strategy_package::cpi::skim(cpi_ctx, amount)?;
     Ok(())
 }
 
@@ -532,16 +991,14 @@ pub fn base_harvest<'info>(
             .create_harvest_context()
             .with_signer(signer)
             .with_remaining_accounts(ctx.remaining_accounts.to_vec());
-        strategy_mock::cpi::harvest(harvest_cpi_ctx, balance)?;
+        let result = strategy_package::cpi::harvest(harvest_cpi_ctx, balance)?;
 
         ctx.accounts.strategy_vault.reload()?;
 
-        let var = ctx.accounts.cpi_result_account.try_borrow_data()?;
-        let mut result_bytes =
-            &var[DISCRIMINATOR_BYTES..calculate_end_byte_to_serialize(ResultAmount::SIZE, false)];
-        let result_decoded = ResultAmount::deserialize(&mut result_bytes)?;
-
-        let amount: i64 = result_decoded.amount;
+        let amount: i64 = match result {
+            Some(result) => result.amount,
+            None => read_cpi_result_account(&ctx.accounts.cpi_result_account)?,
+        };
         // Since harvesting of rewards is accounted for seperately we might also have
         //some underlying tokens in the contract that the _harvest call doesn't report.
         //E.g. reward tokens that have been sold into the underlying tokens which are now sitting in the contract.
@@ -553,7 +1010,7 @@ pub fn base_harvest<'info>(
         if amount >= 0 {
             // harvest reported a profit
             if contract_balance > 0 {
-                strategy_mock::cpi::transfer(transfer_cpi_ctx, contract_balance)?;
+                strategy_package::cpi::transfer(transfer_cpi_ctx, contract_balance)?;
             }
 
             return Ok(contract_balance as i64);
@@ -573,11 +1030,11 @@ pub fn base_harvest<'info>(
             if diff > 0 {
                 // we still made some profit
                 // send the profit to BentoBox and reinvest the rest
-                strategy_mock::cpi::transfer(transfer_cpi_ctx, diff as u64)?;
-                strategy_mock::cpi::skim(skim_cpi_ctx, -amount as u64)?;
+                strategy_package::cpi::transfer(transfer_cpi_ctx, diff as u64)?;
+                strategy_package::cpi::skim(skim_cpi_ctx, -amount as u64)?;
             } else {
                 // we made a loss but we have some tokens we can reinvest
-                strategy_mock::cpi::skim(skim_cpi_ctx, contract_balance)?;
+                strategy_package::cpi::skim(skim_cpi_ctx, contract_balance)?;
             }
             return Ok(diff);
         } else {
@@ -588,6 +1045,34 @@ pub fn base_harvest<'info>(
     return Ok(0);
 }
 
+/// Reads harvest result from `cpi_result_account` for strategies which do not set return data.
+#[cfg(feature = "cpi-result-account")]
+fn read_cpi_result_account(cpi_result_account: &Option<UncheckedAccount>) -> Result<i64> {
+    let cpi_result_account = cpi_result_account
+        .as_ref()
+        .ok_or(ErrorCode::StrategyReturnDataMissing)?;
+    let var = cpi_result_account.try_borrow_data()?;
+    let mut result_bytes =
+        &var[DISCRIMINATOR_BYTES..calculate_end_byte_to_serialize(ResultAmount::SIZE, false)];
+    let result_decoded = ResultAmount::deserialize(&mut result_bytes)?;
+    Ok(result_decoded.amount)
+}
+
+#[cfg(not(feature = "cpi-result-account"))]
+fn read_cpi_result_account(_cpi_result_account: &Option<UncheckedAccount>) -> Result<i64> {
+    Err(error!(ErrorCode::StrategyReturnDataMissing))
+}
+
+/// Returns amount withdrawn by strategy. Strategies which do not set return data
+/// are only supported with `cpi-result-account` feature, the whole strategy vault balance is used for them.
+fn withdrawn_or_vault_amount(withdrawn: Option<u64>, strategy_vault: &Account<TokenAccount>) -> Result<u64> {
+    match withdrawn {
+        Some(amount) => Ok(amount),
+        None if cfg!(feature = "cpi-result-account") => Ok(strategy_vault.amount),
+        None => Err(error!(ErrorCode::StrategyReturnDataMissing)),
+    }
+}
+
 /// Base strategy function for withdraw tokens from strategy to Bentobox.
 /// Withdraw tokens. The returned amount can differ from the requested amount due to rounding.
 ///
@@ -613,7 +1098,7 @@ pub fn base_withdraw<'info>(
     is_strategy_active(&base_strategy_info_decerialized)?;
 
     //_withdraw
-    strategy_mock::cpi::withdraw(
+    let withdrawn = strategy_package::cpi::withdraw(
         ctx.accounts
             .create_withdraw_context()
             .with_signer(signer)
@@ -622,8 +1107,8 @@ pub fn base_withdraw<'info>(
     )?;
     ctx.accounts.strategy_vault.reload()?;
 
-    let actual_amount = ctx.accounts.strategy_vault.amount;
-    strategy_mock::cpi::transfer(
+    let actual_amount = withdrawn_or_vault_amount(withdrawn, &ctx.accounts.strategy_vault)?;
+    strategy_package::cpi::transfer(
         ctx.accounts.create_transfer_context().with_signer(signer),
         actual_amount,
     )?;
@@ -650,13 +1135,13 @@ pub fn base_exit<'info>(
         .create_exit_context()
         .with_signer(signer)
         .with_remaining_accounts(ctx.remaining_accounts.to_vec());
-    strategy_mock::cpi::exit(cpi_ctx)?;
+    let withdrawn = strategy_package::cpi::exit(cpi_ctx)?;
     ctx.accounts.strategy_vault.reload()?;
-    let actual_balance = ctx.accounts.strategy_vault.amount;
+    let actual_balance = withdrawn_or_vault_amount(withdrawn, &ctx.accounts.strategy_vault)?;
     
     let amount_added = (actual_balance - balance) as i64;
     let transfer_cpi_ctx = ctx.accounts.create_transfer_context().with_signer(signer);
-    strategy_mock::cpi::transfer(transfer_cpi_ctx, actual_balance)?;
+    strategy_package::cpi::transfer(transfer_cpi_ctx, actual_balance)?;
     
     return Ok(amount_added);
 }
@@ -681,7 +1166,7 @@ pub fn base_safe_harvest<'info>(
             .create_harvest_rewards_context()
             .with_signer(signer)
             .with_remaining_accounts(ctx.remaining_accounts.to_vec());
-        strategy_mock::cpi::harvest_rewards(harvest_rewards_cpi_ctx)?;
+        strategy_package::cpi::harvest_rewards(harvest_rewards_cpi_ctx)?;
     }
 
     let safe_harvest_cpi_ctx = ctx
@@ -689,6 +1174,55 @@ pub fn base_safe_harvest<'info>(
         .create_safe_harvest_context()
         .with_signer(signer)
         .with_remaining_accounts(ctx.remaining_accounts.to_vec());
-    strategy_mock::cpi::safe_harvest(safe_harvest_cpi_ctx, max_balance)?;
+    strategy_package::cpi::safe_harvest(safe_harvest_cpi_ctx, max_balance)?;
     Ok(())
 }
+
+/// Reallocates program `account` of type `T` stored in legacy layout of `legacy_size` bytes to `new_size` bytes.
+/// Rent for the new size is paid by `payer`.
+///
+/// Return: Legacy account data without discriminator.
+pub fn realloc_legacy_account<'info, T: Discriminator>(
+    account: &AccountInfo<'info>,
+    legacy_size: usize,
+    new_size: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<Vec<u8>> {
+    require_keys_eq!(*account.owner, crate::ID, ErrorCode::InvalidLegacyAccount);
+
+    let legacy_data = {
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() == legacy_size && data[..DISCRIMINATOR_BYTES] == T::discriminator(),
+            ErrorCode::InvalidLegacyAccount
+        );
+        data[DISCRIMINATOR_BYTES..].to_vec()
+    };
+
+    let rent_diff = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(account.lamports());
+    if rent_diff > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_diff,
+        )?;
+    }
+    account.realloc(new_size, true)?;
+
+    Ok(legacy_data)
+}
+
+/// Writes `value` in current layout to `account` reallocated by `realloc_legacy_account`.
+pub fn write_migrated_account<T: AccountSerialize>(account: &AccountInfo, value: &T) -> Result<()> {
+    let mut data = account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    value.try_serialize(&mut writer)
+}
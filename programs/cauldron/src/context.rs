@@ -11,10 +11,7 @@ use bentobox::{
     state::{Balance, BentoBox, MasterContractApproved, MasterContractWhitelisted, Total as BentoBoxTotal},
 };
 
-use swapper_package::swapper_interface::Swap;
-use swapper_orca::cpi::accounts::Swap as SwapOrca;
-
-use swapper_raydium::cpi::accounts::Swap as SwapRaydium;
+use swapper_package::cpi::accounts::Swap;
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -56,6 +53,24 @@ pub struct CreateUserBalance<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct CloseUserBalance<'info> {
+    /// User balance pda account.
+    #[account(mut,
+              seeds = [USER_BALANCE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       authority.key().as_ref()],
+              bump,
+              constraint = user_balance.collateral_share == 0 && user_balance.borrow_part == 0 @ ErrorCode::UserBalanceIsNotEmpty,
+              close = authority)]
+    pub user_balance: Box<Account<'info, UserBalance>>,
+    /// Cauldron account.
+    pub cauldron_account: Box<Account<'info, Cauldron>>,
+    /// User which owns balance account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct CreateTotal<'info> {
     /// Cauldron pda total account.
@@ -220,6 +235,12 @@ pub struct Borrow<'info> {
     /// CHECK: account key.
     #[account(constraint = switchboard_data_feed.key() == cauldron_account.switchboard_data_feed @ ErrorCode::IncompatibleSwitchboardDataFeed)]
     pub switchboard_data_feed: UncheckedAccount<'info>,
+    /// Pool oracle pda account, used for price only if it is created for cauldron.
+    /// CHECK: seeds, loaded in `get_collateral_price`.
+    #[account(seeds = [POOL_ORACLE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()],
+              bump)]
+    pub pool_oracle: UncheckedAccount<'info>,
     // pub switchboard_data_feed: AccountLoader<'info,AggregatorAccountData>,
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -333,6 +354,12 @@ pub struct RemoveCollateral<'info> {
     /// CHECK: account key.
     #[account(constraint = switchboard_data_feed.key() == cauldron_account.switchboard_data_feed @ ErrorCode::IncompatibleSwitchboardDataFeed)]
     pub switchboard_data_feed: UncheckedAccount<'info>,
+    /// Pool oracle pda account, used for price only if it is created for cauldron.
+    /// CHECK: seeds, loaded in `get_collateral_price`.
+    #[account(seeds = [POOL_ORACLE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()],
+              bump)]
+    pub pool_oracle: UncheckedAccount<'info>,
     // pub switchboard_data_feed: AccountLoader<'info,AggregatorAccountData>,
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -705,6 +732,7 @@ pub struct BeginLiquidate<'info> {
     #[account(constraint = bentobox_program.key() == cauldron_account.bentobox_program @ ErrorCode::InvalidBentoboxProgramAccount)]
     pub bentobox_program: Program<'info, Bentobox>,
     /// Cauldron account.
+    #[account(mut)]
     pub cauldron_account: Box<Account<'info, Cauldron>>,
     /// Cauldron authority account.
     /// CHECK: seeds.
@@ -717,6 +745,12 @@ pub struct BeginLiquidate<'info> {
     #[account(constraint = switchboard_data_feed.key() == cauldron_account.switchboard_data_feed @ ErrorCode::IncompatibleSwitchboardDataFeed)]
     // pub switchboard_data_feed: AccountLoader<'info,AggregatorAccountData>,
     pub switchboard_data_feed: UncheckedAccount<'info>,
+    /// Pool oracle pda account, used for price only if it is created for cauldron.
+    /// CHECK: seeds, loaded in `get_collateral_price`.
+    #[account(seeds = [POOL_ORACLE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()],
+              bump)]
+    pub pool_oracle: UncheckedAccount<'info>,
     /// Cauldron pda total account.
     #[account(mut, 
               seeds = [TOTAL_SEED_PART.as_ref(),
@@ -775,7 +809,7 @@ pub struct LiquidateSwap<'info> {
     #[account(mut,
               seeds = [LIQUIDATOR_ACCOUNT_SEED_PART.as_ref(),
                        cauldron_account.key().as_ref(),
-                       liquidator_account.origin_liquidator.as_ref()],
+                       liquidator_account.payer.as_ref()],
               bump)]
     pub liquidator_account: Box<Account<'info, LiquidatorAccount>>,
     /// Cauldron account.
@@ -810,10 +844,19 @@ pub struct LiquidateSwap<'info> {
               constraint = cauldron_destination_vault.owner == cauldron_authority.key() @ ErrorCode::InvalidCauldronDestinationVault)]
     pub cauldron_destination_vault: Box<Account<'info, TokenAccount>>,
     /// Concreet swapper program account.
-    /// CHECK:
+    /// CHECK: should be executable and whitelisted in Cauldron.
+    #[account(executable)]
     pub swapper_program: UncheckedAccount<'info>,
-    /// Swap program account.
-    /// CHECK: inside swap
+    /// Swapper program PDA account that store whitelisted information.
+    #[account(seeds = [SWAPPER_WHITELISTED_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       swapper_program.key().as_ref()],
+              bump,
+              constraint = swapper_whitelisted.whitelisted @ ErrorCode::InvalidSwapper)]
+    pub swapper_whitelisted: Box<Account<'info, SwapperWhitelisted>>,
+    /// Swap program account, receives cauldron authority signature from swapper.
+    /// CHECK: one of swap programs of whitelisted swapper.
+    #[account(constraint = swapper_whitelisted.swap_programs.contains(swap_program.key) @ ErrorCode::InvalidSwapProgram)]
     pub swap_program: UncheckedAccount<'info>,
     /// Token program account.
     pub token_program: Program<'info, Token>,
@@ -837,61 +880,13 @@ impl<'info> LiquidateSwap<'info> {
             cauldron_authority.is_signer = true;
 
             Ok(CpiContext::new(self.swapper_program.to_account_info(), Swap {
-                source_token_account: self.cauldron_source_vault.clone(),
-                destination_token_account: self.cauldron_destination_vault.clone(),
-                token_program: self.token_program.clone(),
-                swap_program: self.swap_program.clone(),
-                authority: Signer::try_from(&cauldron_authority)?,
-            }))
-        }
-    pub fn create_orca_swap_ctx(&self) -> Result<CpiContext<'_,'_,'_,'info,SwapOrca<'info>>> {
-            let mut cauldron_authority = self.cauldron_authority.to_account_info();
-            cauldron_authority.is_signer = true;
-
-            Ok(CpiContext::new(self.swapper_program.to_account_info(), SwapOrca {
                 source_token_account: self.cauldron_source_vault.to_account_info(),
                 destination_token_account: self.cauldron_destination_vault.to_account_info(),
-                token_program: self.token_program.to_account_info(),
                 swap_program: self.swap_program.to_account_info(),
-                authority: Signer::try_from(&cauldron_authority)?.to_account_info(),
-            }))
-    }
-    pub fn create_raydium_swap_ctx(&self) -> Result<CpiContext<'_,'_,'_,'info,SwapRaydium<'info>>>{
-            let mut cauldron_authority = self.cauldron_authority.to_account_info();
-            cauldron_authority.is_signer = true;
-
-            Ok(CpiContext::new(self.swapper_program.to_account_info(), SwapRaydium {
-                source_token_account: self.cauldron_source_vault.to_account_info(),
-                destination_token_account: self.cauldron_destination_vault.to_account_info(),
                 token_program: self.token_program.to_account_info(),
-                swap_program: self.swap_program.to_account_info(),
-                authority: Signer::try_from(&cauldron_authority)?.to_account_info(),
+                authority: cauldron_authority,
             }))
-    }
-    // pub fn create_orca_swapper_ctx(&self) 
-    //     -> Result<CpiContext<'_,'_,'_,'info,SwapOrca<'info>>>{
-    //         let mut cauldron_authority = self.cauldron_authority.to_account_info();
-    //         cauldron_authority.is_signer = true;
-
-    //         Ok(CpiContext::new(self.swapper_program.to_account_info(),SwapOrca{
-    //             source_token_account: self.cauldron_source_vault.to_account_info(),
-    //             destination_token_account: self.cauldron_destination_vault.to_account_info(),
-    //             swap_program: self.swap_program.to_account_info(),
-    //             token_program: self.token_program.to_account_info(),
-    //             token_swap: self.token_swap.to_account_info(),
-    //             swap_authority: self.swap_authority.to_account_info(),
-    //             pool_source: todo!(),
-    //             pool_destination: todo!(),
-    //             pool_token_mint: todo!(),
-    //             pool_fee_account: todo!(),
-    //             host_fee_account: todo!(),
-    //             source_mint: todo!(),
-    //             destination_mint: todo!(),
-    //             cauldron_authority,
-    //             authority: Signer::try_from(&cauldron_authority)?.to_account_info(),
-    //         }))
-            
-    //     }
+        }
 }
 
 #[derive(Accounts)]
@@ -900,10 +895,14 @@ pub struct CompleteLiquidate<'info> {
     #[account(mut,
               seeds = [LIQUIDATOR_ACCOUNT_SEED_PART.as_ref(),
                        cauldron_account.key().as_ref(),
-                       liquidator_account.origin_liquidator.as_ref()],
+                       liquidator_account.payer.as_ref()],
               bump,
-              close = authority)]
+              close = payer)]
     pub liquidator_account: Box<Account<'info, LiquidatorAccount>>,
+    /// Liquidator which began liquidation, receives rent of liquidator account.
+    /// CHECK: liquidator account payer.
+    #[account(mut, constraint = payer.key() == liquidator_account.payer @ ErrorCode::InvalidLiquidatorAccountPayer)]
+    pub payer: UncheckedAccount<'info>,
     /// Cauldron MIM balance account on bentobox.
     #[account(mut)]
     pub cauldron_mim_bentobox_balance: Box<Account<'info, Balance>>,
@@ -951,6 +950,226 @@ pub struct CompleteLiquidate<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct CancelLiquidate<'info> {
+    /// Liquidator account pda account.
+    #[account(mut,
+              seeds = [LIQUIDATOR_ACCOUNT_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       liquidator_account.payer.as_ref()],
+              bump,
+              close = payer)]
+    pub liquidator_account: Box<Account<'info, LiquidatorAccount>>,
+    /// Liquidator which began liquidation, receives rent of liquidator account.
+    /// CHECK: liquidator account payer.
+    #[account(mut, constraint = payer.key() == liquidator_account.payer @ ErrorCode::InvalidLiquidatorAccountPayer)]
+    pub payer: UncheckedAccount<'info>,
+    /// Cauldron account.
+    #[account(mut)]
+    pub cauldron_account: Box<Account<'info, Cauldron>>,
+    /// Cauldron authority account.
+    /// CHECK: seeds.
+    #[account(mut, seeds = [CAULDRON_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()], 
+              bump)]
+    pub cauldron_authority: UncheckedAccount<'info>,
+    /// Cauldron pda total account.
+    #[account(mut, 
+              seeds = [TOTAL_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()],
+              bump,)]
+    pub total_data: AccountLoader<'info, Total>,
+    /// User balance pda account of liquidated user.
+    #[account(mut, 
+              seeds = [USER_BALANCE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       liquidator_account.user.as_ref()],
+              bump)]
+    pub user_balance: Box<Account<'info, UserBalance>>,
+    /// Bentobox account.
+    #[account(constraint = bentobox_account.key() == cauldron_account.bentobox @ ErrorCode::InvalidBentoboxAccount)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// Bentobox program account.
+    #[account(constraint = bentobox_program.key() == cauldron_account.bentobox_program @ ErrorCode::InvalidBentoboxProgramAccount)]
+    pub bentobox_program: Program<'info, Bentobox>,
+    /// Collateral mint account.
+    #[account(constraint = collateral.key() == cauldron_account.collateral @ ErrorCode::InvalidCollateral)]
+    pub collateral: Box<Account<'info, Mint>>,
+    /// Cauldron collateral vault account.
+    #[account(mut,
+              seeds = [TOTAL_VAULT_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       collateral.key().as_ref()],
+              bump)]
+    pub cauldron_source_vault: Box<Account<'info, TokenAccount>>,
+    /// Cauldron collateral balance account on bentobox.
+    #[account(mut)]
+    pub cauldron_collateral_bentobox_balance: Box<Account<'info, Balance>>,
+    /// Collateral total account which stores all Bentobox amount by token.
+    /// CHECK: inside bento-deposit.
+    #[account(mut)]
+    pub bentobox_collateral_total_data: UncheckedAccount<'info>,
+    /// Bentobox collateral vault account.
+    #[account(mut)]
+    pub bentobox_collateral_vault: Box<Account<'info, TokenAccount>>,
+    /// Strategy data account for collateral token.
+    /// CHECK: inside bento-deposit.
+    pub collateral_strategy_data: UncheckedAccount<'info>,
+    /// Token program account.
+    pub token_program: Program<'info, Token>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(payer: Pubkey)]
+pub struct MigrateLiquidatorAccount<'info> {
+    /// Liquidator account in legacy layout.
+    /// CHECK: layout checked in instruction.
+    #[account(mut,
+              seeds = [LIQUIDATOR_ACCOUNT_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       payer.as_ref()],
+              bump)]
+    pub liquidator_account: UncheckedAccount<'info>,
+    /// Cauldron account.
+    #[account(has_one = authority)]
+    pub cauldron_account: Box<Account<'info, Cauldron>>,
+    /// Cauldron authority account, pays rent for the new account size.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct FlashLiquidate<'info> {
+    /// Collateral total account which stores all Bentobox amount by token.
+    #[account(mut)]
+    pub bentobox_collateral_total_data: AccountLoader<'info, BentoBoxTotal>,
+    /// MiM total account which stores all Bentobox amount by token.
+    #[account(mut)]
+    pub bentobox_mim_total_data: AccountLoader<'info, BentoBoxTotal>,
+    /// Bentobox account.
+    #[account(constraint = bentobox_account.key() == cauldron_account.bentobox @ ErrorCode::InvalidBentoboxAccount)]
+    pub bentobox_account: Box<Account<'info, BentoBox>>,
+    /// Bentobox program account.
+    #[account(constraint = bentobox_program.key() == cauldron_account.bentobox_program @ ErrorCode::InvalidBentoboxProgramAccount)]
+    pub bentobox_program: Program<'info, Bentobox>,
+    /// Cauldron account.
+    #[account(mut)]
+    pub cauldron_account: Box<Account<'info, Cauldron>>,
+    /// Cauldron authority account.
+    /// CHECK: seeds.
+    #[account(mut, seeds = [CAULDRON_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()], 
+              bump)]
+    pub cauldron_authority: UncheckedAccount<'info>,
+    /// Switchboard data feed account.
+    /// CHECK: account owner.
+    #[account(constraint = switchboard_data_feed.key() == cauldron_account.switchboard_data_feed @ ErrorCode::IncompatibleSwitchboardDataFeed)]
+    pub switchboard_data_feed: UncheckedAccount<'info>,
+    /// Pool oracle pda account, used for price only if it is created for cauldron.
+    /// CHECK: seeds, loaded in `get_collateral_price`.
+    #[account(seeds = [POOL_ORACLE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()],
+              bump)]
+    pub pool_oracle: UncheckedAccount<'info>,
+    /// Cauldron pda total account.
+    #[account(mut, 
+              seeds = [TOTAL_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()],
+              bump,)]
+    pub total_data: AccountLoader<'info, Total>,
+    /// User balance pda account.
+    #[account(mut, 
+              seeds = [USER_BALANCE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       user.as_ref()],
+              bump)]
+    pub user_balance: Box<Account<'info, UserBalance>>,
+    /// Cauldron collateral balance account on bentobox.
+    #[account(mut)]
+    pub cauldron_collateral_bentobox_balance: Box<Account<'info, Balance>>,
+    /// Cauldron MIM balance account on bentobox.
+    #[account(mut)]
+    pub cauldron_mim_bentobox_balance: Box<Account<'info, Balance>>,
+    /// Authority MIM balance account on bentobox, receives liquidation profit.
+    #[account(mut)]
+    pub authority_mim_bentobox_balance: Box<Account<'info, Balance>>,
+    /// Collateral mint account.
+    #[account(constraint = collateral.key() == cauldron_account.collateral @ ErrorCode::InvalidCollateral)]
+    pub collateral: Box<Account<'info, Mint>>,
+    /// MIM mint account.
+    #[account(constraint = cauldron_account.magic_internet_money == magic_internet_money_mint.key() @ ErrorCode::BentoBoxAccountOwnerDoesNotMatchProgram)]
+    pub magic_internet_money_mint: Box<Account<'info, Mint>>,
+    /// Cauldron collateral vault account.
+    #[account(mut,
+              seeds = [TOTAL_VAULT_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       collateral.key().as_ref()],
+              bump,
+              constraint = cauldron_source_vault.owner == cauldron_authority.key() @ ErrorCode::InvalidCauldronSourceVault)]
+    pub cauldron_source_vault: Box<Account<'info, TokenAccount>>,
+    /// Cauldron MIM vault account.
+    #[account(mut,
+              seeds = [TOTAL_VAULT_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       magic_internet_money_mint.key().as_ref()],
+              bump,
+              constraint = cauldron_destination_vault.owner == cauldron_authority.key() @ ErrorCode::InvalidCauldronDestinationVault)]
+    pub cauldron_destination_vault: Box<Account<'info, TokenAccount>>,
+    /// Bentobox vault authority account account.
+    /// CHECK: inside bentobox withdraw instruction.
+    pub bentobox_vault_authority: UncheckedAccount<'info>,
+    /// Bentobox collateral vault account.
+    #[account(mut)]
+    pub bentobox_collateral_vault: Box<Account<'info, TokenAccount>>,
+    /// Bentobox MIM vault account.
+    #[account(mut)]
+    pub bentobox_mim_vault: Box<Account<'info, TokenAccount>>,
+    /// Strategy data account for MIM token.
+    /// CHECK: inside bento-deposit.
+    pub mim_strategy_data: UncheckedAccount<'info>,
+    /// Concreet swapper program account.
+    /// CHECK: should be executable and whitelisted in Cauldron.
+    #[account(executable)]
+    pub swapper_program: UncheckedAccount<'info>,
+    /// Swapper program PDA account that store whitelisted information.
+    #[account(seeds = [SWAPPER_WHITELISTED_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       swapper_program.key().as_ref()],
+              bump,
+              constraint = swapper_whitelisted.whitelisted @ ErrorCode::InvalidSwapper)]
+    pub swapper_whitelisted: Box<Account<'info, SwapperWhitelisted>>,
+    /// Swap program account, receives cauldron authority signature from swapper.
+    /// CHECK: one of swap programs of whitelisted swapper.
+    #[account(constraint = swapper_whitelisted.swap_programs.contains(swap_program.key) @ ErrorCode::InvalidSwapProgram)]
+    pub swap_program: UncheckedAccount<'info>,
+    /// Token program account.
+    pub token_program: Program<'info, Token>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> FlashLiquidate<'info> {
+    /// Helper function to create swap context.
+    pub fn create_swap_ctx(&self) 
+        -> Result<CpiContext<'_, '_, '_, 'info, Swap<'info>>> {
+            let mut cauldron_authority = self.cauldron_authority.to_account_info();
+            cauldron_authority.is_signer = true;
+
+            Ok(CpiContext::new(self.swapper_program.to_account_info(), Swap {
+                source_token_account: self.cauldron_source_vault.to_account_info(),
+                destination_token_account: self.cauldron_destination_vault.to_account_info(),
+                swap_program: self.swap_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                authority: cauldron_authority,
+            }))
+        }
+}
+
 #[derive(Accounts)]
 #[instruction(user: Pubkey)]
 pub struct Liquidate<'info> {
@@ -979,6 +1198,12 @@ pub struct Liquidate<'info> {
     #[account(constraint = switchboard_data_feed.key() == cauldron_account.switchboard_data_feed @ ErrorCode::IncompatibleSwitchboardDataFeed)]
     // pub switchboard_data_feed: AccountLoader<'info,AggregatorAccountData>,
     pub switchboard_data_feed: UncheckedAccount<'info>,
+    /// Pool oracle pda account, used for price only if it is created for cauldron.
+    /// CHECK: seeds, loaded in `get_collateral_price`.
+    #[account(seeds = [POOL_ORACLE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()],
+              bump)]
+    pub pool_oracle: UncheckedAccount<'info>,
     /// Cauldron pda total account.
     #[account(mut, 
               seeds = [TOTAL_SEED_PART.as_ref(),
@@ -1028,4 +1253,139 @@ pub struct UpdateSwitchboardDataFeed<'info> {
     pub switchboard_data_feed: UncheckedAccount<'info>, 
     #[account(mut)]
     pub authority: Signer<'info>
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct CreateSwapperWhitelist<'info> {
+    /// Swapper program PDA account to store whitelisted information.
+    #[account(init,
+              seeds = [SWAPPER_WHITELISTED_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       swapper_program.key().as_ref()],
+              bump,
+              payer = authority,
+              space = SwapperWhitelisted::SIZE)]
+    pub swapper_whitelisted: Box<Account<'info, SwapperWhitelisted>>,
+    /// Cauldron account.
+    #[account(has_one = authority)]
+    pub cauldron_account: Box<Account<'info, Cauldron>>,
+    /// CHECK: If this account is swapper program executable
+    #[account(executable)]
+    pub swapper_program: UncheckedAccount<'info>,
+    /// Cauldron authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WhitelistSwapper<'info> {
+    /// Swapper program PDA account that store whitelisted information.
+    #[account(mut,
+              seeds = [SWAPPER_WHITELISTED_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       swapper_program.key().as_ref()],
+              bump)]
+    pub swapper_whitelisted: Box<Account<'info, SwapperWhitelisted>>,
+    /// CHECK: If this account is swapper program executable
+    #[account(executable)]
+    pub swapper_program: UncheckedAccount<'info>,
+    /// Cauldron account.
+    #[account(has_one = authority)]
+    pub cauldron_account: Box<Account<'info, Cauldron>>,
+    /// Cauldron authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePoolOracle<'info> {
+    /// Pool oracle pda account.
+    #[account(init,
+              seeds = [POOL_ORACLE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()],
+              bump,
+              payer = authority,
+              space = PoolOracle::SIZE)]
+    pub pool_oracle: Box<Account<'info, PoolOracle>>,
+    /// Cauldron account.
+    #[account(has_one = authority)]
+    pub cauldron_account: Box<Account<'info, Cauldron>>,
+    /// Swapper program which quotes the pool spot price.
+    /// CHECK: If this account is swapper program executable
+    #[account(executable)]
+    pub swapper_program: UncheckedAccount<'info>,
+    /// Swapper program PDA account that store whitelisted information.
+    #[account(seeds = [SWAPPER_WHITELISTED_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       swapper_program.key().as_ref()],
+              bump,
+              constraint = swapper_whitelisted.whitelisted @ ErrorCode::InvalidSwapper)]
+    pub swapper_whitelisted: Box<Account<'info, SwapperWhitelisted>>,
+    /// Cauldron authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePoolOracleConfig<'info> {
+    /// Pool oracle pda account.
+    #[account(mut,
+              seeds = [POOL_ORACLE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()],
+              bump)]
+    pub pool_oracle: Box<Account<'info, PoolOracle>>,
+    /// Cauldron account.
+    #[account(has_one = authority)]
+    pub cauldron_account: Box<Account<'info, Cauldron>>,
+    /// Cauldron authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePoolOracle<'info> {
+    /// Pool oracle pda account.
+    #[account(mut,
+              close = authority,
+              seeds = [POOL_ORACLE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()],
+              bump)]
+    pub pool_oracle: Box<Account<'info, PoolOracle>>,
+    /// Cauldron account.
+    #[account(has_one = authority)]
+    pub cauldron_account: Box<Account<'info, Cauldron>>,
+    /// Cauldron authority account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePoolOraclePrice<'info> {
+    /// Pool oracle pda account.
+    #[account(mut,
+              seeds = [POOL_ORACLE_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref()],
+              bump)]
+    pub pool_oracle: Box<Account<'info, PoolOracle>>,
+    /// Cauldron account.
+    pub cauldron_account: Box<Account<'info, Cauldron>>,
+    /// Swapper program which quotes the pool spot price.
+    /// CHECK: pool oracle swapper program.
+    #[account(executable,
+              constraint = swapper_program.key() == pool_oracle.swapper_program @ ErrorCode::InvalidSwapper)]
+    pub swapper_program: UncheckedAccount<'info>,
+    /// Swapper program PDA account that store whitelisted information.
+    #[account(seeds = [SWAPPER_WHITELISTED_SEED_PART.as_ref(),
+                       cauldron_account.key().as_ref(),
+                       swapper_program.key().as_ref()],
+              bump,
+              constraint = swapper_whitelisted.whitelisted @ ErrorCode::InvalidSwapper)]
+    pub swapper_whitelisted: Box<Account<'info, SwapperWhitelisted>>,
+    /// Any signer, price update is permissionless.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
@@ -75,5 +75,43 @@ pub enum ErrorCode {
     #[msg("Invalid owner of cauldron destination token account.")]
     InvalidCauldronDestinationVault,
 
-    
+    #[msg("User balance can not be closed, it still has collateral or borrow part.")]
+    UserBalanceIsNotEmpty,
+
+    #[msg("Swap returned less MIM than borrow share of liquidated position.")]
+    InsufficientSwapAmount,
+
+    #[msg("Liquidation can be cancelled only after timestamp.")]
+    LiquidationNotExpired,
+
+    #[msg("Liquidation can not be cancelled, collateral is already swapped.")]
+    LiquidationAlreadySwapped,
+
+    #[msg("Invalid payer of liquidator account.")]
+    InvalidLiquidatorAccountPayer,
+
+    #[msg("Invalid pool oracle config.")]
+    InvalidPoolOracleConfig,
+
+    #[msg("Pool accounts do not match pool oracle.")]
+    InvalidPoolOracleAccounts,
+
+    #[msg("Pool oracle TWAP is not calculated yet.")]
+    PoolOraclePriceNotAvailable,
+
+    #[msg("Pool oracle TWAP is stale.")]
+    StalePoolOraclePrice,
+
+    #[msg("Switchboard price deviates from pool oracle TWAP too much.")]
+    PoolOraclePriceDeviation,
+
+    #[msg("Swap program is not allowed for swapper.")]
+    InvalidSwapProgram,
+
+    #[msg("Too many swap programs for swapper.")]
+    TooManySwapPrograms,
+
+    // migrations
+    #[msg("Account is not in legacy layout or is already migrated.")]
+    InvalidLegacyAccount,
 }
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::state::PoolOracleMode;
+
 #[event]
 pub struct LogAccrue {
     pub extra_amount: u128,
@@ -57,3 +59,43 @@ pub struct LogReduceSuply {
     pub reduce_amount: u64,
     pub amount_left: u64,
 }
+
+#[event]
+pub struct LogFlashLiquidate {
+    pub user: Pubkey,
+    pub liquidator: Pubkey,
+    pub collateral_amount: u64,
+    pub borrow_share: u64,
+    pub profit_share: u64,
+}
+
+#[event]
+pub struct LogCancelLiquidate {
+    pub user: Pubkey,
+    pub liquidator: Pubkey,
+    pub collateral_share: u64,
+    pub borrow_part: u64,
+}
+
+#[event]
+pub struct LogWhitelistSwapper {
+    pub swapper_program: Pubkey,
+    pub whitelisted: bool,
+    pub swap_programs: Vec<Pubkey>,
+}
+
+#[event]
+pub struct LogPoolOracleConfig {
+    pub swapper_program: Pubkey,
+    pub mode: PoolOracleMode,
+    pub max_deviation: u64,
+    pub twap_period: u64,
+    pub max_price_age: u64,
+}
+
+#[event]
+pub struct LogPoolOraclePrice {
+    pub spot_price: u64,
+    pub twap_price: u64,
+    pub timestamp: u64,
+}
@@ -87,6 +87,11 @@ pub mod cauldron {
         Ok(())
     }
 
+    /// Close user balance account without collateral and borrow part and return its rent to the user.
+    pub fn close_user_balance(_ctx: Context<CloseUserBalance>) -> Result<()> {
+        Ok(())
+    }
+
     /// Creating token account for Cauldron.
     pub fn create_vault(ctx: Context<CreateVault>) -> Result<()> {
         let (vault_authority_address, _) = Pubkey::find_program_address(
@@ -239,8 +244,9 @@ pub mod cauldron {
 
         let (part, share) = utils::borrow_internal(to, amount, ctx.accounts)?;
 
-        let price_decimal = get_switchboard_price(
+        let price_decimal = get_collateral_price(
             &ctx.accounts.switchboard_data_feed,
+            &ctx.accounts.pool_oracle,
             &Clock::get()?,
             &ctx.accounts
                 .cauldron_account
@@ -407,8 +413,9 @@ pub mod cauldron {
             )?;
         }
 
-        let price_decimal = get_switchboard_price(
+        let price_decimal = get_collateral_price(
             &ctx.accounts.switchboard_data_feed,
+            &ctx.accounts.pool_oracle,
             &Clock::get()?,
             &ctx.accounts
                 .cauldron_account
@@ -471,12 +478,14 @@ pub mod cauldron {
     /// * `to`     - Which account to push the tokens.
     /// * `amount` - Token amount in native representation to deposit.
     /// * `share`  - Token amount represented in shares to deposit. Takes precedence over `amount`.
+    /// * `min_share_out` - Minimum amount of shares which `to` should receive for the deposit.
 
     pub fn bento_deposit<'info>(
         ctx: Context<BentoDeposit>,
         to: Pubkey,
         amount: u64,
         share: u64,
+        min_share_out: u64,
     ) -> Result<AmountShareOut> {
         let cauldron_account = &ctx.accounts.cauldron_account;
 
@@ -496,6 +505,7 @@ pub mod cauldron {
             to,
             amount,
             share,
+            min_share_out,
             ctx.accounts.from_vault.to_account_info(),
             ctx.accounts.bentobox_vault.to_account_info(),
             ctx.accounts.bentobox_to_balance.to_account_info(),
@@ -521,9 +531,11 @@ pub mod cauldron {
     ///
     /// * `amount` - Token amount in native representation to withdraw.
     /// * `share` - Token amount represented in shares to withdraw. Takes precedence over `amount`.
-
+    ///
+    /// Remaining accounts are forwarded to BentoBox withdraw, so strategy accounts can be passed
+    /// to pull the shortfall from strategy when BentoBox vault is short.
     pub fn bento_withdraw<'info>(
-        ctx: Context<BentoWithdraw>,
+        ctx: Context<'_, '_, '_, 'info, BentoWithdraw<'info>>,
         amount: u64,
         share: u64,
     ) -> Result<AmountShareOut> {
@@ -553,11 +565,15 @@ pub mod cauldron {
             ctx.accounts.token_program.to_account_info(),
             ctx.accounts.bentobox_vault_authority.to_account_info(),
             ctx.accounts.cauldron_authority.to_account_info(),
-            vec![
-                ctx.accounts.master_contract_whitelisted.to_account_info(),
-                ctx.accounts.master_contract_approved.to_account_info(),
-                cauldron_account.to_account_info(),
-            ],
+            [
+                vec![
+                    ctx.accounts.master_contract_whitelisted.to_account_info(),
+                    ctx.accounts.master_contract_approved.to_account_info(),
+                    cauldron_account.to_account_info(),
+                ],
+                ctx.remaining_accounts.to_vec(),
+            ]
+            .concat(),
             &[&authority_seeds[..]],
         )?)
     }
@@ -620,6 +636,65 @@ pub mod cauldron {
         Ok(())
     }
 
+    /// Registers swapper program in Cauldron and creates special account for it.
+    /// Only whitelisted swappers can be used to swap collateral in `liquidate_swap`.
+    ///
+    /// Arguments:
+    ///
+    /// * `whitelisted` - True if swapper program should be whitelisted, False othervise.
+    /// * `swap_programs` - DEX programs swapper can swap through, up to 4. Swapper receives
+    ///                     cauldron authority signature and forwards it to `swap_program`.
+    pub fn create_swapper_whitelist(
+        ctx: Context<CreateSwapperWhitelist>,
+        whitelisted: bool,
+        swap_programs: Vec<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            swap_programs.len() <= MAX_SWAP_PROGRAMS,
+            ErrorCode::TooManySwapPrograms
+        );
+
+        let swapper_whitelisted = &mut ctx.accounts.swapper_whitelisted;
+        swapper_whitelisted.swapper_program = ctx.accounts.swapper_program.key();
+        swapper_whitelisted.whitelisted = whitelisted;
+        swapper_whitelisted.swap_programs = swap_programs;
+
+        emit!(LogWhitelistSwapper {
+            swapper_program: swapper_whitelisted.swapper_program,
+            whitelisted,
+            swap_programs: swapper_whitelisted.swap_programs.clone(),
+        });
+        Ok(())
+    }
+
+    /// Setter instruction to change swapper program whitelisted state and its swap programs.
+    ///
+    /// Arguments:
+    ///
+    /// * `whitelisted` - True if swapper program should be whitelisted, False othervise.
+    /// * `swap_programs` - DEX programs swapper can swap through, up to 4.
+    pub fn whitelist_swapper(
+        ctx: Context<WhitelistSwapper>,
+        whitelisted: bool,
+        swap_programs: Vec<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            swap_programs.len() <= MAX_SWAP_PROGRAMS,
+            ErrorCode::TooManySwapPrograms
+        );
+
+        let swapper_whitelisted = &mut ctx.accounts.swapper_whitelisted;
+        swapper_whitelisted.whitelisted = whitelisted;
+        swapper_whitelisted.swap_programs = swap_programs;
+
+        emit!(LogWhitelistSwapper {
+            swapper_program: swapper_whitelisted.swapper_program,
+            whitelisted,
+            swap_programs: swapper_whitelisted.swap_programs.clone(),
+        });
+        Ok(())
+    }
+
     /// Withdraws the fees accumulated.
     pub fn withdraw_fees<'info>(ctx: Context<WithdrawFees>) -> Result<()> {
         let cauldron_account = ctx.accounts.cauldron_account.clone();
@@ -828,9 +903,10 @@ pub mod cauldron {
         );
         let authority_seeds = &[CAULDRON_SEED_PART, cauldron_key.as_ref(), &[_bump]];
 
-        let (borrow_amount, mut borrow_share, collateral_share) = utils::liquidate_internal(
+        let (borrow_amount, mut borrow_share, collateral_share, _, _) = utils::liquidate_internal(
             max_borrow_part,
             &ctx.accounts.switchboard_data_feed,
+            &ctx.accounts.pool_oracle,
             &mut ctx.accounts.cauldron_account,
             ctx.accounts.total_data.clone(),
             &ctx.accounts.collateral.to_account_info(),
@@ -900,19 +976,21 @@ pub mod cauldron {
         _user: Pubkey,
         max_borrow_part: u64,
     ) -> Result<()> {
-        let (borrow_amount, borrow_share, collateral_share) = utils::liquidate_internal(
-            max_borrow_part,
-            &ctx.accounts.switchboard_data_feed,
-            &mut ctx.accounts.cauldron_account,
-            ctx.accounts.total_data.clone(),
-            &ctx.accounts.collateral.to_account_info(),
-            ctx.accounts.magic_internet_money_mint.to_account_info(),
-            ctx.accounts.bentobox_collateral_total_data.clone(),
-            ctx.accounts.bentobox_mim_total_data.clone(),
-            &ctx.accounts.bentobox_account.to_account_info(),
-            ctx.accounts.bentobox_program.to_account_info(),
-            &mut ctx.accounts.user_balance,
-        )?;
+        let (borrow_amount, borrow_share, collateral_share, borrow_part, distribution_amount) =
+            utils::liquidate_internal(
+                max_borrow_part,
+                &ctx.accounts.switchboard_data_feed,
+                &ctx.accounts.pool_oracle,
+                &mut ctx.accounts.cauldron_account,
+                ctx.accounts.total_data.clone(),
+                &ctx.accounts.collateral.to_account_info(),
+                ctx.accounts.magic_internet_money_mint.to_account_info(),
+                ctx.accounts.bentobox_collateral_total_data.clone(),
+                ctx.accounts.bentobox_mim_total_data.clone(),
+                &ctx.accounts.bentobox_account.to_account_info(),
+                ctx.accounts.bentobox_program.to_account_info(),
+                &mut ctx.accounts.user_balance,
+            )?;
 
         let cauldron_key = ctx.accounts.cauldron_account.key();
         let constants = ctx.accounts.cauldron_account.constants.clone();
@@ -945,6 +1023,10 @@ pub mod cauldron {
 
         let liquidator_account = &mut ctx.accounts.liquidator_account;
         liquidator_account.origin_liquidator = ctx.accounts.authority.key();
+        liquidator_account.payer = ctx.accounts.authority.key();
+        liquidator_account.user = _user;
+        liquidator_account.borrow_part = borrow_part;
+        liquidator_account.distribution_amount = distribution_amount;
         liquidator_account.collateral_share = amount_share_out.amount_out;
         liquidator_account.borrow_amount = borrow_amount;
         liquidator_account.borrow_share = borrow_share;
@@ -979,32 +1061,14 @@ pub mod cauldron {
         let mut cauldron_destination_vault = ctx.accounts.cauldron_destination_vault.clone();
         let mim_vault_amount = cauldron_destination_vault.amount;
 
-        //
-        let swapper_program = ctx.accounts.swapper_program.to_account_info();
-
-        match swapper_program.key {
-            key if key == &swapper_orca::id() => {
-            swapper_orca::cpi::swap(
-                ctx.accounts
-                            .create_orca_swap_ctx()?
-                            .with_signer(&[&authority_seeds[..]])
-                            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
-                liquidator_account.collateral_share,
-                liquidator_account.borrow_share)?;
-            },
-            key if key == &swapper_raydium::id() => {
-            swapper_raydium::cpi::swap(
-                ctx.accounts
-                            .create_raydium_swap_ctx()?
-                            .with_signer(&[&authority_seeds[..]])
-                            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
-                            liquidator_account.collateral_share,
-                            liquidator_account.borrow_share)?;
-            },
-            _ => {
-                return Err(ErrorCode::InvalidSwapper.into());
-            }
-        };
+        swapper_package::cpi::swap(
+            ctx.accounts
+                .create_swap_ctx()?
+                .with_signer(&[&authority_seeds[..]])
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            liquidator_account.collateral_share,
+            liquidator_account.borrow_share,
+        )?;
 
         cauldron_destination_vault.reload()?;
 
@@ -1012,7 +1076,7 @@ pub mod cauldron {
 
         require!(
             liquidator_account.borrow_share < real_amount,
-            ErrorCode::InvalidSwapper
+            ErrorCode::InsufficientSwapAmount
         );
 
         let liquidator_account = &mut ctx.accounts.liquidator_account;
@@ -1051,6 +1115,7 @@ pub mod cauldron {
             ctx.accounts.cauldron_authority.key(),
             liquidator_account.real_amount,
             0,
+            0,
             ctx.accounts.cauldron_mim_vault.to_account_info(),
             ctx.accounts.bentobox_mim_vault.to_account_info(),
             ctx.accounts.cauldron_mim_bentobox_balance.to_account_info(),
@@ -1099,6 +1164,263 @@ pub mod cauldron {
         Ok(())
     }
 
+    /// Cancels liquidation stuck after `begin_liquidate`. Can be called by anyone after
+    /// `complete_liquidation_duration`, if collateral is not swapped yet. Deposits collateral back
+    /// to bentobox and restores collateral and borrow part of user and cauldron totals.
+    /// Liquidator account is closed and its rent is returned to liquidator which began liquidation.
+    pub fn cancel_liquidate(ctx: Context<CancelLiquidate>) -> Result<()> {
+        let liquidator_account = ctx.accounts.liquidator_account.clone();
+
+        require!(
+            liquidator_account.real_amount == 0,
+            ErrorCode::LiquidationAlreadySwapped
+        );
+        require!(
+            liquidator_account.timestamp < Clock::get()?.unix_timestamp as u64,
+            ErrorCode::LiquidationNotExpired
+        );
+
+        let cauldron_key = ctx.accounts.cauldron_account.key();
+        let (_, _bump) = Pubkey::find_program_address(
+            &[CAULDRON_SEED_PART, cauldron_key.as_ref()],
+            &ctx.program_id,
+        );
+        let authority_seeds = &[CAULDRON_SEED_PART, cauldron_key.as_ref(), &[_bump]];
+
+        let amount_share_out: AmountShareOut = utils::bento_deposit(
+            ctx.accounts.cauldron_authority.key(),
+            liquidator_account.collateral_share,
+            0,
+            0,
+            ctx.accounts.cauldron_source_vault.to_account_info(),
+            ctx.accounts.bentobox_collateral_vault.to_account_info(),
+            ctx.accounts
+                .cauldron_collateral_bentobox_balance
+                .to_account_info(),
+            ctx.accounts
+                .bentobox_collateral_total_data
+                .to_account_info(),
+            ctx.accounts.bentobox_account.to_account_info(),
+            ctx.accounts.bentobox_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.collateral.to_account_info(),
+            ctx.accounts.collateral_strategy_data.to_account_info(),
+            ctx.accounts.cauldron_authority.to_account_info(),
+            vec![],
+            &[&authority_seeds[..]],
+        )?;
+
+        let total_data = &mut ctx.accounts.total_data.load_mut()?;
+        utils::accrue_internal(&mut ctx.accounts.cauldron_account.accrue_info, total_data)?;
+
+        total_data.collateral_share = total_data
+            .collateral_share
+            .checked_add(amount_share_out.share_out)
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+
+        // borrow part is restored with interest accrued since liquidation began
+        let mut rebase: Rebase = total_data.borrow.into();
+        let borrow_elastic = rebase.to_elastic(liquidator_account.borrow_part, true)?;
+        total_data.borrow =
+            CauldronRebase::from(rebase.add_e_b(borrow_elastic, liquidator_account.borrow_part)?);
+
+        // distribution part was never paid, fees could be already withdrawn
+        let accrue_info = &mut ctx.accounts.cauldron_account.accrue_info;
+        accrue_info.fees_earned = accrue_info
+            .fees_earned
+            .saturating_sub(liquidator_account.distribution_amount.into());
+
+        let user_balance = &mut ctx.accounts.user_balance;
+        user_balance.collateral_share = user_balance
+            .collateral_share
+            .checked_add(amount_share_out.share_out)
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+        user_balance.borrow_part = user_balance
+            .borrow_part
+            .checked_add(liquidator_account.borrow_part)
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+
+        emit!(LogCancelLiquidate {
+            user: liquidator_account.user,
+            liquidator: liquidator_account.payer,
+            collateral_share: amount_share_out.share_out,
+            borrow_part: liquidator_account.borrow_part,
+        });
+        Ok(())
+    }
+
+    /// Migrates liquidator account created by `begin_liquidate` before liquidations could be cancelled
+    /// to the current layout, so it can be completed or cancelled. ONLY for cauldron account authority,
+    /// `user` and `borrow_part` are not stored in legacy layout and are taken from `begin_liquidate` transaction.
+    /// Legacy `begin_liquidate` didn't save distribution part to cauldron fees, so `distribution_amount` is 0.
+    ///
+    /// Arguments:
+    ///
+    /// * `payer` - Liquidator which began liquidation, used in liquidator account seeds.
+    /// * `user` - The user which position was liquidated.
+    /// * `borrow_part` - Borrow part taken from user position.
+    pub fn migrate_liquidator_account(
+        ctx: Context<MigrateLiquidatorAccount>,
+        payer: Pubkey,
+        user: Pubkey,
+        borrow_part: u64,
+    ) -> Result<()> {
+        let liquidator_info = ctx.accounts.liquidator_account.to_account_info();
+        let legacy_data = realloc_legacy_account::<LiquidatorAccount>(
+            &liquidator_info,
+            LegacyLiquidatorAccount::SIZE,
+            LiquidatorAccount::SIZE,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        let legacy = LegacyLiquidatorAccount::deserialize(&mut legacy_data.as_slice())?;
+        let liquidator_account = LiquidatorAccount {
+            origin_liquidator: legacy.origin_liquidator,
+            payer,
+            user,
+            borrow_part,
+            distribution_amount: 0,
+            collateral_share: legacy.collateral_share,
+            borrow_amount: legacy.borrow_amount,
+            borrow_share: legacy.borrow_share,
+            real_amount: legacy.real_amount,
+            timestamp: legacy.timestamp,
+        };
+        write_migrated_account(&liquidator_info, &liquidator_account)
+    }
+
+    /// Liquidates position with swapper in one instruction, without `LiquidatorAccount`.
+    /// User debt is repaid with MIM lent from cauldron balance on bentobox, seized collateral
+    /// is swapped to MIM which repays the loan, the rest of MIM is transferred to liquidator.
+    /// Bentobox `flash_loan` isn't used, its receiver is called back through token lending
+    /// program and Solana doesn't allow to reenter cauldron while it calls bentobox, so
+    /// cauldron MIM balance must hold borrow share of liquidated part.
+    ///
+    /// Arguments:
+    ///
+    /// * `user` - The user to liquidate.
+    /// * `max_borrow_part` - Maximum borrow part to liquidate.
+    pub fn flash_liquidate<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashLiquidate<'info>>,
+        user: Pubkey,
+        max_borrow_part: u64,
+    ) -> Result<()> {
+        let (borrow_amount, borrow_share, collateral_share, _, _) = utils::liquidate_internal(
+            max_borrow_part,
+            &ctx.accounts.switchboard_data_feed,
+            &ctx.accounts.pool_oracle,
+            &mut ctx.accounts.cauldron_account,
+            ctx.accounts.total_data.clone(),
+            &ctx.accounts.collateral.to_account_info(),
+            ctx.accounts.magic_internet_money_mint.to_account_info(),
+            ctx.accounts.bentobox_collateral_total_data.clone(),
+            ctx.accounts.bentobox_mim_total_data.clone(),
+            &ctx.accounts.bentobox_account.to_account_info(),
+            ctx.accounts.bentobox_program.to_account_info(),
+            &mut ctx.accounts.user_balance,
+        )?;
+
+        let cauldron_key = ctx.accounts.cauldron_account.key();
+        let (_, _bump) = Pubkey::find_program_address(
+            &[CAULDRON_SEED_PART, cauldron_key.as_ref()],
+            &ctx.program_id,
+        );
+        let authority_seeds = &[CAULDRON_SEED_PART, cauldron_key.as_ref(), &[_bump]];
+
+        let collateral_out: AmountShareOut = utils::bento_withdraw(
+            ctx.accounts.cauldron_authority.key(),
+            0,
+            collateral_share,
+            ctx.accounts.cauldron_source_vault.to_account_info(),
+            ctx.accounts.bentobox_collateral_vault.to_account_info(),
+            ctx.accounts
+                .cauldron_collateral_bentobox_balance
+                .to_account_info(),
+            ctx.accounts
+                .bentobox_collateral_total_data
+                .to_account_info(),
+            ctx.accounts.bentobox_account.to_account_info(),
+            ctx.accounts.bentobox_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.bentobox_vault_authority.to_account_info(),
+            ctx.accounts.cauldron_authority.to_account_info(),
+            vec![],
+            &[&authority_seeds[..]],
+        )?;
+
+        let mim_vault_amount = ctx.accounts.cauldron_destination_vault.amount;
+
+        swapper_package::cpi::swap(
+            ctx.accounts
+                .create_swap_ctx()?
+                .with_signer(&[&authority_seeds[..]])
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            collateral_out.amount_out,
+            borrow_amount,
+        )?;
+
+        ctx.accounts.cauldron_destination_vault.reload()?;
+        let real_amount = ctx
+            .accounts
+            .cauldron_destination_vault
+            .amount
+            .checked_sub(mim_vault_amount)
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+
+        let mim_out: AmountShareOut = utils::bento_deposit(
+            ctx.accounts.cauldron_authority.key(),
+            real_amount,
+            0,
+            0,
+            ctx.accounts.cauldron_destination_vault.to_account_info(),
+            ctx.accounts.bentobox_mim_vault.to_account_info(),
+            ctx.accounts.cauldron_mim_bentobox_balance.to_account_info(),
+            ctx.accounts.bentobox_mim_total_data.to_account_info(),
+            ctx.accounts.bentobox_account.to_account_info(),
+            ctx.accounts.bentobox_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.magic_internet_money_mint.to_account_info(),
+            ctx.accounts.mim_strategy_data.to_account_info(),
+            ctx.accounts.cauldron_authority.to_account_info(),
+            vec![],
+            &[&authority_seeds[..]],
+        )?;
+
+        // swapped MIM should at least repay MIM lent for user debt
+        let profit_share = mim_out
+            .share_out
+            .checked_sub(borrow_share)
+            .ok_or(ErrorCode::InsufficientSwapAmount)?;
+
+        if profit_share > 0 {
+            create_bentobox_transfer_context(
+                profit_share,
+                ctx.accounts.cauldron_authority.key(),
+                ctx.accounts.cauldron_mim_bentobox_balance.to_account_info(),
+                ctx.accounts.authority.key(),
+                ctx.accounts
+                    .authority_mim_bentobox_balance
+                    .to_account_info(),
+                ctx.accounts.magic_internet_money_mint.to_account_info(),
+                ctx.accounts.bentobox_account.to_account_info(),
+                ctx.accounts.cauldron_authority.to_account_info(),
+                ctx.accounts.bentobox_program.to_account_info(),
+                &[&authority_seeds[..]],
+                &[],
+            )?;
+        }
+
+        emit!(LogFlashLiquidate {
+            user,
+            liquidator: ctx.accounts.authority.key(),
+            collateral_amount: collateral_out.amount_out,
+            borrow_share,
+            profit_share,
+        });
+        Ok(())
+    }
+
     /// Allows to change switchboard data feed. ONLY for cauldron account authority.
     pub fn update_switchboard_data_feed<'info>(
         ctx: Context<UpdateSwitchboardDataFeed>,
@@ -1111,4 +1433,126 @@ pub mod cauldron {
 
         Ok(())
     }
+    /// Creates pool oracle which derives collateral price from pool reserves. ONLY for cauldron account authority.
+    /// Spot price is quoted by whitelisted swapper with `get_spot_price`, pool accounts must quote
+    /// from MIM to collateral. First spot price is quoted on creation, pool accounts are passed as
+    /// remaining accounts in the same order as `pool_accounts`.
+    ///
+    /// Arguments:
+    ///
+    /// * `pool_accounts` - Pool accounts passed to swapper, up to 4.
+    /// * `mode` - `SanityCheck` to check switchboard price against TWAP, `Primary` to use TWAP as price.
+    /// * `max_deviation` - Max deviation of switchboard price and of new spot price from TWAP, in `POOL_ORACLE_DEVIATION_PRECISION`, more than 0.
+    /// * `twap_period` - Min duration in seconds which TWAP is averaged over.
+    /// * `max_price_age` - Max age in seconds of TWAP which cauldron accepts.
+    pub fn create_pool_oracle<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreatePoolOracle<'info>>,
+        pool_accounts: Vec<Pubkey>,
+        mode: PoolOracleMode,
+        max_deviation: u64,
+        twap_period: u64,
+        max_price_age: u64,
+    ) -> Result<()> {
+        require!(
+            !pool_accounts.is_empty() && pool_accounts.len() <= POOL_ORACLE_MAX_POOL_ACCOUNTS,
+            ErrorCode::InvalidPoolOracleConfig
+        );
+        validate_pool_oracle_config(max_deviation, twap_period, max_price_age)?;
+
+        let pool_oracle = &mut ctx.accounts.pool_oracle;
+        pool_oracle.cauldron = ctx.accounts.cauldron_account.key();
+        pool_oracle.swapper_program = ctx.accounts.swapper_program.key();
+        pool_oracle.pool_accounts = pool_accounts;
+        pool_oracle.mode = mode;
+        pool_oracle.max_deviation = max_deviation;
+        pool_oracle.twap_period = twap_period;
+        pool_oracle.max_price_age = max_price_age;
+
+        // first spot price isn't clamped, so it is quoted by authority instead of permissionless update
+        let spot_price = quote_pool_oracle_spot_price(
+            pool_oracle,
+            ctx.accounts.swapper_program.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.remaining_accounts,
+        )?;
+        let timestamp = Clock::get()?.unix_timestamp as u64;
+        accumulate_pool_oracle_price(pool_oracle, spot_price, timestamp)?;
+
+        emit!(LogPoolOracleConfig {
+            swapper_program: pool_oracle.swapper_program,
+            mode,
+            max_deviation,
+            twap_period,
+            max_price_age,
+        });
+        emit!(LogPoolOraclePrice {
+            spot_price: pool_oracle.spot_price,
+            twap_price: pool_oracle.twap_price,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    /// Changes pool oracle settings. ONLY for cauldron account authority.
+    ///
+    /// Arguments:
+    ///
+    /// * `mode` - `SanityCheck` to check switchboard price against TWAP, `Primary` to use TWAP as price.
+    /// * `max_deviation` - Max deviation of switchboard price and of new spot price from TWAP, in `POOL_ORACLE_DEVIATION_PRECISION`, more than 0.
+    /// * `twap_period` - Min duration in seconds which TWAP is averaged over.
+    /// * `max_price_age` - Max age in seconds of TWAP which cauldron accepts.
+    pub fn update_pool_oracle_config(
+        ctx: Context<UpdatePoolOracleConfig>,
+        mode: PoolOracleMode,
+        max_deviation: u64,
+        twap_period: u64,
+        max_price_age: u64,
+    ) -> Result<()> {
+        validate_pool_oracle_config(max_deviation, twap_period, max_price_age)?;
+
+        let pool_oracle = &mut ctx.accounts.pool_oracle;
+        pool_oracle.mode = mode;
+        pool_oracle.max_deviation = max_deviation;
+        pool_oracle.twap_period = twap_period;
+        pool_oracle.max_price_age = max_price_age;
+
+        emit!(LogPoolOracleConfig {
+            swapper_program: pool_oracle.swapper_program,
+            mode,
+            max_deviation,
+            twap_period,
+            max_price_age,
+        });
+        Ok(())
+    }
+
+    /// Closes pool oracle, cauldron uses switchboard price only. ONLY for cauldron account authority.
+    pub fn close_pool_oracle(_ctx: Context<ClosePoolOracle>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Quotes pool spot price and updates cumulative price and TWAP. Can be called by anyone.
+    /// Spot price is clamped to `max_deviation` from TWAP to limit manipulation within one transaction.
+    /// Pool accounts of pool oracle are passed as remaining accounts in the same order.
+    pub fn update_pool_oracle_price<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdatePoolOraclePrice<'info>>,
+    ) -> Result<()> {
+        let pool_oracle = &mut ctx.accounts.pool_oracle;
+        let spot_price = quote_pool_oracle_spot_price(
+            pool_oracle,
+            ctx.accounts.swapper_program.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.remaining_accounts,
+        )?;
+
+        let timestamp = Clock::get()?.unix_timestamp as u64;
+        accumulate_pool_oracle_price(pool_oracle, spot_price, timestamp)?;
+
+        emit!(LogPoolOraclePrice {
+            spot_price: pool_oracle.spot_price,
+            twap_price: pool_oracle.twap_price,
+            timestamp,
+        });
+        Ok(())
+    }
 }
@@ -13,8 +13,26 @@ pub const TOTAL_VAULT_SEED_PART: &[u8] = b"cauldrontotalvault";
 #[constant]
 pub const LIQUIDATOR_ACCOUNT_SEED_PART: &[u8] = b"liquidatoraccount";
 #[constant]
+pub const SWAPPER_WHITELISTED_SEED_PART: &[u8] = b"swapperwhitelisted";
+#[constant]
+pub const POOL_ORACLE_SEED_PART: &[u8] = b"pooloracle";
+#[constant]
 pub const THREE_DAYS: u64 = 259200;
 
+/// Max number of pool accounts swapper needs to quote spot price.
+#[constant]
+pub const POOL_ORACLE_MAX_POOL_ACCOUNTS: usize = 4;
+/// Scale of pool oracle price, same as `swapper_package::utils::SWAP_PRICE_PRECISION`.
+#[constant]
+pub const POOL_ORACLE_PRICE_SCALE: u32 = 9;
+/// Precision of `max_deviation` of pool oracle, 10000 = 100%.
+#[constant]
+pub const POOL_ORACLE_DEVIATION_PRECISION: u64 = 10000;
+
+/// Max number of DEX programs whitelisted swapper can forward cauldron authority signature to.
+#[constant]
+pub const MAX_SWAP_PROGRAMS: usize = 4;
+
 #[constant]
 pub const ADD_COLLATERAL_REMAINING_ACCOUNTS_COUNT: usize = 6;
 
@@ -160,6 +178,14 @@ impl AccrueInfo {
 pub struct LiquidatorAccount {
     /// Liquidator public key.
     pub origin_liquidator: Pubkey,
+    /// Liquidator which began liquidation and paid rent, used in pda seeds.
+    pub payer: Pubkey,
+    /// Liquidated user public key.
+    pub user: Pubkey,
+    /// Borrow part taken from user position.
+    pub borrow_part: u64,
+    /// Part of `borrow_amount` added to cauldron fees.
+    pub distribution_amount: u64,
     /// Collateral user amount from position for swap.
     pub collateral_share: u64,
     /// Liquidator`s amount (liquidation fee).
@@ -173,5 +199,97 @@ pub struct LiquidatorAccount {
 }
 
 impl LiquidatorAccount {
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+}
+
+/// LiquidatorAccount layout before liquidations could be cancelled.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyLiquidatorAccount {
+    pub origin_liquidator: Pubkey,
+    pub collateral_share: u64,
+    pub borrow_amount: u64,
+    pub borrow_share: u64,
+    pub real_amount: u64,
+    pub timestamp: u64,
+}
+
+impl LegacyLiquidatorAccount {
     pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8;
 }
+
+#[account]
+#[derive(Default)]
+pub struct SwapperWhitelisted {
+    /// Swapper program which implements `swapper_interface`.
+    pub swapper_program: Pubkey,
+    /// Indicates if cauldron can swap collateral through swapper program.
+    pub whitelisted: bool,
+    /// DEX programs which can be passed to swapper as `swap_program`, up to `MAX_SWAP_PROGRAMS`.
+    pub swap_programs: Vec<Pubkey>,
+}
+
+impl SwapperWhitelisted {
+    pub const SIZE: usize = 8 + // discriminator
+    32 + // swapper_program
+    1 + // whitelisted
+    4 + 32 * MAX_SWAP_PROGRAMS; // swap_programs
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PoolOracleMode {
+    /// Switchboard price is used, it must not deviate from pool TWAP more than `max_deviation`.
+    SanityCheck,
+    /// Pool TWAP is used instead of switchboard price.
+    Primary,
+}
+
+#[account]
+pub struct PoolOracle {
+    /// Cauldron which uses the oracle.
+    pub cauldron: Pubkey,
+    /// Whitelisted swapper program which quotes pool spot price.
+    pub swapper_program: Pubkey,
+    /// Pool accounts passed to swapper `get_spot_price`, quoted from MIM to collateral.
+    pub pool_accounts: Vec<Pubkey>,
+    /// How cauldron uses the oracle.
+    pub mode: PoolOracleMode,
+    /// Max deviation of switchboard price from TWAP in `SanityCheck` mode and of new spot price
+    /// from TWAP in both modes, in `POOL_ORACLE_DEVIATION_PRECISION`.
+    pub max_deviation: u64,
+    /// Min duration in seconds which TWAP is averaged over.
+    pub twap_period: u64,
+    /// Max age in seconds of TWAP which cauldron accepts.
+    pub max_price_age: u64,
+    /// Last spot price, collateral per MIM with `POOL_ORACLE_PRICE_SCALE`.
+    pub spot_price: u64,
+    /// Sum of spot prices weighted by seconds they were active.
+    pub price_cumulative: u128,
+    /// Timestamp of last spot price update.
+    pub last_update: u64,
+    /// Cumulative price at the start of current TWAP period.
+    pub twap_checkpoint_cumulative: u128,
+    /// Timestamp of the start of current TWAP period.
+    pub twap_checkpoint_timestamp: u64,
+    /// Last TWAP, collateral per MIM with `POOL_ORACLE_PRICE_SCALE`.
+    pub twap_price: u64,
+    /// Timestamp when last TWAP was calculated.
+    pub twap_timestamp: u64,
+}
+
+impl PoolOracle {
+    pub const SIZE: usize = 8 + // discriminator
+    32 + // cauldron
+    32 + // swapper_program
+    4 + 32 * POOL_ORACLE_MAX_POOL_ACCOUNTS + // pool_accounts
+    1 + // mode
+    8 + // max_deviation
+    8 + // twap_period
+    8 + // max_price_age
+    8 + // spot_price
+    16 + // price_cumulative
+    8 + // last_update
+    16 + // twap_checkpoint_cumulative
+    8 + // twap_checkpoint_timestamp
+    8 + // twap_price
+    8; // twap_timestamp
+}
@@ -1,6 +1,7 @@
 use crate::{error::ErrorCode, event::*, id, state::*, Borrow};
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, Discriminator};
 use common::big_number::U256;
+use common::constants::DISCRIMINATOR_BYTES;
 use common::errors::ErrorCode as CommonErrorCode;
 use common::rebase::Rebase;
 
@@ -9,7 +10,7 @@ use bentobox::{
     cpi::accounts::{Conversion, Deposit, TransferInternal, Withdraw},
     state::{AmountShareOut, MasterContractWhitelisted, Total as BentoBoxTotal},
 };
-
+use swapper_package::cpi::accounts::GetSpotPrice;
 
 use switchboard_solana::{decimal::SwitchboardDecimal, AggregatorAccountData};
 
@@ -189,6 +190,205 @@ pub fn  get_switchboard_price(
     }
 }
 
+/// Returns collateral price used by cauldron. Without pool oracle it is switchboard price,
+/// otherwise pool oracle `mode` decides which price is used.
+pub fn get_collateral_price(
+    switchboard_data_feed: &AccountInfo,
+    pool_oracle: &AccountInfo,
+    clock: &Clock,
+    stale_after_slots_elapsed: &u64,
+) -> Result<SwitchboardDecimal> {
+    // pool oracle pda is always passed, it is used only if authority created it
+    if *pool_oracle.owner != id() || pool_oracle.data_is_empty() {
+        return get_switchboard_price(switchboard_data_feed, clock, stale_after_slots_elapsed);
+    }
+
+    let pool_oracle = Account::<PoolOracle>::try_from(pool_oracle)?;
+    let twap_price = get_pool_oracle_twap(&pool_oracle, clock)?;
+
+    match pool_oracle.mode {
+        PoolOracleMode::Primary => Ok(SwitchboardDecimal::new(
+            twap_price.into(),
+            POOL_ORACLE_PRICE_SCALE,
+        )),
+        PoolOracleMode::SanityCheck => {
+            let price_decimal =
+                get_switchboard_price(switchboard_data_feed, clock, stale_after_slots_elapsed)?;
+            check_price_deviation(&price_decimal, twap_price, pool_oracle.max_deviation)?;
+            Ok(price_decimal)
+        }
+    }
+}
+
+/// Returns pool oracle TWAP if it is calculated and not older than `max_price_age`.
+pub fn get_pool_oracle_twap(pool_oracle: &PoolOracle, clock: &Clock) -> Result<u64> {
+    require!(
+        pool_oracle.twap_price > 0,
+        ErrorCode::PoolOraclePriceNotAvailable
+    );
+
+    let price_age = (clock.unix_timestamp as u64)
+        .checked_sub(pool_oracle.twap_timestamp)
+        .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+    require!(
+        price_age <= pool_oracle.max_price_age,
+        ErrorCode::StalePoolOraclePrice
+    );
+
+    Ok(pool_oracle.twap_price)
+}
+
+/// Checks that switchboard price doesn't deviate from TWAP more than `max_deviation`.
+pub fn check_price_deviation(
+    price_decimal: &SwitchboardDecimal,
+    twap_price: u64,
+    max_deviation: u64,
+) -> Result<()> {
+    let mantissa =
+        u128::try_from(price_decimal.mantissa).map_err(|_| ErrorCode::InvalidSwitchboardPrice)?;
+    // switchboard price in `POOL_ORACLE_PRICE_SCALE`
+    let price: u128 = if price_decimal.scale >= POOL_ORACLE_PRICE_SCALE {
+        mantissa
+            .checked_div(10u128.pow(price_decimal.scale - POOL_ORACLE_PRICE_SCALE))
+            .ok_or(CommonErrorCode::WrongIntegerDivision)?
+    } else {
+        mantissa
+            .checked_mul(10u128.pow(POOL_ORACLE_PRICE_SCALE - price_decimal.scale))
+            .ok_or(CommonErrorCode::WrongIntegerMultiplication)?
+    };
+
+    let twap_price = u128::from(twap_price);
+    let deviation = if price > twap_price {
+        price - twap_price
+    } else {
+        twap_price - price
+    };
+
+    require!(
+        deviation
+            .checked_mul(POOL_ORACLE_DEVIATION_PRECISION.into())
+            .ok_or(CommonErrorCode::WrongIntegerMultiplication)?
+            <= twap_price
+                .checked_mul(max_deviation.into())
+                .ok_or(CommonErrorCode::WrongIntegerMultiplication)?,
+        ErrorCode::PoolOraclePriceDeviation
+    );
+
+    Ok(())
+}
+
+/// Validates pool oracle settings.
+pub fn validate_pool_oracle_config(
+    max_deviation: u64,
+    twap_period: u64,
+    max_price_age: u64,
+) -> Result<()> {
+    require!(
+        max_deviation > 0
+            && max_deviation <= POOL_ORACLE_DEVIATION_PRECISION
+            && twap_period > 0
+            && max_price_age > 0,
+        ErrorCode::InvalidPoolOracleConfig
+    );
+    Ok(())
+}
+
+/// Limits spot price to `max_deviation` from last TWAP, or from last spot price
+/// before the first TWAP, so a pool moved within one transaction can't shift TWAP freely.
+pub fn clamp_pool_oracle_spot_price(pool_oracle: &PoolOracle, spot_price: u64) -> Result<u64> {
+    let reference_price = if pool_oracle.twap_price > 0 {
+        pool_oracle.twap_price
+    } else {
+        pool_oracle.spot_price
+    };
+
+    let max_change = u128::from(reference_price)
+        .checked_mul(pool_oracle.max_deviation.into())
+        .ok_or(CommonErrorCode::WrongIntegerMultiplication)?
+        .checked_div(POOL_ORACLE_DEVIATION_PRECISION.into())
+        .ok_or(CommonErrorCode::WrongIntegerDivision)?;
+    let max_change = u64::try_from(max_change).unwrap_or(u64::MAX);
+
+    Ok(spot_price.clamp(
+        reference_price.saturating_sub(max_change),
+        reference_price.saturating_add(max_change),
+    ))
+}
+
+/// Quotes pool spot price through pool oracle swapper, `pool_accounts` must be the pool accounts
+/// of pool oracle in the same order.
+pub fn quote_pool_oracle_spot_price<'info>(
+    pool_oracle: &PoolOracle,
+    swapper_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    pool_accounts: &[AccountInfo<'info>],
+) -> Result<u64> {
+    require!(
+        pool_accounts.len() == pool_oracle.pool_accounts.len()
+            && pool_accounts
+                .iter()
+                .zip(pool_oracle.pool_accounts.iter())
+                .all(|(account, pool_account)| account.key() == *pool_account),
+        ErrorCode::InvalidPoolOracleAccounts
+    );
+
+    swapper_package::cpi::get_spot_price(
+        CpiContext::new(swapper_program, GetSpotPrice { authority })
+            .with_remaining_accounts(pool_accounts.to_vec()),
+    )
+}
+
+/// Adds spot price which was active since last update to cumulative price and
+/// calculates TWAP once `twap_period` passed since the start of the period.
+/// New spot price is clamped by `clamp_pool_oracle_spot_price`, except the first one which
+/// is quoted by cauldron authority in `create_pool_oracle`.
+pub fn accumulate_pool_oracle_price(
+    pool_oracle: &mut PoolOracle,
+    spot_price: u64,
+    timestamp: u64,
+) -> Result<()> {
+    if pool_oracle.last_update == 0 {
+        pool_oracle.spot_price = spot_price;
+        pool_oracle.last_update = timestamp;
+        pool_oracle.twap_checkpoint_cumulative = pool_oracle.price_cumulative;
+        pool_oracle.twap_checkpoint_timestamp = timestamp;
+        return Ok(());
+    }
+
+    let elapsed = timestamp
+        .checked_sub(pool_oracle.last_update)
+        .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+    pool_oracle.price_cumulative = pool_oracle
+        .price_cumulative
+        .checked_add(
+            u128::from(pool_oracle.spot_price)
+                .checked_mul(elapsed.into())
+                .ok_or(CommonErrorCode::WrongIntegerMultiplication)?,
+        )
+        .ok_or(CommonErrorCode::WrongIntegerAddition)?;
+    pool_oracle.spot_price = clamp_pool_oracle_spot_price(pool_oracle, spot_price)?;
+    pool_oracle.last_update = timestamp;
+
+    let twap_elapsed = timestamp
+        .checked_sub(pool_oracle.twap_checkpoint_timestamp)
+        .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+    if twap_elapsed >= pool_oracle.twap_period {
+        let twap_price = pool_oracle
+            .price_cumulative
+            .checked_sub(pool_oracle.twap_checkpoint_cumulative)
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?
+            .checked_div(twap_elapsed.into())
+            .ok_or(CommonErrorCode::WrongIntegerDivision)?;
+
+        pool_oracle.twap_price = u64::try_from(twap_price).unwrap_or(u64::MAX);
+        pool_oracle.twap_timestamp = timestamp;
+        pool_oracle.twap_checkpoint_cumulative = pool_oracle.price_cumulative;
+        pool_oracle.twap_checkpoint_timestamp = timestamp;
+    }
+
+    Ok(())
+}
+
 pub fn accrue_internal(accrue_info: &mut AccrueInfo, total_data: &mut Total) -> Result<()> {
     // Number of seconds since accrue was called
     let block_timestamp = Clock::get().unwrap().unix_timestamp as u64;
@@ -506,6 +706,7 @@ pub fn bento_deposit<'info>(
     to: Pubkey,
     amount: u64,
     share: u64,
+    min_share_out: u64,
     from: AccountInfo<'info>,
     bentobox_vault: AccountInfo<'info>,
     bentobox_to_balance: AccountInfo<'info>,
@@ -542,6 +743,7 @@ pub fn bento_deposit<'info>(
         to,
         amount,
         share,
+        min_share_out,
     )?
     .get())
 }
@@ -570,6 +772,7 @@ pub fn liquidate_internal<'info>(
     max_borrow_part: u64,
     // switchboard_data_feed: &AccountLoader<'info,AggregatorAccountData>,
     switchboard_data_feed: &AccountInfo<'info>,
+    pool_oracle: &AccountInfo<'info>,
     cauldron_account: &mut Account<'info, Cauldron>,
     total_data: AccountLoader<'info, Total>,
     collateral: &AccountInfo<'info>,
@@ -579,10 +782,11 @@ pub fn liquidate_internal<'info>(
     bentobox_account: &AccountInfo<'info>,
     bentobox_program: AccountInfo<'info>,
     user_balance: &mut Account<'info, UserBalance>,
-) -> Result<(u64, u64, u64)> {
-    // borrow_amount , borrow_share, collateral_share
-    let price_decimal = get_switchboard_price(
+) -> Result<(u64, u64, u64, u64, u64)> {
+    // borrow_amount , borrow_share, collateral_share, borrow_part, distribution_amount
+    let price_decimal = get_collateral_price(
         &switchboard_data_feed,
+        pool_oracle,
         &Clock::get()?,
         &cauldron_account.constants.stale_after_slots_elapsed,
     )?;
@@ -709,5 +913,60 @@ pub fn liquidate_internal<'info>(
     )?
     .get();
 
-    Ok((borrow_amount, borrow_share, collateral_share))
+    Ok((
+        borrow_amount,
+        borrow_share,
+        collateral_share,
+        borrow_part,
+        distribution_amount,
+    ))
+}
+
+/// Reallocates program `account` of type `T` stored in legacy layout of `legacy_size` bytes to `new_size` bytes.
+/// Rent for the new size is paid by `payer`.
+///
+/// Return: Legacy account data without discriminator.
+pub fn realloc_legacy_account<'info, T: Discriminator>(
+    account: &AccountInfo<'info>,
+    legacy_size: usize,
+    new_size: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<Vec<u8>> {
+    require_keys_eq!(*account.owner, crate::ID, ErrorCode::InvalidLegacyAccount);
+
+    let legacy_data = {
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() == legacy_size && data[..DISCRIMINATOR_BYTES] == T::discriminator(),
+            ErrorCode::InvalidLegacyAccount
+        );
+        data[DISCRIMINATOR_BYTES..].to_vec()
+    };
+
+    let rent_diff = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(account.lamports());
+    if rent_diff > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_diff,
+        )?;
+    }
+    account.realloc(new_size, true)?;
+
+    Ok(legacy_data)
+}
+
+/// Writes `value` in current layout to `account` reallocated by `realloc_legacy_account`.
+pub fn write_migrated_account<T: AccountSerialize>(account: &AccountInfo, value: &T) -> Result<()> {
+    let mut data = account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    value.try_serialize(&mut writer)
 }
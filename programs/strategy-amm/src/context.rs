@@ -0,0 +1,512 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use common::constants::{BENTOBOX_SEED_PART, STRATEGY_SEED_PART, TOTAL_VAULT_KEY_SEED_PART};
+
+use crate::error::ErrorCode;
+use crate::state::*;
+
+use common::errors::ErrorCode as CommonErrorCode;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// Strategy token account.
+    #[account(init,
+        seeds = [STRATEGY_VAULT_SEED_PART.as_ref(),
+                 strategy_account.key().as_ref(),
+                 mint.key().as_ref()],
+        bump,
+        payer = authority,
+        token::mint = mint,
+        token::authority = strategy_authority)]
+    pub strategy_vault: Box<Account<'info, TokenAccount>>,
+    /// Strategy LP token account.
+    #[account(init,
+        seeds = [LP_VAULT_SEED_PART.as_ref(),
+                 strategy_account.key().as_ref(),
+                 pool_mint.key().as_ref()],
+        bump,
+        payer = authority,
+        token::mint = pool_mint,
+        token::authority = strategy_authority)]
+    pub lp_vault: Box<Account<'info, TokenAccount>>,
+    /// Strategy reward token account.
+    #[account(init,
+        seeds = [REWARD_VAULT_SEED_PART.as_ref(),
+                 strategy_account.key().as_ref(),
+                 reward_mint.key().as_ref()],
+        bump,
+        payer = authority,
+        token::mint = reward_mint,
+        token::authority = strategy_authority)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    /// Strategy account.
+    #[account(init,
+        payer = authority,
+        space = StrategyAmm::SIZE)]
+    pub strategy_account: Box<Account<'info, StrategyAmm>>,
+    /// Account which holds all base info for strategy.
+    #[account(init,
+        payer = authority,
+        space = BaseStrategyInfo::SIZE)]
+    pub base_strategy_info: Box<Account<'info, BaseStrategyInfo>>,
+    /// Token mint account.
+    pub mint: Box<Account<'info, Mint>>,
+    /// Token swap pool mint account.
+    pub pool_mint: Box<Account<'info, Mint>>,
+    /// Reward token mint account.
+    pub reward_mint: Box<Account<'info, Mint>>,
+    /// CHECK: swap program executable.
+    #[account(executable)]
+    pub swap_program: UncheckedAccount<'info>,
+    /// CHECK: owner is swap program, pool mint and strategy token are checked inside instruction.
+    #[account(constraint = token_swap.owner == &swap_program.key() @ ErrorCode::InvalidTokenSwapAccount)]
+    pub token_swap: UncheckedAccount<'info>,
+    /// CHECK: swapper program executable.
+    #[account(executable)]
+    pub swapper_program: UncheckedAccount<'info>,
+    /// CHECK: reward swap program executable.
+    #[account(executable)]
+    pub reward_swap_program: UncheckedAccount<'info>,
+    /// CHECK: owner = bentobox program
+    #[account(constraint = bentobox_account.owner == &bentobox_program.key() @ CommonErrorCode::InvalidAccountOwnerBentoboxProgram)]
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// CHECK: account checked and saved if strategy account.
+    pub bentobox_program: UncheckedAccount<'info>,
+    /// Payer account and future owner of strategy account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+    /// Rent account.
+    pub rent: Sysvar<'info, Rent>,
+    /// Token program account.
+    pub token_program: Program<'info, Token>,
+    #[account(init,
+        seeds = [EXECUTOR_INFO_SEED.as_ref(),
+        strategy_account.key().as_ref(),
+        authority.key().as_ref()],bump,
+        payer = authority,
+        space = ExecutorInfo::SIZE)]
+    /// Executor info account for strategy authority for storing info if if is executor or not.
+    pub executor_info: Box<Account<'info, ExecutorInfo>>,
+    /// Strategy token authority pda account.
+    /// CHECK: seeds
+    #[account(seeds = [STRATEGY_SEED_PART.as_ref(),
+    strategy_account.key().as_ref()], bump)]
+    pub strategy_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AfterExit<'info> {
+    #[account(has_one = authority @ ErrorCode::UnauthorizedAfterExit)]
+    pub strategy_account: Box<Account<'info, StrategyAmm>>,
+    #[account(constraint = strategy_account.base_strategy_info == base_strategy_info.key() @ ErrorCode::InvalidBaseStrategyInfoAccount,
+     constraint = base_strategy_info.exited == true @ ErrorCode::StrategyNotExited)]
+    pub base_strategy_info: Box<Account<'info, BaseStrategyInfo>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK:
+    pub strategy_program: UncheckedAccount<'info>,
+}
+
+ 
+
+#[derive(Accounts)]
+pub struct Skim<'info> {
+    /// Strategy program account.
+    /// CHECK: using for check strategy_authority seeds and account owner.
+    pub strategy_program: UncheckedAccount<'info>,
+    /// Bentobox program account.
+    /// CHECK: using for check accounts seeds and account owner.
+    pub bentobox_program: UncheckedAccount<'info>,
+    /// Bentobox account.
+    /// CHECK: for owner and for rent_exempt enforce, inside inside instruction: whether bentobox is the same as initialized for strategy.
+    #[account(rent_exempt = enforce,
+              constraint = bentobox_account.owner == &bentobox_program.key() @ CommonErrorCode::InvalidAccountOwnerBentoboxProgram)]
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// Strategy token account.
+    #[account(mut,
+              seeds = [STRATEGY_VAULT_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref(),
+                       strategy_vault.mint.as_ref()],
+              bump,
+              seeds::program = strategy_program.key(),
+              constraint = strategy_vault.owner == strategy_authority.key() @ CommonErrorCode::StrategyVaultInvalidAuthority)]
+    pub strategy_vault: Box<Account<'info, TokenAccount>>,
+    /// Strategy account.
+    /// CHECK: on owner and on rent_exempt enforce.
+    #[account(rent_exempt = enforce,
+              constraint = strategy_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub strategy_account: UncheckedAccount<'info>,
+    /// Strategy token authority pda account.
+    /// CHECK: seeds and seeds::program.
+    #[account(seeds = [STRATEGY_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref()],
+                       bump,
+                       seeds::program = strategy_program.key())]
+    pub strategy_authority: UncheckedAccount<'info>,
+    /// Account which holds all base info for strategy.
+    /// CHECK: on owner and on rent_exempt enforce.
+    #[account(rent_exempt = enforce,
+              constraint = base_strategy_info.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub base_strategy_info: UncheckedAccount<'info>,
+    /// Token program account.
+    pub token_program: Program<'info, Token>,
+    /// Bentobox token authority pda account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(executor: Pubkey, value: bool)]
+pub struct SetStrategyExecutor<'info> {
+    #[account(has_one = authority @ ErrorCode::UnauthorizedSetStrategyExecutor)]
+    pub strategy_account: Box<Account<'info, StrategyAmm>>,
+    #[account(init_if_needed,
+        seeds = [EXECUTOR_INFO_SEED.as_ref(),
+        strategy_account.key().as_ref(), 
+        executor.as_ref()],
+        bump,
+        payer = authority,
+        space = ExecutorInfo::SIZE)]
+    pub executor_info: Box<Account<'info, ExecutorInfo>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRewardMinAmountOut<'info> {
+    #[account(mut)]
+    pub strategy_account: Box<Account<'info, StrategyAmm>>,
+    /// Executor info account of `authority`.
+    #[account(seeds = [EXECUTOR_INFO_SEED.as_ref(),
+                       strategy_account.key().as_ref(),
+                       authority.key().as_ref()],
+              bump,
+              constraint = executor_info.is_executor @ ErrorCode::UnauthorizedSetRewardMinAmountOut)]
+    pub executor_info: Box<Account<'info, ExecutorInfo>>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSkimMinPoolTokens<'info> {
+    #[account(mut)]
+    pub strategy_account: Box<Account<'info, StrategyAmm>>,
+    /// Executor info account of `authority`.
+    #[account(seeds = [EXECUTOR_INFO_SEED.as_ref(),
+                       strategy_account.key().as_ref(),
+                       authority.key().as_ref()],
+              bump,
+              constraint = executor_info.is_executor @ ErrorCode::UnauthorizedSetSkimMinPoolTokens)]
+    pub executor_info: Box<Account<'info, ExecutorInfo>>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Harvest<'info> {
+    /// Strategy program account.
+    /// CHECK: using for check seeds.
+    pub strategy_program: UncheckedAccount<'info>,
+    /// Bentobox program account..
+    /// CHECK: using for check signer's seeds.
+    pub bentobox_program: UncheckedAccount<'info>,
+    /// Strategy account used by Bentobox.
+    /// CHECK: on owner and on rent_exempt enforce.
+    #[account(rent_exempt = enforce,
+              constraint = strategy_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]    
+    pub strategy_account: UncheckedAccount<'info>,
+    /// Bentobox account.
+    /// CHECK: whether bentobox is the same as initialized for strategy.
+    #[account(rent_exempt = enforce,
+              constraint = bentobox_account.owner == &bentobox_program.key() @ CommonErrorCode::InvalidAccountOwnerBentoboxProgram)]
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// Bentobox token authority pda account.
+    #[account(mut, 
+              seeds = [BENTOBOX_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref()],
+              bump, 
+              seeds::program = bentobox_program.key())]
+    pub authority: Signer<'info>,
+    /// Strategy token account.
+    #[account(mut,
+              seeds = [STRATEGY_VAULT_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref(),
+                       strategy_vault.mint.as_ref()],
+              bump,
+              seeds::program = strategy_program.key(),
+              constraint = strategy_vault.owner == strategy_authority.key() @ CommonErrorCode::StrategyVaultInvalidAuthority)]
+    pub strategy_vault: Box<Account<'info, TokenAccount>>,
+    /// Strategy token authority pda account.
+    /// CHECK: account checked in CPI
+    #[account(seeds = [STRATEGY_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref()], 
+              bump,
+              seeds::program = strategy_program.key())]
+    pub strategy_authority: UncheckedAccount<'info>,
+    /// Token program account.
+    pub token_program: Program<'info, Token>,
+    /// Account which holds all base info for strategy.
+    /// CHECK: on owner and on rent_exempt enforce.
+    #[account(rent_exempt = enforce,
+              constraint = base_strategy_info.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub base_strategy_info: UncheckedAccount<'info>,
+    /// Not used, harvest result is returned via return data.
+    /// CHECK: not used.
+    pub cpi_result_account: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    /// Strategy program account.
+    /// CHECK: using for check seeds.
+    pub strategy_program: UncheckedAccount<'info>,
+    /// Bentobox program account.
+    /// CHECK: using for check signer's seeds.
+    pub bentobox_program: UncheckedAccount<'info>,
+    /// Strategy account.
+    /// CHECK: on owner and on rent_exempt enforce.
+    #[account(rent_exempt = enforce,
+              constraint = strategy_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]    
+    pub strategy_account: UncheckedAccount<'info>,
+    /// Strategy token account.
+    #[account(mut,
+              seeds = [STRATEGY_VAULT_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref(),
+                       strategy_vault.mint.as_ref()],
+              bump,
+              seeds::program = strategy_program.key(),
+              constraint = strategy_vault.owner == strategy_authority.key() @ CommonErrorCode::StrategyVaultInvalidAuthority)]
+    pub strategy_vault: Box<Account<'info, TokenAccount>>,
+    /// Strategy token authority pda account.
+    ///  CHECK: strategy authority
+    #[account(seeds = [STRATEGY_SEED_PART.as_ref(), 
+                       strategy_account.key().as_ref()], 
+                       bump, 
+                       seeds::program = strategy_program.key())]
+    pub strategy_authority: UncheckedAccount<'info>,
+    /// Bentobox account.
+    /// CHECK: whether bentobox is the same as initialized for strategy.
+    #[account(rent_exempt = enforce,
+              constraint = bentobox_account.owner == &bentobox_program.key() @ CommonErrorCode::InvalidAccountOwnerBentoboxProgram)]
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// Token program account.
+    pub token_program: Program<'info, Token>,
+    /// Bentobox token authority pda account.
+    #[account(mut, 
+              seeds = [BENTOBOX_SEED_PART.as_ref(), 
+                       bentobox_account.key().as_ref()], 
+              bump, 
+              seeds::program = bentobox_program.key())]
+    pub authority: Signer<'info>,
+    /// Account which holds all base info for strategy.
+    /// CHECK: on owner and on rent_exempt enforce.
+    #[account(rent_exempt = enforce,
+              constraint = base_strategy_info.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub base_strategy_info: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Exit<'info> {
+    /// Strategy program account.
+    /// CHECK: using for check seeds.
+    pub strategy_program: UncheckedAccount<'info>,
+    /// Bentobox program account.
+    /// CHECK: using for check signer's seeds.
+    pub bentobox_program: UncheckedAccount<'info>,
+    /// Bentobox account.
+    /// CHECK: whether bentobox is the same as initialized for strategy.
+    #[account(rent_exempt = enforce,
+              constraint = bentobox_account.owner == &bentobox_program.key() @ CommonErrorCode::InvalidAccountOwnerBentoboxProgram)]
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// Strategy account.
+    /// CHECK: using to check strategy_authority seeds and accounts inside exit implementation.
+    #[account(rent_exempt = enforce,
+              constraint = strategy_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub strategy_account: UncheckedAccount<'info>,
+    /// Strategy token account.
+    #[account(mut,
+              seeds = [STRATEGY_VAULT_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref(),
+                       strategy_vault.mint.as_ref()],
+              bump,
+              seeds::program = strategy_program.key(),
+              constraint = strategy_vault.owner == strategy_authority.key() @ CommonErrorCode::StrategyVaultInvalidAuthority)]
+
+    pub strategy_vault: Box<Account<'info, TokenAccount>>,
+    /// Strategy token authority pda account.
+    /// CHECK: seeds
+    #[account(seeds = [STRATEGY_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref()],
+              bump,
+              seeds::program = strategy_program.key())]
+    pub strategy_authority: UncheckedAccount<'info>,
+    /// Token program account.
+    pub token_program: Program<'info, Token>,
+    /// Bentobox token authority pda account.
+    #[account(mut,
+              seeds = [BENTOBOX_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref()],
+              bump,
+              seeds::program = bentobox_program.key())]
+    pub authority: Signer<'info>,
+    /// Account which holds all base info for strategy.
+    /// CHECK: on owner and on rent_exempt enforce.
+    #[account(mut,
+              rent_exempt = enforce,
+              constraint = base_strategy_info.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub base_strategy_info: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct HarvestRewards<'info> {
+    /// Strategy program account.
+    /// CHECK: using for check signer's seeds.
+    pub strategy_program: UncheckedAccount<'info>,
+    /// Bentobox program account.
+    /// CHECK: using for check signer's seeds.
+    pub bentobox_program: UncheckedAccount<'info>,
+    /// Strategy account.
+    /// CHECK: using to check signer's seeds and accounts inside harvest rewards implementation, reward swap minimum is reset.
+    #[account(mut,
+              rent_exempt = enforce,
+              constraint = strategy_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub strategy_account: UncheckedAccount<'info>,
+    /// Bentobox account.
+    /// CHECK: whether bentobox is the same as initialized for strategy.
+    #[account(rent_exempt = enforce,
+              constraint = bentobox_account.owner == &bentobox_program.key() @ CommonErrorCode::InvalidAccountOwnerBentoboxProgram)]
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// Account which holds all base info for strategy.
+    /// CHECK: on owner and on rent_exempt enforce.
+    #[account(rent_exempt = enforce,
+              constraint = base_strategy_info.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub base_strategy_info: UncheckedAccount<'info>,
+    /// Strategy token account.
+    #[account(mut,
+              seeds = [STRATEGY_VAULT_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref(),
+                       strategy_vault.mint.as_ref()],
+              bump,
+              seeds::program = strategy_program.key(),
+              constraint = strategy_vault.owner == strategy_authority.key() @ CommonErrorCode::StrategyVaultInvalidAuthority)]
+    pub strategy_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, 
+              seeds = [BENTOBOX_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref()],
+              bump,
+              seeds::program = bentobox_program.key())]
+    pub authority: Signer<'info>,
+    /// Strategy token authority pda account.
+    /// CHECK: seeds.
+    #[account(mut, 
+              seeds = [STRATEGY_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref()],
+              bump, 
+              seeds::program = strategy_program.key())]
+    pub strategy_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SafeHarvest<'info> {
+    /// CHECK: on owner and on rent_exempt enforce.
+    #[account(mut, 
+              rent_exempt = enforce,
+              constraint = base_strategy_info.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub base_strategy_info: UncheckedAccount<'info>,
+    /// Bentobox account.
+    /// CHECK: whether bentobox is the same as initialized for strategy.
+    #[account(rent_exempt = enforce,
+              constraint = bentobox_account.owner == &bentobox_program.key() @ CommonErrorCode::InvalidAccountOwnerBentoboxProgram)]
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// Strategy program account.
+    /// CHECK: using for check seeds.
+    pub strategy_program: UncheckedAccount<'info>,
+    /// Bentobox progra, account.
+    /// CHECK: using for check signer's seeds.
+    pub bentobox_program: UncheckedAccount<'info>,
+    /// Strategy account used by Bentobox.
+    /// CHECK: using to check accounts inside safe harvest implementation.
+    #[account(rent_exempt = enforce,
+              constraint = strategy_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub strategy_account: UncheckedAccount<'info>,
+    /// Strategy token account.
+    #[account(mut,
+              seeds = [STRATEGY_VAULT_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref(),
+                       strategy_vault.mint.as_ref()],
+              bump,
+              seeds::program = strategy_program.key(),
+              constraint = strategy_vault.owner == strategy_authority.key() @ CommonErrorCode::StrategyVaultInvalidAuthority)]
+    pub strategy_vault: Box<Account<'info, TokenAccount>>,
+    /// Bentobox token authority pda account.
+    #[account(mut, 
+              seeds = [BENTOBOX_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref()],
+              bump,
+              seeds::program = bentobox_program.key())]
+    pub authority: Signer<'info>,
+    /// Strategy token authority pda account.
+    /// CHECK:
+    #[account(mut,
+              seeds = [STRATEGY_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref()],
+              bump,
+              seeds::program = strategy_program.key())]
+    pub strategy_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Transfer<'info> {
+    /// Strategy program account.
+    /// CHECK: using for check signer's seeds.
+    pub strategy_program: UncheckedAccount<'info>,
+    /// Bentobox program account.
+    /// CHECK: using for check signer's seeds.
+    pub bentobox_program: UncheckedAccount<'info>,
+    /// Bentobox account.
+    /// CHECK: whether bentobox is the same as initialized for strategy.
+    #[account(rent_exempt = enforce,
+              constraint = bentobox_account.owner == &bentobox_program.key() @ CommonErrorCode::InvalidAccountOwnerBentoboxProgram)]
+    pub bentobox_account: UncheckedAccount<'info>,
+    /// Strategy account.
+    /// CHECK: on owner and on rent_exempt enforce.
+    #[account(rent_exempt = enforce,
+              constraint = strategy_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
+    pub strategy_account: UncheckedAccount<'info>,
+    /// Bentobox token authority pda account.
+    #[account(mut,
+              seeds = [BENTOBOX_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref()],
+              bump,
+              seeds::program = bentobox_program.key())]
+    pub authority: Signer<'info>,
+    /// Bentobox token account.
+    #[account(mut, 
+              seeds = [TOTAL_VAULT_KEY_SEED_PART.as_ref(),
+                       bentobox_account.key().as_ref(),
+                       bentobox_vault.mint.as_ref()],
+             bump,
+             seeds::program = bentobox_program.key(),
+             constraint = bentobox_vault.owner == authority.key() @ CommonErrorCode::BentoboxVaultInvalidAuthority)]
+    pub bentobox_vault: Box<Account<'info, TokenAccount>>,
+    /// Strategy token account.
+    #[account(mut,
+              seeds = [STRATEGY_VAULT_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref(),
+                       strategy_vault.mint.as_ref()],
+              bump,
+              seeds::program = strategy_program.key(),
+              constraint = strategy_vault.owner == strategy_authority.key() @ CommonErrorCode::StrategyVaultInvalidAuthority)]
+    pub strategy_vault: Box<Account<'info, TokenAccount>>,
+    /// Strategy token authority pda account.
+    /// CHECK: strategy authority
+    #[account(seeds = [STRATEGY_SEED_PART.as_ref(),
+                       strategy_account.key().as_ref()],
+              bump,
+              seeds::program = strategy_program.key())]
+    pub strategy_authority: UncheckedAccount<'info>,
+    /// Token program account.
+    pub token_program: Program<'info, Token>,
+}
+
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Error convertion from AccountInfo to StrategyAmm.")]
+    WrongConvertionFromAccountInfoToStrategyAmm,
+
+    #[msg("Invalid base strategy info account.")]
+    InvalidBaseStrategyInfoAccount,
+
+    #[msg("BentoBox Strategy: not exited.")]
+    StrategyNotExited,
+
+    #[msg("BentoBox Strategy: invalid strategy vault token account.")]
+    InvalidStrategyVaultAccount,
+
+    #[msg("Unauthorized after_exit.")]
+    UnauthorizedAfterExit,
+
+    #[msg("Unauthorized set_strategy_executor.")]
+    UnauthorizedSetStrategyExecutor,
+
+    #[msg("Unauthorized set_reward_min_amount_out.")]
+    UnauthorizedSetRewardMinAmountOut,
+
+    #[msg("Unauthorized set_skim_min_pool_tokens.")]
+    UnauthorizedSetSkimMinPoolTokens,
+
+    #[msg("Invalid remaining accounts.")]
+    InvalidRemainingAccounts,
+
+    #[msg("Invalid bentobox account.")]
+    InvalidBentoBoxAccount,
+
+    #[msg("Invalid swap program.")]
+    InvalidSwapProgram,
+
+    #[msg("Invalid token swap account.")]
+    InvalidTokenSwapAccount,
+
+    #[msg("Token swap accounts do not match token swap state.")]
+    InvalidTokenSwapState,
+
+    #[msg("Strategy token is not traded in token swap pool.")]
+    InvalidTokenSwapMint,
+
+    #[msg("Invalid LP vault account.")]
+    InvalidLpVaultAccount,
+
+    #[msg("Invalid reward vault account.")]
+    InvalidRewardVaultAccount,
+
+    #[msg("Invalid swapper program.")]
+    InvalidSwapperProgram,
+
+    #[msg("Invalid reward swap program.")]
+    InvalidRewardSwapProgram,
+
+    #[msg("Minimum amount out for reward swap is not set.")]
+    RewardMinAmountOutNotSet,
+
+    #[msg("Reward swap returned less than minimum amount out.")]
+    RewardSwapSlippageExceeded,
+
+    #[msg("Token swap calculation failed.")]
+    TokenSwapCalculationFailure,
+
+    #[msg("Minimum pool tokens for skim deposit is not set.")]
+    SkimMinPoolTokensNotSet,
+
+    #[msg("Invalid bentobox program.")]
+    InvalidBentoBoxProgram,
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+#[event]
+pub struct LogSetStrategyExecutor {
+    pub executor: Pubkey,
+    pub value: bool,
+}
+#[event]
+pub struct LogSetRewardMinAmountOut {
+    pub executor: Pubkey,
+    pub min_amount_out: u64,
+}
+#[event]
+pub struct LogSetSkimMinPoolTokens {
+    pub executor: Pubkey,
+    pub min_pool_tokens: u64,
+}
+#[event]
+pub struct LogAmmHarvest {
+    /// Value of strategy LP tokens in underlying tokens if withdrawn single-sided.
+    pub invested_amount: u64,
+    /// Profit (+) or loss (-) in contrast to the balance Bentobox thinks it has invested.
+    pub amount: i64,
+}
+#[event]
+pub struct LogHarvestRewards {
+    /// Amount of reward tokens swapped.
+    pub reward_amount: u64,
+    /// Amount of underlying tokens received to strategy vault.
+    pub amount_out: u64,
+}
@@ -0,0 +1,465 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer as TokenTransfer};
+use spl_token_swap::state::SwapVersion;
+
+use common::errors::ErrorCode as CommonErrorCode;
+
+pub mod context;
+pub mod error;
+pub mod event;
+pub mod state;
+
+use crate::context::*;
+use crate::error::ErrorCode;
+use crate::event::*;
+use crate::state::*;
+
+use strategy_package::base_after_exit;
+
+declare_id!("FFTsVKppoiAEXXA2jTdEdJq1MKz7aKCSjuH2ZHXfDgv9");
+
+/// Strategy which provides idle BentoBox liquidity single-sided to spl-token-swap pool.
+/// Profit comes from pool trading fees and from pool rewards sold for strategy token.
+#[program]
+pub mod strategy_amm {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let token_swap = SwapVersion::unpack(&ctx.accounts.token_swap.try_borrow_data()?)?;
+        require_keys_eq!(
+            *token_swap.pool_mint(),
+            ctx.accounts.pool_mint.key(),
+            ErrorCode::InvalidTokenSwapState
+        );
+        let mint_key = ctx.accounts.mint.key();
+        require!(
+            token_swap.token_a_mint() == &mint_key || token_swap.token_b_mint() == &mint_key,
+            ErrorCode::InvalidTokenSwapMint
+        );
+
+        let strategy_account = &mut ctx.accounts.strategy_account;
+        let authority_key = ctx.accounts.authority.key();
+        //set strategy authority
+        strategy_account.authority = authority_key;
+        strategy_account.base_strategy_info = ctx.accounts.base_strategy_info.key();
+        //set bentobox
+        strategy_account.bentobox_account = ctx.accounts.bentobox_account.key();
+        //set bentobox program id
+        strategy_account.bentobox_program = ctx.accounts.bentobox_program.key();
+        //set pool accounts
+        strategy_account.swap_program = ctx.accounts.swap_program.key();
+        strategy_account.token_swap = ctx.accounts.token_swap.key();
+        strategy_account.lp_vault = ctx.accounts.lp_vault.key();
+        //set reward accounts
+        strategy_account.reward_vault = ctx.accounts.reward_vault.key();
+        strategy_account.swapper_program = ctx.accounts.swapper_program.key();
+        strategy_account.reward_swap_program = ctx.accounts.reward_swap_program.key();
+        strategy_account.strategy_authority_bump = *ctx
+            .bumps
+            .get("strategy_authority")
+            .ok_or(ErrorCode::WrongConvertionFromAccountInfoToStrategyAmm)?;
+
+        // set strategy_token
+        ctx.accounts.base_strategy_info.strategy_token = mint_key;
+
+        //set payer account as executor
+        let payer_executor_info = &mut ctx.accounts.executor_info;
+        payer_executor_info.is_executor = true;
+        payer_executor_info.user = authority_key;
+
+        Ok(())
+    }
+
+    pub fn after_exit(ctx: Context<AfterExit>, name: String, args: Vec<Vec<u8>>) -> Result<()> {
+        let strategy_key = ctx.accounts.strategy_account.key();
+        let bump = [ctx.accounts.strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+        let signer = &[&seeds[..]];
+
+        base_after_exit(
+            ctx.remaining_accounts.to_vec(),
+            name,
+            args,
+            ctx.program_id.clone(),
+            signer,
+        )?;
+        Ok(())
+    }
+
+    pub fn set_strategy_executor(
+        ctx: Context<SetStrategyExecutor>,
+        executor: Pubkey,
+        value: bool,
+    ) -> Result<()> {
+        let executor_info = &mut ctx.accounts.executor_info;
+        executor_info.is_executor = value;
+        executor_info.user = executor;
+        emit!(LogSetStrategyExecutor { executor, value });
+
+        Ok(())
+    }
+
+    /// Sets minimum amount of strategy tokens for the next reward swap in `harvest_rewards`.
+    /// Should be called by executor in the same transaction as Bentobox `safe_harvest`.
+    ///
+    /// Arguments:
+    ///
+    /// * `min_amount_out` - Minimum amount of strategy tokens for all rewards in reward vault.
+    pub fn set_reward_min_amount_out(
+        ctx: Context<SetRewardMinAmountOut>,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        ctx.accounts.strategy_account.reward_min_amount_out = min_amount_out;
+        emit!(LogSetRewardMinAmountOut {
+            executor: ctx.accounts.authority.key(),
+            min_amount_out,
+        });
+
+        Ok(())
+    }
+
+    /// Sets minimum amount of pool tokens for the next single-sided deposit in `skim`.
+    /// Should be called by executor in the same transaction as Bentobox `harvest` or `safe_harvest`
+    /// which rebalances tokens to the strategy.
+    ///
+    /// Arguments:
+    ///
+    /// * `min_pool_tokens` - Minimum amount of pool tokens minted for skimmed tokens.
+    pub fn set_skim_min_pool_tokens(
+        ctx: Context<SetSkimMinPoolTokens>,
+        min_pool_tokens: u64,
+    ) -> Result<()> {
+        ctx.accounts.strategy_account.skim_min_pool_tokens = min_pool_tokens;
+        emit!(LogSetSkimMinPoolTokens {
+            executor: ctx.accounts.authority.key(),
+            min_pool_tokens,
+        });
+
+        Ok(())
+    }
+
+    /// Deposits `amount` tokens from strategy vault to the pool single-sided.
+    /// Requires minimum pool tokens set by executor with `set_skim_min_pool_tokens`.
+    // remaining - token swap accounts, see `AmmAccounts`
+    pub fn skim<'info>(ctx: Context<'_, '_, '_, 'info, Skim<'info>>, amount: u64) -> Result<()> {
+        let (mut strategy_account, strategy_token) = check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+        let amm = AmmAccounts::try_from_remaining(&strategy_account, ctx.remaining_accounts)?;
+
+        let strategy_key = strategy_account.key();
+        let bump = [strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+
+        let min_pool_tokens = strategy_account.skim_min_pool_tokens;
+        require!(min_pool_tokens > 0, ErrorCode::SkimMinPoolTokensNotSet);
+
+        // check that passed pool accounts belong to token swap
+        amm.pool_state(&strategy_token)?;
+        amm.deposit(
+            amount,
+            min_pool_tokens,
+            &ctx.accounts.strategy_vault.to_account_info(),
+            &ctx.accounts.strategy_authority,
+            &ctx.accounts.token_program,
+            &[&seeds[..]],
+        )?;
+
+        // minimum pool tokens is valid only for one skim
+        strategy_account.skim_min_pool_tokens = 0;
+        strategy_account.exit(ctx.program_id)?;
+        Ok(())
+    }
+
+    /// Reports profit or loss of provided liquidity and withdraws profit to strategy vault.
+    /// Liquidity is valued by single-sided withdrawal from current pool state, so harvest
+    /// should be protected by `max_bentobox_balance` set in `safe_harvest`.
+    // remaining - token swap accounts, see `AmmAccounts`
+    pub fn harvest<'info>(
+        ctx: Context<'_, '_, '_, 'info, Harvest<'info>>,
+        balance: u64,
+    ) -> Result<ResultAmount> {
+        let (strategy_account, strategy_token) = check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+        let amm = AmmAccounts::try_from_remaining(&strategy_account, ctx.remaining_accounts)?;
+
+        let strategy_key = strategy_account.key();
+        let bump = [strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+
+        let pool_state = amm.pool_state(&strategy_token)?;
+        let invested_amount = pool_state.max_withdraw_amount(amm.lp_amount()?);
+
+        let amount = i64::try_from(invested_amount)
+            .map_err(|_| CommonErrorCode::TryIntoConversionError)?
+            .checked_sub(
+                i64::try_from(balance).map_err(|_| CommonErrorCode::TryIntoConversionError)?,
+            )
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+
+        if amount > 0 {
+            amm.withdraw(
+                &pool_state,
+                amount as u64,
+                &mut ctx.accounts.strategy_vault,
+                &ctx.accounts.strategy_authority,
+                &ctx.accounts.token_program,
+                &[&seeds[..]],
+            )?;
+        }
+
+        emit!(LogAmmHarvest {
+            invested_amount,
+            amount,
+        });
+        Ok(ResultAmount { amount })
+    }
+
+    /// Withdraws `amount` tokens single-sided to strategy vault.
+    ///
+    /// Return: the amount of tokens actually withdrawn, can be less if strategy LP tokens are worth less.
+    // remaining - token swap accounts, see `AmmAccounts`
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        amount: u64,
+    ) -> Result<u64> {
+        let (strategy_account, strategy_token) = check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+        let amm = AmmAccounts::try_from_remaining(&strategy_account, ctx.remaining_accounts)?;
+
+        let strategy_key = strategy_account.key();
+        let bump = [strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+
+        let pool_state = amm.pool_state(&strategy_token)?;
+        amm.withdraw(
+            &pool_state,
+            amount,
+            &mut ctx.accounts.strategy_vault,
+            &ctx.accounts.strategy_authority,
+            &ctx.accounts.token_program,
+            &[&seeds[..]],
+        )
+    }
+
+    /// Withdraws all liquidity single-sided and sets strategy as exited.
+    ///
+    /// Return: the amount of tokens withdrawn to strategy vault.
+    // remaining - token swap accounts, see `AmmAccounts`
+    pub fn exit<'info>(ctx: Context<'_, '_, '_, 'info, Exit<'info>>) -> Result<u64> {
+        let (strategy_account, strategy_token) = check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+        let amm = AmmAccounts::try_from_remaining(&strategy_account, ctx.remaining_accounts)?;
+
+        // set exited to true
+        let mut info = Account::<BaseStrategyInfo>::try_from(&ctx.accounts.base_strategy_info)?;
+        info.exited = true;
+        info.exit(ctx.program_id)?;
+
+        let strategy_key = strategy_account.key();
+        let bump = [strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+
+        let pool_state = amm.pool_state(&strategy_token)?;
+        amm.withdraw(
+            &pool_state,
+            u64::MAX,
+            &mut ctx.accounts.strategy_vault,
+            &ctx.accounts.strategy_authority,
+            &ctx.accounts.token_program,
+            &[&seeds[..]],
+        )
+    }
+
+    /// Sells all tokens from reward vault for strategy token through swapper program.
+    /// Swapped tokens stay in strategy vault and are reported as profit by the following harvest.
+    // remaining:
+    //   0..8 - token swap accounts, see `AmmAccounts`
+    //   8.   `[writable]` reward vault
+    //   9.   `[]` swapper program
+    //   10.  `[]` reward swap program
+    //   11.  `[]` token program
+    //   12.. swapper remaining accounts
+    pub fn harvest_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, HarvestRewards<'info>>,
+    ) -> Result<()> {
+        let (mut strategy_account, _) = check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+
+        let reward_accounts_start = AMM_REMAINING_ACCOUNTS_COUNT;
+        let swapper_accounts_start = reward_accounts_start + REWARD_REMAINING_ACCOUNTS_COUNT;
+        require!(
+            ctx.remaining_accounts.len() >= swapper_accounts_start,
+            ErrorCode::InvalidRemainingAccounts
+        );
+        let reward_vault = &ctx.remaining_accounts[reward_accounts_start];
+        let swapper_program = &ctx.remaining_accounts[reward_accounts_start + 1];
+        let reward_swap_program = &ctx.remaining_accounts[reward_accounts_start + 2];
+        let token_program =
+            Program::<Token>::try_from(&ctx.remaining_accounts[reward_accounts_start + 3])?;
+
+        require_keys_eq!(
+            reward_vault.key(),
+            strategy_account.reward_vault,
+            ErrorCode::InvalidRewardVaultAccount
+        );
+        require_keys_eq!(
+            swapper_program.key(),
+            strategy_account.swapper_program,
+            ErrorCode::InvalidSwapperProgram
+        );
+        require_keys_eq!(
+            reward_swap_program.key(),
+            strategy_account.reward_swap_program,
+            ErrorCode::InvalidRewardSwapProgram
+        );
+
+        let reward_amount = Account::<TokenAccount>::try_from(reward_vault)?.amount;
+        if reward_amount == 0 {
+            return Ok(());
+        }
+
+        let min_amount_out = strategy_account.reward_min_amount_out;
+        require!(min_amount_out > 0, ErrorCode::RewardMinAmountOutNotSet);
+
+        let strategy_key = strategy_account.key();
+        let bump = [strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+        let signer = &[&seeds[..]];
+
+        let mut strategy_authority = ctx.accounts.strategy_authority.to_account_info();
+        strategy_authority.is_signer = true;
+
+        let balance_before = ctx.accounts.strategy_vault.amount;
+        swapper_package::cpi::swap(
+            CpiContext::new_with_signer(
+                swapper_program.clone(),
+                swapper_package::cpi::accounts::Swap {
+                    source_token_account: reward_vault.clone(),
+                    destination_token_account: ctx.accounts.strategy_vault.to_account_info(),
+                    swap_program: reward_swap_program.clone(),
+                    token_program: token_program.to_account_info(),
+                    authority: strategy_authority,
+                },
+                signer,
+            )
+            .with_remaining_accounts(ctx.remaining_accounts[swapper_accounts_start..].to_vec()),
+            reward_amount,
+            min_amount_out,
+        )?;
+        ctx.accounts.strategy_vault.reload()?;
+
+        let amount_out = ctx
+            .accounts
+            .strategy_vault
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+        require!(
+            amount_out >= min_amount_out,
+            ErrorCode::RewardSwapSlippageExceeded
+        );
+
+        // minimum amount out is valid only for one reward swap
+        strategy_account.reward_min_amount_out = 0;
+        strategy_account.exit(ctx.program_id)?;
+
+        emit!(LogHarvestRewards {
+            reward_amount,
+            amount_out,
+        });
+        Ok(())
+    }
+
+    pub fn safe_harvest<'info>(
+        ctx: Context<'_, '_, '_, 'info, SafeHarvest<'info>>,
+        max_balance: u64,
+    ) -> Result<()> {
+        check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+
+        if max_balance > 0 {
+            let mut info =
+                Account::<BaseStrategyInfo>::try_from(&ctx.accounts.base_strategy_info)?;
+            info.max_bentobox_balance = max_balance;
+            info.exit(ctx.program_id)?;
+        }
+        Ok(())
+    }
+
+    pub fn transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, Transfer<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let strategy_account = Account::<StrategyAmm>::try_from(&ctx.accounts.strategy_account)?;
+        require_keys_eq!(
+            strategy_account.bentobox_program,
+            ctx.accounts.bentobox_program.key(),
+            ErrorCode::InvalidBentoBoxProgram
+        );
+        require_keys_eq!(
+            strategy_account.bentobox_account,
+            ctx.accounts.bentobox_account.key(),
+            ErrorCode::InvalidBentoBoxAccount
+        );
+
+        let strategy_key = strategy_account.key();
+        let bump = [strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+
+        transfer_internal(
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_vault,
+            &ctx.accounts.strategy_authority,
+            &ctx.accounts.token_program,
+            &[&seeds[..]],
+            amount,
+        )
+    }
+}
+
+fn transfer_internal<'info>(
+    from_vault: &Account<'info, TokenAccount>,
+    to_vault: &Account<'info, TokenAccount>,
+    from_authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    signer: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            TokenTransfer {
+                from: from_vault.to_account_info(),
+                to: to_vault.to_account_info(),
+                authority: from_authority.clone(),
+            },
+        )
+        .with_signer(signer),
+        amount,
+    )
+}
@@ -0,0 +1,381 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use spl_token_swap::curve::calculator::{RoundDirection, TradeDirection};
+use spl_token_swap::state::{SwapState, SwapVersion};
+
+use crate::error::ErrorCode;
+use common::constants::STRATEGY_SEED_PART;
+use strategy_package::errors::ErrorCode as StrategyErrorCode;
+
+#[constant]
+pub const STRATEGY_VAULT_SEED_PART: &[u8] = b"strategyvaultkey";
+#[constant]
+pub const LP_VAULT_SEED_PART: &[u8] = b"lpvaultkey";
+#[constant]
+pub const REWARD_VAULT_SEED_PART: &[u8] = b"rewardvaultkey";
+#[constant]
+pub const EXECUTOR_INFO_SEED: &[u8] = b"executorinfo";
+/// Token swap accounts passed as first remaining accounts to skim, harvest, withdraw, exit and harvest_rewards.
+#[constant]
+pub const AMM_REMAINING_ACCOUNTS_COUNT: usize = 8;
+/// Reward accounts passed to harvest_rewards after token swap accounts, followed by swapper remaining accounts.
+#[constant]
+pub const REWARD_REMAINING_ACCOUNTS_COUNT: usize = 4;
+
+#[account]
+#[derive(Default)]
+pub struct StrategyAmm {
+    pub authority: Pubkey,
+    pub base_strategy_info: Pubkey,
+    pub bentobox_program: Pubkey,
+    pub bentobox_account: Pubkey,
+    /// spl-token-swap compatible program.
+    pub swap_program: Pubkey,
+    /// Token swap pool to which strategy provides liquidity.
+    pub token_swap: Pubkey,
+    /// Strategy LP token account.
+    pub lp_vault: Pubkey,
+    /// Strategy reward token account.
+    pub reward_vault: Pubkey,
+    /// Swapper program used to sell rewards for strategy token.
+    pub swapper_program: Pubkey,
+    /// Swap program invoked by swapper to sell rewards.
+    pub reward_swap_program: Pubkey,
+    /// Minimum amount of strategy tokens for next reward swap, set by executor.
+    pub reward_min_amount_out: u64,
+    /// Minimum amount of pool tokens for next skim deposit, set by executor.
+    pub skim_min_pool_tokens: u64,
+    pub strategy_authority_bump: u8,
+}
+
+impl StrategyAmm {
+    pub const SIZE: usize = 8 + 32 * 10 + 8 + 8 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct BaseStrategyInfo {
+    pub strategy_token: Pubkey,
+    pub exited: bool,
+    pub max_bentobox_balance: u64,
+}
+
+impl BaseStrategyInfo {
+    pub const SIZE: usize = 8 + 32 + 1 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ResultAmount {
+    pub amount: i64,
+}
+
+#[account]
+#[derive(Default)]
+pub struct ExecutorInfo {
+    pub is_executor: bool,
+    pub user: Pubkey,
+}
+
+impl ExecutorInfo {
+    pub const SIZE: usize = 8 + 1 + 32;
+}
+
+/// Token swap accounts expected in remaining accounts:
+///
+///   0. `[]` Swap program.
+///   1. `[]` Token swap account.
+///   2. `[]` Token swap authority.
+///   3. `[writable]` Token swap token A account.
+///   4. `[writable]` Token swap token B account.
+///   5. `[writable]` Pool mint.
+///   6. `[writable]` Pool fee account.
+///   7. `[writable]` Strategy LP token account.
+pub struct AmmAccounts<'info> {
+    pub swap_program: AccountInfo<'info>,
+    pub token_swap: AccountInfo<'info>,
+    pub swap_authority: AccountInfo<'info>,
+    pub swap_token_a: AccountInfo<'info>,
+    pub swap_token_b: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub pool_fee_account: AccountInfo<'info>,
+    pub lp_vault: AccountInfo<'info>,
+}
+
+/// Token swap state needed for single-sided liquidity calculations.
+pub struct PoolState {
+    pub swap: Box<dyn SwapState>,
+    pub token_a_amount: u128,
+    pub token_b_amount: u128,
+    pub pool_supply: u128,
+    /// `AtoB` if strategy token is token A of the pool.
+    pub trade_direction: TradeDirection,
+}
+
+impl PoolState {
+    /// Amount of strategy tokens in the pool.
+    pub fn token_amount(&self) -> u128 {
+        match self.trade_direction {
+            TradeDirection::AtoB => self.token_a_amount,
+            TradeDirection::BtoA => self.token_b_amount,
+        }
+    }
+
+    /// Pool tokens burned, including owner withdraw fee, for single-sided withdrawal of `amount` strategy tokens.
+    pub fn withdraw_pool_tokens(&self, amount: u64) -> Option<u128> {
+        let amount = amount as u128;
+        let (token_a_amount, token_b_amount) = match self.trade_direction {
+            TradeDirection::AtoB => (self.token_a_amount.checked_sub(amount)?, self.token_b_amount),
+            TradeDirection::BtoA => (self.token_a_amount, self.token_b_amount.checked_sub(amount)?),
+        };
+        let burn_amount = self.swap.swap_curve().trading_tokens_to_pool_tokens(
+            amount,
+            token_a_amount,
+            token_b_amount,
+            self.pool_supply,
+            self.trade_direction,
+            RoundDirection::Ceiling,
+            self.swap.fees(),
+        )?;
+        burn_amount.checked_add(self.swap.fees().owner_withdraw_fee(burn_amount)?)
+    }
+
+    /// The biggest amount of strategy tokens which can be withdrawn single-sided by burning at most `pool_tokens`.
+    pub fn max_withdraw_amount(&self, pool_tokens: u64) -> u64 {
+        if pool_tokens == 0 {
+            return 0;
+        }
+
+        let mut low: u64 = 0;
+        let mut high: u64 = self.token_amount().min(u64::MAX as u128) as u64;
+        while low < high {
+            let middle = high - (high - low) / 2;
+            match self.withdraw_pool_tokens(middle) {
+                Some(needed) if needed <= pool_tokens as u128 => low = middle,
+                _ => high = middle - 1,
+            }
+        }
+        low
+    }
+}
+
+impl<'info> AmmAccounts<'info> {
+    /// Parses token swap accounts from remaining accounts and checks them with saved in strategy account.
+    /// The rest of accounts are checked against token swap state and by the swap program.
+    pub fn try_from_remaining(
+        strategy_account: &StrategyAmm,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<Self> {
+        require!(
+            remaining_accounts.len() >= AMM_REMAINING_ACCOUNTS_COUNT,
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        let accounts = AmmAccounts {
+            swap_program: remaining_accounts[0].clone(),
+            token_swap: remaining_accounts[1].clone(),
+            swap_authority: remaining_accounts[2].clone(),
+            swap_token_a: remaining_accounts[3].clone(),
+            swap_token_b: remaining_accounts[4].clone(),
+            pool_mint: remaining_accounts[5].clone(),
+            pool_fee_account: remaining_accounts[6].clone(),
+            lp_vault: remaining_accounts[7].clone(),
+        };
+
+        require_keys_eq!(
+            accounts.swap_program.key(),
+            strategy_account.swap_program,
+            ErrorCode::InvalidSwapProgram
+        );
+        require_keys_eq!(
+            accounts.token_swap.key(),
+            strategy_account.token_swap,
+            ErrorCode::InvalidTokenSwapAccount
+        );
+        require_keys_eq!(
+            accounts.lp_vault.key(),
+            strategy_account.lp_vault,
+            ErrorCode::InvalidLpVaultAccount
+        );
+        Ok(accounts)
+    }
+
+    /// Reads token swap state and checks that passed pool accounts belong to it.
+    pub fn pool_state(&self, strategy_token: &Pubkey) -> Result<PoolState> {
+        let swap = SwapVersion::unpack(&self.token_swap.try_borrow_data()?)?;
+        require!(
+            swap.token_a_account() == self.swap_token_a.key
+                && swap.token_b_account() == self.swap_token_b.key
+                && swap.pool_mint() == self.pool_mint.key
+                && swap.pool_fee_account() == self.pool_fee_account.key,
+            ErrorCode::InvalidTokenSwapState
+        );
+
+        let trade_direction = if swap.token_a_mint() == strategy_token {
+            TradeDirection::AtoB
+        } else if swap.token_b_mint() == strategy_token {
+            TradeDirection::BtoA
+        } else {
+            return Err(ErrorCode::InvalidTokenSwapMint.into());
+        };
+
+        Ok(PoolState {
+            swap,
+            token_a_amount: Account::<TokenAccount>::try_from(&self.swap_token_a)?.amount as u128,
+            token_b_amount: Account::<TokenAccount>::try_from(&self.swap_token_b)?.amount as u128,
+            pool_supply: Account::<Mint>::try_from(&self.pool_mint)?.supply as u128,
+            trade_direction,
+        })
+    }
+
+    /// Current amount of LP tokens held by strategy.
+    pub fn lp_amount(&self) -> Result<u64> {
+        Ok(Account::<TokenAccount>::try_from(&self.lp_vault)?.amount)
+    }
+
+    /// Deposits `amount` tokens from strategy vault to the pool single-sided.
+    /// Fails in swap program if less than `minimum_pool_token_amount` pool tokens are minted.
+    pub fn deposit(
+        &self,
+        amount: u64,
+        minimum_pool_token_amount: u64,
+        strategy_vault: &AccountInfo<'info>,
+        strategy_authority: &AccountInfo<'info>,
+        token_program: &Program<'info, Token>,
+        signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        let ix = spl_token_swap::instruction::deposit_single_token_type_exact_amount_in(
+            &self.swap_program.key(),
+            &token_program.key(),
+            &self.token_swap.key(),
+            &self.swap_authority.key(),
+            &strategy_authority.key(),
+            &strategy_vault.key(),
+            &self.swap_token_a.key(),
+            &self.swap_token_b.key(),
+            &self.pool_mint.key(),
+            &self.lp_vault.key(),
+            spl_token_swap::instruction::DepositSingleTokenTypeExactAmountIn {
+                source_token_amount: amount,
+                minimum_pool_token_amount,
+            },
+        )?;
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                self.token_swap.clone(),
+                self.swap_authority.clone(),
+                strategy_authority.clone(),
+                strategy_vault.clone(),
+                self.swap_token_a.clone(),
+                self.swap_token_b.clone(),
+                self.pool_mint.clone(),
+                self.lp_vault.clone(),
+                token_program.to_account_info(),
+                self.swap_program.clone(),
+            ],
+            signer,
+        )?;
+        Ok(())
+    }
+
+    /// Withdraws `amount` tokens single-sided to strategy vault, limited by held LP tokens.
+    ///
+    /// Return: the amount of tokens received by strategy vault.
+    pub fn withdraw(
+        &self,
+        pool_state: &PoolState,
+        amount: u64,
+        strategy_vault: &mut Box<Account<'info, TokenAccount>>,
+        strategy_authority: &AccountInfo<'info>,
+        token_program: &Program<'info, Token>,
+        signer: &[&[&[u8]]],
+    ) -> Result<u64> {
+        let lp_amount = self.lp_amount()?;
+        let amount = amount.min(pool_state.max_withdraw_amount(lp_amount));
+
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        let ix = spl_token_swap::instruction::withdraw_single_token_type_exact_amount_out(
+            &self.swap_program.key(),
+            &token_program.key(),
+            &self.token_swap.key(),
+            &self.swap_authority.key(),
+            &strategy_authority.key(),
+            &self.pool_mint.key(),
+            &self.pool_fee_account.key(),
+            &self.lp_vault.key(),
+            &self.swap_token_a.key(),
+            &self.swap_token_b.key(),
+            &strategy_vault.key(),
+            spl_token_swap::instruction::WithdrawSingleTokenTypeExactAmountOut {
+                destination_token_amount: amount,
+                maximum_pool_token_amount: lp_amount,
+            },
+        )?;
+
+        let balance_before = strategy_vault.amount;
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                self.token_swap.clone(),
+                self.swap_authority.clone(),
+                strategy_authority.clone(),
+                self.pool_mint.clone(),
+                self.lp_vault.clone(),
+                self.swap_token_a.clone(),
+                self.swap_token_b.clone(),
+                strategy_vault.to_account_info(),
+                self.pool_fee_account.clone(),
+                token_program.to_account_info(),
+                self.swap_program.clone(),
+            ],
+            signer,
+        )?;
+        strategy_vault.reload()?;
+
+        Ok(strategy_vault.amount - balance_before)
+    }
+}
+
+/// Checks that accounts passed to strategy instruction belong to strategy account.
+///
+/// Return: deserialized strategy account and strategy token.
+pub fn check_basis_accounts<'info>(
+    strategy_account: &AccountInfo<'info>,
+    base_strategy_info: &AccountInfo<'info>,
+    strategy_vault: &Box<Account<'info, TokenAccount>>,
+    bentobox_account: &AccountInfo<'info>,
+) -> Result<(Account<'info, StrategyAmm>, Pubkey)> {
+    let strategy_account = match Account::<StrategyAmm>::try_from(strategy_account) {
+        Ok(account) => account,
+        Err(_) => return Err(ErrorCode::WrongConvertionFromAccountInfoToStrategyAmm.into()),
+    };
+
+    let info = &match Account::<BaseStrategyInfo>::try_from(base_strategy_info) {
+        Ok(account) => account,
+        _ => {
+            return Err(StrategyErrorCode::WrongConvertionFromAccountInfoToBaseStrategyInfo.into())
+        }
+    };
+
+    if strategy_account.base_strategy_info != info.key() {
+        return Err(ErrorCode::InvalidBaseStrategyInfoAccount.into());
+    }
+
+    if strategy_account.bentobox_account != bentobox_account.key() {
+        return Err(ErrorCode::InvalidBentoBoxAccount.into());
+    }
+
+    if info.strategy_token != strategy_vault.mint {
+        return Err(ErrorCode::InvalidStrategyVaultAccount.into());
+    }
+    Ok((strategy_account, info.strategy_token))
+}
+
+/// Strategy authority pda seeds.
+pub fn strategy_authority_seeds<'a>(strategy_key: &'a Pubkey, bump: &'a [u8]) -> [&'a [u8]; 3] {
+    [STRATEGY_SEED_PART, strategy_key.as_ref(), bump]
+}
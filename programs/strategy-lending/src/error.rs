@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Error convertion from AccountInfo to StrategyLending.")]
+    WrongConvertionFromAccountInfoToStrategyLending,
+
+    #[msg("Invalid base strategy info account.")]
+    InvalidBaseStrategyInfoAccount,
+
+    #[msg("BentoBox Strategy: not exited.")]
+    StrategyNotExited,
+
+    #[msg("BentoBox Strategy: invalid strategy vault token account.")]
+    InvalidStrategyVaultAccount,
+
+    #[msg("Unauthorized after_exit.")]
+    UnauthorizedAfterExit,
+
+    #[msg("Unauthorized set_strategy_executor.")]
+    UnauthorizedSetStrategyExecutor,
+
+    #[msg("Invalid remaining accounts.")]
+    InvalidRemainingAccounts,
+
+    #[msg("Invalid bentobox account.")]
+    InvalidBentoBoxAccount,
+
+    #[msg("Invalid lending program.")]
+    InvalidLendingProgram,
+
+    #[msg("Invalid reserve account.")]
+    InvalidReserveAccount,
+
+    #[msg("Invalid collateral vault account.")]
+    InvalidCollateralVaultAccount,
+
+    #[msg("Reserve liquidity mint is not the strategy token.")]
+    InvalidReserveLiquidityMint,
+
+    #[msg("Reserve collateral mint does not match.")]
+    InvalidReserveCollateralMint,
+
+    #[msg("Invalid bentobox program.")]
+    InvalidBentoBoxProgram,
+}
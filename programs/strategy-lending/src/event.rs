@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+#[event]
+pub struct LogSetStrategyExecutor {
+    pub executor: Pubkey,
+    pub value: bool,
+}
+#[event]
+pub struct LogLendingHarvest {
+    /// Value of strategy cTokens in underlying tokens.
+    pub invested_amount: u64,
+    /// Profit (+) or loss (-) in contrast to the balance Bentobox thinks it has invested.
+    pub amount: i64,
+}
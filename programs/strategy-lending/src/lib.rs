@@ -0,0 +1,318 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program_pack::Pack, pubkey::Pubkey};
+use anchor_spl::token::{self, Token, TokenAccount, Transfer as TokenTransfer};
+use spl_token_lending::state::Reserve;
+
+use common::errors::ErrorCode as CommonErrorCode;
+
+pub mod context;
+pub mod error;
+pub mod event;
+pub mod state;
+
+use crate::context::*;
+use crate::error::ErrorCode;
+use crate::event::*;
+use crate::state::*;
+
+use strategy_package::base_after_exit;
+
+declare_id!("F8PYcT415Ly6trmHDaJVp63kyWkQxJFvbPHiJUtywn6m");
+
+/// Strategy which supplies idle BentoBox liquidity to SPL token-lending reserve.
+/// Profit comes from growth of reserve cToken exchange rate.
+#[program]
+pub mod strategy_lending {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let reserve = Reserve::unpack(&ctx.accounts.reserve.try_borrow_data()?)?;
+        require_keys_eq!(
+            reserve.liquidity.mint_pubkey,
+            ctx.accounts.mint.key(),
+            ErrorCode::InvalidReserveLiquidityMint
+        );
+        require_keys_eq!(
+            reserve.collateral.mint_pubkey,
+            ctx.accounts.collateral_mint.key(),
+            ErrorCode::InvalidReserveCollateralMint
+        );
+
+        let strategy_account = &mut ctx.accounts.strategy_account;
+        let authority_key = ctx.accounts.authority.key();
+        //set strategy authority
+        strategy_account.authority = authority_key;
+        strategy_account.base_strategy_info = ctx.accounts.base_strategy_info.key();
+        //set bentobox
+        strategy_account.bentobox_account = ctx.accounts.bentobox_account.key();
+        //set bentobox program id
+        strategy_account.bentobox_program = ctx.accounts.bentobox_program.key();
+        //set lending accounts
+        strategy_account.lending_program = ctx.accounts.lending_program.key();
+        strategy_account.reserve = ctx.accounts.reserve.key();
+        strategy_account.collateral_vault = ctx.accounts.collateral_vault.key();
+        strategy_account.strategy_authority_bump = *ctx
+            .bumps
+            .get("strategy_authority")
+            .ok_or(ErrorCode::WrongConvertionFromAccountInfoToStrategyLending)?;
+
+        // set strategy_token
+        ctx.accounts.base_strategy_info.strategy_token = ctx.accounts.mint.key();
+
+        //set payer account as executor
+        let payer_executor_info = &mut ctx.accounts.executor_info;
+        payer_executor_info.is_executor = true;
+        payer_executor_info.user = authority_key;
+
+        Ok(())
+    }
+
+    pub fn after_exit(ctx: Context<AfterExit>, name: String, args: Vec<Vec<u8>>) -> Result<()> {
+        let strategy_key = ctx.accounts.strategy_account.key();
+        let bump = [ctx.accounts.strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+        let signer = &[&seeds[..]];
+
+        base_after_exit(
+            ctx.remaining_accounts.to_vec(),
+            name,
+            args,
+            ctx.program_id.clone(),
+            signer,
+        )?;
+        Ok(())
+    }
+
+    pub fn set_strategy_executor(
+        ctx: Context<SetStrategyExecutor>,
+        executor: Pubkey,
+        value: bool,
+    ) -> Result<()> {
+        let executor_info = &mut ctx.accounts.executor_info;
+        executor_info.is_executor = value;
+        executor_info.user = executor;
+        emit!(LogSetStrategyExecutor { executor, value });
+
+        Ok(())
+    }
+
+    /// Supplies `amount` tokens from strategy vault to the reserve.
+    // remaining - lending accounts, see `LendingAccounts`
+    pub fn skim<'info>(ctx: Context<'_, '_, '_, 'info, Skim<'info>>, amount: u64) -> Result<()> {
+        let strategy_account = check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+        let lending = LendingAccounts::try_from_remaining(&strategy_account, ctx.remaining_accounts)?;
+
+        let strategy_key = strategy_account.key();
+        let bump = [strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+
+        lending.refresh_reserve()?;
+        lending.deposit(
+            amount,
+            &ctx.accounts.strategy_vault.to_account_info(),
+            &ctx.accounts.strategy_authority,
+            &ctx.accounts.token_program,
+            &[&seeds[..]],
+        )?;
+        Ok(())
+    }
+
+    /// Reports profit or loss of supplied liquidity and redeems profit to strategy vault.
+    // remaining - lending accounts, see `LendingAccounts`
+    pub fn harvest<'info>(
+        ctx: Context<'_, '_, '_, 'info, Harvest<'info>>,
+        balance: u64,
+    ) -> Result<ResultAmount> {
+        let strategy_account = check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+        let lending = LendingAccounts::try_from_remaining(&strategy_account, ctx.remaining_accounts)?;
+
+        let strategy_key = strategy_account.key();
+        let bump = [strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+
+        let reserve = lending.refresh_reserve()?;
+        let invested_amount = reserve
+            .collateral_exchange_rate()?
+            .collateral_to_liquidity(lending.collateral_amount()?)?;
+
+        let amount = (invested_amount as i64)
+            .checked_sub(balance as i64)
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+
+        if amount > 0 {
+            lending.redeem_liquidity(
+                &reserve,
+                amount as u64,
+                &mut ctx.accounts.strategy_vault,
+                &ctx.accounts.strategy_authority,
+                &ctx.accounts.token_program,
+                &[&seeds[..]],
+            )?;
+        }
+
+        emit!(LogLendingHarvest {
+            invested_amount,
+            amount,
+        });
+        Ok(ResultAmount { amount })
+    }
+
+    /// Redeems cTokens worth `amount` tokens to strategy vault.
+    ///
+    /// Return: the amount of tokens actually withdrawn, can be less if reserve lacks liquidity.
+    // remaining - lending accounts, see `LendingAccounts`
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        amount: u64,
+    ) -> Result<u64> {
+        let strategy_account = check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+        let lending = LendingAccounts::try_from_remaining(&strategy_account, ctx.remaining_accounts)?;
+
+        let strategy_key = strategy_account.key();
+        let bump = [strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+
+        let reserve = lending.refresh_reserve()?;
+        lending.redeem_liquidity(
+            &reserve,
+            amount,
+            &mut ctx.accounts.strategy_vault,
+            &ctx.accounts.strategy_authority,
+            &ctx.accounts.token_program,
+            &[&seeds[..]],
+        )
+    }
+
+    /// Redeems all cTokens which reserve liquidity allows and sets strategy as exited.
+    ///
+    /// Return: the amount of tokens withdrawn to strategy vault.
+    // remaining - lending accounts, see `LendingAccounts`
+    pub fn exit<'info>(ctx: Context<'_, '_, '_, 'info, Exit<'info>>) -> Result<u64> {
+        let strategy_account = check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+        let lending = LendingAccounts::try_from_remaining(&strategy_account, ctx.remaining_accounts)?;
+
+        // set exited to true
+        let mut info = Account::<BaseStrategyInfo>::try_from(&ctx.accounts.base_strategy_info)?;
+        info.exited = true;
+        info.exit(ctx.program_id)?;
+
+        let strategy_key = strategy_account.key();
+        let bump = [strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+
+        let reserve = lending.refresh_reserve()?;
+        lending.redeem_liquidity(
+            &reserve,
+            u64::MAX,
+            &mut ctx.accounts.strategy_vault,
+            &ctx.accounts.strategy_authority,
+            &ctx.accounts.token_program,
+            &[&seeds[..]],
+        )
+    }
+
+    /// SPL token-lending has no reward tokens.
+    pub fn harvest_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, HarvestRewards<'info>>,
+    ) -> Result<()> {
+        check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+        Ok(())
+    }
+
+    pub fn safe_harvest<'info>(
+        ctx: Context<'_, '_, '_, 'info, SafeHarvest<'info>>,
+        max_balance: u64,
+    ) -> Result<()> {
+        check_basis_accounts(
+            &ctx.accounts.strategy_account,
+            &ctx.accounts.base_strategy_info,
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_account,
+        )?;
+
+        if max_balance > 0 {
+            let mut info =
+                Account::<BaseStrategyInfo>::try_from(&ctx.accounts.base_strategy_info)?;
+            info.max_bentobox_balance = max_balance;
+            info.exit(ctx.program_id)?;
+        }
+        Ok(())
+    }
+
+    pub fn transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, Transfer<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let strategy_account = Account::<StrategyLending>::try_from(&ctx.accounts.strategy_account)?;
+        require_keys_eq!(
+            strategy_account.bentobox_program,
+            ctx.accounts.bentobox_program.key(),
+            ErrorCode::InvalidBentoBoxProgram
+        );
+        require_keys_eq!(
+            strategy_account.bentobox_account,
+            ctx.accounts.bentobox_account.key(),
+            ErrorCode::InvalidBentoBoxAccount
+        );
+
+        let strategy_key = strategy_account.key();
+        let bump = [strategy_account.strategy_authority_bump];
+        let seeds = strategy_authority_seeds(&strategy_key, &bump);
+
+        transfer_internal(
+            &ctx.accounts.strategy_vault,
+            &ctx.accounts.bentobox_vault,
+            &ctx.accounts.strategy_authority,
+            &ctx.accounts.token_program,
+            &[&seeds[..]],
+            amount,
+        )
+    }
+}
+
+fn transfer_internal<'info>(
+    from_vault: &Account<'info, TokenAccount>,
+    to_vault: &Account<'info, TokenAccount>,
+    from_authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    signer: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            TokenTransfer {
+                from: from_vault.to_account_info(),
+                to: to_vault.to_account_info(),
+                authority: from_authority.clone(),
+            },
+        )
+        .with_signer(signer),
+        amount,
+    )
+}
@@ -0,0 +1,311 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program_pack::Pack, pubkey::Pubkey};
+use anchor_spl::token::{Token, TokenAccount};
+use spl_token_lending::state::Reserve;
+
+use crate::error::ErrorCode;
+use common::constants::STRATEGY_SEED_PART;
+use strategy_package::errors::ErrorCode as StrategyErrorCode;
+
+#[constant]
+pub const STRATEGY_VAULT_SEED_PART: &[u8] = b"strategyvaultkey";
+#[constant]
+pub const COLLATERAL_VAULT_SEED_PART: &[u8] = b"collateralvaultkey";
+#[constant]
+pub const EXECUTOR_INFO_SEED: &[u8] = b"executorinfo";
+/// Lending accounts passed as remaining accounts to skim, harvest, withdraw and exit.
+#[constant]
+pub const LENDING_REMAINING_ACCOUNTS_COUNT: usize = 9;
+
+#[account]
+#[derive(Default)]
+pub struct StrategyLending {
+    pub authority: Pubkey,
+    pub base_strategy_info: Pubkey,
+    pub bentobox_program: Pubkey,
+    pub bentobox_account: Pubkey,
+    /// SPL token-lending program.
+    pub lending_program: Pubkey,
+    /// Reserve to which strategy supplies liquidity.
+    pub reserve: Pubkey,
+    /// Strategy collateral (cToken) account.
+    pub collateral_vault: Pubkey,
+    pub strategy_authority_bump: u8,
+}
+
+impl StrategyLending {
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 32 + 32 + 32 + 32 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct BaseStrategyInfo {
+    pub strategy_token: Pubkey,
+    pub exited: bool,
+    pub max_bentobox_balance: u64,
+}
+
+impl BaseStrategyInfo {
+    pub const SIZE: usize = 8 + 32 + 1 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ResultAmount {
+    pub amount: i64,
+}
+
+#[account]
+#[derive(Default)]
+pub struct ExecutorInfo {
+    pub is_executor: bool,
+    pub user: Pubkey,
+}
+
+impl ExecutorInfo {
+    pub const SIZE: usize = 8 + 1 + 32;
+}
+
+/// SPL token-lending accounts expected in remaining accounts:
+///
+///   0. `[]` Lending program.
+///   1. `[writable]` Reserve account.
+///   2. `[writable]` Reserve liquidity supply token account.
+///   3. `[writable]` Reserve collateral mint.
+///   4. `[]` Lending market account.
+///   5. `[]` Derived lending market authority.
+///   6. `[]` Reserve liquidity oracle account.
+///   7. `[writable]` Strategy collateral token account.
+///   8. `[]` Clock sysvar.
+pub struct LendingAccounts<'info> {
+    pub lending_program: AccountInfo<'info>,
+    pub reserve: AccountInfo<'info>,
+    pub reserve_liquidity_supply: AccountInfo<'info>,
+    pub reserve_collateral_mint: AccountInfo<'info>,
+    pub lending_market: AccountInfo<'info>,
+    pub lending_market_authority: AccountInfo<'info>,
+    pub reserve_liquidity_oracle: AccountInfo<'info>,
+    pub collateral_vault: AccountInfo<'info>,
+    pub clock: AccountInfo<'info>,
+}
+
+impl<'info> LendingAccounts<'info> {
+    /// Parses lending accounts from remaining accounts and checks them with saved in strategy account.
+    /// The rest of accounts are checked by the lending program against the reserve.
+    pub fn try_from_remaining(
+        strategy_account: &StrategyLending,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<Self> {
+        require!(
+            remaining_accounts.len() >= LENDING_REMAINING_ACCOUNTS_COUNT,
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        let accounts = LendingAccounts {
+            lending_program: remaining_accounts[0].clone(),
+            reserve: remaining_accounts[1].clone(),
+            reserve_liquidity_supply: remaining_accounts[2].clone(),
+            reserve_collateral_mint: remaining_accounts[3].clone(),
+            lending_market: remaining_accounts[4].clone(),
+            lending_market_authority: remaining_accounts[5].clone(),
+            reserve_liquidity_oracle: remaining_accounts[6].clone(),
+            collateral_vault: remaining_accounts[7].clone(),
+            clock: remaining_accounts[8].clone(),
+        };
+
+        require_keys_eq!(
+            accounts.lending_program.key(),
+            strategy_account.lending_program,
+            ErrorCode::InvalidLendingProgram
+        );
+        require_keys_eq!(
+            accounts.reserve.key(),
+            strategy_account.reserve,
+            ErrorCode::InvalidReserveAccount
+        );
+        require_keys_eq!(
+            accounts.collateral_vault.key(),
+            strategy_account.collateral_vault,
+            ErrorCode::InvalidCollateralVaultAccount
+        );
+        Ok(accounts)
+    }
+
+    /// Refreshes reserve interest and returns its up to date state.
+    pub fn refresh_reserve(&self) -> Result<Reserve> {
+        let ix = spl_token_lending::instruction::refresh_reserve(
+            self.lending_program.key(),
+            self.reserve.key(),
+            self.reserve_liquidity_oracle.key(),
+        );
+        anchor_lang::solana_program::program::invoke(
+            &ix,
+            &[
+                self.reserve.clone(),
+                self.reserve_liquidity_oracle.clone(),
+                self.clock.clone(),
+                self.lending_program.clone(),
+            ],
+        )?;
+
+        Ok(Reserve::unpack(&self.reserve.try_borrow_data()?)?)
+    }
+
+    /// Current amount of cTokens held by strategy.
+    pub fn collateral_amount(&self) -> Result<u64> {
+        Ok(Account::<TokenAccount>::try_from(&self.collateral_vault)?.amount)
+    }
+
+    /// Supplies `liquidity_amount` tokens from strategy vault to reserve for cTokens.
+    pub fn deposit(
+        &self,
+        liquidity_amount: u64,
+        strategy_vault: &AccountInfo<'info>,
+        strategy_authority: &AccountInfo<'info>,
+        token_program: &Program<'info, Token>,
+        signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        let ix = spl_token_lending::instruction::deposit_reserve_liquidity(
+            self.lending_program.key(),
+            liquidity_amount,
+            strategy_vault.key(),
+            self.collateral_vault.key(),
+            self.reserve.key(),
+            self.reserve_liquidity_supply.key(),
+            self.reserve_collateral_mint.key(),
+            self.lending_market.key(),
+            strategy_authority.key(),
+        );
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                strategy_vault.clone(),
+                self.collateral_vault.clone(),
+                self.reserve.clone(),
+                self.reserve_liquidity_supply.clone(),
+                self.reserve_collateral_mint.clone(),
+                self.lending_market.clone(),
+                self.lending_market_authority.clone(),
+                strategy_authority.clone(),
+                self.clock.clone(),
+                token_program.to_account_info(),
+                self.lending_program.clone(),
+            ],
+            signer,
+        )?;
+        Ok(())
+    }
+
+    /// Redeems `collateral_amount` cTokens for liquidity to strategy vault.
+    pub fn redeem(
+        &self,
+        collateral_amount: u64,
+        strategy_vault: &AccountInfo<'info>,
+        strategy_authority: &AccountInfo<'info>,
+        token_program: &Program<'info, Token>,
+        signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        let ix = spl_token_lending::instruction::redeem_reserve_collateral(
+            self.lending_program.key(),
+            collateral_amount,
+            self.collateral_vault.key(),
+            strategy_vault.key(),
+            self.reserve.key(),
+            self.reserve_collateral_mint.key(),
+            self.reserve_liquidity_supply.key(),
+            self.lending_market.key(),
+            strategy_authority.key(),
+        );
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                self.collateral_vault.clone(),
+                strategy_vault.clone(),
+                self.reserve.clone(),
+                self.reserve_collateral_mint.clone(),
+                self.reserve_liquidity_supply.clone(),
+                self.lending_market.clone(),
+                self.lending_market_authority.clone(),
+                strategy_authority.clone(),
+                self.clock.clone(),
+                token_program.to_account_info(),
+                self.lending_program.clone(),
+            ],
+            signer,
+        )?;
+        Ok(())
+    }
+
+    /// Redeems cTokens worth `liquidity_amount` tokens, limited by held cTokens and by reserve available liquidity.
+    ///
+    /// Return: the amount of tokens received by strategy vault.
+    pub fn redeem_liquidity(
+        &self,
+        reserve: &Reserve,
+        liquidity_amount: u64,
+        strategy_vault: &mut Box<Account<'info, TokenAccount>>,
+        strategy_authority: &AccountInfo<'info>,
+        token_program: &Program<'info, Token>,
+        signer: &[&[&[u8]]],
+    ) -> Result<u64> {
+        let exchange_rate = reserve.collateral_exchange_rate()?;
+        let collateral_amount = exchange_rate
+            .liquidity_to_collateral(liquidity_amount.min(reserve.liquidity.available_amount))?
+            .min(self.collateral_amount()?);
+
+        if collateral_amount == 0 {
+            return Ok(0);
+        }
+
+        let balance_before = strategy_vault.amount;
+        self.redeem(
+            collateral_amount,
+            &strategy_vault.to_account_info(),
+            strategy_authority,
+            token_program,
+            signer,
+        )?;
+        strategy_vault.reload()?;
+
+        Ok(strategy_vault.amount - balance_before)
+    }
+}
+
+/// Checks that accounts passed to strategy instruction belong to strategy account.
+///
+/// Return: deserialized strategy account.
+pub fn check_basis_accounts<'info>(
+    strategy_account: &AccountInfo<'info>,
+    base_strategy_info: &AccountInfo<'info>,
+    strategy_vault: &Box<Account<'info, TokenAccount>>,
+    bentobox_account: &AccountInfo<'info>,
+) -> Result<Account<'info, StrategyLending>> {
+    let strategy_account = match Account::<StrategyLending>::try_from(strategy_account) {
+        Ok(account) => account,
+        Err(_) => return Err(ErrorCode::WrongConvertionFromAccountInfoToStrategyLending.into()),
+    };
+
+    let info = &match Account::<BaseStrategyInfo>::try_from(base_strategy_info) {
+        Ok(account) => account,
+        _ => {
+            return Err(StrategyErrorCode::WrongConvertionFromAccountInfoToBaseStrategyInfo.into())
+        }
+    };
+
+    if strategy_account.base_strategy_info != info.key() {
+        return Err(ErrorCode::InvalidBaseStrategyInfoAccount.into());
+    }
+
+    if strategy_account.bentobox_account != bentobox_account.key() {
+        return Err(ErrorCode::InvalidBentoBoxAccount.into());
+    }
+
+    if info.strategy_token != strategy_vault.mint {
+        return Err(ErrorCode::InvalidStrategyVaultAccount.into());
+    }
+    Ok(strategy_account)
+}
+
+/// Strategy authority pda seeds.
+pub fn strategy_authority_seeds<'a>(strategy_key: &'a Pubkey, bump: &'a [u8]) -> [&'a [u8]; 3] {
+    [STRATEGY_SEED_PART, strategy_key.as_ref(), bump]
+}
@@ -256,7 +256,7 @@ pub struct Harvest<'info> {
     #[account(mut, 
               rent_exempt = enforce,
               constraint = cpi_result_account.owner == &strategy_program.key() @ CommonErrorCode::InvalidAccountOwnerStrategyProgram)]
-    pub cpi_result_account: UncheckedAccount<'info>,
+    pub cpi_result_account: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
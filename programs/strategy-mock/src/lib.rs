@@ -134,7 +134,7 @@ pub mod strategy_mock {
         }
 
         // remaining [0] - pool vault
-        pub fn harvest<'info>(ctx: Context<'_, '_, '_, 'info, Harvest<'info>>, balance: u64) -> Result<()> {
+        pub fn harvest<'info>(ctx: Context<'_, '_, '_, 'info, Harvest<'info>>, balance: u64) -> Result<ResultAmount> {
             require!(
                 ctx.remaining_accounts.len() >= HARVEST_REMAINING_ACCOUNTS_COUNT,
                 ErrorCode::InvalidRemainingAccounts
@@ -148,43 +148,43 @@ pub mod strategy_mock {
                 &ctx.remaining_accounts[0].clone(),
             )?;
 
-            let mut var = ctx.accounts.cpi_result_account.try_borrow_mut_data()?;
-            let result_account_size_to_serialize =
-                calculate_end_byte_to_serialize(ResultAmount::SIZE, true);
-
-            let mut result_bytes = &var[DISCRIMINATOR_BYTES..result_account_size_to_serialize];
-            let mut result_decoded = ResultAmount::deserialize(&mut result_bytes)?;
-            result_decoded.amount = 0;
-
             let pool_vault = &mut Account::<TokenAccount>::try_from(&ctx.remaining_accounts[0])
                 .ok()
                 .ok_or(CommonErrorCode::WrongConvertionFromAccountInfoToTokenAccount)?;
 
             let pool_vault_amount: i64 = pool_vault.amount as i64;
-            result_decoded.amount = match pool_vault_amount.checked_sub(balance as i64) {
-                Some(value) => value as i64,
-                None => return Err(CommonErrorCode::WrongIntegerSubtraction.into()),
+            let result = ResultAmount {
+                amount: match pool_vault_amount.checked_sub(balance as i64) {
+                    Some(value) => value as i64,
+                    None => return Err(CommonErrorCode::WrongIntegerSubtraction.into()),
+                },
             };
 
-            let result_bytes = &mut var[DISCRIMINATOR_BYTES..result_account_size_to_serialize];
-            let mut cursor = std::io::Cursor::new(result_bytes);
-            result_decoded.serialize(&mut cursor)?;
+            // keep result account up to date for BentoBox built with `cpi-result-account`
+            if let Some(cpi_result_account) = &ctx.accounts.cpi_result_account {
+                let mut var = cpi_result_account.try_borrow_mut_data()?;
+                let result_account_size_to_serialize =
+                    calculate_end_byte_to_serialize(ResultAmount::SIZE, true);
+                let result_bytes = &mut var[DISCRIMINATOR_BYTES..result_account_size_to_serialize];
+                let mut cursor = std::io::Cursor::new(result_bytes);
+                result.serialize(&mut cursor)?;
+            }
 
-            if result_decoded.amount > 0 {
+            if result.amount > 0 {
                 transfer_internal(
                     pool_vault,
                     &mut ctx.accounts.strategy_vault,
                     &ctx.accounts.strategy_authority,
                     &ctx.accounts.token_program,
                     &ctx.accounts.strategy_account,
-                    result_decoded.amount as u64,
+                    result.amount as u64,
                 )?;
             }
-            Ok(())
+            Ok(result)
         }
 
         // remaining [0] - pool vault
-        pub fn withdraw<'info>(ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>, amount: u64) -> Result<()> {
+        pub fn withdraw<'info>(ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>, amount: u64) -> Result<u64> {
             require!(
                 ctx.remaining_accounts.len() >= WITHDRAW_REMAINING_ACCOUNTS_COUNT,
                 ErrorCode::InvalidRemainingAccounts
@@ -212,11 +212,11 @@ pub mod strategy_mock {
                 amount,
             )?;
 
-            Ok(())
+            Ok(amount)
         }
 
         // remaining [0] - pool vault
-        pub fn exit<'info>(ctx: Context<'_, '_, '_, 'info, Exit<'info>>) -> Result<()> {
+        pub fn exit<'info>(ctx: Context<'_, '_, '_, 'info, Exit<'info>>) -> Result<u64> {
             require!(
                 ctx.remaining_accounts.len() >= EXIT_REMAINING_ACCOUNTS_COUNT,
                 ErrorCode::InvalidRemainingAccounts
@@ -263,7 +263,7 @@ pub mod strategy_mock {
                 amount,
             )?;
 
-            Ok(())
+            Ok(amount)
         }
 
         pub fn harvest_rewards<'info>(ctx: Context<'_, '_, '_, 'info, HarvestRewards<'info>>) -> Result<()> {
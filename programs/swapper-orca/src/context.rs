@@ -22,3 +22,27 @@ pub struct Swap<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 }
+
+/// Accounts for a get_amount_in instruction. Pool accounts are passed as remaining accounts.
+#[derive(Accounts, Clone)]
+pub struct GetAmountIn<'info> {
+    /// Signer account
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Accounts for a get_amount_out instruction. Pool accounts are passed as remaining accounts.
+#[derive(Accounts, Clone)]
+pub struct GetAmountOut<'info> {
+    /// Signer account
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Accounts for a get_spot_price instruction. Pool accounts are passed as remaining accounts.
+#[derive(Accounts, Clone)]
+pub struct GetSpotPrice<'info> {
+    /// Signer account
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
@@ -8,4 +8,16 @@ pub enum ErrorCode {
     NotEnoughRemainingAccounts,
     #[msg("Invalid pool host fee account.")]
     InvalidPoolHostFeeAccount,
+    #[msg("Pool token accounts do not belong to the token swap.")]
+    InvalidPoolTokenAccount,
+    #[msg("Not enough liquidity in the pool to quote the swap.")]
+    InsufficientPoolLiquidity,
+    #[msg("Failed to calculate swap quote.")]
+    QuoteCalculationFailed,
+    #[msg("Token swap account is not owned by swap program.")]
+    InvalidTokenSwapAccount,
+    #[msg("Source and destination mints do not match the token swap.")]
+    InvalidPoolMints,
+    #[msg("Swap program is not an allowed token swap program.")]
+    InvalidSwapProgram,
 }
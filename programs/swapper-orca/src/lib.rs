@@ -4,12 +4,15 @@ use anchor_lang::solana_program::pubkey::Pubkey;
 
 pub mod context;
 pub mod error;
+pub mod event;
 pub mod utils;
 
 use crate::context::*;
 use crate::utils::*;
 
-use swapper_package::swapper_interface::{GetAmountIn, GetAmountOut, Swap, Swapper};
+use swapper_package::swapper_interface::{
+    GetAmountIn, GetAmountOut, GetSpotPrice, Swap, Swapper,
+};
 
 
 declare_id!("3Hm9snMqyCdNHXFZ6B3jgwnY1gE86N12WrcPsfniHyjf");
@@ -30,19 +33,53 @@ pub mod swapper_orca {
         swap_orca(&ctx, amount_in, minimum_amount_out)
     }
 
+    // Remaining accounts required by quote
+    ///   1. `[]` token swap
+    ///   2. `[]` pool source account
+    ///   3. `[]` pool destination account
+    pub fn get_amount_in<'info>(
+        ctx: Context<'_, '_, '_, 'info, GetAmountIn<'info>>,
+        amount_out: u64,
+    ) -> Result<u64> {
+        get_amount_in_orca(&ctx, amount_out)
+    }
+
+    // Remaining accounts required by quote
+    ///   1. `[]` token swap
+    ///   2. `[]` pool source account
+    ///   3. `[]` pool destination account
+    pub fn get_amount_out<'info>(
+        ctx: Context<'_, '_, '_, 'info, GetAmountOut<'info>>,
+        amount_in: u64,
+    ) -> Result<u64> {
+        get_amount_out_orca(&ctx, amount_in)
+    }
+
+    // Remaining accounts required by quote
+    ///   1. `[]` token swap
+    ///   2. `[]` pool source account
+    ///   3. `[]` pool destination account
+    pub fn get_spot_price<'info>(ctx: Context<'_, '_, '_, 'info, GetSpotPrice<'info>>) -> Result<u64> {
+        get_spot_price_orca(&ctx)
+    }
+
     impl<'info> Swapper<'info> for SwapperOrca {
         fn get_amount_in<'a>(
-            _ctx: Context<'_, '_, '_, 'a, GetAmountIn<'a>>,
-            _amount_out: u64,
-        ) -> Result<()> {
-            Ok(())
+            ctx: Context<'_, '_, '_, 'a, GetAmountIn<'a>>,
+            amount_out: u64,
+        ) -> Result<u64> {
+            get_amount_in_orca(&ctx, amount_out)
         }
 
         fn get_amount_out<'a>(
-            _ctx: Context<'_, '_, '_, 'a, GetAmountOut<'a>>,
-            _amount_in: u64,
-        ) -> Result<()> {
-            Ok(())
+            ctx: Context<'_, '_, '_, 'a, GetAmountOut<'a>>,
+            amount_in: u64,
+        ) -> Result<u64> {
+            get_amount_out_orca(&ctx, amount_in)
+        }
+
+        fn get_spot_price<'a>(ctx: Context<'_, '_, '_, 'a, GetSpotPrice<'a>>) -> Result<u64> {
+            get_spot_price_orca(&ctx)
         }
 
         // Remaining accounts required by Orca Swap
@@ -52,8 +89,9 @@ pub mod swapper_orca {
         ///   4. `[writable]` pool destination account
         ///   5. `[writable]` pool token mint
         ///   6. `[writable]` pool fee account
-        ///   7. `[writable]` pool host fee account
-        //    8. `[]` source mint account
+        ///   7. `[writable]` pool host fee account, optional. Pool token account, omitted
+        ///      for pools without host fee
+        ///   8. `[]` source mint account
         ///   9. `[]` destination mint account
         fn swap<'a>(
             ctx: Context<'_, '_, '_, 'a, Swap<'a>>,
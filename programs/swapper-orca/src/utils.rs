@@ -1,14 +1,39 @@
-use anchor_lang::{prelude::*, solana_program::program::invoke};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, pubkey},
+};
 
 use anchor_spl::token::TokenAccount;
-use swapper_package::swapper_interface::Swap;
+use spl_token_swap::{
+    curve::calculator::TradeDirection,
+    state::{SwapState, SwapVersion},
+};
+use swapper_package::{
+    swapper_interface::{GetAmountIn, GetAmountOut, GetSpotPrice, Swap},
+    utils::{spot_price, SwapBalances},
+};
 
 use crate::error::ErrorCode;
+use crate::event::LogSwap;
 use common::errors::ErrorCode as CommonErrorCode;
 
 #[constant]
 pub const SWAP_REMAINING_ACCOUNTS_COUNT: usize = 9;
 
+#[constant]
+pub const SWAP_NO_HOST_FEE_REMAINING_ACCOUNTS_COUNT: usize = 8;
+
+#[constant]
+pub const QUOTE_REMAINING_ACCOUNTS_COUNT: usize = 3;
+
+/// Token swap programs whose pools are allowed: spl-token-swap, Orca v1, Orca v2 and Orca devnet.
+pub const TOKEN_SWAP_PROGRAM_IDS: [Pubkey; 4] = [
+    pubkey!("SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8"),
+    pubkey!("9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP"),
+    pubkey!("DjVE6JNiYqPL2QXyCUUh8rNjHrbz9hXHNYt99MQ59qw1"),
+    pubkey!("3xQ8SWv2GaFXXpHZNqkXsdxq5DZciHBz6ZFoPPfbFd7U"),
+];
+
 pub fn swap_orca<'info>(
     ctx: &Context<'_, '_, '_, 'info, Swap<'info>>,
     amount_in: u64,
@@ -16,74 +41,261 @@ pub fn swap_orca<'info>(
 ) -> Result<()> {
     let remaining_accounts = ctx.remaining_accounts;
     require!(
-        remaining_accounts.len() >= SWAP_REMAINING_ACCOUNTS_COUNT,
+        remaining_accounts.len() >= SWAP_NO_HOST_FEE_REMAINING_ACCOUNTS_COUNT,
         ErrorCode::NotEnoughRemainingAccounts
     );
 
+    // host fee account is optional, pools without host fee skip it
+    let has_host_fee = remaining_accounts.len() >= SWAP_REMAINING_ACCOUNTS_COUNT;
+    let mints_offset = if has_host_fee { 7 } else { 6 };
+
     let token_swap = remaining_accounts[0].clone();
     let swap_authority = remaining_accounts[1].clone();
     let pool_source_account = remaining_accounts[2].clone();
     let pool_destination_account = remaining_accounts[3].clone();
     let pool_token_mint = remaining_accounts[4].clone();
     let pool_fee_account = remaining_accounts[5].clone();
-    let pool_host_fee_account = remaining_accounts[6].clone();
-    let source_mint = remaining_accounts[7].clone();
-    let destination_mint = remaining_accounts[8].clone();
+    let pool_host_fee_account = has_host_fee.then(|| remaining_accounts[6].clone());
+    let source_mint = remaining_accounts[mints_offset].clone();
+    let destination_mint = remaining_accounts[mints_offset + 1].clone();
     let source_token_account = ctx.accounts.source_token_account.to_account_info();
     let destination_token_account = ctx.accounts.destination_token_account.to_account_info();
 
-    let cauldron_authority = ctx.accounts.authority.to_account_info();
+    let authority = ctx.accounts.authority.to_account_info();
+
+    require!(
+        TOKEN_SWAP_PROGRAM_IDS.contains(ctx.accounts.swap_program.key),
+        ErrorCode::InvalidSwapProgram
+    );
+    require_keys_eq!(
+        *token_swap.owner,
+        ctx.accounts.swap_program.key(),
+        ErrorCode::InvalidTokenSwapAccount
+    );
+    require!(
+        source_mint.key() == ctx.accounts.source_token_account.mint
+            && destination_mint.key() == ctx.accounts.destination_token_account.mint,
+        ErrorCode::InvalidPoolMints
+    );
+
+    {
+        let swap_state = SwapVersion::unpack(&token_swap.try_borrow_data()?)?;
 
-    let pool_host_fee_token_account =
-        &mut Account::<TokenAccount>::try_from(&pool_host_fee_account)
+        // pool side is taken from mints stored in token swap, not from accounts order
+        let (swap_source_account, swap_destination_account) = if source_mint.key()
+            == *swap_state.token_a_mint()
+            && destination_mint.key() == *swap_state.token_b_mint()
+        {
+            (swap_state.token_a_account(), swap_state.token_b_account())
+        } else if source_mint.key() == *swap_state.token_b_mint()
+            && destination_mint.key() == *swap_state.token_a_mint()
+        {
+            (swap_state.token_b_account(), swap_state.token_a_account())
+        } else {
+            return err!(ErrorCode::InvalidPoolMints);
+        };
+
+        require!(
+            pool_source_account.key() == *swap_source_account
+                && pool_destination_account.key() == *swap_destination_account
+                && pool_token_mint.key() == *swap_state.pool_mint()
+                && pool_fee_account.key() == *swap_state.pool_fee_account(),
+            ErrorCode::InvalidPoolTokenAccount
+        );
+    }
+
+    if let Some(pool_host_fee_account) = &pool_host_fee_account {
+        let pool_host_fee_token_account = Account::<TokenAccount>::try_from(pool_host_fee_account)
             .ok()
             .ok_or(CommonErrorCode::WrongConvertionFromAccountInfoToTokenAccount)?;
 
-    // check host fee account
-    require!(
-        pool_host_fee_token_account.owner == cauldron_authority.key(),
-        ErrorCode::InvalidPoolHostFeeAccount
-    );
+        // host fee is paid in pool tokens to any account chosen by caller
+        require_keys_eq!(
+            pool_host_fee_token_account.mint,
+            pool_token_mint.key(),
+            ErrorCode::InvalidPoolHostFeeAccount
+        );
+    }
+
+    let pool = token_swap.key();
+    let balances = SwapBalances::load(&source_token_account, &destination_token_account)?;
 
     let ix = spl_token_swap::instruction::swap(
         &ctx.accounts.swap_program.key(),
         &ctx.accounts.token_program.key(),
         &token_swap.key(),
         &swap_authority.key(),
-        &cauldron_authority.key(),
+        &authority.key(),
         &source_token_account.key(),
         &pool_source_account.key(),
         &pool_destination_account.key(),
         &destination_token_account.key(),
         &pool_token_mint.key(),
         &pool_fee_account.key(),
-        Some(&pool_host_fee_account.key()),
+        pool_host_fee_account.as_ref().map(|account| account.key),
         spl_token_swap::instruction::Swap {
             amount_in,
             minimum_amount_out,
         },
     )?;
 
-    invoke(
-        &ix,
-        &[
-            token_swap,
-            swap_authority,
-            cauldron_authority,
-            source_token_account.to_account_info(),
-            pool_source_account.to_account_info(),
-            pool_destination_account.to_account_info(),
-            destination_token_account.to_account_info(),
-            pool_token_mint.to_account_info(),
-            pool_fee_account.to_account_info(),
-            source_mint.to_account_info(),
-            destination_mint.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-            pool_host_fee_account.to_account_info(),
-        ],
+    let mut account_infos = vec![
+        token_swap,
+        swap_authority,
+        authority,
+        source_token_account,
+        pool_source_account,
+        pool_destination_account,
+        destination_token_account,
+        pool_token_mint,
+        pool_fee_account,
+        source_mint,
+        destination_mint,
+        ctx.accounts.token_program.to_account_info(),
+    ];
+    account_infos.extend(pool_host_fee_account);
+
+    invoke(&ix, &account_infos)?;
+
+    let swap_result = balances.verify(
+        &ctx.accounts.source_token_account.to_account_info(),
+        &ctx.accounts.destination_token_account.to_account_info(),
+        amount_in,
+        minimum_amount_out,
     )?;
 
+    emit!(LogSwap {
+        pool,
+        amount_in: swap_result.amount_in,
+        amount_out: swap_result.amount_out,
+        price: swap_result.price,
+    });
+
     Ok(())
 }
+
+/// Pool state needed to quote a swap in one direction.
+struct OrcaQuote {
+    swap_state: Box<dyn SwapState>,
+    swap_source_amount: u128,
+    swap_destination_amount: u128,
+    trade_direction: TradeDirection,
+}
+
+impl OrcaQuote {
+    /// Reads pool state from remaining accounts:
+    ///   1. `[]` token swap
+    ///   2. `[]` pool source account
+    ///   3. `[]` pool destination account
+    fn load(remaining_accounts: &[AccountInfo]) -> Result<Self> {
+        require!(
+            remaining_accounts.len() >= QUOTE_REMAINING_ACCOUNTS_COUNT,
+            ErrorCode::NotEnoughRemainingAccounts
+        );
+
+        let token_swap = &remaining_accounts[0];
+        let pool_source_account = &remaining_accounts[1];
+        let pool_destination_account = &remaining_accounts[2];
+
+        require!(
+            TOKEN_SWAP_PROGRAM_IDS.contains(token_swap.owner),
+            ErrorCode::InvalidTokenSwapAccount
+        );
+
+        let swap_state = SwapVersion::unpack(&token_swap.try_borrow_data()?)?;
+
+        let trade_direction = if pool_source_account.key() == *swap_state.token_a_account()
+            && pool_destination_account.key() == *swap_state.token_b_account()
+        {
+            TradeDirection::AtoB
+        } else if pool_source_account.key() == *swap_state.token_b_account()
+            && pool_destination_account.key() == *swap_state.token_a_account()
+        {
+            TradeDirection::BtoA
+        } else {
+            return err!(ErrorCode::InvalidPoolTokenAccount);
+        };
+
+        let swap_source_amount = Account::<TokenAccount>::try_from(pool_source_account)
+            .ok()
+            .ok_or(CommonErrorCode::WrongConvertionFromAccountInfoToTokenAccount)?
+            .amount;
+        let swap_destination_amount = Account::<TokenAccount>::try_from(pool_destination_account)
+            .ok()
+            .ok_or(CommonErrorCode::WrongConvertionFromAccountInfoToTokenAccount)?
+            .amount;
+
+        Ok(OrcaQuote {
+            swap_state,
+            swap_source_amount: u128::from(swap_source_amount),
+            swap_destination_amount: u128::from(swap_destination_amount),
+            trade_direction,
+        })
+    }
+
+    /// Amount of destination token received for `amount_in`, fees included.
+    fn amount_out(&self, amount_in: u64) -> Option<u64> {
+        let result = self.swap_state.swap_curve().swap(
+            u128::from(amount_in),
+            self.swap_source_amount,
+            self.swap_destination_amount,
+            self.trade_direction,
+            self.swap_state.fees(),
+        )?;
+        result.destination_amount_swapped.try_into().ok()
+    }
+}
+
+/// Returns amount of destination token received for `amount_in` of source token.
+pub fn get_amount_out_orca<'info>(
+    ctx: &Context<'_, '_, '_, 'info, GetAmountOut<'info>>,
+    amount_in: u64,
+) -> Result<u64> {
+    let quote = OrcaQuote::load(ctx.remaining_accounts)?;
+
+    quote
+        .amount_out(amount_in)
+        .ok_or_else(|| error!(ErrorCode::QuoteCalculationFailed))
+}
+
+/// Returns spot price from pool reserves. For non constant product curves it is the
+/// reserves ratio, not the marginal price of the curve.
+pub fn get_spot_price_orca<'info>(
+    ctx: &Context<'_, '_, '_, 'info, GetSpotPrice<'info>>,
+) -> Result<u64> {
+    let quote = OrcaQuote::load(ctx.remaining_accounts)?;
+
+    spot_price(quote.swap_source_amount, quote.swap_destination_amount)
+}
+
+/// Returns smallest amount of source token which yields at least `amount_out` of
+/// destination token. Searches the curve instead of inverting it, so the quote
+/// matches what `swap` delivers for every curve type.
+pub fn get_amount_in_orca<'info>(
+    ctx: &Context<'_, '_, '_, 'info, GetAmountIn<'info>>,
+    amount_out: u64,
+) -> Result<u64> {
+    let quote = OrcaQuote::load(ctx.remaining_accounts)?;
+
+    require!(
+        u128::from(amount_out) < quote.swap_destination_amount,
+        ErrorCode::InsufficientPoolLiquidity
+    );
+
+    let mut low: u64 = 0;
+    let mut high: u64 = u64::MAX;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match quote.amount_out(mid) {
+            Some(out) if out >= amount_out => high = mid,
+            _ => low = mid + 1,
+        }
+    }
+
+    require!(
+        quote.amount_out(high).unwrap_or(0) >= amount_out,
+        ErrorCode::QuoteCalculationFailed
+    );
+
+    Ok(high)
+}
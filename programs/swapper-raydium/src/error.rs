@@ -4,4 +4,16 @@ use anchor_lang::prelude::*;
 pub enum ErrorCode {
     #[msg("There are not enough remaining accounts to make a Raydium swap.")]
     NotEnoughRemainingAccounts,
+    #[msg("Invalid amm account.")]
+    InvalidAmmAccount,
+    #[msg("Invalid amm open orders account.")]
+    InvalidAmmOpenOrdersAccount,
+    #[msg("Pool token accounts do not belong to the amm.")]
+    InvalidPoolTokenAccount,
+    #[msg("Not enough liquidity in the pool to quote the swap.")]
+    InsufficientPoolLiquidity,
+    #[msg("Failed to calculate swap quote.")]
+    QuoteCalculationFailed,
+    #[msg("Swap program is not an allowed Raydium amm program.")]
+    InvalidSwapProgram,
 }
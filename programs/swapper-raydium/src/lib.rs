@@ -2,13 +2,16 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::pubkey::Pubkey;
 
 pub mod error;
+pub mod event;
 pub mod state;
 pub mod utils;
 pub mod context;
 
 use crate::utils::*;
 use crate::context::*;
-use swapper_package::swapper_interface::{GetAmountIn, GetAmountOut, Swap, Swapper};
+use swapper_package::swapper_interface::{
+    GetAmountIn, GetAmountOut, GetSpotPrice, Swap, Swapper,
+};
 
 
 declare_id!("Bbh4JSnawctDsQZDgme2d9S8cH16nQoJSukPmAv8qLPM");
@@ -25,22 +28,61 @@ pub mod swapper_raydium {
     ) -> Result<()> {
         swap_raydium(&ctx, amount_in, minimum_amount_out)
     }
+
+
+    // Remaining accounts required by quote
+    ///   1. `[]` amm Account
+    ///   2. `[]` amm open_orders Account
+    ///   3. `[]` pool token Account to swap FROM
+    ///   4. `[]` pool token Account to swap TO
+    pub fn get_amount_in<'a>(
+        ctx: Context<'_, '_, '_, 'a, GetAmountIn<'a>>,
+        amount_out: u64,
+    ) -> Result<u64> {
+        get_amount_in_raydium(&ctx, amount_out)
+    }
+
+    // Remaining accounts required by quote
+    ///   1. `[]` amm Account
+    ///   2. `[]` amm open_orders Account
+    ///   3. `[]` pool token Account to swap FROM
+    ///   4. `[]` pool token Account to swap TO
+    pub fn get_amount_out<'a>(
+        ctx: Context<'_, '_, '_, 'a, GetAmountOut<'a>>,
+        amount_in: u64,
+    ) -> Result<u64> {
+        get_amount_out_raydium(&ctx, amount_in)
+    }
+
+    // Remaining accounts required by quote
+    ///   1. `[]` amm Account
+    ///   2. `[]` amm open_orders Account
+    ///   3. `[]` pool token Account to swap FROM
+    ///   4. `[]` pool token Account to swap TO
+    pub fn get_spot_price<'a>(ctx: Context<'_, '_, '_, 'a, GetSpotPrice<'a>>) -> Result<u64> {
+        get_spot_price_raydium(&ctx)
+    }
+
     // #[state]
     pub struct SwappperRaydium;
 
     impl<'info> Swapper<'info> for SwappperRaydium {
         fn get_amount_in<'a>(
-            _ctx: Context<'_, '_, '_, 'a, GetAmountIn<'a>>,
-            _amount_out: u64,
-        ) -> Result<()> {
-            Ok(())
+            ctx: Context<'_, '_, '_, 'a, GetAmountIn<'a>>,
+            amount_out: u64,
+        ) -> Result<u64> {
+            get_amount_in_raydium(&ctx, amount_out)
         }
 
         fn get_amount_out<'a>(
-            _ctx: Context<'_, '_, '_, 'a, GetAmountOut<'a>>,
-            _amount_in: u64,
-        ) -> Result<()> {
-            Ok(())
+            ctx: Context<'_, '_, '_, 'a, GetAmountOut<'a>>,
+            amount_in: u64,
+        ) -> Result<u64> {
+            get_amount_out_raydium(&ctx, amount_in)
+        }
+
+        fn get_spot_price<'a>(ctx: Context<'_, '_, '_, 'a, GetSpotPrice<'a>>) -> Result<u64> {
+            get_spot_price_raydium(&ctx)
         }
 
         // Remaining accounts required by Raydium Swap
@@ -58,6 +100,12 @@ pub mod swapper_raydium {
         ///   12. `[writable]` coin_vault Account
         ///   13. `[writable]` pc_vault Account
         ///   14. '[]` vault_signer Account
+        //
+        // Pools without orderbook need only 4 remaining accounts
+        ///   1. `[writable]` amm Account
+        ///   2. `[]` amm authority
+        ///   3. `[writable]` pool_token_coin Amm Account to swap FROM or To,
+        ///   4. `[writable]` pool_token_pc Amm Account to swap FROM or To,
         fn swap<'a>(
             ctx: Context<'_, '_, '_, 'a, Swap<'a>>,
             amount_in: u64,
@@ -1,4 +1,4 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::pubkey};
 use std::mem::size_of;
 
 /// Instructions supported by the AmmInfo program.
@@ -38,6 +38,22 @@ pub enum AmmInstruction {
     Reserved8,
     Reserved9,
     Reserved10,
+    Reserved11,
+    Reserved12,
+    Reserved13,
+    Reserved14,
+    Reserved15,
+    /// Swap coin or pc from pool, without orderbook accounts
+    ///
+    ///   0. `[]` Spl Token program id
+    ///   1. `[writable]` amm Account
+    ///   2. `[]` $authority
+    ///   3. `[writable]` pool_token_coin Amm Account to swap FROM or To,
+    ///   4. `[writable]` pool_token_pc Amm Account to swap FROM or To,
+    ///   5. `[writable]` user source token Account. user Account to swap from.
+    ///   6. `[writable]` user destination token Account. user Account to swap to.
+    ///   7. `[singer]` user owner Account
+    SwapBaseInV2(SwapInstructionBaseIn),
 }
 
 impl AmmInstruction {
@@ -53,6 +69,14 @@ impl AmmInstruction {
                 buf.extend_from_slice(&amount_in.to_le_bytes());
                 buf.extend_from_slice(&minimum_amount_out.to_le_bytes());
             }
+            Self::SwapBaseInV2(SwapInstructionBaseIn {
+                amount_in,
+                minimum_amount_out,
+            }) => {
+                buf.push(16);
+                buf.extend_from_slice(&amount_in.to_le_bytes());
+                buf.extend_from_slice(&minimum_amount_out.to_le_bytes());
+            }
             _ => {}
         }
         Ok(buf)
@@ -67,3 +91,42 @@ pub struct SwapInstructionBaseIn {
     /// Minimum amount of DESTINATION token to output, prevents excessive slippage
     pub minimum_amount_out: u64,
 }
+
+/// Raydium AMM v4 programs whose pools are allowed: mainnet and devnet.
+pub const AMM_PROGRAM_IDS: [Pubkey; 2] = [
+    pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8"),
+    pubkey!("9rpQHSyFVM1dkkHFQ2TtTzPEW7DVmEyPmN8wVniqJtuC"),
+];
+
+/// Byte offsets of the Raydium AMM v4 `AmmInfo` fields used for quoting.
+pub mod amm_info {
+    pub const SWAP_FEE_NUMERATOR_OFFSET: usize = 176;
+    pub const SWAP_FEE_DENOMINATOR_OFFSET: usize = 184;
+    pub const NEED_TAKE_PNL_COIN_OFFSET: usize = 192;
+    pub const NEED_TAKE_PNL_PC_OFFSET: usize = 200;
+    pub const COIN_VAULT_OFFSET: usize = 336;
+    pub const PC_VAULT_OFFSET: usize = 368;
+    pub const OPEN_ORDERS_OFFSET: usize = 496;
+    pub const LEN: usize = 752;
+}
+
+/// Byte offsets of the Serum `OpenOrders` fields used for quoting.
+pub mod open_orders {
+    pub const NATIVE_COIN_TOTAL_OFFSET: usize = 85;
+    pub const NATIVE_PC_TOTAL_OFFSET: usize = 101;
+    pub const LEN: usize = 3228;
+}
+
+/// Reads little endian u64 at `offset`.
+pub fn read_u64(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// Reads pubkey at `offset`.
+pub fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&data[offset..offset + 32]);
+    Pubkey::new_from_array(bytes)
+}
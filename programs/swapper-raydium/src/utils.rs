@@ -3,17 +3,87 @@ use anchor_lang::{
     solana_program::{instruction::Instruction, program::invoke},
 };
 
-use swapper_package::swapper_interface::Swap;
+use anchor_spl::token::TokenAccount;
+use swapper_package::{
+    swapper_interface::{GetAmountIn, GetAmountOut, GetSpotPrice, Swap},
+    utils::{spot_price, SwapBalances},
+};
 
 use crate::error::ErrorCode as RaydiumErrorCode;
+use crate::event::LogSwap;
 use crate::state::*;
+use common::errors::ErrorCode as CommonErrorCode;
 
-const SWAP_REMAINING_ACCOUNTS_COUNT: usize = 14;
+/// Number of remaining accounts of `swap base in` with orderbook accounts, more accounts are ignored.
+#[constant]
+pub const SWAP_REMAINING_ACCOUNTS_COUNT: usize = 14;
+/// Exact number of remaining accounts of `swap base in v2` without orderbook accounts.
+#[constant]
+pub const SWAP_V2_REMAINING_ACCOUNTS_COUNT: usize = 4;
+#[constant]
+pub const QUOTE_REMAINING_ACCOUNTS_COUNT: usize = 4;
 
 pub fn swap_raydium<'a, 'b, 'c, 'info>(
     ctx: &Context<'a, 'b, 'c, 'info, Swap<'info>>,
     amount_in: u64,
     minimum_amount_out: u64,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty(),
+        RaydiumErrorCode::NotEnoughRemainingAccounts
+    );
+
+    // swap program receives swapper signer, so only Raydium amm programs and their pools are allowed
+    require!(
+        AMM_PROGRAM_IDS.contains(ctx.accounts.swap_program.key),
+        RaydiumErrorCode::InvalidSwapProgram
+    );
+    require_keys_eq!(
+        *remaining_accounts[0].owner,
+        ctx.accounts.swap_program.key(),
+        RaydiumErrorCode::InvalidAmmAccount
+    );
+
+    let pool = remaining_accounts[0].key();
+    let balances = SwapBalances::load(
+        &ctx.accounts.source_token_account.to_account_info(),
+        &ctx.accounts.destination_token_account.to_account_info(),
+    )?;
+
+    // instruction is selected by the number of remaining accounts: exactly
+    // `SWAP_V2_REMAINING_ACCOUNTS_COUNT` for pools without orderbook, at least
+    // `SWAP_REMAINING_ACCOUNTS_COUNT` with orderbook, any other count is rejected
+    match remaining_accounts.len() {
+        SWAP_V2_REMAINING_ACCOUNTS_COUNT => swap_raydium_v2(ctx, amount_in, minimum_amount_out)?,
+        count if count >= SWAP_REMAINING_ACCOUNTS_COUNT => {
+            swap_raydium_orderbook(ctx, amount_in, minimum_amount_out)?
+        }
+        _ => return err!(RaydiumErrorCode::NotEnoughRemainingAccounts),
+    }
+
+    let swap_result = balances.verify(
+        &ctx.accounts.source_token_account.to_account_info(),
+        &ctx.accounts.destination_token_account.to_account_info(),
+        amount_in,
+        minimum_amount_out,
+    )?;
+
+    emit!(LogSwap {
+        pool,
+        amount_in: swap_result.amount_in,
+        amount_out: swap_result.amount_out,
+        price: swap_result.price,
+    });
+
+    Ok(())
+}
+
+/// Swaps through amm with Serum/OpenBook accounts.
+fn swap_raydium_orderbook<'a, 'b, 'c, 'info>(
+    ctx: &Context<'a, 'b, 'c, 'info, Swap<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
 ) -> Result<()> {
     let remaining_accounts = ctx.remaining_accounts;
     require!(
@@ -87,7 +157,52 @@ pub fn swap_raydium<'a, 'b, 'c, 'info>(
     )?;
     Ok(())
 }
-    
+
+/// Swaps through amm without Serum/OpenBook accounts.
+fn swap_raydium_v2<'a, 'b, 'c, 'info>(
+    ctx: &Context<'a, 'b, 'c, 'info, Swap<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+
+    let amm = remaining_accounts[0].clone();
+    let amm_authority = remaining_accounts[1].clone();
+    let pool_coin_token_account = remaining_accounts[2].clone();
+    let pool_pc_token_account = remaining_accounts[3].clone();
+    let source_token_account = ctx.accounts.source_token_account.to_account_info();
+    let destination_token_account = ctx.accounts.destination_token_account.to_account_info();
+
+    let ix = make_swap_base_in_v2_instruction(
+        ctx.accounts.swap_program.key,
+        amm.key,
+        amm_authority.key,
+        pool_coin_token_account.key,
+        pool_pc_token_account.key,
+        source_token_account.key,
+        destination_token_account.key,
+        ctx.accounts.authority.key,
+        ctx.accounts.token_program.key,
+        amount_in,
+        minimum_amount_out,
+    )?;
+
+    invoke(
+        &ix,
+        &vec![
+            ctx.accounts.token_program.to_account_info(),
+            amm,
+            amm_authority,
+            pool_coin_token_account,
+            pool_pc_token_account,
+            source_token_account,
+            destination_token_account,
+            ctx.accounts.authority.to_account_info(),
+        ],
+    )?;
+    Ok(())
+}
+
 /// Creates a 'swap base in' instruction.
 fn make_swap_base_in_instruction(
     program_id: &Pubkey,
@@ -105,8 +220,8 @@ fn make_swap_base_in_instruction(
     serum_coin_vault_account: &Pubkey,
     serum_pc_vault_account: &Pubkey,
     serum_vault_signer: &Pubkey,
-    uer_source_token_account: &Pubkey,
-    uer_destination_token_account: &Pubkey,
+    user_source_token_account: &Pubkey,
+    user_destination_token_account: &Pubkey,
     user_source_owner: &Pubkey,
     spl_token_program: &Pubkey,
     amount_in: u64,
@@ -138,8 +253,8 @@ fn make_swap_base_in_instruction(
         AccountMeta::new(*serum_pc_vault_account, false),
         AccountMeta::new_readonly(*serum_vault_signer, false),
         // user
-        AccountMeta::new(*uer_source_token_account, false),
-        AccountMeta::new(*uer_destination_token_account, false),
+        AccountMeta::new(*user_source_token_account, false),
+        AccountMeta::new(*user_destination_token_account, false),
         AccountMeta::new(*user_source_owner, true),
     ];
 
@@ -149,3 +264,232 @@ fn make_swap_base_in_instruction(
         data,
     })
 }
+
+/// Creates a 'swap base in v2' instruction, which doesn't need orderbook accounts.
+fn make_swap_base_in_v2_instruction(
+    program_id: &Pubkey,
+    amm_id: &Pubkey,
+    amm_authority: &Pubkey,
+    pool_coin_token_account: &Pubkey,
+    pool_pc_token_account: &Pubkey,
+    user_source_token_account: &Pubkey,
+    user_destination_token_account: &Pubkey,
+    user_source_owner: &Pubkey,
+    spl_token_program: &Pubkey,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<Instruction> {
+    let data = AmmInstruction::SwapBaseInV2(SwapInstructionBaseIn {
+        amount_in,
+        minimum_amount_out,
+    })
+    .pack()?;
+
+    let accounts = vec![
+        // spl token
+        AccountMeta::new_readonly(*spl_token_program, false),
+        // amm
+        AccountMeta::new(*amm_id, false),
+        AccountMeta::new_readonly(*amm_authority, false),
+        AccountMeta::new(*pool_coin_token_account, false),
+        AccountMeta::new(*pool_pc_token_account, false),
+        // user
+        AccountMeta::new(*user_source_token_account, false),
+        AccountMeta::new(*user_destination_token_account, false),
+        AccountMeta::new(*user_source_owner, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Pool state needed to quote a swap in one direction.
+struct RaydiumQuote {
+    swap_fee_numerator: u128,
+    swap_fee_denominator: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+}
+
+impl RaydiumQuote {
+    /// Reads pool state from remaining accounts:
+    ///   1. `[]` amm Account
+    ///   2. `[]` amm open_orders Account
+    ///   3. `[]` pool token Account to swap FROM
+    ///   4. `[]` pool token Account to swap TO
+    fn load(remaining_accounts: &[AccountInfo]) -> Result<Self> {
+        require!(
+            remaining_accounts.len() >= QUOTE_REMAINING_ACCOUNTS_COUNT,
+            RaydiumErrorCode::NotEnoughRemainingAccounts
+        );
+
+        let amm = &remaining_accounts[0];
+        let amm_open_orders = &remaining_accounts[1];
+        let pool_source_account = &remaining_accounts[2];
+        let pool_destination_account = &remaining_accounts[3];
+
+        let amm_data = amm.try_borrow_data()?;
+        require!(
+            AMM_PROGRAM_IDS.contains(amm.owner) && amm_data.len() == amm_info::LEN,
+            RaydiumErrorCode::InvalidAmmAccount
+        );
+        require_keys_eq!(
+            amm_open_orders.key(),
+            read_pubkey(&amm_data, amm_info::OPEN_ORDERS_OFFSET),
+            RaydiumErrorCode::InvalidAmmOpenOrdersAccount
+        );
+
+        let open_orders_data = amm_open_orders.try_borrow_data()?;
+        require!(
+            open_orders_data.len() >= open_orders::LEN,
+            RaydiumErrorCode::InvalidAmmOpenOrdersAccount
+        );
+
+        let coin_vault = read_pubkey(&amm_data, amm_info::COIN_VAULT_OFFSET);
+        let pc_vault = read_pubkey(&amm_data, amm_info::PC_VAULT_OFFSET);
+
+        let coin_to_pc = if pool_source_account.key() == coin_vault
+            && pool_destination_account.key() == pc_vault
+        {
+            true
+        } else if pool_source_account.key() == pc_vault
+            && pool_destination_account.key() == coin_vault
+        {
+            false
+        } else {
+            return err!(RaydiumErrorCode::InvalidPoolTokenAccount);
+        };
+
+        let source_amount = Account::<TokenAccount>::try_from(pool_source_account)
+            .ok()
+            .ok_or(CommonErrorCode::WrongConvertionFromAccountInfoToTokenAccount)?
+            .amount;
+        let destination_amount = Account::<TokenAccount>::try_from(pool_destination_account)
+            .ok()
+            .ok_or(CommonErrorCode::WrongConvertionFromAccountInfoToTokenAccount)?
+            .amount;
+        let (coin_vault_amount, pc_vault_amount) = if coin_to_pc {
+            (source_amount, destination_amount)
+        } else {
+            (destination_amount, source_amount)
+        };
+
+        // same reserves as amm uses: vault + open orders total - pnl waiting to be taken
+        let coin_reserve = u128::from(coin_vault_amount)
+            .checked_add(u128::from(read_u64(
+                &open_orders_data,
+                open_orders::NATIVE_COIN_TOTAL_OFFSET,
+            )))
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?
+            .checked_sub(u128::from(read_u64(
+                &amm_data,
+                amm_info::NEED_TAKE_PNL_COIN_OFFSET,
+            )))
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+        let pc_reserve = u128::from(pc_vault_amount)
+            .checked_add(u128::from(read_u64(
+                &open_orders_data,
+                open_orders::NATIVE_PC_TOTAL_OFFSET,
+            )))
+            .ok_or(CommonErrorCode::WrongIntegerAddition)?
+            .checked_sub(u128::from(read_u64(
+                &amm_data,
+                amm_info::NEED_TAKE_PNL_PC_OFFSET,
+            )))
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+
+        let swap_fee_denominator =
+            u128::from(read_u64(&amm_data, amm_info::SWAP_FEE_DENOMINATOR_OFFSET));
+        require!(
+            swap_fee_denominator > 0,
+            RaydiumErrorCode::InvalidAmmAccount
+        );
+
+        let (reserve_in, reserve_out) = if coin_to_pc {
+            (coin_reserve, pc_reserve)
+        } else {
+            (pc_reserve, coin_reserve)
+        };
+
+        Ok(RaydiumQuote {
+            swap_fee_numerator: u128::from(read_u64(
+                &amm_data,
+                amm_info::SWAP_FEE_NUMERATOR_OFFSET,
+            )),
+            swap_fee_denominator,
+            reserve_in,
+            reserve_out,
+        })
+    }
+
+    /// Amount of destination token received for `amount_in`, mirrors amm `swap_base_in`.
+    fn amount_out(&self, amount_in: u64) -> Option<u64> {
+        let amount_in = u128::from(amount_in);
+        let swap_fee = amount_in
+            .checked_mul(self.swap_fee_numerator)?
+            .checked_add(self.swap_fee_denominator - 1)?
+            .checked_div(self.swap_fee_denominator)?;
+        let amount_in_after_fee = amount_in.checked_sub(swap_fee)?;
+
+        self.reserve_out
+            .checked_mul(amount_in_after_fee)?
+            .checked_div(self.reserve_in.checked_add(amount_in_after_fee)?)?
+            .try_into()
+            .ok()
+    }
+}
+
+/// Returns amount of destination token received for `amount_in` of source token.
+pub fn get_amount_out_raydium<'info>(
+    ctx: &Context<'_, '_, '_, 'info, GetAmountOut<'info>>,
+    amount_in: u64,
+) -> Result<u64> {
+    let quote = RaydiumQuote::load(ctx.remaining_accounts)?;
+
+    quote
+        .amount_out(amount_in)
+        .ok_or_else(|| error!(RaydiumErrorCode::QuoteCalculationFailed))
+}
+
+/// Returns spot price from the same reserves as the amm uses for swaps.
+pub fn get_spot_price_raydium<'info>(
+    ctx: &Context<'_, '_, '_, 'info, GetSpotPrice<'info>>,
+) -> Result<u64> {
+    let quote = RaydiumQuote::load(ctx.remaining_accounts)?;
+
+    spot_price(quote.reserve_in, quote.reserve_out)
+}
+
+/// Returns smallest amount of source token which yields at least `amount_out` of
+/// destination token, so the quote matches what `swap` delivers after rounding.
+pub fn get_amount_in_raydium<'info>(
+    ctx: &Context<'_, '_, '_, 'info, GetAmountIn<'info>>,
+    amount_out: u64,
+) -> Result<u64> {
+    let quote = RaydiumQuote::load(ctx.remaining_accounts)?;
+
+    require!(
+        u128::from(amount_out) < quote.reserve_out,
+        RaydiumErrorCode::InsufficientPoolLiquidity
+    );
+
+    let mut low: u64 = 0;
+    let mut high: u64 = u64::MAX;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match quote.amount_out(mid) {
+            Some(out) if out >= amount_out => high = mid,
+            _ => low = mid + 1,
+        }
+    }
+
+    require!(
+        quote.amount_out(high).unwrap_or(0) >= amount_out,
+        RaydiumErrorCode::QuoteCalculationFailed
+    );
+
+    Ok(high)
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use common::errors::ErrorCode as CommonErrorCode;
+
+use crate::{error::ErrorCode, state::*};
+
+#[derive(Accounts, Clone)]
+pub struct Swap<'info> {
+    /// Base Account to swap INTO.Must be the SOURCE token. Cauldron_authority must be owner.
+    #[account(mut,
+              constraint = source_token_account.owner == authority.key() @ CommonErrorCode::InvalidTokenAccountOwner)]
+    pub source_token_account: Box<Account<'info, TokenAccount>>,
+    /// Base Account to swap FROM. Must be the DESTINATION token. Cauldron_authority must be owner.
+    #[account(mut,
+              constraint = destination_token_account.owner == authority.key() @ CommonErrorCode::InvalidTokenAccountOwner)]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+    /// Not used by router, every hop passes its own swap program.
+    /// CHECK:
+    pub swap_program: UncheckedAccount<'info>,
+    /// Token program account.
+    pub token_program: Program<'info, Token>,
+    /// Signer account
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Accounts for a get_amount_in instruction. Pool accounts are passed as remaining accounts.
+#[derive(Accounts, Clone)]
+pub struct GetAmountIn<'info> {
+    /// Signer account
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Accounts for a get_amount_out instruction. Pool accounts are passed as remaining accounts.
+#[derive(Accounts, Clone)]
+pub struct GetAmountOut<'info> {
+    /// Signer account
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Accounts for a get_spot_price instruction. Pool accounts are passed as remaining accounts.
+#[derive(Accounts, Clone)]
+pub struct GetSpotPrice<'info> {
+    /// Signer account
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// Router PDA account, one per router program.
+    #[account(init,
+              seeds = [ROUTER_SEED_PART.as_ref()],
+              bump,
+              payer = authority,
+              space = Router::SIZE)]
+    pub router_account: Box<Account<'info, Router>>,
+    /// Router program account.
+    #[account(constraint = router_program.programdata_address()? == Some(program_data.key()) @ ErrorCode::UnauthorizedRouterInitialize)]
+    pub router_program: Program<'info, crate::program::SwapperRouter>,
+    /// Program data account of router program.
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::UnauthorizedRouterInitialize)]
+    pub program_data: Account<'info, ProgramData>,
+    /// Router owner account, upgrade authority of router program.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WhitelistHopSwapper<'info> {
+    /// Router account.
+    #[account(mut, has_one = authority)]
+    pub router_account: Box<Account<'info, Router>>,
+    /// CHECK: If this account is swapper program executable
+    #[account(executable)]
+    pub swapper_program: UncheckedAccount<'info>,
+    /// Router owner account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(hops: Vec<RouteHop>)]
+pub struct CreateRoute<'info> {
+    /// Route account.
+    #[account(init,
+              payer = authority,
+              space = Route::SIZE,
+              constraint = (MIN_ROUTE_HOPS..=MAX_ROUTE_HOPS).contains(&hops.len()) @ ErrorCode::InvalidRouteHopsCount)]
+    pub route: Box<Account<'info, Route>>,
+    /// Router account.
+    #[account(has_one = authority)]
+    pub router_account: Box<Account<'info, Router>>,
+    /// Router owner account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// System program account.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRoute<'info> {
+    /// Route account.
+    #[account(mut,
+              close = authority,
+              constraint = route.router == router_account.key() @ ErrorCode::InvalidRouteAccount)]
+    pub route: Box<Account<'info, Route>>,
+    /// Router account.
+    #[account(has_one = authority)]
+    pub router_account: Box<Account<'info, Router>>,
+    /// Router owner account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Route should have from 2 to 3 hops.")]
+    InvalidRouteHopsCount,
+    #[msg("Route account is not owned by router program.")]
+    InvalidRouteAccount,
+    #[msg("There are not enough remaining accounts to make a routed swap.")]
+    NotEnoughRemainingAccounts,
+    #[msg("Swapper program does not match route hop.")]
+    InvalidHopSwapper,
+    #[msg("Intermediate token account should be owned by swap authority.")]
+    InvalidIntermediateTokenAccount,
+    #[msg("Quote for routed swap is not supported, quote every hop separately.")]
+    QuoteNotSupported,
+    #[msg("Router can be initialized only by upgrade authority of router program.")]
+    UnauthorizedRouterInitialize,
+    #[msg("Router account is not router PDA.")]
+    InvalidRouterAccount,
+    #[msg("Hop swapper program is not whitelisted in router.")]
+    HopSwapperNotWhitelisted,
+    #[msg("Router already has maximum number of whitelisted hop swappers.")]
+    HopSwappersLimitReached,
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct LogSwap {
+    pub pool: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub price: u64,
+}
+
+#[event]
+pub struct LogWhitelistHopSwapper {
+    pub swapper_program: Pubkey,
+    pub whitelisted: bool,
+}
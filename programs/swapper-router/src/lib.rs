@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey::Pubkey;
+
+pub mod context;
+pub mod error;
+pub mod event;
+pub mod state;
+pub mod utils;
+
+use crate::context::*;
+use crate::error::ErrorCode;
+use crate::event::LogWhitelistHopSwapper;
+use crate::state::*;
+use crate::utils::*;
+use swapper_package::swapper_interface::{
+    GetAmountIn, GetAmountOut, GetSpotPrice, Swap, Swapper,
+};
+
+declare_id!("3DDgZBCVmVn7MFPsJ5Jno1T5UUxjMyBuadqu7SWXAvn7");
+
+#[program]
+pub mod swapper_router {
+    use super::*;
+
+    /// Initialize router PDA account. ONLY for upgrade authority of router program.
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let router_account = &mut ctx.accounts.router_account;
+        router_account.authority = ctx.accounts.authority.key();
+        router_account.bump = *ctx
+            .bumps
+            .get("router_account")
+            .ok_or(ErrorCode::InvalidRouterAccount)?;
+        Ok(())
+    }
+
+    /// Adds swapper program to or removes it from swappers allowed in route hops.
+    /// ONLY for router account authority.
+    ///
+    /// Arguments:
+    ///
+    /// * `whitelisted` - True if swapper program should be whitelisted, False othervise.
+    pub fn whitelist_hop_swapper(
+        ctx: Context<WhitelistHopSwapper>,
+        whitelisted: bool,
+    ) -> Result<()> {
+        let router_account = &mut ctx.accounts.router_account;
+        let swapper_program = ctx.accounts.swapper_program.key();
+
+        if whitelisted {
+            if !router_account.is_hop_swapper_whitelisted(&swapper_program) {
+                require!(
+                    router_account.hop_swappers.len() < MAX_HOP_SWAPPERS,
+                    ErrorCode::HopSwappersLimitReached
+                );
+                router_account.hop_swappers.push(swapper_program);
+            }
+        } else {
+            router_account
+                .hop_swappers
+                .retain(|hop_swapper| *hop_swapper != swapper_program);
+        }
+
+        emit!(LogWhitelistHopSwapper {
+            swapper_program,
+            whitelisted,
+        });
+        Ok(())
+    }
+
+    /// Creates route which can be used by `swap`. ONLY for router account authority.
+    /// Hop swappers get swap authority signer, so every hop swapper should be whitelisted in router.
+    ///
+    /// Arguments:
+    ///
+    /// * `hops` - From 2 to 3 hops in swap order.
+    pub fn create_route(ctx: Context<CreateRoute>, hops: Vec<RouteHop>) -> Result<()> {
+        for hop in hops.iter() {
+            require!(
+                ctx.accounts
+                    .router_account
+                    .is_hop_swapper_whitelisted(&hop.swapper_program),
+                ErrorCode::HopSwapperNotWhitelisted
+            );
+        }
+
+        let route = &mut ctx.accounts.route;
+        route.router = ctx.accounts.router_account.key();
+        route.hops = hops;
+        Ok(())
+    }
+
+    /// Closes route. ONLY for router account authority.
+    pub fn close_route(_ctx: Context<CloseRoute>) -> Result<()> {
+        Ok(())
+    }
+
+    // Remaining accounts required by routed swap
+    ///   1. `[]` route Account
+    ///   2. `[]` router PDA Account of the route
+    // For every hop of the route
+    ///   1. `[]` swapper program of the hop
+    ///   2. `[]` swap program of the hop
+    ///   3. `[writable]` hop destination token Account, owned by authority. For the last hop
+    ///      it is destination_token_account
+    ///   4. pool accounts required by hop swapper, `pool_accounts_count` of the hop
+    pub fn swap<'a>(
+        ctx: Context<'_, '_, '_, 'a, Swap<'a>>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        swap_router(&ctx, amount_in, minimum_amount_out)
+    }
+
+    pub fn get_amount_in<'a>(
+        _ctx: Context<'_, '_, '_, 'a, GetAmountIn<'a>>,
+        _amount_out: u64,
+    ) -> Result<u64> {
+        err!(ErrorCode::QuoteNotSupported)
+    }
+
+    pub fn get_amount_out<'a>(
+        _ctx: Context<'_, '_, '_, 'a, GetAmountOut<'a>>,
+        _amount_in: u64,
+    ) -> Result<u64> {
+        err!(ErrorCode::QuoteNotSupported)
+    }
+
+    pub fn get_spot_price<'a>(_ctx: Context<'_, '_, '_, 'a, GetSpotPrice<'a>>) -> Result<u64> {
+        err!(ErrorCode::QuoteNotSupported)
+    }
+
+    // #[state]
+    pub struct SwapperRouter;
+
+    impl<'info> Swapper<'info> for SwapperRouter {
+        // Hop swappers expect different quote accounts, so every hop
+        // should be quoted separately.
+        fn get_amount_in<'a>(
+            _ctx: Context<'_, '_, '_, 'a, GetAmountIn<'a>>,
+            _amount_out: u64,
+        ) -> Result<u64> {
+            err!(ErrorCode::QuoteNotSupported)
+        }
+
+        fn get_amount_out<'a>(
+            _ctx: Context<'_, '_, '_, 'a, GetAmountOut<'a>>,
+            _amount_in: u64,
+        ) -> Result<u64> {
+            err!(ErrorCode::QuoteNotSupported)
+        }
+
+        fn get_spot_price<'a>(_ctx: Context<'_, '_, '_, 'a, GetSpotPrice<'a>>) -> Result<u64> {
+            err!(ErrorCode::QuoteNotSupported)
+        }
+
+        fn swap<'a>(
+            ctx: Context<'_, '_, '_, 'a, Swap<'a>>,
+            amount_in: u64,
+            minimum_amount_out: u64,
+        ) -> Result<()> {
+            swap_router(&ctx, amount_in, minimum_amount_out)
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+#[constant]
+pub const ROUTER_SEED_PART: &[u8] = b"swapperrouter";
+#[constant]
+pub const MIN_ROUTE_HOPS: usize = 2;
+#[constant]
+pub const MAX_ROUTE_HOPS: usize = 3;
+/// Accounts which each hop passes before its pool accounts:
+/// swapper program, swap program and hop destination token account.
+#[constant]
+pub const HOP_ACCOUNTS_COUNT: usize = 3;
+/// Accounts which swap passes before hop accounts: route and router.
+#[constant]
+pub const ROUTE_ACCOUNTS_COUNT: usize = 2;
+#[constant]
+pub const MAX_HOP_SWAPPERS: usize = 8;
+
+#[account]
+#[derive(Default)]
+pub struct Router {
+    /// Owner of router, only owner can create routes.
+    pub authority: Pubkey,
+    /// Bump of router PDA.
+    pub bump: u8,
+    /// Swapper programs allowed in route hops.
+    pub hop_swappers: Vec<Pubkey>,
+}
+
+impl Router {
+    pub const SIZE: usize = 8 + 32 + 1 + 4 + 32 * MAX_HOP_SWAPPERS;
+
+    /// Checks that swapper program is allowed in route hops.
+    pub fn is_hop_swapper_whitelisted(&self, swapper_program: &Pubkey) -> bool {
+        self.hop_swappers.contains(swapper_program)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy)]
+pub struct RouteHop {
+    /// Swapper program which implements `swapper_interface`.
+    pub swapper_program: Pubkey,
+    /// Number of pool accounts which swapper expects as remaining accounts.
+    pub pool_accounts_count: u8,
+}
+
+impl RouteHop {
+    pub const SIZE: usize = 32 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct Route {
+    /// Router which route belongs to.
+    pub router: Pubkey,
+    /// Hops in swap order, destination of each hop is source of the next one.
+    pub hops: Vec<RouteHop>,
+}
+
+impl Route {
+    pub const SIZE: usize = 8 + 32 + 4 + RouteHop::SIZE * MAX_ROUTE_HOPS;
+}
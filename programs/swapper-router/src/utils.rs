@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token::TokenAccount;
+use swapper_package::{
+    cpi::accounts::Swap as SwapCpi, swapper_interface::Swap, utils::SwapBalances,
+};
+
+use crate::error::ErrorCode;
+use crate::event::LogSwap;
+use crate::state::*;
+use common::errors::ErrorCode as CommonErrorCode;
+
+/// Swaps `amount_in` through every hop of the route. Output of each hop is input of the
+/// next one, `minimum_amount_out` is checked only for the output of the last hop.
+pub fn swap_router<'info>(
+    ctx: &Context<'_, '_, '_, 'info, Swap<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        remaining_accounts.len() >= ROUTE_ACCOUNTS_COUNT,
+        ErrorCode::NotEnoughRemainingAccounts
+    );
+
+    require_keys_eq!(
+        *remaining_accounts[0].owner,
+        crate::id(),
+        ErrorCode::InvalidRouteAccount
+    );
+    require_keys_eq!(
+        *remaining_accounts[1].owner,
+        crate::id(),
+        ErrorCode::InvalidRouterAccount
+    );
+    let route = Account::<Route>::try_from(&remaining_accounts[0])?;
+    let router = Account::<Router>::try_from(&remaining_accounts[1])?;
+
+    // only routes of the router PDA created at init are allowed
+    let router_pda =
+        Pubkey::create_program_address(&[ROUTER_SEED_PART, &[router.bump]], &crate::id())
+            .map_err(|_| error!(ErrorCode::InvalidRouterAccount))?;
+    require_keys_eq!(router.key(), router_pda, ErrorCode::InvalidRouterAccount);
+    require_keys_eq!(route.router, router.key(), ErrorCode::InvalidRouteAccount);
+    let hops_count = route.hops.len();
+    require!(
+        (MIN_ROUTE_HOPS..=MAX_ROUTE_HOPS).contains(&hops_count),
+        ErrorCode::InvalidRouteHopsCount
+    );
+
+    let balances = SwapBalances::load(
+        &ctx.accounts.source_token_account.to_account_info(),
+        &ctx.accounts.destination_token_account.to_account_info(),
+    )?;
+
+    let authority = ctx.accounts.authority.to_account_info();
+    let mut hop_source = ctx.accounts.source_token_account.to_account_info();
+    let mut hop_amount_in = amount_in;
+    let mut offset = ROUTE_ACCOUNTS_COUNT;
+
+    for (index, hop) in route.hops.iter().enumerate() {
+        let is_last_hop = index == hops_count - 1;
+        let hop_accounts_count = HOP_ACCOUNTS_COUNT + hop.pool_accounts_count as usize;
+        require!(
+            remaining_accounts.len() >= offset + hop_accounts_count,
+            ErrorCode::NotEnoughRemainingAccounts
+        );
+
+        let swapper_program = remaining_accounts[offset].clone();
+        let swap_program = remaining_accounts[offset + 1].clone();
+        let hop_destination = remaining_accounts[offset + 2].clone();
+        let pool_accounts = remaining_accounts[offset + HOP_ACCOUNTS_COUNT..offset + hop_accounts_count].to_vec();
+
+        require_keys_eq!(
+            swapper_program.key(),
+            hop.swapper_program,
+            ErrorCode::InvalidHopSwapper
+        );
+        // swapper could be removed from whitelist after route was created
+        require!(
+            router.is_hop_swapper_whitelisted(&hop.swapper_program),
+            ErrorCode::HopSwapperNotWhitelisted
+        );
+        if is_last_hop {
+            require_keys_eq!(
+                hop_destination.key(),
+                ctx.accounts.destination_token_account.key(),
+                ErrorCode::InvalidIntermediateTokenAccount
+            );
+        }
+
+        let hop_destination_amount = {
+            let hop_destination_account = Account::<TokenAccount>::try_from(&hop_destination)
+                .ok()
+                .ok_or(CommonErrorCode::WrongConvertionFromAccountInfoToTokenAccount)?;
+            require_keys_eq!(
+                hop_destination_account.owner,
+                authority.key(),
+                ErrorCode::InvalidIntermediateTokenAccount
+            );
+            hop_destination_account.amount
+        };
+
+        // intermediate hops are not limited, slippage is checked on final output
+        let hop_minimum_amount_out = if is_last_hop { minimum_amount_out } else { 0 };
+
+        swapper_package::cpi::swap(
+            CpiContext::new(
+                swapper_program,
+                SwapCpi {
+                    source_token_account: hop_source,
+                    destination_token_account: hop_destination.clone(),
+                    swap_program,
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    authority: authority.clone(),
+                },
+            )
+            .with_remaining_accounts(pool_accounts),
+            hop_amount_in,
+            hop_minimum_amount_out,
+        )?;
+
+        hop_amount_in = Account::<TokenAccount>::try_from(&hop_destination)
+            .ok()
+            .ok_or(CommonErrorCode::WrongConvertionFromAccountInfoToTokenAccount)?
+            .amount
+            .checked_sub(hop_destination_amount)
+            .ok_or(CommonErrorCode::WrongIntegerSubtraction)?;
+        hop_source = hop_destination;
+        offset += hop_accounts_count;
+    }
+
+    let swap_result = balances.verify(
+        &ctx.accounts.source_token_account.to_account_info(),
+        &ctx.accounts.destination_token_account.to_account_info(),
+        amount_in,
+        minimum_amount_out,
+    )?;
+
+    emit!(LogSwap {
+        pool: route.key(),
+        amount_in: swap_result.amount_in,
+        amount_out: swap_result.amount_out,
+        price: swap_result.price,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("There are not enough remaining accounts to make a Whirlpool swap.")]
+    NotEnoughRemainingAccounts,
+    #[msg("Whirlpool account is not owned by Whirlpool program.")]
+    InvalidWhirlpoolAccount,
+    #[msg("Source and destination tokens do not match whirlpool mints.")]
+    InvalidWhirlpoolMints,
+    #[msg("Pool token vaults do not belong to the whirlpool.")]
+    InvalidPoolTokenVault,
+    #[msg("Concentrated liquidity quote is not supported on-chain, use Whirlpool SDK.")]
+    QuoteNotSupported,
+    #[msg("Swap program is not Whirlpool program.")]
+    InvalidSwapProgram,
+}
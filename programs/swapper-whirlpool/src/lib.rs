@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey::Pubkey;
+
+pub mod context;
+pub mod error;
+pub mod event;
+pub mod state;
+pub mod utils;
+
+use crate::context::*;
+use crate::error::ErrorCode;
+use crate::utils::*;
+use swapper_package::swapper_interface::{
+    GetAmountIn, GetAmountOut, GetSpotPrice, Swap, Swapper,
+};
+
+declare_id!("2wRGVWzyVP66d6a1Y2uhxdvSUZs74rpU2BiQfMwYDhRA");
+
+#[program]
+pub mod swapper_whirlpool {
+    use super::*;
+
+    // Remaining accounts required by Whirlpool Swap
+    ///   1. `[writable]` whirlpool Account
+    ///   2. `[writable]` token_vault_a Account of whirlpool
+    ///   3. `[writable]` token_vault_b Account of whirlpool
+    ///   4. `[writable]` tick_array_0 Account, contains current tick
+    ///   5. `[writable]` tick_array_1 Account, next in swap direction
+    ///   6. `[writable]` tick_array_2 Account, next after tick_array_1
+    ///   7. `[]` oracle Account of whirlpool
+    pub fn swap<'a>(
+        ctx: Context<'_, '_, '_, 'a, Swap<'a>>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        swap_whirlpool(&ctx, amount_in, minimum_amount_out)
+    }
+
+    pub fn get_amount_in<'a>(
+        _ctx: Context<'_, '_, '_, 'a, GetAmountIn<'a>>,
+        _amount_out: u64,
+    ) -> Result<u64> {
+        err!(ErrorCode::QuoteNotSupported)
+    }
+
+    pub fn get_amount_out<'a>(
+        _ctx: Context<'_, '_, '_, 'a, GetAmountOut<'a>>,
+        _amount_in: u64,
+    ) -> Result<u64> {
+        err!(ErrorCode::QuoteNotSupported)
+    }
+
+    pub fn get_spot_price<'a>(_ctx: Context<'_, '_, '_, 'a, GetSpotPrice<'a>>) -> Result<u64> {
+        err!(ErrorCode::QuoteNotSupported)
+    }
+
+    // #[state]
+    pub struct SwapperWhirlpool;
+
+    impl<'info> Swapper<'info> for SwapperWhirlpool {
+        // Quote depends on initialized ticks crossed by the swap, tick arrays
+        // are not traversed on-chain.
+        fn get_amount_in<'a>(
+            _ctx: Context<'_, '_, '_, 'a, GetAmountIn<'a>>,
+            _amount_out: u64,
+        ) -> Result<u64> {
+            err!(ErrorCode::QuoteNotSupported)
+        }
+
+        fn get_amount_out<'a>(
+            _ctx: Context<'_, '_, '_, 'a, GetAmountOut<'a>>,
+            _amount_in: u64,
+        ) -> Result<u64> {
+            err!(ErrorCode::QuoteNotSupported)
+        }
+
+        fn get_spot_price<'a>(_ctx: Context<'_, '_, '_, 'a, GetSpotPrice<'a>>) -> Result<u64> {
+            err!(ErrorCode::QuoteNotSupported)
+        }
+
+        // Remaining accounts required by Whirlpool Swap
+        ///   1. `[writable]` whirlpool Account
+        ///   2. `[writable]` token_vault_a Account of whirlpool
+        ///   3. `[writable]` token_vault_b Account of whirlpool
+        ///   4. `[writable]` tick_array_0 Account, contains current tick
+        ///   5. `[writable]` tick_array_1 Account, next in swap direction
+        ///   6. `[writable]` tick_array_2 Account, next after tick_array_1
+        ///   7. `[]` oracle Account of whirlpool
+        fn swap<'a>(
+            ctx: Context<'_, '_, '_, 'a, Swap<'a>>,
+            amount_in: u64,
+            minimum_amount_out: u64,
+        ) -> Result<()> {
+            swap_whirlpool(&ctx, amount_in, minimum_amount_out)
+        }
+    }
+}
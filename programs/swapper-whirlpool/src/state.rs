@@ -0,0 +1,41 @@
+use anchor_lang::{prelude::*, solana_program::pubkey};
+
+/// Lowest sqrt price supported by Whirlpool, used as price limit for A to B swaps.
+#[constant]
+pub const MIN_SQRT_PRICE_X64: u128 = 4295048016;
+/// Highest sqrt price supported by Whirlpool, used as price limit for B to A swaps.
+#[constant]
+pub const MAX_SQRT_PRICE_X64: u128 = 79226673515401279992447579055;
+
+/// Orca Whirlpool program, the only program swaps are forwarded to.
+pub const WHIRLPOOL_PROGRAM_ID: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+
+/// Byte offsets of the `Whirlpool` account fields used by swapper.
+pub mod whirlpool {
+    pub const TOKEN_MINT_A_OFFSET: usize = 101;
+    pub const TOKEN_VAULT_A_OFFSET: usize = 133;
+    pub const TOKEN_MINT_B_OFFSET: usize = 181;
+    pub const TOKEN_VAULT_B_OFFSET: usize = 213;
+    pub const LEN: usize = 653;
+}
+
+/// Reads pubkey at `offset`.
+pub fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&data[offset..offset + 32]);
+    Pubkey::new_from_array(bytes)
+}
+
+/// Arguments of Whirlpool `swap` instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct WhirlpoolSwapArgs {
+    /// Amount of input token if `amount_specified_is_input` is true, amount of output token othervise.
+    pub amount: u64,
+    /// Minimum amount of output token (or maximum amount of input token), prevents excessive slippage.
+    pub other_amount_threshold: u64,
+    /// Price at which swap stops, even if `amount` is not fully swapped.
+    pub sqrt_price_limit: u128,
+    pub amount_specified_is_input: bool,
+    /// True if swap goes from token A to token B.
+    pub a_to_b: bool,
+}
@@ -0,0 +1,186 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke},
+};
+
+use common::utils::sighash;
+use swapper_package::{swapper_interface::Swap, utils::SwapBalances};
+
+use crate::error::ErrorCode as WhirlpoolErrorCode;
+use crate::event::LogSwap;
+use crate::state::*;
+
+const SWAP_REMAINING_ACCOUNTS_COUNT: usize = 7;
+
+pub fn swap_whirlpool<'a, 'b, 'c, 'info>(
+    ctx: &Context<'a, 'b, 'c, 'info, Swap<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        remaining_accounts.len() >= SWAP_REMAINING_ACCOUNTS_COUNT,
+        WhirlpoolErrorCode::NotEnoughRemainingAccounts
+    );
+
+    let whirlpool = remaining_accounts[0].clone();
+    let token_vault_a = remaining_accounts[1].clone();
+    let token_vault_b = remaining_accounts[2].clone();
+    let tick_array_0 = remaining_accounts[3].clone();
+    let tick_array_1 = remaining_accounts[4].clone();
+    let tick_array_2 = remaining_accounts[5].clone();
+    let oracle = remaining_accounts[6].clone();
+    let source_token_account = ctx.accounts.source_token_account.to_account_info();
+    let destination_token_account = ctx.accounts.destination_token_account.to_account_info();
+
+    require_keys_eq!(
+        ctx.accounts.swap_program.key(),
+        WHIRLPOOL_PROGRAM_ID,
+        WhirlpoolErrorCode::InvalidSwapProgram
+    );
+    require_keys_eq!(
+        *whirlpool.owner,
+        ctx.accounts.swap_program.key(),
+        WhirlpoolErrorCode::InvalidWhirlpoolAccount
+    );
+
+    let a_to_b = {
+        let whirlpool_data = whirlpool.try_borrow_data()?;
+        require!(
+            whirlpool_data.len() == whirlpool::LEN,
+            WhirlpoolErrorCode::InvalidWhirlpoolAccount
+        );
+        require!(
+            token_vault_a.key() == read_pubkey(&whirlpool_data, whirlpool::TOKEN_VAULT_A_OFFSET)
+                && token_vault_b.key()
+                    == read_pubkey(&whirlpool_data, whirlpool::TOKEN_VAULT_B_OFFSET),
+            WhirlpoolErrorCode::InvalidPoolTokenVault
+        );
+
+        let token_mint_a = read_pubkey(&whirlpool_data, whirlpool::TOKEN_MINT_A_OFFSET);
+        let token_mint_b = read_pubkey(&whirlpool_data, whirlpool::TOKEN_MINT_B_OFFSET);
+        let source_mint = ctx.accounts.source_token_account.mint;
+        let destination_mint = ctx.accounts.destination_token_account.mint;
+
+        if source_mint == token_mint_a && destination_mint == token_mint_b {
+            true
+        } else if source_mint == token_mint_b && destination_mint == token_mint_a {
+            false
+        } else {
+            return err!(WhirlpoolErrorCode::InvalidWhirlpoolMints);
+        }
+    };
+
+    let balances = SwapBalances::load(&source_token_account, &destination_token_account)?;
+
+    // whirlpool expects owner accounts in A, B order
+    let (token_owner_account_a, token_owner_account_b) = if a_to_b {
+        (source_token_account, destination_token_account)
+    } else {
+        (destination_token_account, source_token_account)
+    };
+
+    // slippage is checked by `minimum_amount_out`, so price limit is the end of the curve
+    let sqrt_price_limit = if a_to_b {
+        MIN_SQRT_PRICE_X64
+    } else {
+        MAX_SQRT_PRICE_X64
+    };
+
+    let pool = whirlpool.key();
+    let ix = make_swap_instruction(
+        ctx.accounts.swap_program.key,
+        ctx.accounts.token_program.key,
+        ctx.accounts.authority.key,
+        whirlpool.key,
+        token_owner_account_a.key,
+        token_vault_a.key,
+        token_owner_account_b.key,
+        token_vault_b.key,
+        tick_array_0.key,
+        tick_array_1.key,
+        tick_array_2.key,
+        oracle.key,
+        WhirlpoolSwapArgs {
+            amount: amount_in,
+            other_amount_threshold: minimum_amount_out,
+            sqrt_price_limit,
+            amount_specified_is_input: true,
+            a_to_b,
+        },
+    )?;
+
+    invoke(
+        &ix,
+        &[
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            whirlpool,
+            token_owner_account_a,
+            token_vault_a,
+            token_owner_account_b,
+            token_vault_b,
+            tick_array_0,
+            tick_array_1,
+            tick_array_2,
+            oracle,
+        ],
+    )?;
+
+    let swap_result = balances.verify(
+        &ctx.accounts.source_token_account.to_account_info(),
+        &ctx.accounts.destination_token_account.to_account_info(),
+        amount_in,
+        minimum_amount_out,
+    )?;
+
+    emit!(LogSwap {
+        pool,
+        amount_in: swap_result.amount_in,
+        amount_out: swap_result.amount_out,
+        price: swap_result.price,
+    });
+
+    Ok(())
+}
+
+/// Creates a Whirlpool 'swap' instruction.
+#[allow(clippy::too_many_arguments)]
+fn make_swap_instruction(
+    program_id: &Pubkey,
+    token_program: &Pubkey,
+    token_authority: &Pubkey,
+    whirlpool: &Pubkey,
+    token_owner_account_a: &Pubkey,
+    token_vault_a: &Pubkey,
+    token_owner_account_b: &Pubkey,
+    token_vault_b: &Pubkey,
+    tick_array_0: &Pubkey,
+    tick_array_1: &Pubkey,
+    tick_array_2: &Pubkey,
+    oracle: &Pubkey,
+    args: WhirlpoolSwapArgs,
+) -> Result<Instruction> {
+    let mut data = sighash("global", "swap").to_vec();
+    data.extend_from_slice(&args.try_to_vec()?);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(*token_authority, true),
+        AccountMeta::new(*whirlpool, false),
+        AccountMeta::new(*token_owner_account_a, false),
+        AccountMeta::new(*token_vault_a, false),
+        AccountMeta::new(*token_owner_account_b, false),
+        AccountMeta::new(*token_vault_b, false),
+        AccountMeta::new(*tick_array_0, false),
+        AccountMeta::new(*tick_array_1, false),
+        AccountMeta::new(*tick_array_2, false),
+        AccountMeta::new_readonly(*oracle, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
@@ -0,0 +1,219 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { StrategyAmm as StrategyAmmProgram } from "../../target/types/strategy_amm";
+import { Keypair, SystemProgram, PublicKey, AccountMeta } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+import { getStrategyExecutorInfoAddress, getStrategyAddress, getStrategyVaultAddress } from "./mock_strategy_pda_helper";
+import { getLpVaultAddress, getRewardVaultAddress } from "./amm_strategy_pda_helper";
+
+export interface TokenSwapPool {
+    swapProgram: PublicKey
+    tokenSwap: PublicKey
+    swapAuthority: PublicKey
+    swapTokenA: PublicKey
+    swapTokenB: PublicKey
+    poolMint: PublicKey
+    poolFeeAccount: PublicKey
+}
+
+export class StrategyAmm {
+    strategyAmmProgram: anchor.Program<StrategyAmmProgram>
+
+    strategyAmmAccount: Keypair
+    strategyOwner: Keypair
+    strategyAmmAuthorityPda: PublicKey
+    lpVaultPda: PublicKey
+    rewardVaultPda: PublicKey
+    pool: TokenSwapPool
+    swapperProgram: PublicKey
+    rewardSwapProgram: PublicKey
+
+    totalVaultPdas: Map<String, PublicKey>; // mint -> pda
+    strategyAmmExecutorInfoPda: Map<String, PublicKey>; // user -> pda
+
+    baseStrategyInfo: Keypair
+
+    constructor() {
+        this.strategyAmmProgram = anchor.workspace.StrategyAmm as Program<StrategyAmmProgram>;
+        this.strategyAmmAccount = Keypair.generate();
+        this.baseStrategyInfo = Keypair.generate();
+        this.totalVaultPdas = new Map<String, PublicKey>();
+        this.strategyAmmExecutorInfoPda = new Map<String, PublicKey>();
+    }
+
+    getStrategyAmmProgram() {
+        return this.strategyAmmProgram.programId
+    }
+
+    getStrategyAmmAccount() {
+        return this.strategyAmmAccount.publicKey
+    }
+
+    getStrategyAmmAuthority() {
+        let result = this.strategyAmmAuthorityPda
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`StrategyAmmAuthority is not created`);
+        }
+    }
+
+    getTotalVaultPda(mint: PublicKey) {
+        let result = this.totalVaultPdas[mint.toBase58()]
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`Total vault for token ${mint} is not created`);
+        }
+    }
+
+    getLpVaultPda() {
+        let result = this.lpVaultPda
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`LP vault is not created`);
+        }
+    }
+
+    getRewardVaultPda() {
+        let result = this.rewardVaultPda
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`Reward vault is not created`);
+        }
+    }
+
+    getBaseStrategyInfoAccount() {
+        return this.baseStrategyInfo.publicKey
+    }
+
+    getStrategyAmmExecutorInfoPda(user: PublicKey) {
+        let result = this.strategyAmmExecutorInfoPda[user.toBase58()]
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`Executor info account for user ${user} is not created`);
+        }
+    }
+
+    /// Token swap accounts in order expected by strategy `skim`, `harvest`, `withdraw` and `exit`.
+    getAmmRemainingAccounts(): AccountMeta[] {
+        return [
+            { pubkey: this.pool.swapProgram, isWritable: false, isSigner: false },
+            { pubkey: this.pool.tokenSwap, isWritable: false, isSigner: false },
+            { pubkey: this.pool.swapAuthority, isWritable: false, isSigner: false },
+            { pubkey: this.pool.swapTokenA, isWritable: true, isSigner: false },
+            { pubkey: this.pool.swapTokenB, isWritable: true, isSigner: false },
+            { pubkey: this.pool.poolMint, isWritable: true, isSigner: false },
+            { pubkey: this.pool.poolFeeAccount, isWritable: true, isSigner: false },
+            { pubkey: this.getLpVaultPda(), isWritable: true, isSigner: false },
+        ];
+    }
+
+    /// Remaining accounts for Bentobox `safe_harvest` with `harvest_rewards`: token swap accounts,
+    /// reward accounts and accounts required by swapper to sell rewards.
+    getHarvestRewardsRemainingAccounts(swapperRemainingAccounts: AccountMeta[]): AccountMeta[] {
+        return [
+            ...this.getAmmRemainingAccounts(),
+            { pubkey: this.getRewardVaultPda(), isWritable: true, isSigner: false },
+            { pubkey: this.swapperProgram, isWritable: false, isSigner: false },
+            { pubkey: this.rewardSwapProgram, isWritable: false, isSigner: false },
+            { pubkey: TOKEN_PROGRAM_ID, isWritable: false, isSigner: false },
+            ...swapperRemainingAccounts,
+        ];
+    }
+
+    async initialize(mint: PublicKey, rewardMint: PublicKey, pool: TokenSwapPool, swapperProgram: PublicKey, rewardSwapProgram: PublicKey,
+        bentoboxAccount: PublicKey, bentoboxProgram: PublicKey, strategyOwner: Keypair) {
+        this.strategyOwner = strategyOwner;
+        this.pool = pool;
+        this.swapperProgram = swapperProgram;
+        this.rewardSwapProgram = rewardSwapProgram;
+
+        const [_strategy_vault, _strategy_vault_nonce] = await getStrategyVaultAddress(
+            mint,
+            this.strategyAmmAccount.publicKey,
+            this.strategyAmmProgram.programId
+        );
+        this.totalVaultPdas[mint.toBase58()] = _strategy_vault;
+
+        const [_lp_vault, _lp_vault_nonce] = await getLpVaultAddress(
+            pool.poolMint,
+            this.strategyAmmAccount.publicKey,
+            this.strategyAmmProgram.programId
+        );
+        this.lpVaultPda = _lp_vault;
+
+        const [_reward_vault, _reward_vault_nonce] = await getRewardVaultAddress(
+            rewardMint,
+            this.strategyAmmAccount.publicKey,
+            this.strategyAmmProgram.programId
+        );
+        this.rewardVaultPda = _reward_vault;
+
+        const [_strategy_authority_pda, _strategy_authority_bump] =
+            await getStrategyAddress(this.strategyAmmAccount.publicKey, this.strategyAmmProgram.programId);
+        this.strategyAmmAuthorityPda = _strategy_authority_pda;
+
+        const [_owner_executor_info, _owner_executor_info_nonce] = await getStrategyExecutorInfoAddress(
+            this.strategyAmmAccount.publicKey,
+            this.strategyOwner.publicKey,
+            this.strategyAmmProgram.programId
+        );
+        this.strategyAmmExecutorInfoPda[this.strategyOwner.publicKey.toBase58()] = _owner_executor_info
+
+        await this.strategyAmmProgram.methods.initialize()
+            .accounts({
+                strategyVault: _strategy_vault,
+                lpVault: _lp_vault,
+                rewardVault: _reward_vault,
+                strategyAccount: this.strategyAmmAccount.publicKey,
+                baseStrategyInfo: this.baseStrategyInfo.publicKey,
+                mint,
+                poolMint: pool.poolMint,
+                rewardMint,
+                swapProgram: pool.swapProgram,
+                tokenSwap: pool.tokenSwap,
+                swapperProgram,
+                rewardSwapProgram,
+                bentoboxAccount,
+                bentoboxProgram,
+                authority: strategyOwner.publicKey,
+                systemProgram: SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                executorInfo: _owner_executor_info,
+                strategyAuthority: _strategy_authority_pda
+            })
+            .signers([strategyOwner, this.strategyAmmAccount, this.baseStrategyInfo])
+            .rpc();
+    }
+
+    setRewardMinAmountOut(minAmountOut: anchor.BN, executor: Keypair) {
+        return this.strategyAmmProgram.methods.setRewardMinAmountOut(minAmountOut)
+            .accounts({
+                strategyAccount: this.strategyAmmAccount.publicKey,
+                executorInfo: this.getStrategyAmmExecutorInfoPda(executor.publicKey),
+                authority: executor.publicKey,
+            })
+            .signers([executor])
+    }
+
+    setSkimMinPoolTokens(minPoolTokens: anchor.BN, executor: Keypair) {
+        return this.strategyAmmProgram.methods.setSkimMinPoolTokens(minPoolTokens)
+            .accounts({
+                strategyAccount: this.strategyAmmAccount.publicKey,
+                executorInfo: this.getStrategyAmmExecutorInfoPda(executor.publicKey),
+                authority: executor.publicKey,
+            })
+            .signers([executor])
+    }
+}
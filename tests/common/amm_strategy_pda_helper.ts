@@ -0,0 +1,20 @@
+import { PublicKey } from '@solana/web3.js';
+import * as anchor from '@coral-xyz/anchor';
+
+export async function getLpVaultAddress(poolMint: PublicKey, strategy: PublicKey, program: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode('lpvaultkey')),
+        strategy.toBytes(),
+        poolMint.toBytes()],
+        program
+    );
+}
+
+export async function getRewardVaultAddress(rewardMint: PublicKey, strategy: PublicKey, program: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode('rewardvaultkey')),
+        strategy.toBytes(),
+        rewardMint.toBytes()],
+        program
+    );
+}
@@ -1,10 +1,10 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import { Bentobox as BentoboxProgram } from "../../target/types/bentobox";
-import { Keypair, SystemProgram, PublicKey } from "@solana/web3.js";
+import { Keypair, SystemProgram, PublicKey, AccountMeta, TransactionInstruction, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { MINIMUM_SHARE_BALANCE, MAX_TARGET_PERCENTAGE } from "./src/constants";
-import { getBentoboxBalanceAddress, getBentoboxTotalAddress, getMasterContractWhitelistAddress, getMasterContractApprovedAddress, getBentoboxTotalVaultAddress, getBentoboxStrategyDataAddress, getBentoBoxAddress } from "./bentobox_pda_helper";
+import { getBentoboxBalanceAddress, getBentoboxTotalAddress, getMasterContractWhitelistAddress, getStrategyProgramWhitelistAddress, getMasterContractApprovedAddress, getBentoboxTotalVaultAddress, getBentoboxStrategyDataAddress, getBentoBoxAddress, getBentoboxStrategyKeeperAddress, getPermitNonceAddress } from "./bentobox_pda_helper";
 export class Bentobox {
     bentoboxProgram: anchor.Program<BentoboxProgram>
 
@@ -16,8 +16,10 @@ export class Bentobox {
     totalVaultPdas: Map<String, PublicKey>; // mint -> pda
     balancePdas: Map<String, PublicKey>; // user + mint -> pda
     masterContractWhitelistedPdas: Map<String, PublicKey>; // master contract -> whitelisted pda
+    strategyProgramWhitelistedPdas: Map<String, PublicKey>; // strategy program -> whitelisted pda
     masterContractApprovedPdas: Map<String, PublicKey>; // (user + master contract) -> master contract approved pda pda
     strategyDataPdas: Map<String, PublicKey>; // mint -> pda 
+    strategyKeeperPdas: Map<String, PublicKey>; // keeper + mint -> pda
     approvedBentoboxPda: PublicKey
     bentoboxAuthorityBump: number
 
@@ -28,8 +30,10 @@ export class Bentobox {
         this.totalDataPdas = new Map<String, PublicKey>();
         this.totalVaultPdas = new Map<String, PublicKey>();
         this.masterContractWhitelistedPdas = new Map<String, PublicKey>();
+        this.strategyProgramWhitelistedPdas = new Map<String, PublicKey>();
         this.masterContractApprovedPdas = new Map<String, PublicKey>();
         this.strategyDataPdas = new Map<String, PublicKey>();
+        this.strategyKeeperPdas = new Map<String, PublicKey>();
     }
 
     getBentoboxProgram() {
@@ -96,6 +100,16 @@ export class Bentobox {
         }
     }
 
+    getStrategyKeeperPda(keeper: PublicKey, mint: PublicKey) {
+        let result = this.strategyKeeperPdas[keeper.toBase58() + mint.toBase58()]
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`Strategy keeper account for keeper ${keeper} is not created`);
+        }
+    }
+
     getMasterContractWhitelistedPda(masterContract: PublicKey) {
         let result = this.masterContractWhitelistedPdas[masterContract.toBase58()]
         if (result) {
@@ -106,6 +120,16 @@ export class Bentobox {
         }
     }
 
+    getStrategyProgramWhitelistedPda(strategyProgram: PublicKey) {
+        let result = this.strategyProgramWhitelistedPdas[strategyProgram.toBase58()]
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`Strategy program ${strategyProgram} is not whitelisted for bentobox`);
+        }
+    }
+
     getMasterContractApprovedPda(user: PublicKey, masterContract: PublicKey) {
         let result = this.masterContractApprovedPdas[user.toBase58() + masterContract.toBase58()]
         if (result) {
@@ -207,6 +231,18 @@ export class Bentobox {
             .rpc();
     }
 
+    async closeBalance(user: Keypair, mint: PublicKey) {
+        await this.bentoboxProgram.methods.closeBalance()
+            .accounts({
+                balance: this.getBalancePda(user.publicKey, mint),
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                authority: user.publicKey,
+                mint,
+            })
+            .signers([user])
+            .rpc();
+    }
+
     async createMasterContractWhitelist(masterContractAccount: PublicKey, masterContractProgram: PublicKey) {
         const [whitelistSeed, _whitelistBump] = await getMasterContractWhitelistAddress(masterContractAccount, this.bentoboxAccount.publicKey, this.bentoboxProgram.programId);
 
@@ -226,6 +262,102 @@ export class Bentobox {
 
     }
 
+    async whitelistMasterContract(whitelisted: boolean, masterContractAccount: PublicKey, masterContractProgram: PublicKey) {
+        await this.bentoboxProgram.methods.whitelistMasterContract(whitelisted)
+            .accounts({
+                masterContractWhitelisted: this.getMasterContractWhitelistedPda(masterContractAccount),
+                masterContractProgram: masterContractProgram,
+                masterContractAccount: masterContractAccount,
+                authority: this.bentoboxOwner.publicKey,
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
+    }
+
+    async revokeMasterContractApprovals(masterContractAccount: PublicKey) {
+        await this.bentoboxProgram.methods.revokeMasterContractApprovals()
+            .accounts({
+                masterContractWhitelisted: this.getMasterContractWhitelistedPda(masterContractAccount),
+                masterContractAccount: masterContractAccount,
+                authority: this.bentoboxOwner.publicKey,
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
+    }
+
+    async setMasterContractApproval(user: Keypair, approved: boolean, masterContractAccount: PublicKey, masterContractProgram: PublicKey) {
+        await this.bentoboxProgram.methods.setMasterContractApproval(approved)
+            .accounts({
+                masterContractApproved: this.getMasterContractApprovedPda(user.publicKey, masterContractAccount),
+                masterContractWhitelisted: this.getMasterContractWhitelistedPda(masterContractAccount),
+                masterContractProgram: masterContractProgram,
+                masterContractAccount: masterContractAccount,
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                authority: user.publicKey,
+            }).signers([user])
+            .rpc();
+    }
+
+    async createStrategyProgramWhitelist(strategyProgram: PublicKey) {
+        const [whitelistSeed, _whitelistBump] = await getStrategyProgramWhitelistAddress(strategyProgram, this.bentoboxAccount.publicKey, this.bentoboxProgram.programId);
+
+        this.strategyProgramWhitelistedPdas[strategyProgram.toBase58()] = whitelistSeed;
+
+        await this.bentoboxProgram.methods.createStrategyProgramWhitelist(true)
+            .accounts({
+                strategyProgramWhitelisted: whitelistSeed,
+                strategyProgram: strategyProgram,
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                authority: this.bentoboxOwner.publicKey,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
+    }
+
+    async createStrategyKeeper(keeper: PublicKey, mint: PublicKey) {
+        const [keeperSeed, _keeperBump] = await getBentoboxStrategyKeeperAddress(this.bentoboxAccount.publicKey, mint, keeper, this.bentoboxProgram.programId);
+
+        this.strategyKeeperPdas[keeper.toBase58() + mint.toBase58()] = keeperSeed;
+
+        await this.bentoboxProgram.methods.createStrategyKeeper(keeper, true)
+            .accounts({
+                strategyKeeper: keeperSeed,
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                mint,
+                authority: this.bentoboxOwner.publicKey,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
+    }
+
+    async setStrategyKeeper(keeper: PublicKey, mint: PublicKey, isKeeper: boolean) {
+        await this.bentoboxProgram.methods.setStrategyKeeper(isKeeper)
+            .accounts({
+                strategyKeeper: this.getStrategyKeeperPda(keeper, mint),
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                mint,
+                authority: this.bentoboxOwner.publicKey,
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
+    }
+
+    async setKeeperReward(keeperRewardBps: BN, mint: PublicKey) {
+        await this.bentoboxProgram.methods.setKeeperReward(keeperRewardBps)
+            .accounts({
+                strategyData: this.getStrategyDataPda(mint),
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                mint,
+                authority: this.bentoboxOwner.publicKey,
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
+    }
+
     async createMasterContractApproval(user: Keypair, masterContractAccount: PublicKey, masterContractProgram: PublicKey) {
         const [approvedSeed, _approvedBump] = await getMasterContractApprovedAddress(masterContractAccount, user.publicKey, this.bentoboxAccount.publicKey, this.bentoboxProgram.programId);
         this.masterContractApprovedPdas[user.publicKey.toBase58() + masterContractAccount.toBase58()] = approvedSeed;
@@ -243,6 +375,65 @@ export class Bentobox {
             .rpc();
     }
 
+    async closeMasterContractApproval(user: Keypair, masterContractAccount: PublicKey) {
+        await this.bentoboxProgram.methods.closeMasterContractApproval()
+            .accounts({
+                masterContractApproved: this.getMasterContractApprovedPda(user.publicKey, masterContractAccount),
+                masterContractAccount: masterContractAccount,
+                authority: user.publicKey,
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+            }).signers([user])
+            .rpc();
+    }
+
+    async getPermitNoncePda(user: PublicKey) {
+        const [permitNonceSeed, _permitNonceBump] = await getPermitNonceAddress(user, this.bentoboxAccount.publicKey, this.bentoboxProgram.programId);
+        return permitNonceSeed
+    }
+
+    // `APPROVAL_PERMIT_PREFIX` followed by borsh serialized `ApprovalPermit`, user signs it off-chain
+    getApprovalPermitMessage(masterContractAccount: PublicKey, user: PublicKey, approved: boolean, nonce: BN, expiry: BN): Buffer {
+        return Buffer.concat([
+            Buffer.from(anchor.utils.bytes.utf8.encode('BentoBox: master contract approval')),
+            this.bentoboxAccount.publicKey.toBuffer(),
+            masterContractAccount.toBuffer(),
+            user.toBuffer(),
+            Buffer.from([approved ? 1 : 0]),
+            nonce.toArrayLike(Buffer, "le", 8),
+            expiry.toTwos(64).toArrayLike(Buffer, "le", 8),
+        ]);
+    }
+
+    // `preInstructions` should end with ed25519 verification of the permit message
+    async setMasterContractApprovalWithPermit(
+        user: PublicKey,
+        approved: boolean,
+        nonce: BN,
+        expiry: BN,
+        masterContractAccount: PublicKey,
+        masterContractProgram: PublicKey,
+        payer: Keypair,
+        preInstructions: TransactionInstruction[]) {
+        const [approvedSeed, _approvedBump] = await getMasterContractApprovedAddress(masterContractAccount, user, this.bentoboxAccount.publicKey, this.bentoboxProgram.programId);
+        this.masterContractApprovedPdas[user.toBase58() + masterContractAccount.toBase58()] = approvedSeed;
+
+        await this.bentoboxProgram.methods.setMasterContractApprovalWithPermit(user, approved, nonce, expiry)
+            .accounts({
+                masterContractApproved: approvedSeed,
+                permitNonce: await this.getPermitNoncePda(user),
+                masterContractWhitelisted: this.getMasterContractWhitelistedPda(masterContractAccount),
+                masterContractProgram,
+                masterContractAccount,
+                instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+                payer: payer.publicKey,
+                systemProgram: SystemProgram.programId,
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+            })
+            .preInstructions(preInstructions)
+            .signers([payer])
+            .rpc({ commitment: "confirmed" });
+    }
+
     async createStrategyData(mint: PublicKey) {
         const [strategyDataAddress, _strategyDataNonce] = await getBentoboxStrategyDataAddress(
             this.bentoboxAccount.publicKey,
@@ -263,8 +454,8 @@ export class Bentobox {
             .rpc();
     }
 
-    async deposit(mint: PublicKey, from: PublicKey, to: PublicKey, amount: BN, share: BN, signer: Keypair) {
-        await this.bentoboxProgram.methods.deposit(to, amount, share)
+    async deposit(mint: PublicKey, from: PublicKey, to: PublicKey, amount: BN, share: BN, signer: Keypair, minShareOut: BN = new BN(0)) {
+        await this.bentoboxProgram.methods.deposit(to, amount, share, minShareOut)
             .accounts({
                 from,
                 bentoboxVault: this.getTotalVaultPda(mint),
@@ -280,7 +471,7 @@ export class Bentobox {
             .rpc();
     }
 
-    async withdraw(mint: PublicKey, from: PublicKey, to: PublicKey, amount: BN, share: BN, signer: Keypair) {
+    async withdraw(mint: PublicKey, from: PublicKey, to: PublicKey, amount: BN, share: BN, signer: Keypair, remainingAccounts: AccountMeta[] = []) {
         await this.bentoboxProgram.methods.withdraw(from, amount, share)
             .accounts({
                 bentoboxVault: this.getTotalVaultPda(mint),
@@ -292,11 +483,26 @@ export class Bentobox {
                 vaultAuthority: this.getBentoboxAuthority(),
                 tokenProgram: TOKEN_PROGRAM_ID,
             })
+            .remainingAccounts(remainingAccounts)
             .signers([signer])
             .rpc();
 
     }
 
+    getStrategyWithdrawAccounts(strategyProgram: PublicKey, strategyAccount: PublicKey, strategyVault: PublicKey, strategyAuthority: PublicKey, baseStrategyInfo: PublicKey, mint: PublicKey, strategyRemainingAccounts: AccountMeta[]): AccountMeta[] {
+        return [
+            { pubkey: this.getStrategyDataPda(mint), isWritable: true, isSigner: false },
+            { pubkey: this.bentoboxProgram.programId, isWritable: false, isSigner: false },
+            { pubkey: strategyProgram, isWritable: false, isSigner: false },
+            { pubkey: strategyAccount, isWritable: true, isSigner: false },
+            { pubkey: strategyVault, isWritable: true, isSigner: false },
+            { pubkey: strategyAuthority, isWritable: true, isSigner: false },
+            { pubkey: baseStrategyInfo, isWritable: true, isSigner: false },
+            { pubkey: this.getStrategyProgramWhitelistedPda(strategyProgram), isWritable: false, isSigner: false },
+            ...strategyRemainingAccounts,
+        ]
+    }
+
     async createBentoboxAuthorityMasterContractApproval(masterContractAccount: PublicKey, masterContractProgram: PublicKey, signer: Keypair) {
         const [approvedSeed, _approvedBump] = await getMasterContractApprovedAddress(masterContractAccount, this.bentoboxAuthorityPda, this.bentoboxAccount.publicKey, this.bentoboxProgram.programId);
 
@@ -318,18 +524,117 @@ export class Bentobox {
 
     }
 
-    async setStrategyTargetPercentage(percentage: BN, mint: PublicKey) {
-        await this.bentoboxProgram.methods.setStrategyTargetPercentage(percentage)
+    async setStrategyTargetPercentage(percentage: BN, mint: PublicKey, strategy: PublicKey) {
+        await this.bentoboxProgram.methods.setStrategyTargetPercentage(strategy, percentage)
+            .accounts({
+                strategyData: this.getStrategyDataPda(mint),
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                mint,
+                authority: this.bentoboxOwner.publicKey,
+
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
+
+    }
+
+    async setStrategyLossLimits(maxLossBps: BN, maxCumulativeLossBps: BN, exitOnMaxLoss: boolean, mint: PublicKey) {
+        await this.bentoboxProgram.methods.setStrategyLossLimits(maxLossBps, maxCumulativeLossBps, exitOnMaxLoss)
             .accounts({
                 strategyData: this.getStrategyDataPda(mint),
                 bentoboxAccount: this.bentoboxAccount.publicKey,
                 mint,
                 authority: this.bentoboxOwner.publicKey,
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
+    }
 
+    async setStrategyWithdrawOrder(withdrawOrder: PublicKey[], mint: PublicKey) {
+        await this.bentoboxProgram.methods.setStrategyWithdrawOrder(withdrawOrder)
+            .accounts({
+                strategyData: this.getStrategyDataPda(mint),
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                mint,
+                authority: this.bentoboxOwner.publicKey,
             })
             .signers([this.bentoboxOwner])
             .rpc();
+    }
 
+    async queueStrategy(strategyProgram: PublicKey, strategyAccount: PublicKey, mint: PublicKey) {
+        await this.bentoboxProgram.methods.queueStrategy()
+            .accounts({
+                strategyProgram,
+                strategyProgramWhitelisted: this.getStrategyProgramWhitelistedPda(strategyProgram),
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                strategyAccount,
+                strategyData: this.getStrategyDataPda(mint),
+                bentoboxVault: this.getTotalVaultPda(mint),
+                authority: this.bentoboxOwner.publicKey,
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
+    }
+
+    async activateStrategy(strategyProgram: PublicKey, strategyAccount: PublicKey, mint: PublicKey) {
+        await this.bentoboxProgram.methods.activateStrategy()
+            .accounts({
+                strategyProgram,
+                strategyProgramWhitelisted: this.getStrategyProgramWhitelistedPda(strategyProgram),
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                strategyAccount,
+                strategyData: this.getStrategyDataPda(mint),
+                bentoboxVault: this.getTotalVaultPda(mint),
+                authority: this.bentoboxOwner.publicKey,
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
+    }
+
+    async cancelPendingStrategy(mint: PublicKey) {
+        await this.bentoboxProgram.methods.cancelPendingStrategy()
+            .accounts({
+                strategyData: this.getStrategyDataPda(mint),
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                mint,
+                authority: this.bentoboxOwner.publicKey,
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
+    }
+
+    async removeStrategy(strategyProgram: PublicKey, strategyAccount: PublicKey, strategyVault: PublicKey, strategyAuthority: PublicKey, baseStrategyInfo: PublicKey, mint: PublicKey, remainingAccounts: AccountMeta[]) {
+        await this.bentoboxProgram.methods.removeStrategy()
+            .accounts({
+                strategyProgram,
+                bentoboxProgram: this.bentoboxProgram.programId,
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                strategyAccount,
+                strategyData: this.getStrategyDataPda(mint),
+                bentoboxVault: this.getTotalVaultPda(mint),
+                strategyVault,
+                totalData: this.getTotalDataPda(mint),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                baseStrategyInfo,
+                bentoboxAuthority: this.bentoboxAuthorityPda,
+                authority: this.bentoboxOwner.publicKey,
+                strategyAuthority,
+                systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([this.bentoboxOwner])
+            .rpc();
+    }
+
+    async setPerformanceFee(performanceFeeBps: BN, feeTo: PublicKey) {
+        await this.bentoboxProgram.methods.setPerformanceFee(performanceFeeBps, feeTo)
+            .accounts({
+                bentoboxAccount: this.bentoboxAccount.publicKey,
+                authority: this.bentoboxOwner.publicKey,
+            })
+            .signers([this.bentoboxOwner])
+            .rpc();
     }
 
     async setStrategyDelay(amount: BN) {
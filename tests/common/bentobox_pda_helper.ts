@@ -55,6 +55,15 @@ export async function getMasterContractWhitelistAddress(mastercontract_id: Publi
     );
 }
 
+export async function getStrategyProgramWhitelistAddress(strategy_program: PublicKey, bentobox: PublicKey, program: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode('whitelistedstrategyprogramkey')),
+        bentobox.toBytes(),
+        strategy_program.toBytes()],
+        program
+    );
+}
+
 export async function getMasterContractApprovedAddress(mastercontract_id: PublicKey, approver_user: PublicKey, bentobox: PublicKey, program: PublicKey): Promise<[PublicKey, number]> {
     return PublicKey.findProgramAddressSync(
         [Buffer.from(anchor.utils.bytes.utf8.encode('approvedmastercontractkey')),
@@ -73,4 +82,23 @@ export async function getBentoboxStrategyDataAddress(bentobox: PublicKey, mint:
         mint.toBytes()],
         program
     );
-}
\ No newline at end of file
+}
+
+export async function getBentoboxStrategyKeeperAddress(bentobox: PublicKey, mint: PublicKey, keeper: PublicKey, program: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode('bentoboxstrategykeeperkey')),
+        bentobox.toBytes(),
+        mint.toBytes(),
+        keeper.toBytes()],
+        program
+    );
+}
+
+export async function getPermitNonceAddress(user: PublicKey, bentobox: PublicKey, program: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode('permitnoncekey')),
+        bentobox.toBytes(),
+        user.toBytes()],
+        program
+    );
+}
@@ -4,6 +4,7 @@ import {
   PublicKey
 } from '@solana/web3.js';
 import {
+  borrowObligationLiquidityInstruction,
   depositObligationCollateralInstruction,
   depositReserveLiquidityInstruction,
   initLendingMarketInstruction,
@@ -20,18 +21,18 @@ import {
   ReserveFees,
   WAD_BigInt,
   parseObligation,
-} from '../../common/src';
+} from './src';
 import {
   Token,
   TOKEN_PROGRAM_ID,
 } from '@solana/spl-token';
 
-import * as common from "../../common/common";
+import * as common from "./common";
 
 import { Program, BN } from "@coral-xyz/anchor";
 
-import { Bentobox as BentoboxProgram } from '../../../target/types/bentobox';
-import { Bentobox } from "../../common/bentobox"
+import { Bentobox as BentoboxProgram } from '../../target/types/bentobox';
+import { Bentobox } from "./bentobox"
 
 // ============================================================================= bc class
 interface IToken {
@@ -134,7 +135,7 @@ export class Blockchain {
   // ======================================= RESERVE (SUPPLY SIDE)
   // --------------------------------------- init reserve
 
-  async initReserve(token: IToken, mintAmount: number, initAmount: number) {
+  async initReserve(token: IToken, mintAmount: number, initAmount: number, reserveConfig: ReserveConfig = Blockchain._generateStandardReserveConfig()) {
     console.log(`prepare ${token.currency} accounts`);
     //init'ed client-side
     token.mint = await common.createMintAccount(this.connection, this.lendingMarketOwnerKp, this.lendingMarketOwnerKp.publicKey, 0);
@@ -171,8 +172,6 @@ export class Blockchain {
       RESERVE_SIZE,
       this.connection
     );
-    const reserveConfig = Blockchain._generateStandardReserveConfig();
-
     //when we FIND the pda, we only pass OUR seed, not the bump seed
     let nonce;
     [this.lendingMarketAuthority, nonce] = PublicKey.findProgramAddressSync(
@@ -290,6 +289,38 @@ export class Blockchain {
     );
   }
 
+  // --------------------------------------- borrow liquidity against obligation collateral
+
+  async borrowObligationLiquidity(token: IToken, borrowLiquidityAmount: number) {
+    console.log(`borrow ${token.currency} liquidity from obligation`);
+    await this._refreshObligDepositsAndBorrows();
+    const refreshReserveIx = refreshReserveInstruction(
+      token.reserveKp.publicKey,
+      token.pythPricePk,
+    );
+    const refreshObligIx = refreshObligationInstruction(
+      this.obligationKp.publicKey,
+      this.obligationDeposits,
+      this.obligationBorrows,
+    );
+    const borrowObligLiqIx = borrowObligationLiquidityInstruction(
+      borrowLiquidityAmount,
+      token.protocolKp.publicKey,
+      token.userPk,
+      token.reserveKp.publicKey,
+      token.feeReceiverKp.publicKey,
+      this.obligationKp.publicKey,
+      this.lendingMarketKp.publicKey,
+      this.lendingMarketAuthority,
+      this.lendingMarketOwnerKp.publicKey,
+    );
+    await common.prepareAndSendTx(
+      [refreshReserveIx, refreshObligIx, borrowObligLiqIx],
+      [this.lendingMarketOwnerKp],
+      this.connection
+    );
+  }
+
   // --------------------------------------- set host PK for token
 
   async setTokenHost(hostPk: PublicKey) {
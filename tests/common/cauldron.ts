@@ -1,10 +1,10 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import { Cauldron as CauldronProgram } from "../../target/types/cauldron";
-import { Keypair, SystemProgram, PublicKey } from "@solana/web3.js";
+import { Keypair, SystemProgram, PublicKey, AccountMeta, ComputeBudgetProgram } from "@solana/web3.js";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 
-import { getCauldronUserBalanceAddress, getCauldronAuthorityAddress, getCauldronLiquidatorAccountAddress, getCauldronTotalAddress, getCauldronVaultAddress } from "./cauldron_pda_helper";
+import { getCauldronUserBalanceAddress, getCauldronAuthorityAddress, getCauldronLiquidatorAccountAddress, getCauldronTotalAddress, getCauldronVaultAddress, getCauldronSwapperWhitelistedAddress, getCauldronPoolOracleAddress } from "./cauldron_pda_helper";
 import { getMasterContractApprovedAddress } from "./bentobox_pda_helper";
 
 import { INTEREST_PER_SECOND, DISTRIBUTION_PART, DISTRIBUTION_PRECISION, LIQUIDATION_MULTIPLIER, LIQUIDATION_MULTIPLIER_PRECISION, BORROW_OPENING_FEE, BORROW_OPENING_FEE_PRECISION, COLLATERIZATION_RATE, COMPLETE_LIQUIDATION_DURATION, ONE_PERCENT_RATE } from "./src/constants";
@@ -24,9 +24,11 @@ export class Cauldron {
     totalVaultPda: Map<String, PublicKey>; // mint -> pda
     cauldronAuthorityPda: PublicKey
     cauldronAuthorityApprovalPda: PublicKey
+    poolOraclePda: PublicKey
 
     userBalancePdas: Map<String, PublicKey>;
     liquidatorAccountPdas: Map<String, PublicKey>;
+    swapperWhitelistedPdas: Map<String, PublicKey>;
 
     constructor() {
         this.cauldronProgram = anchor.workspace.Cauldron as Program<CauldronProgram>;
@@ -34,6 +36,7 @@ export class Cauldron {
         this.userBalancePdas = new Map<String, PublicKey>();
         this.totalVaultPda = new Map<String, PublicKey>();
         this.liquidatorAccountPdas = new Map<String, PublicKey>();
+        this.swapperWhitelistedPdas = new Map<String, PublicKey>();
     }
 
     getCauldronProgram() {
@@ -94,6 +97,26 @@ export class Cauldron {
         }
     }
 
+    getSwapperWhitelistedPda(swapperProgram: PublicKey) {
+        let result = this.swapperWhitelistedPdas[swapperProgram.toBase58()]
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`Swapper whitelisted account for swapper ${swapperProgram} is not created`);
+        }
+    }
+
+    getPoolOraclePda() {
+        let result = this.poolOraclePda
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`Cauldron is not initialized`);
+        }
+    }
+
     getCauldronAuthorityApprovedPda() {
         let result = this.cauldronAuthorityApprovalPda
         if (result) {
@@ -135,6 +158,10 @@ export class Cauldron {
         const [_cauldronAuthorityAddress, _cauldronAuthorityNonce] =
             await getCauldronAuthorityAddress(this.cauldronAccount.publicKey, this.cauldronProgram.programId);
         this.cauldronAuthorityPda = _cauldronAuthorityAddress;
+
+        const [_poolOracleAddress, _poolOracleNonce] =
+            await getCauldronPoolOracleAddress(this.cauldronAccount.publicKey, this.cauldronProgram.programId);
+        this.poolOraclePda = _poolOracleAddress;
     }
 
     async createVault(mint: PublicKey, signer: Keypair) {
@@ -180,6 +207,100 @@ export class Cauldron {
             .rpc();
     }
 
+    // `swapPrograms` are DEX programs which can be passed to swapper as `swapProgram`
+    async createSwapperWhitelist(swapperProgram: PublicKey, whitelisted: boolean, swapPrograms: PublicKey[], signer: Keypair) {
+        const [_swapperWhitelisted, _swapperWhitelistedNonce] = await getCauldronSwapperWhitelistedAddress(
+            swapperProgram,
+            this.cauldronAccount.publicKey,
+            this.cauldronProgram.programId
+        );
+        this.swapperWhitelistedPdas[swapperProgram.toBase58()] = _swapperWhitelisted;
+
+        await this.cauldronProgram.methods.createSwapperWhitelist(whitelisted, swapPrograms)
+            .accounts({
+                swapperWhitelisted: _swapperWhitelisted,
+                cauldronAccount: this.cauldronAccount.publicKey,
+                swapperProgram,
+                authority: signer.publicKey,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    async whitelistSwapper(swapperProgram: PublicKey, whitelisted: boolean, swapPrograms: PublicKey[], signer: Keypair) {
+        await this.cauldronProgram.methods.whitelistSwapper(whitelisted, swapPrograms)
+            .accounts({
+                swapperWhitelisted: this.getSwapperWhitelistedPda(swapperProgram),
+                swapperProgram,
+                cauldronAccount: this.cauldronAccount.publicKey,
+                authority: signer.publicKey,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    // mode is `{ sanityCheck: {} }` or `{ primary: {} }`
+    // first spot price is quoted with `quoteAccounts`, pool accounts by default
+    async createPoolOracle(
+        swapperProgram: PublicKey,
+        poolAccounts: PublicKey[],
+        mode: any,
+        maxDeviation: BN,
+        twapPeriod: BN,
+        maxPriceAge: BN,
+        signer: Keypair,
+        quoteAccounts: PublicKey[] = poolAccounts) {
+        await this.cauldronProgram.methods.createPoolOracle(poolAccounts, mode, maxDeviation, twapPeriod, maxPriceAge)
+            .accounts({
+                poolOracle: this.getPoolOraclePda(),
+                cauldronAccount: this.cauldronAccount.publicKey,
+                swapperProgram,
+                swapperWhitelisted: this.getSwapperWhitelistedPda(swapperProgram),
+                authority: signer.publicKey,
+                systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts(quoteAccounts.map(pubkey => ({ pubkey, isWritable: false, isSigner: false })))
+            .signers([signer])
+            .rpc({ commitment: "confirmed" });
+    }
+
+    async updatePoolOracleConfig(mode: any, maxDeviation: BN, twapPeriod: BN, maxPriceAge: BN, signer: Keypair) {
+        await this.cauldronProgram.methods.updatePoolOracleConfig(mode, maxDeviation, twapPeriod, maxPriceAge)
+            .accounts({
+                poolOracle: this.getPoolOraclePda(),
+                cauldronAccount: this.cauldronAccount.publicKey,
+                authority: signer.publicKey,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    async closePoolOracle(signer: Keypair) {
+        await this.cauldronProgram.methods.closePoolOracle()
+            .accounts({
+                poolOracle: this.getPoolOraclePda(),
+                cauldronAccount: this.cauldronAccount.publicKey,
+                authority: signer.publicKey,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    async updatePoolOraclePrice(swapperProgram: PublicKey, poolAccounts: AccountMeta[], signer: Keypair) {
+        await this.cauldronProgram.methods.updatePoolOraclePrice()
+            .accounts({
+                poolOracle: this.getPoolOraclePda(),
+                cauldronAccount: this.cauldronAccount.publicKey,
+                swapperProgram,
+                swapperWhitelisted: this.getSwapperWhitelistedPda(swapperProgram),
+                authority: signer.publicKey,
+            })
+            .remainingAccounts(poolAccounts)
+            .signers([signer])
+            .rpc({ commitment: "confirmed" });
+    }
+
     async createUserBalance(user: PublicKey, signer: Keypair) {
         const [_cauldronUserBalance, _cauldronUserBalanceNonce] = await getCauldronUserBalanceAddress(
             user,
@@ -214,6 +335,7 @@ export class Cauldron {
                 bentoboxProgram,
                 magicInternetMoneyMint: this.magicInternetMoney,
                 switchboardDataFeed: this.switchboardDataFeed,
+                poolOracle: this.getPoolOraclePda(),
                 authority: signer.publicKey,
             }).signers([signer])
             .rpc();
@@ -279,6 +401,7 @@ export class Cauldron {
                 cauldronAccount: this.cauldronAccount.publicKey,
                 cauldronAuthority: this.getCauldronAuthority(),
                 switchboardDataFeed: this.switchboardDataFeed,
+                poolOracle: this.getPoolOraclePda(),
                 totalData: this.getTotalDataPda(),
                 userBalance: this.getUserBalancePda(user),
                 cauldronCollateralBentoboxBalance: bentobox.getBalancePda(this.getCauldronAuthority(), this.collateral),
@@ -316,6 +439,7 @@ export class Cauldron {
                     cauldronAccount: this.cauldronAccount.publicKey,
                     cauldronAuthority: this.getCauldronAuthority(),
                     switchboardDataFeed: this.switchboardDataFeed,
+                    poolOracle: this.getPoolOraclePda(),
                     totalData: this.getTotalDataPda(),
                     userBalance: this.getUserBalancePda(user),
                     cauldronCollateralBentoboxBalance: bentobox.getBalancePda(this.getCauldronAuthority(), this.collateral),
@@ -344,7 +468,7 @@ export class Cauldron {
     //   4. `[writable]` pool destination account
     //   5. `[writable]` pool token mint
     //   6. `[writable]` pool fee account
-    //   7. `[writable]` pool host fee account
+    //   7. `[writable]` pool host fee account, skipped when `withHostFee` is false
     //   8. `[]` source mint account
     //   9. `[]` destination mint account
     async liquidateSwapOrca(
@@ -358,7 +482,10 @@ export class Cauldron {
         poolSourceVault: PublicKey,
         poolDestinationVault: PublicKey,
         poolTokenMint: PublicKey,
-        poolFeeAccount: PublicKey) {
+        poolFeeAccount: PublicKey,
+        withHostFee: boolean = true) {
+        const hostFeeAccounts = withHostFee ?
+            [{ pubkey: this.getTotalVaultPda(poolTokenMint), isWritable: true, isSigner: false }] : [];
 
         try {
             let tx = await this.cauldronProgram.methods.liquidateSwap()
@@ -371,6 +498,7 @@ export class Cauldron {
                     cauldronSourceVault: this.getTotalVaultPda(this.collateral),
                     cauldronDestinationVault: this.getTotalVaultPda(this.magicInternetMoney),
                     swapperProgram,
+                    swapperWhitelisted: this.getSwapperWhitelistedPda(swapperProgram),
                     swapProgram,
                     tokenProgram: TOKEN_PROGRAM_ID,
                     authority: signer.publicKey,
@@ -382,7 +510,7 @@ export class Cauldron {
                     { pubkey: poolDestinationVault, isWritable: true, isSigner: false },
                     { pubkey: poolTokenMint, isWritable: true, isSigner: false },
                     { pubkey: poolFeeAccount, isWritable: true, isSigner: false },
-                    { pubkey: this.getTotalVaultPda(poolTokenMint), isWritable: true, isSigner: false },
+                    ...hostFeeAccounts,
                     { pubkey: this.collateral, isWritable: false, isSigner: false },
                     { pubkey: this.magicInternetMoney, isWritable: false, isSigner: false },
                 ]).signers([signer])
@@ -408,10 +536,37 @@ export class Cauldron {
     //   12. `[writable]` coin_vault Account
     //   13. `[writable]` pc_vault Account
     //   14. '[]` vault_signer Account
+    // Pools without orderbook (`withOrderbook` false) need only accounts 1, 2, 5 and 6.
     async liquidateSwapRaydium(signer: Keypair,
         originLiquidator: PublicKey,
         swapperProgram: PublicKey,
-        raydiumPoolProperties: any) {
+        raydiumPoolProperties: any,
+        withOrderbook: boolean = true) {
+        const remainingAccounts = withOrderbook ? [
+            // Raydium specific
+            { pubkey: raydiumPoolProperties.id, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.authority, isWritable: false, isSigner: false },
+            { pubkey: raydiumPoolProperties.openOrders, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.targetOrders, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.baseVault, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.quoteVault, isWritable: true, isSigner: false },
+
+            { pubkey: raydiumPoolProperties.marketProgramId, isWritable: false, isSigner: false },
+            { pubkey: raydiumPoolProperties.marketId, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.marketBids, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.marketAsks, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.marketEventQueue, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.marketBaseVault, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.marketQuoteVault, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.marketAuthority, isWritable: false, isSigner: false },
+        ] : [
+            // Raydium specific, without orderbook
+            { pubkey: raydiumPoolProperties.id, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.authority, isWritable: false, isSigner: false },
+            { pubkey: raydiumPoolProperties.baseVault, isWritable: true, isSigner: false },
+            { pubkey: raydiumPoolProperties.quoteVault, isWritable: true, isSigner: false },
+        ];
+
         try {
             let tx = await this.cauldronProgram.methods.liquidateSwap()
                 .accounts({
@@ -423,27 +578,12 @@ export class Cauldron {
                     cauldronSourceVault: this.getTotalVaultPda(this.collateral),
                     cauldronDestinationVault: this.getTotalVaultPda(this.magicInternetMoney),
                     swapperProgram,
+                    swapperWhitelisted: this.getSwapperWhitelistedPda(swapperProgram),
                     swapProgram: raydiumPoolProperties.programId,
                     tokenProgram: TOKEN_PROGRAM_ID,
                     authority: signer.publicKey,
-                }).remainingAccounts([
-                    // Raydium specific
-                    { pubkey: raydiumPoolProperties.id, isWritable: true, isSigner: false },
-                    { pubkey: raydiumPoolProperties.authority, isWritable: false, isSigner: false },
-                    { pubkey: raydiumPoolProperties.openOrders, isWritable: true, isSigner: false },
-                    { pubkey: raydiumPoolProperties.targetOrders, isWritable: true, isSigner: false },
-                    { pubkey: raydiumPoolProperties.baseVault, isWritable: true, isSigner: false },
-                    { pubkey: raydiumPoolProperties.quoteVault, isWritable: true, isSigner: false },
-
-                    { pubkey: raydiumPoolProperties.marketProgramId, isWritable: false, isSigner: false },
-                    { pubkey: raydiumPoolProperties.marketId, isWritable: true, isSigner: false },
-                    { pubkey: raydiumPoolProperties.marketBids, isWritable: true, isSigner: false },
-                    { pubkey: raydiumPoolProperties.marketAsks, isWritable: true, isSigner: false },
-                    { pubkey: raydiumPoolProperties.marketEventQueue, isWritable: true, isSigner: false },
-                    { pubkey: raydiumPoolProperties.marketBaseVault, isWritable: true, isSigner: false },
-                    { pubkey: raydiumPoolProperties.marketQuoteVault, isWritable: true, isSigner: false },
-                    { pubkey: raydiumPoolProperties.marketAuthority, isWritable: false, isSigner: false },
-                ]).signers([signer])
+                }).remainingAccounts(remainingAccounts)
+                .signers([signer])
                 .rpc({ commitment: "confirmed" });
             console.log("Raydium swap tx", tx)
         } catch (_err) {
@@ -451,6 +591,79 @@ export class Cauldron {
         }
     }
 
+    // Cancels liquidation which is not swapped till liquidator account timestamp, can be called by anyone.
+    async cancelLiquidate(
+        signer: Keypair,
+        originLiquidator: PublicKey,
+        user: PublicKey,
+        bentobox: Bentobox) {
+        return await this.cauldronProgram.methods.cancelLiquidate()
+            .accounts({
+                liquidatorAccount: this.getLiquidatorAccountPda(originLiquidator),
+                payer: originLiquidator,
+                cauldronAccount: this.getCauldronAccount(),
+                cauldronAuthority: this.getCauldronAuthority(),
+                totalData: this.getTotalDataPda(),
+                userBalance: this.getUserBalancePda(user),
+                bentoboxAccount: this.bentoboxAccount,
+                bentoboxProgram: bentobox.getBentoboxProgram(),
+                collateral: this.collateral,
+                cauldronSourceVault: this.getTotalVaultPda(this.collateral),
+                cauldronCollateralBentoboxBalance: bentobox.getBalancePda(this.getCauldronAuthority(), this.collateral),
+                bentoboxCollateralTotalData: bentobox.getTotalDataPda(this.collateral),
+                bentoboxCollateralVault: bentobox.getTotalVaultPda(this.collateral),
+                collateralStrategyData: bentobox.getStrategyDataPda(this.collateral),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                authority: signer.publicKey,
+            }).signers([signer])
+            .rpc({ commitment: "confirmed" });
+    }
+
+    // Liquidates position in one instruction, `swapAccounts` are remaining accounts required by swapper,
+    // see `liquidateSwapOrca` and `liquidateSwapRaydium`.
+    async flashLiquidate(
+        user: PublicKey,
+        maxBorrowPart: BN,
+        signer: Keypair,
+        bentobox: Bentobox,
+        swapperProgram: PublicKey,
+        swapProgram: PublicKey,
+        swapAccounts: AccountMeta[]) {
+        return await this.cauldronProgram.methods.flashLiquidate(user, maxBorrowPart)
+            .accounts({
+                bentoboxCollateralTotalData: bentobox.getTotalDataPda(this.collateral),
+                bentoboxMimTotalData: bentobox.getTotalDataPda(this.magicInternetMoney),
+                bentoboxAccount: this.bentoboxAccount,
+                bentoboxProgram: bentobox.getBentoboxProgram(),
+                cauldronAccount: this.cauldronAccount.publicKey,
+                cauldronAuthority: this.getCauldronAuthority(),
+                switchboardDataFeed: this.switchboardDataFeed,
+                poolOracle: this.getPoolOraclePda(),
+                totalData: this.getTotalDataPda(),
+                userBalance: this.getUserBalancePda(user),
+                cauldronCollateralBentoboxBalance: bentobox.getBalancePda(this.getCauldronAuthority(), this.collateral),
+                cauldronMimBentoboxBalance: bentobox.getBalancePda(this.getCauldronAuthority(), this.magicInternetMoney),
+                authorityMimBentoboxBalance: bentobox.getBalancePda(signer.publicKey, this.magicInternetMoney),
+                collateral: this.collateral,
+                magicInternetMoneyMint: this.magicInternetMoney,
+                cauldronSourceVault: this.getTotalVaultPda(this.collateral),
+                cauldronDestinationVault: this.getTotalVaultPda(this.magicInternetMoney),
+                bentoboxVaultAuthority: bentobox.getBentoboxAuthority(),
+                bentoboxCollateralVault: bentobox.getTotalVaultPda(this.collateral),
+                bentoboxMimVault: bentobox.getTotalVaultPda(this.magicInternetMoney),
+                mimStrategyData: bentobox.getStrategyDataPda(this.magicInternetMoney),
+                swapperProgram,
+                swapperWhitelisted: this.getSwapperWhitelistedPda(swapperProgram),
+                swapProgram,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                authority: signer.publicKey,
+            })
+            .remainingAccounts(swapAccounts)
+            .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 600_000 })])
+            .signers([signer])
+            .rpc({ commitment: "confirmed" });
+    }
+
     async completeLiquidate(
         signer: Keypair,
         originLiquidator: PublicKey,
@@ -459,6 +672,7 @@ export class Cauldron {
             let tx = await this.cauldronProgram.methods.completeLiquidate()
                 .accounts({
                     liquidatorAccount: this.getLiquidatorAccountPda(originLiquidator),
+                    payer: originLiquidator,
                     cauldronMimBentoboxBalance: bentobox.getBalancePda(this.cauldronAuthorityPda, this.magicInternetMoney),
                     authorityMimBentoboxBalance: bentobox.getBalancePda(signer.publicKey, this.magicInternetMoney),
                     cauldronAccount: this.getCauldronAccount(),
@@ -53,4 +53,21 @@ export async function getCauldronLiquidatorAccountAddress(liquidator: PublicKey,
         liquidator.toBytes()],
         program
     );
-}
\ No newline at end of file
+}
+
+export async function getCauldronPoolOracleAddress(cauldron: PublicKey, program: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode('pooloracle')),
+        cauldron.toBytes()],
+        program
+    );
+}
+
+export async function getCauldronSwapperWhitelistedAddress(swapperProgram: PublicKey, cauldron: PublicKey, program: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode('swapperwhitelisted')),
+        cauldron.toBytes(),
+        swapperProgram.toBytes()],
+        program
+    );
+}
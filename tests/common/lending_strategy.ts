@@ -0,0 +1,164 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { StrategyLending as StrategyLendingProgram } from "../../target/types/strategy_lending";
+import { Keypair, SystemProgram, PublicKey, AccountMeta, SYSVAR_CLOCK_PUBKEY } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+import { getStrategyExecutorInfoAddress, getStrategyAddress, getStrategyVaultAddress } from "./mock_strategy_pda_helper";
+import { getCollateralVaultAddress, getLendingMarketAuthorityAddress } from "./lending_strategy_pda_helper";
+
+export interface LendingReserve {
+    lendingProgram: PublicKey
+    reserve: PublicKey
+    reserveLiquiditySupply: PublicKey
+    reserveCollateralMint: PublicKey
+    lendingMarket: PublicKey
+    reserveLiquidityOracle: PublicKey
+}
+
+export class StrategyLending {
+    strategyLendingProgram: anchor.Program<StrategyLendingProgram>
+
+    strategyLendingAccount: Keypair
+    strategyOwner: Keypair
+    strategyLendingAuthorityPda: PublicKey
+    collateralVaultPda: PublicKey
+    lendingReserve: LendingReserve
+
+    totalVaultPdas: Map<String, PublicKey>; // mint -> pda
+    strategyLendingExecutorInfoPda: Map<String, PublicKey>; // user -> pda
+
+    baseStrategyInfo: Keypair
+
+    constructor() {
+        this.strategyLendingProgram = anchor.workspace.StrategyLending as Program<StrategyLendingProgram>;
+        this.strategyLendingAccount = Keypair.generate();
+        this.baseStrategyInfo = Keypair.generate();
+        this.totalVaultPdas = new Map<String, PublicKey>();
+        this.strategyLendingExecutorInfoPda = new Map<String, PublicKey>();
+    }
+
+    getStrategyLendingProgram() {
+        return this.strategyLendingProgram.programId
+    }
+
+    getStrategyLendingAccount() {
+        return this.strategyLendingAccount.publicKey
+    }
+
+    getStrategyLendingAuthority() {
+        let result = this.strategyLendingAuthorityPda
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`StrategyLendingAuthority is not created`);
+        }
+    }
+
+    getTotalVaultPda(mint: PublicKey) {
+        let result = this.totalVaultPdas[mint.toBase58()]
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`Total vault for token ${mint} is not created`);
+        }
+    }
+
+    getCollateralVaultPda() {
+        let result = this.collateralVaultPda
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`Collateral vault is not created`);
+        }
+    }
+
+    getBaseStrategyInfoAccount() {
+        return this.baseStrategyInfo.publicKey
+    }
+
+    getStrategyLendingExecutorInfoPda(user: PublicKey) {
+        let result = this.strategyLendingExecutorInfoPda[user.toBase58()]
+        if (result) {
+            return result
+        }
+        else {
+            throw new Error(`Executor info account for user ${user} is not created`);
+        }
+    }
+
+    /// Lending accounts in order expected by strategy `skim`, `harvest`, `withdraw` and `exit`.
+    async getLendingRemainingAccounts(): Promise<AccountMeta[]> {
+        const [lendingMarketAuthority, _bump] = await getLendingMarketAuthorityAddress(
+            this.lendingReserve.lendingMarket,
+            this.lendingReserve.lendingProgram
+        );
+
+        return [
+            { pubkey: this.lendingReserve.lendingProgram, isWritable: false, isSigner: false },
+            { pubkey: this.lendingReserve.reserve, isWritable: true, isSigner: false },
+            { pubkey: this.lendingReserve.reserveLiquiditySupply, isWritable: true, isSigner: false },
+            { pubkey: this.lendingReserve.reserveCollateralMint, isWritable: true, isSigner: false },
+            { pubkey: this.lendingReserve.lendingMarket, isWritable: false, isSigner: false },
+            { pubkey: lendingMarketAuthority, isWritable: false, isSigner: false },
+            { pubkey: this.lendingReserve.reserveLiquidityOracle, isWritable: false, isSigner: false },
+            { pubkey: this.getCollateralVaultPda(), isWritable: true, isSigner: false },
+            { pubkey: SYSVAR_CLOCK_PUBKEY, isWritable: false, isSigner: false },
+        ];
+    }
+
+    async initialize(mint: PublicKey, lendingReserve: LendingReserve, bentoboxAccount: PublicKey, bentoboxProgram: PublicKey, strategyOwner: Keypair) {
+        this.strategyOwner = strategyOwner;
+        this.lendingReserve = lendingReserve;
+
+        const [_strategy_vault, _strategy_vault_nonce] = await getStrategyVaultAddress(
+            mint,
+            this.strategyLendingAccount.publicKey,
+            this.strategyLendingProgram.programId
+        );
+        this.totalVaultPdas[mint.toBase58()] = _strategy_vault;
+
+        const [_collateral_vault, _collateral_vault_nonce] = await getCollateralVaultAddress(
+            lendingReserve.reserveCollateralMint,
+            this.strategyLendingAccount.publicKey,
+            this.strategyLendingProgram.programId
+        );
+        this.collateralVaultPda = _collateral_vault;
+
+        const [_strategy_authority_pda, _strategy_authority_bump] =
+            await getStrategyAddress(this.strategyLendingAccount.publicKey, this.strategyLendingProgram.programId);
+        this.strategyLendingAuthorityPda = _strategy_authority_pda;
+
+        const [_owner_executor_info, _owner_executor_info_nonce] = await getStrategyExecutorInfoAddress(
+            this.strategyLendingAccount.publicKey,
+            this.strategyOwner.publicKey,
+            this.strategyLendingProgram.programId
+        );
+        this.strategyLendingExecutorInfoPda[this.strategyOwner.publicKey.toBase58()] = _owner_executor_info
+
+        await this.strategyLendingProgram.methods.initialize()
+            .accounts({
+                strategyVault: _strategy_vault,
+                collateralVault: _collateral_vault,
+                strategyAccount: this.strategyLendingAccount.publicKey,
+                baseStrategyInfo: this.baseStrategyInfo.publicKey,
+                mint,
+                collateralMint: lendingReserve.reserveCollateralMint,
+                lendingProgram: lendingReserve.lendingProgram,
+                reserve: lendingReserve.reserve,
+                bentoboxAccount,
+                bentoboxProgram,
+                authority: strategyOwner.publicKey,
+                systemProgram: SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                executorInfo: _owner_executor_info,
+                strategyAuthority: _strategy_authority_pda
+            })
+            .signers([strategyOwner, this.strategyLendingAccount, this.baseStrategyInfo])
+            .rpc();
+    }
+}
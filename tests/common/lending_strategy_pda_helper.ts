@@ -0,0 +1,18 @@
+import { PublicKey } from '@solana/web3.js';
+import * as anchor from '@coral-xyz/anchor';
+
+export async function getCollateralVaultAddress(collateralMint: PublicKey, strategy: PublicKey, program: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode('collateralvaultkey')),
+        strategy.toBytes(),
+        collateralMint.toBytes()],
+        program
+    );
+}
+
+export async function getLendingMarketAuthorityAddress(lendingMarket: PublicKey, lendingProgram: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+        [lendingMarket.toBytes()],
+        lendingProgram
+    );
+}
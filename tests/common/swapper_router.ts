@@ -0,0 +1,142 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { SwapperRouter as SwapperRouterProgram } from "../../target/types/swapper_router";
+import { Keypair, SystemProgram, PublicKey, AccountMeta } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+// One hop of routed swap.
+export interface RouteHopAccounts {
+    swapperProgram: PublicKey
+    swapProgram: PublicKey
+    // token account receiving hop output, owned by swap authority
+    destination: PublicKey
+    poolAccounts: AccountMeta[]
+}
+
+export class SwapperRouter {
+    swapperRouterProgram: anchor.Program<SwapperRouterProgram>
+
+    routerAccount: PublicKey
+
+    constructor() {
+        this.swapperRouterProgram = anchor.workspace.SwapperRouter as Program<SwapperRouterProgram>;
+        [this.routerAccount] = PublicKey.findProgramAddressSync(
+            [Buffer.from("swapperrouter")],
+            this.swapperRouterProgram.programId
+        );
+    }
+
+    getSwapperRouterProgram() {
+        return this.swapperRouterProgram.programId
+    }
+
+    getRouterAccount() {
+        return this.routerAccount
+    }
+
+    getProgramDataAccount() {
+        return PublicKey.findProgramAddressSync(
+            [this.swapperRouterProgram.programId.toBuffer()],
+            BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+        )[0];
+    }
+
+    // Signer should be upgrade authority of router program.
+    async initialize(signer: Keypair) {
+        await this.swapperRouterProgram.methods.initialize()
+            .accounts({
+                routerAccount: this.routerAccount,
+                routerProgram: this.swapperRouterProgram.programId,
+                programData: this.getProgramDataAccount(),
+                authority: signer.publicKey,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    async whitelistHopSwapper(swapperProgram: PublicKey, whitelisted: boolean, signer: Keypair) {
+        await this.swapperRouterProgram.methods.whitelistHopSwapper(whitelisted)
+            .accounts({
+                routerAccount: this.routerAccount,
+                swapperProgram,
+                authority: signer.publicKey,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    async createRoute(hops: RouteHopAccounts[], signer: Keypair): Promise<PublicKey> {
+        const route = Keypair.generate();
+        await this.swapperRouterProgram.methods.createRoute(hops.map((hop) => ({
+            swapperProgram: hop.swapperProgram,
+            poolAccountsCount: hop.poolAccounts.length,
+        })))
+            .accounts({
+                route: route.publicKey,
+                routerAccount: this.routerAccount,
+                authority: signer.publicKey,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([signer, route])
+            .rpc();
+        return route.publicKey;
+    }
+
+    async closeRoute(route: PublicKey, signer: Keypair) {
+        await this.swapperRouterProgram.methods.closeRoute()
+            .accounts({
+                route,
+                routerAccount: this.routerAccount,
+                authority: signer.publicKey,
+            })
+            .signers([signer])
+            .rpc();
+    }
+
+    // Remaining accounts required by Router swapper
+    //   1. `[]` route Account
+    //   2. `[]` router PDA Account
+    // For every hop of the route
+    //   1. `[]` swapper program of the hop
+    //   2. `[]` swap program of the hop
+    //   3. `[writable]` hop destination token Account
+    //   4. pool accounts required by hop swapper
+    getSwapRemainingAccounts(route: PublicKey, hops: RouteHopAccounts[]): AccountMeta[] {
+        const accounts = [
+            { pubkey: route, isWritable: false, isSigner: false },
+            { pubkey: this.routerAccount, isWritable: false, isSigner: false },
+        ];
+        for (const hop of hops) {
+            accounts.push(
+                { pubkey: hop.swapperProgram, isWritable: false, isSigner: false },
+                { pubkey: hop.swapProgram, isWritable: false, isSigner: false },
+                { pubkey: hop.destination, isWritable: true, isSigner: false },
+                ...hop.poolAccounts,
+            );
+        }
+        return accounts;
+    }
+
+    async swap(
+        route: PublicKey,
+        hops: RouteHopAccounts[],
+        source: PublicKey,
+        amountIn: BN,
+        minimumAmountOut: BN,
+        signer: Keypair) {
+        await this.swapperRouterProgram.methods.swap(amountIn, minimumAmountOut)
+            .accounts({
+                sourceTokenAccount: source,
+                destinationTokenAccount: hops[hops.length - 1].destination,
+                swapProgram: hops[0].swapProgram,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                authority: signer.publicKey,
+            })
+            .remainingAccounts(this.getSwapRemainingAccounts(route, hops))
+            .signers([signer])
+            .rpc();
+    }
+}
@@ -0,0 +1,88 @@
+import { BN } from "@coral-xyz/anchor";
+import { Connection, Keypair, PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+import * as common from "./common";
+import { TokenSwapPool } from "./amm_strategy";
+
+export const TOKEN_SWAP_PROGRAM_ID = new PublicKey("SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8");
+/// Owner of pool fee accounts required by production build of spl-token-swap.
+export const TOKEN_SWAP_OWNER_FEE_ADDRESS = new PublicKey("HfoTxFR1Tm6kGmWgYWD6J7YHVy1UwqSULUGVLXkJqaKN");
+
+/// `SwapVersion::LATEST_LEN` of spl-token-swap.
+const TOKEN_SWAP_SIZE = 324;
+const CONSTANT_PRODUCT_CURVE = 0;
+
+/// Fees required by production build of spl-token-swap, numerator/denominator pairs.
+const FEES: number[] = [25, 10000, 5, 10000, 0, 0, 20, 100];
+
+/// Creates spl-token-swap constant product pool for `mintA`/`mintB` funded with `amountA`/`amountB` tokens.
+///
+/// Return: pool accounts and `payer` token account holding initial pool tokens.
+export async function createTokenSwapPool(connection: Connection, payer: Keypair, mintA: Token, mintB: Token,
+    mintAuthority: Keypair, amountA: number, amountB: number): Promise<[TokenSwapPool, PublicKey]> {
+    const tokenSwap = Keypair.generate();
+    const [swapAuthority, _bump] = PublicKey.findProgramAddressSync([tokenSwap.publicKey.toBuffer()], TOKEN_SWAP_PROGRAM_ID);
+
+    const poolMint = await common.createMintAccount(connection, payer, swapAuthority, 0);
+    const poolFeeAccount = await poolMint.createAccount(TOKEN_SWAP_OWNER_FEE_ADDRESS);
+    const destination = await poolMint.createAccount(payer.publicKey);
+
+    const swapTokenA = await common.createAndFundUserAccount(swapAuthority, mintAuthority, mintA, amountA);
+    const swapTokenB = await common.createAndFundUserAccount(swapAuthority, mintAuthority, mintB, amountB);
+
+    const data = Buffer.alloc(1 + FEES.length * 8 + 1 + 32);
+    data.writeUInt8(0, 0); // Initialize
+    FEES.forEach((fee, i) => new BN(fee).toArrayLike(Buffer, "le", 8).copy(data, 1 + i * 8));
+    data.writeUInt8(CONSTANT_PRODUCT_CURVE, 1 + FEES.length * 8);
+
+    const initializeIx = new TransactionInstruction({
+        keys: [
+            { pubkey: tokenSwap.publicKey, isSigner: false, isWritable: true },
+            { pubkey: swapAuthority, isSigner: false, isWritable: false },
+            { pubkey: swapTokenA, isSigner: false, isWritable: false },
+            { pubkey: swapTokenB, isSigner: false, isWritable: false },
+            { pubkey: poolMint.publicKey, isSigner: false, isWritable: true },
+            { pubkey: poolFeeAccount, isSigner: false, isWritable: false },
+            { pubkey: destination, isSigner: false, isWritable: true },
+            { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        ],
+        programId: TOKEN_SWAP_PROGRAM_ID,
+        data,
+    });
+
+    await common.prepareAndSendTx([
+        SystemProgram.createAccount({
+            fromPubkey: payer.publicKey,
+            newAccountPubkey: tokenSwap.publicKey,
+            space: TOKEN_SWAP_SIZE,
+            lamports: await connection.getMinimumBalanceForRentExemption(TOKEN_SWAP_SIZE),
+            programId: TOKEN_SWAP_PROGRAM_ID,
+        }),
+        initializeIx,
+    ], [payer, tokenSwap], connection);
+
+    return [{
+        swapProgram: TOKEN_SWAP_PROGRAM_ID,
+        tokenSwap: tokenSwap.publicKey,
+        swapAuthority,
+        swapTokenA,
+        swapTokenB,
+        poolMint: poolMint.publicKey,
+        poolFeeAccount,
+    }, destination];
+}
+
+/// Remaining accounts of `swapper_orca` swap without host fee from `sourceMint` to `destinationMint`.
+export function getOrcaSwapRemainingAccounts(pool: TokenSwapPool, sourceMint: PublicKey, destinationMint: PublicKey, sourceIsA: boolean) {
+    return [
+        { pubkey: pool.tokenSwap, isWritable: false, isSigner: false },
+        { pubkey: pool.swapAuthority, isWritable: false, isSigner: false },
+        { pubkey: sourceIsA ? pool.swapTokenA : pool.swapTokenB, isWritable: true, isSigner: false },
+        { pubkey: sourceIsA ? pool.swapTokenB : pool.swapTokenA, isWritable: true, isSigner: false },
+        { pubkey: pool.poolMint, isWritable: true, isSigner: false },
+        { pubkey: pool.poolFeeAccount, isWritable: true, isSigner: false },
+        { pubkey: sourceMint, isWritable: false, isSigner: false },
+        { pubkey: destinationMint, isWritable: false, isSigner: false },
+    ];
+}
@@ -0,0 +1,132 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+    WhirlpoolContext,
+    WhirlpoolClient,
+    Whirlpool,
+    WhirlpoolIx,
+    buildWhirlpoolClient,
+    increaseLiquidityQuoteByInputToken,
+    toTx,
+    PDAUtil,
+    PoolUtil,
+    PriceMath,
+    SwapUtils,
+    TickUtil,
+    TICK_ARRAY_SIZE,
+    ORCA_WHIRLPOOL_PROGRAM_ID,
+} from "@orca-so/whirlpools-sdk";
+import { Percentage } from "@orca-so/common-sdk";
+import Decimal from "decimal.js";
+
+export const WHIRLPOOL_TICK_SPACING = 64;
+export const WHIRLPOOL_FEE_RATE = 3000; // 0.3%
+
+// Test pool on Whirlpool program loaded from fixture.
+export class WhirlpoolPool {
+    ctx: WhirlpoolContext
+    client: WhirlpoolClient
+
+    configKeypair: Keypair
+    whirlpoolKey: PublicKey
+    tokenMintA: PublicKey
+    tokenMintB: PublicKey
+
+    constructor(provider: anchor.AnchorProvider) {
+        this.ctx = WhirlpoolContext.withProvider(provider, ORCA_WHIRLPOOL_PROGRAM_ID);
+        this.client = buildWhirlpoolClient(this.ctx);
+        this.configKeypair = Keypair.generate();
+    }
+
+    getProgramId() {
+        return this.ctx.program.programId
+    }
+
+    async getPool(): Promise<Whirlpool> {
+        return this.client.getPool(this.whirlpoolKey, true);
+    }
+
+    // Creates config, fee tier and pool with price 1 and adds full range liquidity.
+    async create(mintX: PublicKey, mintY: PublicKey, liquidityAmountA: number) {
+        const wallet = this.ctx.wallet.publicKey;
+        [this.tokenMintA, this.tokenMintB] = PoolUtil.orderMints(mintX, mintY).map((mint) => new PublicKey(mint));
+
+        await toTx(this.ctx, WhirlpoolIx.initializeConfigIx(this.ctx.program, {
+            whirlpoolsConfigKeypair: this.configKeypair,
+            feeAuthority: wallet,
+            collectProtocolFeesAuthority: wallet,
+            rewardEmissionsSuperAuthority: wallet,
+            defaultProtocolFeeRate: 300,
+            funder: wallet,
+        })).buildAndExecute();
+
+        await toTx(this.ctx, WhirlpoolIx.initializeFeeTierIx(this.ctx.program, {
+            whirlpoolsConfig: this.configKeypair.publicKey,
+            feeTierPda: PDAUtil.getFeeTier(this.getProgramId(), this.configKeypair.publicKey, WHIRLPOOL_TICK_SPACING),
+            tickSpacing: WHIRLPOOL_TICK_SPACING,
+            defaultFeeRate: WHIRLPOOL_FEE_RATE,
+            feeAuthority: wallet,
+            funder: wallet,
+        })).buildAndExecute();
+
+        const initialTick = PriceMath.priceToInitializableTickIndex(new Decimal(1), 6, 6, WHIRLPOOL_TICK_SPACING);
+        const { poolKey, tx } = await this.client.createPool(
+            this.configKeypair.publicKey,
+            this.tokenMintA,
+            this.tokenMintB,
+            WHIRLPOOL_TICK_SPACING,
+            initialTick,
+            wallet);
+        await tx.buildAndExecute();
+        this.whirlpoolKey = poolKey;
+
+        const pool = await this.getPool();
+        const [tickLower, tickUpper] = TickUtil.getFullRangeTickIndex(WHIRLPOOL_TICK_SPACING);
+        // tick arrays of full range position and three arrays on both sides of current price used by swaps
+        const ticksInArray = TICK_ARRAY_SIZE * WHIRLPOOL_TICK_SPACING;
+        const ticks = [-2, -1, 0, 1, 2].map((i) => initialTick + i * ticksInArray).concat([tickLower, tickUpper]);
+        const tickArraysTx = await pool.initTickArrayForTicks(ticks);
+        if (tickArraysTx) {
+            await tickArraysTx.buildAndExecute();
+        }
+
+        const quote = increaseLiquidityQuoteByInputToken(
+            this.tokenMintA,
+            new Decimal(liquidityAmountA),
+            tickLower,
+            tickUpper,
+            Percentage.fromFraction(1, 100),
+            pool);
+        const { tx: positionTx } = await pool.openPosition(tickLower, tickUpper, quote);
+        await positionTx.buildAndExecute();
+    }
+
+    // Remaining accounts required by Whirlpool swapper
+    //   1. `[writable]` whirlpool Account
+    //   2. `[writable]` token_vault_a Account of whirlpool
+    //   3. `[writable]` token_vault_b Account of whirlpool
+    //   4. `[writable]` tick_array_0 Account, contains current tick
+    //   5. `[writable]` tick_array_1 Account, next in swap direction
+    //   6. `[writable]` tick_array_2 Account, next after tick_array_1
+    //   7. `[]` oracle Account of whirlpool
+    async getSwapRemainingAccounts(sourceMint: PublicKey) {
+        const pool = await this.getPool();
+        const data = pool.getData();
+        const aToB = sourceMint.equals(this.tokenMintA);
+
+        const tickArrays = SwapUtils.getTickArrayPublicKeys(
+            data.tickCurrentIndex,
+            data.tickSpacing,
+            aToB,
+            this.getProgramId(),
+            this.whirlpoolKey);
+
+        return [
+            { pubkey: this.whirlpoolKey, isWritable: true, isSigner: false },
+            { pubkey: data.tokenVaultA, isWritable: true, isSigner: false },
+            { pubkey: data.tokenVaultB, isWritable: true, isSigner: false },
+            ...tickArrays.map((tickArray) => ({ pubkey: tickArray, isWritable: true, isSigner: false })),
+            { pubkey: PDAUtil.getOracle(this.getProgramId(), this.whirlpoolKey).publicKey, isWritable: false, isSigner: false },
+        ];
+    }
+}
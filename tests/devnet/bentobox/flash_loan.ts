@@ -1,6 +1,6 @@
 import * as anchor from '@coral-xyz/anchor';
 import { assert } from "chai";
-import { Blockchain } from './blockchain';
+import { Blockchain } from '../../common/blockchain';
 import { Bentobox as BentoboxProgram } from '../../../target/types/bentobox';
 import { Program, BN } from "@coral-xyz/anchor"
 import { Bentobox } from "../../common/bentobox";
@@ -45,7 +45,6 @@ describe('bentobox devnet', () => {
     await bentobox.createVault(blockchain.token.mint.publicKey, bentoboxOwner);
     // create strategy data account for mint token
     await bentobox.createStrategyData(blockchain.token.mint.publicKey)
-    await bentobox.setStrategyTargetPercentage(new BN(10), blockchain.token.mint.publicKey)
 
     await blockchain.setTokenHost(bentobox.getTotalVaultPda(blockchain.token.mint.publicKey));
     await blockchain.calcAndPrintMetrics();
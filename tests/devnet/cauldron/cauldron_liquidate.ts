@@ -152,7 +152,7 @@ describe("Liquidate", () => {
         await cauldron.approveToCauldron(stanCollateralTokenAccount, stan);
 
         // deposited to bentobox from Stan
-        await cauldronProgram.methods.bentoDeposit(stan.publicKey, new BN(collateralBalance), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(stan.publicKey, new BN(collateralBalance), new BN(0), new BN(0))
             .accounts({
                 fromVault: stanCollateralTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
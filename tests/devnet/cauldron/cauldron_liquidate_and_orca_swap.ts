@@ -236,7 +236,7 @@ describe("Liquidate and Swap Orca", () => {
         await cauldron.approveToCauldron(stanCollateralTokenAccount, stan);
 
         // deposited to bentobox from Stan
-        await cauldronProgram.methods.bentoDeposit(stan.publicKey, new BN(collateralBalance), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(stan.publicKey, new BN(collateralBalance), new BN(0), new BN(0))
             .accounts({
                 fromVault: stanCollateralTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(collateralMint),
@@ -288,6 +288,8 @@ describe("Liquidate and Swap Orca", () => {
         await cauldron.createVault(collateralMint, cauldronOwner);
         await cauldron.createVault(mimMint, cauldronOwner);
         await cauldron.createVault(orcaSolPool.poolTokenMint, cauldronOwner);
+        // register Orca swapper, not whitelisted yet
+        await cauldron.createSwapperWhitelist(swapperOrcaProgram.programId, false, [ORCA_TOKEN_SWAP_ID_DEVNET], cauldronOwner);
 
         let staUserBalance = await cauldronProgram.account.userBalance.fetch(cauldron.getUserBalancePda(stan.publicKey));
         let timestampBeforeLiquidate = Date.now() / 1000;
@@ -300,6 +302,47 @@ describe("Liquidate and Swap Orca", () => {
         assert.ok(bob_liquidator_account.originLiquidator.toString() == bob.publicKey.toString());
         assert.ok(bob_liquidator_account.timestamp.toNumber() < Date.now() / 1000 + COMPLETE_LIQUIDATION_DURATION.toNumber() && timestampBeforeLiquidate < bob_liquidator_account.timestamp.toNumber());
 
+        // swap through not whitelisted swapper
+        try {
+            await cauldronProgram.methods.liquidateSwap()
+                .accounts({
+                    liquidatorAccount: cauldron.getLiquidatorAccountPda(bob.publicKey),
+                    cauldronAccount: cauldron.getCauldronAccount(),
+                    cauldronAuthority: cauldron.getCauldronAuthority(),
+                    collateral: cauldron.collateral,
+                    magicInternetMoneyMint: cauldron.magicInternetMoney,
+                    cauldronSourceVault: cauldron.getTotalVaultPda(cauldron.collateral),
+                    cauldronDestinationVault: cauldron.getTotalVaultPda(cauldron.magicInternetMoney),
+                    swapperProgram: swapperOrcaProgram.programId,
+                    swapperWhitelisted: cauldron.getSwapperWhitelistedPda(swapperOrcaProgram.programId),
+                    swapProgram: ORCA_TOKEN_SWAP_ID_DEVNET,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    authority: bob.publicKey,
+                }).remainingAccounts([
+                    // Orca specific
+                    { pubkey: orcaSolPool.poolAddress, isWritable: false, isSigner: false },
+                    { pubkey: authorityForPoolAddress, isWritable: false, isSigner: false },
+                    { pubkey: orcaSolPool.poolSource, isWritable: true, isSigner: false },
+                    { pubkey: orcaSolPool.poolDestination, isWritable: true, isSigner: false },
+                    { pubkey: orcaSolPool.poolTokenMint, isWritable: true, isSigner: false },
+                    { pubkey: orcaSolPool.feeAccount, isWritable: true, isSigner: false },
+                    { pubkey: cauldron.getTotalVaultPda(orcaSolPool.poolTokenMint), isWritable: true, isSigner: false },
+                    { pubkey: cauldron.collateral, isWritable: false, isSigner: false },
+                    { pubkey: cauldron.magicInternetMoney, isWritable: false, isSigner: false },
+                ]).signers([bob])
+                .rpc({ commitment: "confirmed" })
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "InvalidSwapper");
+            let error = await common.getErrorInfo(cauldron_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        };
+
+        await cauldron.whitelistSwapper(swapperOrcaProgram.programId, true, [ORCA_TOKEN_SWAP_ID_DEVNET], cauldronOwner);
+
         // swap by another liquidator before timestamp
         try {
             await cauldronProgram.methods.liquidateSwap()
@@ -312,6 +355,7 @@ describe("Liquidate and Swap Orca", () => {
                     cauldronSourceVault: cauldron.getTotalVaultPda(cauldron.collateral),
                     cauldronDestinationVault: cauldron.getTotalVaultPda(cauldron.magicInternetMoney),
                     swapperProgram: swapperOrcaProgram.programId,
+                    swapperWhitelisted: cauldron.getSwapperWhitelistedPda(swapperOrcaProgram.programId),
                     swapProgram: ORCA_TOKEN_SWAP_ID_DEVNET,
                     tokenProgram: TOKEN_PROGRAM_ID,
                     authority: stan.publicKey,
@@ -359,6 +403,7 @@ describe("Liquidate and Swap Orca", () => {
             await cauldronProgram.methods.completeLiquidate()
                 .accounts({
                     liquidatorAccount: cauldron.getLiquidatorAccountPda(bob.publicKey),
+                    payer: bob.publicKey,
                     cauldronMimBentoboxBalance: bentobox.getBalancePda(cauldron.cauldronAuthorityPda, cauldron.magicInternetMoney),
                     authorityMimBentoboxBalance: bentobox.getBalancePda(stan.publicKey, cauldron.magicInternetMoney),
                     cauldronAccount: cauldron.getCauldronAccount(),
@@ -389,5 +434,9 @@ describe("Liquidate and Swap Orca", () => {
             bob,
             bob.publicKey,
             bentobox)
+
+        // liquidator account is closed
+        let bobLiquidatorAccountInfo = await connection.getAccountInfo(cauldron.getLiquidatorAccountPda(bob.publicKey));
+        assert.isNull(bobLiquidatorAccountInfo);
     });
 });
@@ -151,7 +151,7 @@ describe("Liquidate and Swap Raydium", () => {
         await cauldron.approveToCauldron(stanCollateralTokenAccount, stan);
 
         // deposited to bentobox from Stan
-        await cauldronProgram.methods.bentoDeposit(stan.publicKey, new u64(collateralBalance), new u64(0))
+        await cauldronProgram.methods.bentoDeposit(stan.publicKey, new u64(collateralBalance), new u64(0), new u64(0))
             .accounts({
                 fromVault: stanCollateralTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(collateralMint),
@@ -205,6 +205,8 @@ describe("Liquidate and Swap Raydium", () => {
         await cauldron.createVault(mimMint, mainOwner);
         await cauldron.createVault(raydiumPoolProperties.lpMint, mainOwner);
 
+        await cauldron.createSwapperWhitelist(swapperRaydiumProgram.programId, true, [raydiumPoolProperties.programId], mainOwner);
+
         await cauldron.beginLiquidate(stan.publicKey, mimBorrowAmount, mainOwner, bentobox);
 
         await cauldron.liquidateSwapRaydium(mainOwner, mainOwner.publicKey, swapperRaydiumProgram.programId, raydiumPoolProperties);
@@ -0,0 +1,135 @@
+import * as anchor from '@coral-xyz/anchor';
+import { assert } from "chai";
+import { Blockchain } from '../../common/blockchain';
+import { Bentobox as BentoboxProgram } from '../../../target/types/bentobox';
+import { StrategyLending as StrategyLendingProgram } from "../../../target/types/strategy_lending";
+import { Program, BN } from "@coral-xyz/anchor"
+import { Bentobox } from "../../common/bentobox";
+import { StrategyLending } from "../../common/lending_strategy";
+import { LENDING_PROGRAM_ID } from "../../common/src/constants";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+import * as common from "../../common/common";
+
+describe('strategy lending devnet', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+
+  const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+  const strategyLendingProgram = anchor.workspace.StrategyLending as Program<StrategyLendingProgram>;
+
+  const bentoboxOwner = Keypair.generate();
+  const strategyOwner = Keypair.generate();
+  const Bob = Keypair.generate();
+
+  let bentobox = new Bentobox();
+  let strategyLending = new StrategyLending();
+  let blockchain: Blockchain = null;
+  let mint: PublicKey = null;
+  let BobTokenAccount: PublicKey = null;
+
+  before(async () => {
+    await common.requestAirdrop(connection, LAMPORTS_PER_SOL, bentoboxOwner)
+    await common.requestAirdrop(connection, LAMPORTS_PER_SOL, strategyOwner)
+    await common.requestAirdrop(connection, LAMPORTS_PER_SOL, Bob)
+
+    blockchain = new Blockchain(connection);
+    await blockchain.initLendingMarket();
+    await blockchain.initReserve(blockchain.token, 100, 40);
+    mint = blockchain.token.mint.publicKey;
+
+    BobTokenAccount = await common.createAndFundUserAccount(
+      Bob.publicKey,
+      blockchain.lendingMarketOwnerKp,
+      blockchain.token.mint,
+      40
+    );
+
+    // create bentobox
+    await bentobox.create(bentoboxOwner);
+    // create total vault for mint
+    await bentobox.createVault(mint, Bob);
+    // create mint Balance account for Bob
+    await bentobox.createBalance(Bob.publicKey, mint, Bob)
+    // create strategy data account for mint token
+    await bentobox.createStrategyData(mint)
+    // whitelist strategy program for bentobox
+    await bentobox.createStrategyProgramWhitelist(strategyLendingProgram.programId)
+    await bentobox.setStrategyDelay(new BN(0));
+
+    await bentobox.deposit(mint, BobTokenAccount, Bob.publicKey, new BN(40), new BN(0), Bob)
+    await strategyLending.initialize(mint, {
+      lendingProgram: LENDING_PROGRAM_ID,
+      reserve: blockchain.token.reserveKp.publicKey,
+      reserveLiquiditySupply: blockchain.token.protocolKp.publicKey,
+      reserveCollateralMint: blockchain.token.lpMintKp.publicKey,
+      lendingMarket: blockchain.lendingMarketKp.publicKey,
+      reserveLiquidityOracle: blockchain.token.pythPricePk,
+    }, bentobox.getBentoboxAccount(), bentoboxProgram.programId, strategyOwner)
+  });
+
+  // run this test on devnet with devnet cluster and devnet wallet
+  it('supply liquidity to reserve', async () => {
+    const lendingAccounts = await strategyLending.getLendingRemainingAccounts();
+
+    const setStrategy = (activate: boolean) => (activate ? bentoboxProgram.methods.activateStrategy() : bentoboxProgram.methods.queueStrategy())
+      .accounts({
+        strategyProgram: strategyLendingProgram.programId,
+        strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyLendingProgram.programId),
+        bentoboxProgram: bentoboxProgram.programId,
+        bentoboxAccount: bentobox.getBentoboxAccount(),
+        strategyAccount: strategyLending.getStrategyLendingAccount(),
+        strategyData: bentobox.getStrategyDataPda(mint),
+        bentoboxVault: bentobox.getTotalVaultPda(mint),
+        strategyVault: strategyLending.getTotalVaultPda(mint),
+        totalData: bentobox.getTotalDataPda(mint),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        baseStrategyInfo: strategyLending.getBaseStrategyInfoAccount(),
+        bentoboxAuthority: bentobox.getBentoboxAuthority(),
+        authority: bentoboxOwner.publicKey,
+        strategyAuthority: strategyLending.getStrategyLendingAuthority(),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(lendingAccounts)
+      .signers([bentoboxOwner]).rpc();
+
+    // set pending strategy, then activate it
+    await setStrategy(false);
+    await setStrategy(true);
+    await bentobox.setStrategyTargetPercentage(new BN(50), mint, strategyLending.getStrategyLendingAccount())
+
+    // harvest with balance moves target percentage of tokens to strategy, which supplies them to reserve
+    await bentoboxProgram.methods.harvest(true, new BN(20), bentobox.getBentoboxAuthorityBump())
+      .accounts({
+        strategyAccount: strategyLending.getStrategyLendingAccount(),
+        bentoboxAccount: bentobox.getBentoboxAccount(),
+        strategyProgram: strategyLendingProgram.programId,
+        strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyLendingProgram.programId),
+        bentoboxProgram: bentoboxProgram.programId,
+        strategyVault: strategyLending.getTotalVaultPda(mint),
+        totalData: bentobox.getTotalDataPda(mint),
+        bentoboxVault: bentobox.getTotalVaultPda(mint),
+        authority: strategyOwner.publicKey,
+        bentoboxAuthority: bentobox.getBentoboxAuthority(),
+        strategyAuthority: strategyLending.getStrategyLendingAuthority(),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        cpiResultAccount: null,
+        strategyData: bentobox.getStrategyDataPda(mint),
+        baseStrategyInfo: strategyLending.getBaseStrategyInfoAccount(),
+      })
+      .remainingAccounts(lendingAccounts)
+      .signers([strategyOwner]).rpc()
+
+    let strategyData = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint));
+    assert.equal(strategyData.balance.toNumber(), 20);
+
+    let collateralVault = await connection.getTokenAccountBalance(strategyLending.getCollateralVaultPda());
+    assert.isTrue(Number(collateralVault.value.amount) > 0);
+
+    let bentoboxVault = await connection.getTokenAccountBalance(bentobox.getTotalVaultPda(mint));
+    assert.equal(bentoboxVault.value.uiAmount, 20);
+  });
+});
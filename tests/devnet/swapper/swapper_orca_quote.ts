@@ -0,0 +1,122 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram, Transaction, sendAndConfirmTransaction } from "@solana/web3.js";
+import { assert } from "chai";
+import * as common from "../../common/common";
+import { SwapperOrca as SwapperOrcaProgram } from "../../../target/types/swapper_orca";
+import { OrcaSolPool } from "../../common/src/constants"
+import { getOrca, OrcaPoolConfig, Network } from "@orca-so/sdk";
+import * as fs from 'fs';
+import Decimal from "decimal.js";
+
+describe("Swapper Orca quote", () => {
+    const provider = common.getAnchorProvider();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const swapperOrcaProgram = anchor.workspace.SwapperOrca as Program<SwapperOrcaProgram>;
+    const swapper_orca_idl = require("../../../target/idl/swapper_orca.json");
+
+    const COLLATERAL_DECIMALS = Math.pow(10, 9);
+
+    let orcaSolPool: OrcaSolPool = new OrcaSolPool();
+    let wallet: Keypair = null;
+
+    // SOL -> ORCA
+    const quoteAccounts = (tokenSwap: PublicKey = orcaSolPool.poolAddress) => [
+        { pubkey: tokenSwap, isWritable: false, isSigner: false },
+        { pubkey: orcaSolPool.poolSource, isWritable: false, isSigner: false },
+        { pubkey: orcaSolPool.poolDestination, isWritable: false, isSigner: false },
+    ];
+
+    async function getReturnedAmount(signature: string): Promise<BN> {
+        const tx = await connection.getTransaction(signature, { commitment: "confirmed" });
+        const [key, , buffer] = common.getLastReturnLog(tx);
+        assert.strictEqual(key, swapperOrcaProgram.programId.toBase58());
+        return new BN(buffer, "le");
+    }
+
+    before(async () => {
+        wallet = Keypair.fromSecretKey(Uint8Array.from(JSON.parse(fs.readFileSync(`${__dirname}/../../wallets/devnet.json`, 'utf8'))));
+    });
+
+    it("Get amount out", async () => {
+        const amountIn = new BN(0.1 * COLLATERAL_DECIMALS);
+
+        const signature = await swapperOrcaProgram.methods.getAmountOut(amountIn)
+            .accounts({ authority: wallet.publicKey })
+            .remainingAccounts(quoteAccounts())
+            .signers([wallet])
+            .rpc({ commitment: "confirmed" });
+
+        const amountOut = await getReturnedAmount(signature);
+
+        const orca = getOrca(connection, Network.DEVNET);
+        const pool = orca.getPool(OrcaPoolConfig.ORCA_SOL);
+        const quote = await pool.getQuote(pool.getTokenB(), new Decimal(0.1));
+
+        assert.strictEqual(amountOut.toString(), quote.getExpectedOutputAmount().toU64().toString());
+    });
+
+    it("Get amount in", async () => {
+        const amountOut = new BN(1000);
+
+        let signature = await swapperOrcaProgram.methods.getAmountIn(amountOut)
+            .accounts({ authority: wallet.publicKey })
+            .remainingAccounts(quoteAccounts())
+            .signers([wallet])
+            .rpc({ commitment: "confirmed" });
+
+        const amountIn = await getReturnedAmount(signature);
+
+        // quoted amount in must buy at least requested amount out
+        signature = await swapperOrcaProgram.methods.getAmountOut(amountIn)
+            .accounts({ authority: wallet.publicKey })
+            .remainingAccounts(quoteAccounts())
+            .signers([wallet])
+            .rpc({ commitment: "confirmed" });
+
+        assert.isTrue((await getReturnedAmount(signature)).gte(amountOut));
+
+        // and one lamport less must not
+        signature = await swapperOrcaProgram.methods.getAmountOut(amountIn.subn(1))
+            .accounts({ authority: wallet.publicKey })
+            .remainingAccounts(quoteAccounts())
+            .signers([wallet])
+            .rpc({ commitment: "confirmed" });
+
+        assert.isTrue((await getReturnedAmount(signature)).lt(amountOut));
+    });
+
+    it("Token swap account not owned by token swap program is rejected", async () => {
+        // zeroed account owned by system program
+        const fakeTokenSwap = Keypair.generate();
+        const space = (await connection.getAccountInfo(orcaSolPool.poolAddress)).data.length;
+        const lamports = await connection.getMinimumBalanceForRentExemption(space);
+        const tx = new Transaction().add(SystemProgram.createAccount({
+            fromPubkey: wallet.publicKey,
+            newAccountPubkey: fakeTokenSwap.publicKey,
+            lamports,
+            space,
+            programId: SystemProgram.programId,
+        }));
+        await sendAndConfirmTransaction(connection, tx, [wallet, fakeTokenSwap]);
+
+        try {
+            await swapperOrcaProgram.methods.getAmountOut(new BN(0.1 * COLLATERAL_DECIMALS))
+                .accounts({ authority: wallet.publicKey })
+                .remainingAccounts(quoteAccounts(fakeTokenSwap.publicKey))
+                .signers([wallet])
+                .rpc({ commitment: "confirmed" });
+            assert.fail("quoted pool of not allowed token swap program");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "InvalidTokenSwapAccount");
+            let error = await common.getErrorInfo(swapper_orca_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+});
@@ -0,0 +1,117 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, AccountMeta } from "@solana/web3.js";
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import * as common from "../../common/common";
+import { SwapperOrca as SwapperOrcaProgram } from "../../../target/types/swapper_orca";
+import { OrcaSolPool, ORCA_TOKEN_SWAP_ID_DEVNET, SOL_TOKEN, ORCA_TOKEN } from "../../common/src/constants"
+import { TOKEN_SWAP_PROGRAM_ID } from "../../common/token_swap";
+import * as fs from 'fs';
+
+describe("Swapper Orca swap", () => {
+    const provider = common.getAnchorProvider();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const swapperOrcaProgram = anchor.workspace.SwapperOrca as Program<SwapperOrcaProgram>;
+
+    const swapper_orca_idl = require("../../../target/idl/swapper_orca.json");
+
+    const COLLATERAL_DECIMALS = Math.pow(10, 9);
+
+    let orcaSolPool: OrcaSolPool = new OrcaSolPool();
+    let wallet: Keypair = null;
+
+    let solToken: Token = null;
+    let orcaToken: Token = null;
+    let walletSolTokenAccount: PublicKey = null;
+    let walletOrcaTokenAccount: PublicKey = null;
+
+    // SOL -> ORCA, pool without host fee account
+    const swapAccounts = (poolSource: PublicKey, poolDestination: PublicKey): AccountMeta[] => [
+        { pubkey: orcaSolPool.poolAddress, isWritable: false, isSigner: false },
+        { pubkey: orcaSolPool.authority, isWritable: false, isSigner: false },
+        { pubkey: poolSource, isWritable: true, isSigner: false },
+        { pubkey: poolDestination, isWritable: true, isSigner: false },
+        { pubkey: orcaSolPool.poolTokenMint, isWritable: true, isSigner: false },
+        { pubkey: orcaSolPool.feeAccount, isWritable: true, isSigner: false },
+        { pubkey: SOL_TOKEN, isWritable: false, isSigner: false },
+        { pubkey: ORCA_TOKEN, isWritable: false, isSigner: false },
+    ];
+
+    async function swap(swapProgram: PublicKey, remainingAccounts: AccountMeta[], amountIn: BN) {
+        await swapperOrcaProgram.methods.swap(amountIn, new BN(1))
+            .accounts({
+                sourceTokenAccount: walletSolTokenAccount,
+                destinationTokenAccount: walletOrcaTokenAccount,
+                swapProgram,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                authority: wallet.publicKey,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([wallet])
+            .rpc({ commitment: "confirmed" });
+    }
+
+    async function assertOrcaError(_err: any, code: string) {
+        assert.isTrue(_err instanceof AnchorError);
+        const err: AnchorError = _err;
+
+        assert.strictEqual(err.error.errorCode.code, code);
+        let error = await common.getErrorInfo(swapper_orca_idl, err.error.errorCode.code);
+        assert.strictEqual(err.error.errorMessage, error.errorMsg);
+        assert.strictEqual(err.error.errorCode.number, error.errorCode);
+    }
+
+    before(async () => {
+        wallet = Keypair.fromSecretKey(Uint8Array.from(JSON.parse(fs.readFileSync(`${__dirname}/../../wallets/devnet.json`, 'utf8'))));
+
+        solToken = new Token(connection, SOL_TOKEN, TOKEN_PROGRAM_ID, wallet);
+        orcaToken = new Token(connection, ORCA_TOKEN, TOKEN_PROGRAM_ID, wallet);
+
+        walletSolTokenAccount = await Token.createWrappedNativeAccount(connection, TOKEN_PROGRAM_ID, wallet.publicKey, wallet, 0.1 * COLLATERAL_DECIMALS);
+        walletOrcaTokenAccount = (await orcaToken.getOrCreateAssociatedAccountInfo(wallet.publicKey)).address;
+    });
+
+    it("Swap without host fee account", async () => {
+        const amountIn = new BN(0.01 * COLLATERAL_DECIMALS);
+
+        const solBefore = (await solToken.getAccountInfo(walletSolTokenAccount)).amount;
+        const orcaBefore = (await orcaToken.getAccountInfo(walletOrcaTokenAccount)).amount;
+
+        await swap(ORCA_TOKEN_SWAP_ID_DEVNET, swapAccounts(orcaSolPool.poolSource, orcaSolPool.poolDestination), amountIn);
+
+        const solAfter = (await solToken.getAccountInfo(walletSolTokenAccount)).amount;
+        const orcaAfter = (await orcaToken.getAccountInfo(walletOrcaTokenAccount)).amount;
+
+        assert.strictEqual(solBefore.sub(solAfter).toString(), amountIn.toString());
+        assert.isTrue(orcaAfter.gt(orcaBefore));
+    });
+
+    it("Swap with pool accounts in wrong order", async () => {
+        try {
+            await swap(ORCA_TOKEN_SWAP_ID_DEVNET, swapAccounts(orcaSolPool.poolDestination, orcaSolPool.poolSource), new BN(1000));
+        } catch (_err) {
+            await assertOrcaError(_err, "InvalidPoolTokenAccount");
+        }
+    });
+
+    it("Swap with not allowed swap program", async () => {
+        try {
+            await swap(swapperOrcaProgram.programId, swapAccounts(orcaSolPool.poolSource, orcaSolPool.poolDestination), new BN(1000));
+            assert.fail("swapped through not allowed swap program");
+        } catch (_err) {
+            await assertOrcaError(_err, "InvalidSwapProgram");
+        }
+    });
+
+    it("Swap with token swap of another program", async () => {
+        try {
+            await swap(TOKEN_SWAP_PROGRAM_ID, swapAccounts(orcaSolPool.poolSource, orcaSolPool.poolDestination), new BN(1000));
+            assert.fail("swapped with token swap of another program");
+        } catch (_err) {
+            await assertOrcaError(_err, "InvalidTokenSwapAccount");
+        }
+    });
+});
@@ -0,0 +1,108 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram, Transaction, sendAndConfirmTransaction } from "@solana/web3.js";
+import { assert } from "chai";
+import * as common from "../../common/common";
+import * as raydium from "../../common/src/util/raydium_util"
+import { SwapperRaydium as SwapperRaydiumProgram } from "../../../target/types/swapper_raydium";
+import * as fs from 'fs';
+
+describe("Swapper Raydium quote", () => {
+    const provider = common.getAnchorProvider();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const swapperRaydiumProgram = anchor.workspace.SwapperRaydium as Program<SwapperRaydiumProgram>;
+    const swapper_raydium_idl = require("../../../target/idl/swapper_raydium.json");
+
+    const rayUsdcPool = new PublicKey("ELSGBb45rAQNsMTVzwjUqL8vBophWhPn4rNbqwxenmqY");
+    // size of Raydium AMM v4 `AmmInfo` account
+    const AMM_INFO_LEN = 752;
+
+    const COLLATERAL_DECIMALS = Math.pow(10, 6);
+
+    let poolKeys: any = null;
+    let wallet: Keypair = null;
+
+    // RAY -> USDC
+    const quoteAccounts = (amm: PublicKey = rayUsdcPool) => [
+        { pubkey: amm, isWritable: false, isSigner: false },
+        { pubkey: poolKeys.openOrders, isWritable: false, isSigner: false },
+        { pubkey: poolKeys.baseVault, isWritable: false, isSigner: false },
+        { pubkey: poolKeys.quoteVault, isWritable: false, isSigner: false },
+    ];
+
+    async function getReturnedAmount(signature: string): Promise<BN> {
+        const tx = await connection.getTransaction(signature, { commitment: "confirmed" });
+        const [key, , buffer] = common.getLastReturnLog(tx);
+        assert.strictEqual(key, swapperRaydiumProgram.programId.toBase58());
+        return new BN(buffer, "le");
+    }
+
+    async function getAmountOut(amountIn: BN, amm: PublicKey = rayUsdcPool): Promise<BN> {
+        const signature = await swapperRaydiumProgram.methods.getAmountOut(amountIn)
+            .accounts({ authority: wallet.publicKey })
+            .remainingAccounts(quoteAccounts(amm))
+            .signers([wallet])
+            .rpc({ commitment: "confirmed" });
+
+        return await getReturnedAmount(signature);
+    }
+
+    before(async () => {
+        wallet = Keypair.fromSecretKey(Uint8Array.from(JSON.parse(fs.readFileSync(`${__dirname}/../../wallets/devnet.json`, 'utf8'))));
+        poolKeys = await raydium.fetchPoolKeys(connection, rayUsdcPool);
+    });
+
+    it("Get amount out", async () => {
+        const amountOut = await getAmountOut(new BN(COLLATERAL_DECIMALS));
+
+        const poolDestination = await connection.getTokenAccountBalance(poolKeys.quoteVault);
+        assert.isTrue(amountOut.gtn(0));
+        assert.isTrue(amountOut.lt(new BN(poolDestination.value.amount)));
+    });
+
+    it("Get amount in", async () => {
+        const amountOut = new BN(1000);
+
+        const signature = await swapperRaydiumProgram.methods.getAmountIn(amountOut)
+            .accounts({ authority: wallet.publicKey })
+            .remainingAccounts(quoteAccounts())
+            .signers([wallet])
+            .rpc({ commitment: "confirmed" });
+
+        const amountIn = await getReturnedAmount(signature);
+
+        // quoted amount in must buy at least requested amount out
+        assert.isTrue((await getAmountOut(amountIn)).gte(amountOut));
+        // and one token less must not
+        assert.isTrue((await getAmountOut(amountIn.subn(1))).lt(amountOut));
+    });
+
+    it("Amm account not owned by Raydium AMM program is rejected", async () => {
+        // zeroed account with `AmmInfo` size owned by system program
+        const fakeAmm = Keypair.generate();
+        const lamports = await connection.getMinimumBalanceForRentExemption(AMM_INFO_LEN);
+        const tx = new Transaction().add(SystemProgram.createAccount({
+            fromPubkey: wallet.publicKey,
+            newAccountPubkey: fakeAmm.publicKey,
+            lamports,
+            space: AMM_INFO_LEN,
+            programId: SystemProgram.programId,
+        }));
+        await sendAndConfirmTransaction(connection, tx, [wallet, fakeAmm]);
+
+        try {
+            await getAmountOut(new BN(COLLATERAL_DECIMALS), fakeAmm.publicKey);
+            assert.fail("quoted pool of not Raydium amm");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "InvalidAmmAccount");
+            let error = await common.getErrorInfo(swapper_raydium_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+});
@@ -0,0 +1,116 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, AccountMeta } from "@solana/web3.js";
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import * as common from "../../common/common";
+import * as raydium from "../../common/src/util/raydium_util"
+import { SwapperRaydium as SwapperRaydiumProgram } from "../../../target/types/swapper_raydium";
+import * as fs from 'fs';
+
+describe("Swapper Raydium swap", () => {
+    const provider = common.getAnchorProvider();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const swapperRaydiumProgram = anchor.workspace.SwapperRaydium as Program<SwapperRaydiumProgram>;
+
+    const swapper_raydium_idl = require("../../../target/idl/swapper_raydium.json");
+
+    const rayMintKey = new PublicKey("FSRvxBNrQWX2Fy2qvKMLL3ryEdRtE3PUTZBcdKwASZTU");
+    const usdcMintKey = new PublicKey("BEcGFQK1T1tSu3kvHC17cyCkQ5dvXqAJ7ExB2bb5Do7a");
+
+    const rayUsdcPool = new PublicKey("ELSGBb45rAQNsMTVzwjUqL8vBophWhPn4rNbqwxenmqY");
+
+    // How to airdrop RAY and USDC on devnet see https://sdk.alphadefi.info/guides/swap
+    const COLLATERAL_DECIMALS = Math.pow(10, 6);
+
+    let poolKeys: any = null;
+    let wallet: Keypair = null;
+
+    let rayToken: Token = null;
+    let usdcToken: Token = null;
+    let walletRayTokenAccount: PublicKey = null;
+    let walletUsdcTokenAccount: PublicKey = null;
+
+    // RAY -> USDC, pool without orderbook accounts
+    const swapV2Accounts = (): AccountMeta[] => [
+        { pubkey: poolKeys.id, isWritable: true, isSigner: false },
+        { pubkey: poolKeys.authority, isWritable: false, isSigner: false },
+        { pubkey: poolKeys.baseVault, isWritable: true, isSigner: false },
+        { pubkey: poolKeys.quoteVault, isWritable: true, isSigner: false },
+    ];
+
+    async function swap(remainingAccounts: AccountMeta[], amountIn: BN, swapProgram: PublicKey = poolKeys.programId) {
+        await swapperRaydiumProgram.methods.swap(amountIn, new BN(1))
+            .accounts({
+                sourceTokenAccount: walletRayTokenAccount,
+                destinationTokenAccount: walletUsdcTokenAccount,
+                swapProgram,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                authority: wallet.publicKey,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([wallet])
+            .rpc({ commitment: "confirmed" });
+    }
+
+    before(async () => {
+        wallet = Keypair.fromSecretKey(Uint8Array.from(JSON.parse(fs.readFileSync(`${__dirname}/../../wallets/devnet.json`, 'utf8'))));
+        poolKeys = await raydium.fetchPoolKeys(connection, rayUsdcPool);
+
+        rayToken = new Token(connection, rayMintKey, TOKEN_PROGRAM_ID, wallet);
+        usdcToken = new Token(connection, usdcMintKey, TOKEN_PROGRAM_ID, wallet);
+
+        walletRayTokenAccount = (await rayToken.getOrCreateAssociatedAccountInfo(wallet.publicKey)).address;
+        walletUsdcTokenAccount = (await usdcToken.getOrCreateAssociatedAccountInfo(wallet.publicKey)).address;
+    });
+
+    it("Swap without orderbook accounts", async () => {
+        const amountIn = new BN(0.1 * COLLATERAL_DECIMALS);
+
+        const rayBefore = (await rayToken.getAccountInfo(walletRayTokenAccount)).amount;
+        const usdcBefore = (await usdcToken.getAccountInfo(walletUsdcTokenAccount)).amount;
+
+        await swap(swapV2Accounts(), amountIn);
+
+        const rayAfter = (await rayToken.getAccountInfo(walletRayTokenAccount)).amount;
+        const usdcAfter = (await usdcToken.getAccountInfo(walletUsdcTokenAccount)).amount;
+
+        assert.strictEqual(rayBefore.sub(rayAfter).toString(), amountIn.toString());
+        assert.isTrue(usdcAfter.gt(usdcBefore));
+    });
+
+    it("Swap with number of remaining accounts of neither instruction", async () => {
+        try {
+            await swap([
+                ...swapV2Accounts(),
+                { pubkey: poolKeys.openOrders, isWritable: true, isSigner: false },
+            ], new BN(1000));
+            assert.fail("swapped with 5 remaining accounts");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "NotEnoughRemainingAccounts");
+            let error = await common.getErrorInfo(swapper_raydium_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+
+    it("Swap through program which is not Raydium amm", async () => {
+        try {
+            await swap(swapV2Accounts(), new BN(1000), swapperRaydiumProgram.programId);
+            assert.fail("swapped through not Raydium amm program");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "InvalidSwapProgram");
+            let error = await common.getErrorInfo(swapper_raydium_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+});
@@ -0,0 +1,255 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { Cauldron as CauldronProgram } from "../../target/types/cauldron";
+import { Bentobox as BentoboxProgram } from "../../target/types/bentobox";
+import { SwapperOrca as SwapperOrcaProgram } from "../../target/types/swapper_orca";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import * as common from "../common/common";
+
+import { TEST_PRICE, COLLATERIZATION_RATE_PRECISION, COMPLETE_LIQUIDATION_DURATION } from "../common/src";
+import { Bentobox } from "../common/bentobox"
+import { Cauldron } from "../common/cauldron"
+import { TokenSwapPool } from "../common/amm_strategy";
+import { createTokenSwapPool, TOKEN_SWAP_PROGRAM_ID } from "../common/token_swap";
+
+// Cauldron must be built with `localnet` feature, switchboard price is mocked to 12.5005 collateral per MIM.
+// Pool oracle in `Primary` mode prices collateral twice lower, which makes Stan and Dan positions insolvent.
+describe("Cancel Liquidate localnet", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+    const cauldronProgram = anchor.workspace.Cauldron as Program<CauldronProgram>;
+    const swapperOrcaProgram = anchor.workspace.SwapperOrca as Program<SwapperOrcaProgram>;
+
+    const cauldron_idl = require("../../target/idl/cauldron.json");
+
+    const collateralAmount = new BN(500_000);
+    // solvent with switchboard price, insolvent with pool price
+    const mimBorrowAmount = new BN(3000);
+
+    let mimMint: Token = null;
+    let collateralMint: Token = null;
+    let pool: TokenSwapPool = null;
+
+    const bentoboxOwner = Keypair.generate();
+    const cauldronOwner = Keypair.generate();
+    const stan = Keypair.generate();
+    const dan = Keypair.generate();
+    const bob = Keypair.generate();
+    const carol = Keypair.generate();
+
+    let cauldron = new Cauldron();
+    let bentobox = new Bentobox();
+
+    let cauldronAuthorityPda: PublicKey = null;
+    let cauldronMimBentoboxBalance: PublicKey = null;
+    let cauldronCollateralBentoboxBalance: PublicKey = null;
+
+    async function assertError(promise: Promise<any>, code: string) {
+        try {
+            await promise;
+            assert.fail(`Expected ${code}`);
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, code);
+            let error = await common.getErrorInfo(cauldron_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    }
+
+    // deposits collateral of `user` to bentobox, adds it to cauldron and borrows MIM
+    async function openPosition(user: Keypair) {
+        const collateralTokenAccount = await common.createAndFundUserAccount(user.publicKey, cauldronOwner, collateralMint, collateralAmount.toNumber());
+
+        // create MIM Balance account for user on Bentobox
+        await bentobox.createBalance(user.publicKey, mimMint.publicKey, user);
+        // create collateral Balance account for user on Bentobox
+        await bentobox.createBalance(user.publicKey, collateralMint.publicKey, user);
+        // create user balance on cauldron
+        await cauldron.createUserBalance(user.publicKey, user);
+        // create bentobox approval account for user
+        await bentobox.createMasterContractApproval(user, cauldron.getCauldronAccount(), cauldron.getCauldronProgram());
+
+        await cauldron.approveToCauldron(collateralTokenAccount, user);
+        await cauldronProgram.methods.bentoDeposit(user.publicKey, collateralAmount, new BN(0), new BN(0))
+            .accounts({
+                fromVault: collateralTokenAccount,
+                bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
+                bentoboxToBalance: bentobox.getBalancePda(user.publicKey, collateralMint.publicKey),
+                bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                bentoboxProgram: bentoboxProgram.programId,
+                cauldronAccount: cauldron.getCauldronAccount(),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                bentoboxStrategyData: bentobox.getStrategyDataPda(collateralMint.publicKey),
+                mint: collateralMint.publicKey,
+                cauldronAuthority: cauldronAuthorityPda,
+                masterContractApproved: bentobox.getMasterContractApprovedPda(user.publicKey, cauldron.getCauldronAccount()),
+                masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()),
+                authority: user.publicKey,
+            })
+            .signers([user])
+            .rpc();
+
+        await cauldronProgram.methods.addCollateral(user.publicKey, collateralAmount, false)
+            .accounts({
+                userBalance: cauldron.getUserBalancePda(user.publicKey),
+                cauldronAccount: cauldron.getCauldronAccount(),
+                totalData: cauldron.totalDataPda,
+                cauldronBentoboxBalance: cauldronCollateralBentoboxBalance,
+                authority: user.publicKey,
+                cauldronAuthority: cauldronAuthorityPda
+            }).remainingAccounts([
+                { pubkey: collateralMint.publicKey, isWritable: false, isSigner: false },
+                { pubkey: bentobox.getBalancePda(user.publicKey, collateralMint.publicKey), isWritable: true, isSigner: false },
+                { pubkey: bentoboxProgram.programId, isWritable: false, isSigner: false },
+                { pubkey: bentobox.getBentoboxAccount(), isWritable: false, isSigner: false },
+                { pubkey: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()), isWritable: false, isSigner: false },
+                { pubkey: bentobox.getMasterContractApprovedPda(user.publicKey, cauldron.getCauldronAccount()), isWritable: false, isSigner: false },
+            ])
+            .signers([user])
+            .rpc();
+
+        await cauldron.borrow(user.publicKey,
+            mimBorrowAmount,
+            user,
+            cauldronMimBentoboxBalance,
+            bentobox.getTotalDataPda(mimMint.publicKey),
+            bentobox.getBalancePda(user.publicKey, mimMint.publicKey),
+            bentoboxProgram.programId);
+    }
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, cauldronOwner, stan, dan, bob, carol]);
+
+        // both mints have the same authority to fund the pool
+        mimMint = await common.createMintAccount(connection, cauldronOwner, cauldronOwner.publicKey, 0);
+        collateralMint = await common.createMintAccount(connection, cauldronOwner, cauldronOwner.publicKey, 0);
+
+        const bentoboxOwnerMimTokenAccount = await common.createAndFundUserAccount(bentoboxOwner.publicKey, cauldronOwner, mimMint, 10000);
+
+        // pool price is 25.001 collateral per MIM, twice switchboard price
+        [pool] = await createTokenSwapPool(connection, cauldronOwner, mimMint, collateralMint, cauldronOwner, 1_000_000, 25_001_000);
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for MIM
+        await bentobox.createVault(mimMint.publicKey, cauldronOwner);
+        // create total vault for collateral
+        await bentobox.createVault(collateralMint.publicKey, bentoboxOwner);
+        // create MIM Balance account for Carol on Bentobox, receives liquidation profit
+        await bentobox.createBalance(carol.publicKey, mimMint.publicKey, carol);
+        // create strategy data account for MIM token
+        await bentobox.createStrategyData(mimMint.publicKey);
+        // create strategy data account for collateral token
+        await bentobox.createStrategyData(collateralMint.publicKey);
+
+        // initialize cauldron account
+        await cauldron.initialize(cauldronOwner, mimMint.publicKey, collateralMint.publicKey, TEST_PRICE, bentobox.getBentoboxAccount(), COLLATERIZATION_RATE_PRECISION, new BN(1000000))
+        // create cauldron total vault
+        await cauldron.createTotal(cauldronOwner);
+
+        // create MIM Balance account for cauldron on Bentobox
+        await bentobox.createBalance(cauldron.getCauldronAuthority(), mimMint.publicKey, cauldronOwner)
+        // create collateral Balance account for cauldron on Bentobox
+        await bentobox.createBalance(cauldron.getCauldronAuthority(), collateralMint.publicKey, cauldronOwner)
+
+        cauldronAuthorityPda = cauldron.getCauldronAuthority()
+        cauldronMimBentoboxBalance = bentobox.getBalancePda(cauldronAuthorityPda, mimMint.publicKey)
+        cauldronCollateralBentoboxBalance = bentobox.getBalancePda(cauldronAuthorityPda, collateralMint.publicKey)
+
+        // deposited to cauldron balance account on bentobox
+        await bentobox.deposit(mimMint.publicKey, bentoboxOwnerMimTokenAccount, cauldronAuthorityPda, new BN(10000), new BN(0), bentoboxOwner)
+
+        //register cauldron to bentobox as master contract
+        await bentobox.createMasterContractWhitelist(cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+
+        // cauldron vaults used for swap
+        await cauldron.createVault(collateralMint.publicKey, cauldronOwner);
+        await cauldron.createVault(mimMint.publicKey, cauldronOwner);
+        // whitelist orca swapper which swaps through spl-token-swap pool and quotes its spot price
+        await cauldron.createSwapperWhitelist(swapperOrcaProgram.programId, true, [TOKEN_SWAP_PROGRAM_ID], cauldronOwner);
+
+        // borrow with switchboard price, pool oracle isn't created yet
+        await openPosition(stan);
+        await openPosition(dan);
+
+        // MIM -> collateral
+        const poolOracleAccounts = [pool.tokenSwap, pool.swapTokenA, pool.swapTokenB];
+        await cauldron.createPoolOracle(swapperOrcaProgram.programId, poolOracleAccounts, { primary: {} },
+            new BN(10000), new BN(2), new BN(600), cauldronOwner);
+
+        const poolOracleAccountMetas = poolOracleAccounts.map(pubkey => ({ pubkey, isWritable: false, isSigner: false }));
+        await cauldron.updatePoolOraclePrice(swapperOrcaProgram.programId, poolOracleAccountMetas, bob);
+        await common.pause(3 * 1000);
+        await cauldron.updatePoolOraclePrice(swapperOrcaProgram.programId, poolOracleAccountMetas, bob);
+    });
+
+    let stanUserBalanceBefore = null;
+
+    it("Cancel liquidation before timestamp", async () => {
+        stanUserBalanceBefore = await cauldronProgram.account.userBalance.fetch(cauldron.getUserBalancePda(stan.publicKey));
+
+        await cauldron.beginLiquidate(stan.publicKey, mimBorrowAmount, bob, bentobox);
+
+        let liquidatorAccount = await cauldronProgram.account.liquidatorAccount.fetch(cauldron.getLiquidatorAccountPda(bob.publicKey));
+        assert.ok(liquidatorAccount.user.equals(stan.publicKey));
+        assert.ok(liquidatorAccount.payer.equals(bob.publicKey));
+        assert.ok(liquidatorAccount.borrowPart.gtn(0));
+        assert.strictEqual(liquidatorAccount.realAmount.toString(), "0");
+
+        await assertError(cauldron.cancelLiquidate(carol, bob.publicKey, stan.publicKey, bentobox), "LiquidationNotExpired");
+    });
+
+    it("Cancel swapped liquidation", async () => {
+        await cauldron.beginLiquidate(dan.publicKey, mimBorrowAmount, carol, bentobox);
+        await cauldron.liquidateSwapOrca(carol, carol.publicKey, swapperOrcaProgram.programId, TOKEN_SWAP_PROGRAM_ID,
+            pool.tokenSwap, pool.swapAuthority, pool.swapTokenB, pool.swapTokenA, pool.poolMint, pool.poolFeeAccount, false);
+
+        let liquidatorAccount = await cauldronProgram.account.liquidatorAccount.fetch(cauldron.getLiquidatorAccountPda(carol.publicKey));
+        assert.ok(liquidatorAccount.realAmount.gtn(0));
+
+        await assertError(cauldron.cancelLiquidate(bob, carol.publicKey, dan.publicKey, bentobox), "LiquidationAlreadySwapped");
+
+        // swapped liquidation is completed instead
+        await cauldron.completeLiquidate(carol, carol.publicKey, bentobox);
+        assert.isNull(await connection.getAccountInfo(cauldron.getLiquidatorAccountPda(carol.publicKey)));
+        let carolMimBalance = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(carol.publicKey, mimMint.publicKey));
+        assert.ok(carolMimBalance.amount.gtn(0));
+    });
+
+    it("Cancel liquidation after timestamp", async () => {
+        await common.pause((COMPLETE_LIQUIDATION_DURATION.toNumber() + 2) * 1000);
+
+        const bobLamportsBefore = await connection.getBalance(bob.publicKey);
+
+        let listener = null;
+        let [event, _slot] = await new Promise((resolve, _reject) => {
+            listener = cauldronProgram.addEventListener("LogCancelLiquidate", (event, slot) => {
+                resolve([event, slot]);
+            });
+
+            cauldron.cancelLiquidate(carol, bob.publicKey, stan.publicKey, bentobox);
+        });
+        await cauldronProgram.removeEventListener(listener);
+
+        assert.ok(event.user.equals(stan.publicKey));
+        assert.ok(event.liquidator.equals(bob.publicKey));
+
+        // collateral and borrow part are returned to Stan position
+        let stanUserBalance = await cauldronProgram.account.userBalance.fetch(cauldron.getUserBalancePda(stan.publicKey));
+        assert.strictEqual(stanUserBalance.collateralShare.toString(), stanUserBalanceBefore.collateralShare.toString());
+        assert.strictEqual(stanUserBalance.borrowPart.toString(), stanUserBalanceBefore.borrowPart.toString());
+
+        // liquidator account is closed and rent is returned to Bob
+        assert.isNull(await connection.getAccountInfo(cauldron.getLiquidatorAccountPda(bob.publicKey)));
+        assert.isAbove(await connection.getBalance(bob.publicKey), bobLamportsBefore);
+    });
+});
@@ -0,0 +1,249 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { Cauldron as CauldronProgram } from "../../target/types/cauldron";
+import { Bentobox as BentoboxProgram } from "../../target/types/bentobox";
+import { SwapperOrca as SwapperOrcaProgram } from "../../target/types/swapper_orca";
+import { AccountMeta, PublicKey, Keypair } from "@solana/web3.js";
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import * as common from "../common/common";
+
+import { TEST_PRICE, COLLATERIZATION_RATE_PRECISION } from "../common/src";
+import { Bentobox } from "../common/bentobox"
+import { Cauldron } from "../common/cauldron"
+import { getCauldronLiquidatorAccountAddress } from "../common/cauldron_pda_helper";
+import { TokenSwapPool } from "../common/amm_strategy";
+import { createTokenSwapPool, getOrcaSwapRemainingAccounts, TOKEN_SWAP_PROGRAM_ID } from "../common/token_swap";
+
+// Cauldron must be built with `localnet` feature, switchboard price is mocked to 12.5005 collateral per MIM.
+// Pool oracle in `Primary` mode prices collateral twice lower, which makes Stan position insolvent.
+describe("Flash Liquidate with spl-token-swap pool", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+    const cauldronProgram = anchor.workspace.Cauldron as Program<CauldronProgram>;
+    const swapperOrcaProgram = anchor.workspace.SwapperOrca as Program<SwapperOrcaProgram>;
+
+    const cauldron_idl = require("../../target/idl/cauldron.json");
+
+    const collateralAmount = new BN(500_000);
+    // solvent with switchboard price, insolvent with pool price
+    const mimBorrowAmount = new BN(3000);
+
+    let mimMint: Token = null;
+    let collateralMint: Token = null;
+    let pool: TokenSwapPool = null;
+
+    const bentoboxOwner = Keypair.generate();
+    const cauldronOwner = Keypair.generate();
+    const stan = Keypair.generate();
+    const bob = Keypair.generate();
+
+    let cauldron = new Cauldron();
+    let bentobox = new Bentobox();
+
+    let cauldronAuthorityPda: PublicKey = null;
+    let cauldronMimBentoboxBalance: PublicKey = null;
+    let cauldronCollateralBentoboxBalance: PublicKey = null;
+
+    let swapAccounts: AccountMeta[] = null;
+
+    const flashLiquidate = () => cauldron.flashLiquidate(stan.publicKey, mimBorrowAmount, bob, bentobox,
+        swapperOrcaProgram.programId, TOKEN_SWAP_PROGRAM_ID, swapAccounts);
+
+    async function assertError(promise: Promise<any>, code: string) {
+        try {
+            await promise;
+            assert.fail(`Expected ${code}`);
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, code);
+            let error = await common.getErrorInfo(cauldron_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    }
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, cauldronOwner, stan, bob]);
+
+        // both mints have the same authority to fund the pool
+        mimMint = await common.createMintAccount(connection, cauldronOwner, cauldronOwner.publicKey, 0);
+        collateralMint = await common.createMintAccount(connection, cauldronOwner, cauldronOwner.publicKey, 0);
+
+        const bentoboxOwnerMimTokenAccount = await common.createAndFundUserAccount(bentoboxOwner.publicKey, cauldronOwner, mimMint, 10000);
+        const stanCollateralTokenAccount = await common.createAndFundUserAccount(stan.publicKey, cauldronOwner, collateralMint, collateralAmount.toNumber());
+
+        // pool price is 25.001 collateral per MIM, twice switchboard price
+        [pool] = await createTokenSwapPool(connection, cauldronOwner, mimMint, collateralMint, cauldronOwner, 1_000_000, 25_001_000);
+        swapAccounts = getOrcaSwapRemainingAccounts(pool, collateralMint.publicKey, mimMint.publicKey, false);
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for MIM
+        await bentobox.createVault(mimMint.publicKey, cauldronOwner);
+        // create total vault for collateral
+        await bentobox.createVault(collateralMint.publicKey, bentoboxOwner);
+        // create MIM Balance account for Stan on Bentobox
+        await bentobox.createBalance(stan.publicKey, mimMint.publicKey, stan);
+        // create collateral Balance account for Stan on Bentobox
+        await bentobox.createBalance(stan.publicKey, collateralMint.publicKey, stan);
+        // create MIM Balance account for Bob on Bentobox, receives liquidation profit
+        await bentobox.createBalance(bob.publicKey, mimMint.publicKey, bob);
+        // create strategy data account for MIM token
+        await bentobox.createStrategyData(mimMint.publicKey);
+        // create strategy data account for collateral token
+        await bentobox.createStrategyData(collateralMint.publicKey);
+
+        // initialize cauldron account
+        await cauldron.initialize(cauldronOwner, mimMint.publicKey, collateralMint.publicKey, TEST_PRICE, bentobox.getBentoboxAccount(), COLLATERIZATION_RATE_PRECISION, new BN(1000000))
+        // create cauldron total vault
+        await cauldron.createTotal(cauldronOwner);
+        // create user balance for Stan on cauldron
+        await cauldron.createUserBalance(stan.publicKey, stan)
+
+        // create MIM Balance account for cauldron on Bentobox
+        await bentobox.createBalance(cauldron.getCauldronAuthority(), mimMint.publicKey, cauldronOwner)
+        // create collateral Balance account for cauldron on Bentobox
+        await bentobox.createBalance(cauldron.getCauldronAuthority(), collateralMint.publicKey, cauldronOwner)
+
+        cauldronAuthorityPda = cauldron.getCauldronAuthority()
+        cauldronMimBentoboxBalance = bentobox.getBalancePda(cauldronAuthorityPda, mimMint.publicKey)
+        cauldronCollateralBentoboxBalance = bentobox.getBalancePda(cauldronAuthorityPda, collateralMint.publicKey)
+
+        // deposited to cauldron balance account on bentobox
+        await bentobox.deposit(mimMint.publicKey, bentoboxOwnerMimTokenAccount, cauldronAuthorityPda, new BN(10000), new BN(0), bentoboxOwner)
+
+        //register cauldron to bentobox as master contract
+        await bentobox.createMasterContractWhitelist(cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+        // create bentobox approval account for Stan
+        await bentobox.createMasterContractApproval(stan, cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+
+        // cauldron vaults used for swap
+        await cauldron.createVault(collateralMint.publicKey, cauldronOwner);
+        await cauldron.createVault(mimMint.publicKey, cauldronOwner);
+        // whitelist orca swapper which swaps through spl-token-swap pool and quotes its spot price
+        await cauldron.createSwapperWhitelist(swapperOrcaProgram.programId, true, [TOKEN_SWAP_PROGRAM_ID], cauldronOwner);
+
+        // deposit collateral to bentobox and add it to cauldron
+        await cauldron.approveToCauldron(stanCollateralTokenAccount, stan);
+        await cauldronProgram.methods.bentoDeposit(stan.publicKey, collateralAmount, new BN(0), new BN(0))
+            .accounts({
+                fromVault: stanCollateralTokenAccount,
+                bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
+                bentoboxToBalance: bentobox.getBalancePda(stan.publicKey, collateralMint.publicKey),
+                bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                bentoboxProgram: bentoboxProgram.programId,
+                cauldronAccount: cauldron.getCauldronAccount(),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                bentoboxStrategyData: bentobox.getStrategyDataPda(collateralMint.publicKey),
+                mint: collateralMint.publicKey,
+                cauldronAuthority: cauldronAuthorityPda,
+                masterContractApproved: bentobox.getMasterContractApprovedPda(stan.publicKey, cauldron.getCauldronAccount()),
+                masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()),
+                authority: stan.publicKey,
+            })
+            .signers([stan])
+            .rpc();
+
+        await cauldronProgram.methods.addCollateral(stan.publicKey, collateralAmount, false)
+            .accounts({
+                userBalance: cauldron.getUserBalancePda(stan.publicKey),
+                cauldronAccount: cauldron.getCauldronAccount(),
+                totalData: cauldron.totalDataPda,
+                cauldronBentoboxBalance: cauldronCollateralBentoboxBalance,
+                authority: stan.publicKey,
+                cauldronAuthority: cauldronAuthorityPda
+            }).remainingAccounts([
+                { pubkey: collateralMint.publicKey, isWritable: false, isSigner: false },
+                { pubkey: bentobox.getBalancePda(stan.publicKey, collateralMint.publicKey), isWritable: true, isSigner: false },
+                { pubkey: bentoboxProgram.programId, isWritable: false, isSigner: false },
+                { pubkey: bentobox.getBentoboxAccount(), isWritable: false, isSigner: false },
+                { pubkey: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()), isWritable: false, isSigner: false },
+                { pubkey: bentobox.getMasterContractApprovedPda(stan.publicKey, cauldron.getCauldronAccount()), isWritable: false, isSigner: false },
+            ])
+            .signers([stan])
+            .rpc();
+
+        // borrow with switchboard price, pool oracle isn't created yet
+        await cauldron.borrow(stan.publicKey,
+            mimBorrowAmount,
+            stan,
+            cauldronMimBentoboxBalance,
+            bentobox.getTotalDataPda(mimMint.publicKey),
+            bentobox.getBalancePda(stan.publicKey, mimMint.publicKey),
+            bentoboxProgram.programId);
+    });
+
+    it("Flash liquidate solvent position", async () => {
+        await assertError(flashLiquidate(), "UserIsSolvent");
+    });
+
+    it("Flash liquidate through swap program not allowed for swapper", async () => {
+        // swapper would forward cauldron authority signature to this program
+        await assertError(cauldron.flashLiquidate(stan.publicKey, mimBorrowAmount, bob, bentobox,
+            swapperOrcaProgram.programId, TOKEN_PROGRAM_ID, swapAccounts), "InvalidSwapProgram");
+    });
+
+    it("Flash liquidate position", async () => {
+        // MIM -> collateral
+        const poolOracleAccounts = [pool.tokenSwap, pool.swapTokenA, pool.swapTokenB];
+        await cauldron.createPoolOracle(swapperOrcaProgram.programId, poolOracleAccounts, { primary: {} },
+            new BN(10000), new BN(2), new BN(600), cauldronOwner);
+
+        const poolOracleAccountMetas = poolOracleAccounts.map(pubkey => ({ pubkey, isWritable: false, isSigner: false }));
+        await cauldron.updatePoolOraclePrice(swapperOrcaProgram.programId, poolOracleAccountMetas, bob);
+        await common.pause(3 * 1000);
+        await cauldron.updatePoolOraclePrice(swapperOrcaProgram.programId, poolOracleAccountMetas, bob);
+
+        let poolOracle = await cauldronProgram.account.poolOracle.fetch(cauldron.getPoolOraclePda());
+        assert.strictEqual(poolOracle.twapPrice.toString(), "25001000000");
+
+        let cauldronAccountBefore = await cauldronProgram.account.cauldron.fetch(cauldron.getCauldronAccount());
+        let cauldronMimBalanceBefore = await bentoboxProgram.account.balance.fetch(cauldronMimBentoboxBalance);
+        let stanUserBalanceBefore = await cauldronProgram.account.userBalance.fetch(cauldron.getUserBalancePda(stan.publicKey));
+
+        let listener = null;
+        let [event, _slot] = await new Promise((resolve, _reject) => {
+            listener = cauldronProgram.addEventListener("LogFlashLiquidate", (event, slot) => {
+                resolve([event, slot]);
+            });
+
+            flashLiquidate();
+        });
+        await cauldronProgram.removeEventListener(listener);
+
+        assert.ok(event.user.equals(stan.publicKey));
+        assert.ok(event.liquidator.equals(bob.publicKey));
+
+        // liquidated debt and collateral are taken from Stan position
+        let stanUserBalance = await cauldronProgram.account.userBalance.fetch(cauldron.getUserBalancePda(stan.publicKey));
+        assert.ok(stanUserBalance.borrowPart.lt(stanUserBalanceBefore.borrowPart));
+        assert.ok(stanUserBalance.collateralShare.lt(stanUserBalanceBefore.collateralShare));
+
+        // lent MIM is returned to cauldron, profit goes to Bob
+        let cauldronMimBalance = await bentoboxProgram.account.balance.fetch(cauldronMimBentoboxBalance);
+        assert.ok(cauldronMimBalance.amount.sub(cauldronMimBalanceBefore.amount).eq(event.borrowShare));
+        assert.ok(event.profitShare.gtn(0));
+        let bobMimBalance = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(bob.publicKey, mimMint.publicKey));
+        assert.ok(bobMimBalance.amount.eq(event.profitShare));
+
+        // distribution part of liquidation is saved to cauldron fees
+        let cauldronAccount = await cauldronProgram.account.cauldron.fetch(cauldron.getCauldronAccount());
+        assert.ok(cauldronAccount.accrueInfo.feesEarned.gt(cauldronAccountBefore.accrueInfo.feesEarned));
+
+        // no liquidator account is created
+        const [bobLiquidatorAccountAddress] = await getCauldronLiquidatorAccountAddress(
+            bob.publicKey,
+            cauldron.getCauldronAccount(),
+            cauldronProgram.programId
+        );
+        let bobLiquidatorAccount = await connection.getAccountInfo(bobLiquidatorAccountAddress);
+        assert.isNull(bobLiquidatorAccount);
+    });
+});
@@ -0,0 +1,268 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { Cauldron as CauldronProgram } from "../../target/types/cauldron";
+import { Bentobox as BentoboxProgram } from "../../target/types/bentobox";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import * as common from "../common/common";
+
+import { TEST_PRICE, COLLATERIZATION_RATE_PRECISION } from "../common/src";
+import { Bentobox } from "../common/bentobox"
+import { Cauldron } from "../common/cauldron"
+import { SwapperOrca as SwapperOrcaProgram } from "../../target/types/swapper_orca";
+import { TokenSwapPool } from "../common/amm_strategy";
+import { createTokenSwapPool } from "../common/token_swap";
+
+describe("Pool Oracle", () => {
+    // const provider = common.getAnchorProvider();
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+    const cauldronProgram = anchor.workspace.Cauldron as Program<CauldronProgram>;
+    const swapperOrcaProgram = anchor.workspace.SwapperOrca as Program<SwapperOrcaProgram>;
+
+    const cauldron_idl = require("../../target/idl/cauldron.json");
+
+    let mimMint: Token = null;
+    let collateralMint: Token = null;
+    let pool: TokenSwapPool = null;
+
+    const bentoboxOwner = Keypair.generate();
+    const cauldronOwner = Keypair.generate();
+    const borrowerStan = Keypair.generate();
+    const borrowerDan = Keypair.generate();
+
+    let bentoboxOwnerTokenAccount: PublicKey = null;
+    let stanTokenAccount: PublicKey = null;
+
+    let cauldronCollateralBentoboxBalance: PublicKey = null;
+
+    let cauldron = new Cauldron();
+    let bentobox = new Bentobox();
+
+    let cauldronAuthorityPda: PublicKey = null;
+    let cauldronBentoboxBalance: PublicKey = null;
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, borrowerStan, borrowerDan, cauldronOwner]);
+
+        // both mints have the same authority to fund the pool
+        mimMint = await common.createMintAccount(connection, cauldronOwner, cauldronOwner.publicKey, 0);
+        collateralMint = await common.createMintAccount(connection, cauldronOwner, cauldronOwner.publicKey, 0);
+
+        // create token account for bentoboxOwner with some MiM tokens
+        bentoboxOwnerTokenAccount = await common.createAndFundUserAccount(bentoboxOwner.publicKey, cauldronOwner, mimMint, 2000);
+        stanTokenAccount = await common.createAndFundUserAccount(borrowerStan.publicKey, cauldronOwner, collateralMint, 20000);
+
+        // pool spot price is equal to switchboard price
+        [pool] = await createTokenSwapPool(connection, cauldronOwner, mimMint, collateralMint, cauldronOwner, 1_000_000, 12_500_500);
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for MIM
+        await bentobox.createVault(mimMint.publicKey, cauldronOwner);
+        // create MIM Balance account for Stan on Bentobox
+        await bentobox.createBalance(borrowerStan.publicKey, mimMint.publicKey, borrowerStan)
+        // create MIM Balance account for Dan on Bentobox
+        await bentobox.createBalance(borrowerDan.publicKey, mimMint.publicKey, borrowerDan)
+        // create total vault for collateral
+        await bentobox.createVault(collateralMint.publicKey, bentoboxOwner);
+        // create collateral Balance account for Stan on Bentobox
+        await bentobox.createBalance(borrowerStan.publicKey, collateralMint.publicKey, borrowerStan)
+        // create strategy data account for MIM token
+        await bentobox.createStrategyData(mimMint.publicKey)
+        // create strategy data account for collateral token
+        await bentobox.createStrategyData(collateralMint.publicKey);
+
+        // initialize cauldron account        
+        await cauldron.initialize(cauldronOwner, mimMint.publicKey, collateralMint.publicKey, TEST_PRICE, bentobox.getBentoboxAccount(), COLLATERIZATION_RATE_PRECISION, new BN(1000000))
+        // create cauldron total vault   
+        await cauldron.createTotal(cauldronOwner);
+        // create user balance for Stan on cauldron
+        await cauldron.createUserBalance(borrowerStan.publicKey, borrowerStan)
+        // create user balance for Dan on cauldron
+        await cauldron.createUserBalance(borrowerDan.publicKey, borrowerDan)
+
+        // create MIM Balance account for cauldron on Bentobox
+        await bentobox.createBalance(cauldron.getCauldronAuthority(), mimMint.publicKey, cauldronOwner)
+        cauldronAuthorityPda = cauldron.getCauldronAuthority()
+        cauldronBentoboxBalance = bentobox.getBalancePda(cauldronAuthorityPda, mimMint.publicKey)
+
+        // create collateral Balance account for cauldron on Bentobox
+        await bentobox.createBalance(cauldron.getCauldronAuthority(), collateralMint.publicKey, cauldronOwner)
+        cauldronCollateralBentoboxBalance = bentobox.getBalancePda(cauldronAuthorityPda, collateralMint.publicKey)
+
+        // deposited to cauldron balance account on bentobox
+        await bentobox.deposit(mimMint.publicKey, bentoboxOwnerTokenAccount, cauldronAuthorityPda, new BN(2000), new BN(0), bentoboxOwner)
+
+        //register cauldron to bentobox as master contract
+        await bentobox.createMasterContractWhitelist(cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+        // create bentobox approval account for Stan 
+        await bentobox.createMasterContractApproval(borrowerStan, cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+        // create bentobox approval account for Dan
+        await bentobox.createMasterContractApproval(borrowerDan, cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+
+        // whitelist orca swapper which quotes pool spot price, no swap programs as it doesn't swap here
+        await cauldron.createSwapperWhitelist(swapperOrcaProgram.programId, true, [], cauldronOwner)
+
+        // deposit collateral to bentobox and add it to cauldron
+        await cauldron.approveToCauldron(stanTokenAccount, borrowerStan);
+        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(20000), new BN(0), new BN(0))
+            .accounts({
+                fromVault: stanTokenAccount,
+                bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
+                bentoboxToBalance: bentobox.getBalancePda(borrowerStan.publicKey, collateralMint.publicKey),
+                bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                bentoboxProgram: bentoboxProgram.programId,
+                cauldronAccount: cauldron.getCauldronAccount(),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                bentoboxStrategyData: bentobox.getStrategyDataPda(collateralMint.publicKey),
+                mint: collateralMint.publicKey,
+                cauldronAuthority: cauldronAuthorityPda,
+                masterContractApproved: bentobox.getMasterContractApprovedPda(borrowerStan.publicKey, cauldron.getCauldronAccount()),
+                masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()),
+                authority: borrowerStan.publicKey,
+            })
+            .signers([borrowerStan])
+            .rpc();
+
+        await cauldronProgram.methods.addCollateral(borrowerStan.publicKey, new BN(5000), false)
+            .accounts({
+                userBalance: cauldron.getUserBalancePda(borrowerStan.publicKey),
+                cauldronAccount: cauldron.getCauldronAccount(),
+                totalData: cauldron.totalDataPda,
+                cauldronBentoboxBalance: cauldronCollateralBentoboxBalance,
+                authority: borrowerStan.publicKey,
+                cauldronAuthority: cauldronAuthorityPda
+            }).remainingAccounts([
+                { pubkey: collateralMint.publicKey, isWritable: false, isSigner: false },
+                { pubkey: bentobox.getBalancePda(borrowerStan.publicKey, collateralMint.publicKey), isWritable: true, isSigner: false },
+                { pubkey: bentoboxProgram.programId, isWritable: false, isSigner: false },
+                { pubkey: bentobox.getBentoboxAccount(), isWritable: false, isSigner: false },
+                { pubkey: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()), isWritable: false, isSigner: false },
+                { pubkey: bentobox.getMasterContractApprovedPda(borrowerStan.publicKey, cauldron.getCauldronAccount()), isWritable: false, isSigner: false },
+            ])
+            .signers([borrowerStan])
+            .rpc();
+    });
+
+    // MIM -> collateral
+    const poolAccounts = () => [pool.tokenSwap, pool.swapTokenA, pool.swapTokenB];
+
+    async function borrowStan() {
+        await cauldron.borrow(borrowerStan.publicKey, new BN(10),
+            borrowerStan, cauldronBentoboxBalance,
+            bentobox.getTotalDataPda(mimMint.publicKey),
+            bentobox.getBalancePda(borrowerStan.publicKey,
+                mimMint.publicKey), bentoboxProgram.programId);
+    }
+
+    async function assertError(promise: Promise<any>, code: string) {
+        try {
+            await promise;
+            assert.fail(`Expected ${code}`);
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, code);
+            let error = await common.getErrorInfo(cauldron_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    }
+
+    async function assertHasOneError(promise: Promise<any>) {
+        try {
+            await promise;
+            assert.fail("Expected ConstraintHasOne");
+        } catch (err) {
+            assert.strictEqual(err.error.errorCode.code, "ConstraintHasOne");
+            assert.strictEqual(err.error.errorMessage, "A has one constraint was violated");
+            assert.strictEqual(err.error.errorCode.number, 2001);
+        }
+    }
+
+    it("Borrow uses switchboard price without pool oracle", async () => {
+        await borrowStan();
+
+        let stanMimBalanceOnBentobox = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(borrowerStan.publicKey, mimMint.publicKey));
+        assert.ok(stanMimBalanceOnBentobox.amount.toString() == "10");
+    });
+
+    it("Create pool oracle", async () => {
+        // not cauldron authority
+        await assertHasOneError(
+            cauldron.createPoolOracle(swapperOrcaProgram.programId, poolAccounts(), { primary: {} },
+                new BN(500), new BN(60), new BN(600), borrowerDan));
+
+        // too big deviation
+        await assertError(
+            cauldron.createPoolOracle(swapperOrcaProgram.programId, poolAccounts(), { primary: {} },
+                new BN(10001), new BN(60), new BN(600), cauldronOwner),
+            "InvalidPoolOracleConfig");
+
+        // zero deviation would freeze spot price
+        await assertError(
+            cauldron.createPoolOracle(swapperOrcaProgram.programId, poolAccounts(), { primary: {} },
+                new BN(0), new BN(60), new BN(600), cauldronOwner),
+            "InvalidPoolOracleConfig");
+
+        // first spot price is quoted with other accounts than pool accounts
+        await assertError(
+            cauldron.createPoolOracle(swapperOrcaProgram.programId, poolAccounts(), { primary: {} },
+                new BN(500), new BN(60), new BN(600), cauldronOwner, [Keypair.generate().publicKey]),
+            "InvalidPoolOracleAccounts");
+
+        await cauldron.createPoolOracle(swapperOrcaProgram.programId, poolAccounts(), { primary: {} },
+            new BN(500), new BN(60), new BN(600), cauldronOwner);
+
+        let poolOracle = await cauldronProgram.account.poolOracle.fetch(cauldron.getPoolOraclePda());
+        assert.ok(poolOracle.cauldron.equals(cauldron.getCauldronAccount()));
+        assert.ok(poolOracle.swapperProgram.equals(swapperOrcaProgram.programId));
+        assert.strictEqual(poolOracle.poolAccounts.length, 3);
+        poolAccounts().forEach((poolAccount, i) => assert.ok(poolOracle.poolAccounts[i].equals(poolAccount)));
+        assert.deepEqual(poolOracle.mode, { primary: {} });
+        assert.strictEqual(poolOracle.maxDeviation.toString(), "500");
+        assert.strictEqual(poolOracle.twapPeriod.toString(), "60");
+        assert.strictEqual(poolOracle.maxPriceAge.toString(), "600");
+        // first spot price is quoted on creation
+        assert.strictEqual(poolOracle.spotPrice.toString(), "12500500000");
+        assert.ok(poolOracle.lastUpdate.gtn(0));
+        assert.strictEqual(poolOracle.twapPrice.toString(), "0");
+    });
+
+    it("Update pool oracle price with wrong pool accounts", async () => {
+        await assertError(
+            cauldron.updatePoolOraclePrice(swapperOrcaProgram.programId,
+                [{ pubkey: Keypair.generate().publicKey, isWritable: false, isSigner: false }], borrowerDan),
+            "InvalidPoolOracleAccounts");
+    });
+
+    it("Borrow fails without pool oracle TWAP", async () => {
+        await assertError(borrowStan(), "PoolOraclePriceNotAvailable");
+
+        await cauldron.updatePoolOracleConfig({ sanityCheck: {} }, new BN(500), new BN(60), new BN(600), cauldronOwner);
+        let poolOracle = await cauldronProgram.account.poolOracle.fetch(cauldron.getPoolOraclePda());
+        assert.deepEqual(poolOracle.mode, { sanityCheck: {} });
+
+        await assertError(borrowStan(), "PoolOraclePriceNotAvailable");
+    });
+
+    it("Close pool oracle", async () => {
+        await assertHasOneError(cauldron.closePoolOracle(borrowerDan));
+
+        await cauldron.closePoolOracle(cauldronOwner);
+        let poolOracleInfo = await connection.getAccountInfo(cauldron.getPoolOraclePda());
+        assert.isNull(poolOracleInfo);
+
+        // switchboard price is used again
+        await borrowStan();
+        let stanMimBalanceOnBentobox = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(borrowerStan.publicKey, mimMint.publicKey));
+        assert.ok(stanMimBalanceOnBentobox.amount.toString() == "20");
+    });
+});
@@ -0,0 +1,248 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { Cauldron as CauldronProgram } from "../../target/types/cauldron";
+import { Bentobox as BentoboxProgram } from "../../target/types/bentobox";
+import { SwapperOrca as SwapperOrcaProgram } from "../../target/types/swapper_orca";
+import { AccountMeta, PublicKey, Keypair } from "@solana/web3.js";
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import * as common from "../common/common";
+
+import { TEST_PRICE, COLLATERIZATION_RATE_PRECISION } from "../common/src";
+import { Bentobox } from "../common/bentobox"
+import { Cauldron } from "../common/cauldron"
+import { TokenSwapPool } from "../common/amm_strategy";
+import { createTokenSwapPool, TOKEN_SWAP_PROGRAM_ID } from "../common/token_swap";
+
+// Cauldron must be built with `localnet` feature, switchboard price is mocked to 12.5005 collateral per MIM.
+describe("Pool Oracle TWAP", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+    const cauldronProgram = anchor.workspace.Cauldron as Program<CauldronProgram>;
+    const swapperOrcaProgram = anchor.workspace.SwapperOrca as Program<SwapperOrcaProgram>;
+
+    const cauldron_idl = require("../../target/idl/cauldron.json");
+
+    // mocked switchboard price with `POOL_ORACLE_PRICE_SCALE`
+    const SWITCHBOARD_PRICE = "12500500000";
+    // switchboard price + 5%
+    const CLAMPED_SPOT_PRICE = "13125525000";
+
+    let mimMint: Token = null;
+    let collateralMint: Token = null;
+    let pool: TokenSwapPool = null;
+
+    const bentoboxOwner = Keypair.generate();
+    const cauldronOwner = Keypair.generate();
+    const borrowerStan = Keypair.generate();
+    const keeper = Keypair.generate();
+
+    let bentoboxOwnerTokenAccount: PublicKey = null;
+    let stanTokenAccount: PublicKey = null;
+
+    let cauldron = new Cauldron();
+    let bentobox = new Bentobox();
+
+    let cauldronAuthorityPda: PublicKey = null;
+    let cauldronBentoboxBalance: PublicKey = null;
+    let cauldronCollateralBentoboxBalance: PublicKey = null;
+
+    // MIM -> collateral
+    const poolOracleAccounts = (): AccountMeta[] => [
+        { pubkey: pool.tokenSwap, isWritable: false, isSigner: false },
+        { pubkey: pool.swapTokenA, isWritable: false, isSigner: false },
+        { pubkey: pool.swapTokenB, isWritable: false, isSigner: false },
+    ];
+
+    const updatePrice = async () => {
+        await cauldron.updatePoolOraclePrice(swapperOrcaProgram.programId, poolOracleAccounts(), keeper);
+        return await cauldronProgram.account.poolOracle.fetch(cauldron.getPoolOraclePda());
+    };
+
+    async function borrowStan() {
+        await cauldron.borrow(borrowerStan.publicKey, new BN(10),
+            borrowerStan, cauldronBentoboxBalance,
+            bentobox.getTotalDataPda(mimMint.publicKey),
+            bentobox.getBalancePda(borrowerStan.publicKey,
+                mimMint.publicKey), bentoboxProgram.programId);
+    }
+
+    async function assertError(promise: Promise<any>, code: string) {
+        try {
+            await promise;
+            assert.fail(`Expected ${code}`);
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, code);
+            let error = await common.getErrorInfo(cauldron_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    }
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, borrowerStan, cauldronOwner, keeper]);
+
+        // both mints have the same authority to fund the pool
+        mimMint = await common.createMintAccount(connection, cauldronOwner, cauldronOwner.publicKey, 0);
+        collateralMint = await common.createMintAccount(connection, cauldronOwner, cauldronOwner.publicKey, 0);
+
+        bentoboxOwnerTokenAccount = await common.createAndFundUserAccount(bentoboxOwner.publicKey, cauldronOwner, mimMint, 2000);
+        stanTokenAccount = await common.createAndFundUserAccount(borrowerStan.publicKey, cauldronOwner, collateralMint, 20000);
+
+        // pool spot price is equal to switchboard price
+        [pool] = await createTokenSwapPool(connection, cauldronOwner, mimMint, collateralMint, cauldronOwner, 1_000_000, 12_500_500);
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for MIM
+        await bentobox.createVault(mimMint.publicKey, cauldronOwner);
+        // create MIM Balance account for Stan on Bentobox
+        await bentobox.createBalance(borrowerStan.publicKey, mimMint.publicKey, borrowerStan)
+        // create total vault for collateral
+        await bentobox.createVault(collateralMint.publicKey, bentoboxOwner);
+        // create collateral Balance account for Stan on Bentobox
+        await bentobox.createBalance(borrowerStan.publicKey, collateralMint.publicKey, borrowerStan)
+        // create strategy data account for MIM token
+        await bentobox.createStrategyData(mimMint.publicKey)
+        // create strategy data account for collateral token
+        await bentobox.createStrategyData(collateralMint.publicKey);
+
+        // initialize cauldron account
+        await cauldron.initialize(cauldronOwner, mimMint.publicKey, collateralMint.publicKey, TEST_PRICE, bentobox.getBentoboxAccount(), COLLATERIZATION_RATE_PRECISION, new BN(1000000))
+        // create cauldron total vault
+        await cauldron.createTotal(cauldronOwner);
+        // create user balance for Stan on cauldron
+        await cauldron.createUserBalance(borrowerStan.publicKey, borrowerStan)
+
+        // create MIM Balance account for cauldron on Bentobox
+        await bentobox.createBalance(cauldron.getCauldronAuthority(), mimMint.publicKey, cauldronOwner)
+        cauldronAuthorityPda = cauldron.getCauldronAuthority()
+        cauldronBentoboxBalance = bentobox.getBalancePda(cauldronAuthorityPda, mimMint.publicKey)
+
+        // create collateral Balance account for cauldron on Bentobox
+        await bentobox.createBalance(cauldron.getCauldronAuthority(), collateralMint.publicKey, cauldronOwner)
+        cauldronCollateralBentoboxBalance = bentobox.getBalancePda(cauldronAuthorityPda, collateralMint.publicKey)
+
+        // deposited to cauldron balance account on bentobox
+        await bentobox.deposit(mimMint.publicKey, bentoboxOwnerTokenAccount, cauldronAuthorityPda, new BN(2000), new BN(0), bentoboxOwner)
+
+        //register cauldron to bentobox as master contract
+        await bentobox.createMasterContractWhitelist(cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+        // create bentobox approval account for Stan
+        await bentobox.createMasterContractApproval(borrowerStan, cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+
+        // whitelist orca swapper which quotes pool spot price
+        await cauldron.createSwapperWhitelist(swapperOrcaProgram.programId, true, [TOKEN_SWAP_PROGRAM_ID], cauldronOwner)
+
+        // deposit collateral to bentobox and add it to cauldron
+        await cauldron.approveToCauldron(stanTokenAccount, borrowerStan);
+        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(20000), new BN(0), new BN(0))
+            .accounts({
+                fromVault: stanTokenAccount,
+                bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
+                bentoboxToBalance: bentobox.getBalancePda(borrowerStan.publicKey, collateralMint.publicKey),
+                bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                bentoboxProgram: bentoboxProgram.programId,
+                cauldronAccount: cauldron.getCauldronAccount(),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                bentoboxStrategyData: bentobox.getStrategyDataPda(collateralMint.publicKey),
+                mint: collateralMint.publicKey,
+                cauldronAuthority: cauldronAuthorityPda,
+                masterContractApproved: bentobox.getMasterContractApprovedPda(borrowerStan.publicKey, cauldron.getCauldronAccount()),
+                masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()),
+                authority: borrowerStan.publicKey,
+            })
+            .signers([borrowerStan])
+            .rpc();
+
+        await cauldronProgram.methods.addCollateral(borrowerStan.publicKey, new BN(5000), false)
+            .accounts({
+                userBalance: cauldron.getUserBalancePda(borrowerStan.publicKey),
+                cauldronAccount: cauldron.getCauldronAccount(),
+                totalData: cauldron.totalDataPda,
+                cauldronBentoboxBalance: cauldronCollateralBentoboxBalance,
+                authority: borrowerStan.publicKey,
+                cauldronAuthority: cauldronAuthorityPda
+            }).remainingAccounts([
+                { pubkey: collateralMint.publicKey, isWritable: false, isSigner: false },
+                { pubkey: bentobox.getBalancePda(borrowerStan.publicKey, collateralMint.publicKey), isWritable: true, isSigner: false },
+                { pubkey: bentoboxProgram.programId, isWritable: false, isSigner: false },
+                { pubkey: bentobox.getBentoboxAccount(), isWritable: false, isSigner: false },
+                { pubkey: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()), isWritable: false, isSigner: false },
+                { pubkey: bentobox.getMasterContractApprovedPda(borrowerStan.publicKey, cauldron.getCauldronAccount()), isWritable: false, isSigner: false },
+            ])
+            .signers([borrowerStan])
+            .rpc();
+
+        await cauldron.createPoolOracle(swapperOrcaProgram.programId, [pool.tokenSwap, pool.swapTokenA, pool.swapTokenB],
+            { sanityCheck: {} }, new BN(500), new BN(2), new BN(600), cauldronOwner);
+    });
+
+    it("Calculate TWAP", async () => {
+        // first spot price is quoted on creation, permissionless updates are clamped from it
+        let poolOracle = await cauldronProgram.account.poolOracle.fetch(cauldron.getPoolOraclePda());
+        assert.strictEqual(poolOracle.spotPrice.toString(), SWITCHBOARD_PRICE);
+        assert.strictEqual(poolOracle.twapPrice.toString(), "0");
+
+        await assertError(borrowStan(), "PoolOraclePriceNotAvailable");
+
+        await common.pause(3 * 1000);
+        poolOracle = await updatePrice();
+        // spot price didn't change over the period
+        assert.strictEqual(poolOracle.twapPrice.toString(), SWITCHBOARD_PRICE);
+        assert.ok(poolOracle.twapTimestamp.eq(poolOracle.lastUpdate));
+    });
+
+    it("Borrow in SanityCheck mode with switchboard price close to TWAP", async () => {
+        await borrowStan();
+
+        let stanMimBalanceOnBentobox = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(borrowerStan.publicKey, mimMint.publicKey));
+        assert.strictEqual(stanMimBalanceOnBentobox.amount.toString(), "10");
+    });
+
+    it("Spot price change is clamped to max deviation from TWAP", async () => {
+        // double collateral reserve of the pool, spot price jumps by 100%
+        await collateralMint.mintTo(pool.swapTokenB, cauldronOwner.publicKey, [cauldronOwner], 12_500_500);
+
+        let poolOracle = await updatePrice();
+        assert.strictEqual(poolOracle.spotPrice.toString(), CLAMPED_SPOT_PRICE);
+
+        await common.pause(3 * 1000);
+        poolOracle = await updatePrice();
+        // TWAP moves towards clamped spot price only
+        assert.ok(poolOracle.twapPrice.gt(new BN(SWITCHBOARD_PRICE)));
+        assert.ok(poolOracle.twapPrice.lte(new BN(CLAMPED_SPOT_PRICE)));
+        // spot price is clamped against TWAP before the update, which still was switchboard price
+        assert.strictEqual(poolOracle.spotPrice.toString(), CLAMPED_SPOT_PRICE);
+    });
+
+    it("Borrow fails in SanityCheck mode when switchboard price deviates from TWAP", async () => {
+        // TWAP is above switchboard price by more than 0.1%
+        await cauldron.updatePoolOracleConfig({ sanityCheck: {} }, new BN(10), new BN(2), new BN(600), cauldronOwner);
+
+        await assertError(borrowStan(), "PoolOraclePriceDeviation");
+    });
+
+    it("Borrow in Primary mode uses TWAP without deviation check", async () => {
+        await cauldron.updatePoolOracleConfig({ primary: {} }, new BN(10), new BN(2), new BN(600), cauldronOwner);
+
+        await borrowStan();
+
+        let stanMimBalanceOnBentobox = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(borrowerStan.publicKey, mimMint.publicKey));
+        assert.strictEqual(stanMimBalanceOnBentobox.amount.toString(), "20");
+    });
+
+    it("Borrow fails with stale TWAP", async () => {
+        await cauldron.updatePoolOracleConfig({ primary: {} }, new BN(10), new BN(2), new BN(1), cauldronOwner);
+        await common.pause(3 * 1000);
+
+        await assertError(borrowStan(), "StalePoolOraclePrice");
+    });
+});
@@ -0,0 +1,271 @@
+import * as anchor from '@coral-xyz/anchor';
+import { assert } from "chai";
+import { Bentobox as BentoboxProgram } from '../../target/types/bentobox';
+import { StrategyAmm as StrategyAmmProgram } from "../../target/types/strategy_amm";
+import { SwapperOrca as SwapperOrcaProgram } from "../../target/types/swapper_orca";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor"
+import { Bentobox } from "../common/bentobox";
+import { StrategyAmm, TokenSwapPool } from "../common/amm_strategy";
+import { createTokenSwapPool, getOrcaSwapRemainingAccounts, TOKEN_SWAP_PROGRAM_ID } from "../common/token_swap";
+import { AccountMeta, Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+import * as common from "../common/common";
+
+describe('strategy amm localnet', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+
+  const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+  const strategyAmmProgram = anchor.workspace.StrategyAmm as Program<StrategyAmmProgram>;
+  const swapperOrcaProgram = anchor.workspace.SwapperOrca as Program<SwapperOrcaProgram>;
+
+  const strategy_amm_idl = require("../../target/idl/strategy_amm.json");
+
+  const bentoboxOwner = Keypair.generate();
+  const strategyOwner = Keypair.generate();
+  const Bob = Keypair.generate();
+
+  let bentobox = new Bentobox();
+  let strategyAmm = new StrategyAmm();
+  let mint: Token = null;
+  let otherMint: Token = null;
+  let rewardMint: Token = null;
+  let pool: TokenSwapPool = null;
+  let rewardPool: TokenSwapPool = null;
+  let BobTokenAccount: PublicKey = null;
+
+  const harvest = async (maxChangeAmount: BN) => {
+    await bentoboxProgram.methods.harvest(true, maxChangeAmount, bentobox.getBentoboxAuthorityBump())
+      .accounts({
+        strategyAccount: strategyAmm.getStrategyAmmAccount(),
+        bentoboxAccount: bentobox.getBentoboxAccount(),
+        strategyProgram: strategyAmmProgram.programId,
+        strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyAmmProgram.programId),
+        bentoboxProgram: bentoboxProgram.programId,
+        strategyVault: strategyAmm.getTotalVaultPda(mint.publicKey),
+        totalData: bentobox.getTotalDataPda(mint.publicKey),
+        bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
+        authority: strategyOwner.publicKey,
+        bentoboxAuthority: bentobox.getBentoboxAuthority(),
+        strategyAuthority: strategyAmm.getStrategyAmmAuthority(),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        cpiResultAccount: null,
+        feeToBalance: null,
+        strategyData: bentobox.getStrategyDataPda(mint.publicKey),
+        baseStrategyInfo: strategyAmm.getBaseStrategyInfoAccount(),
+      })
+      .remainingAccounts(strategyAmm.getAmmRemainingAccounts())
+      .signers([strategyOwner]).rpc()
+  };
+
+  const safeHarvest = async (maxBalance: BN, remainingAccounts: AccountMeta[]) => {
+    await bentoboxProgram.methods.safeHarvest(maxBalance, false, new BN(0), true, bentobox.getBentoboxAuthorityBump())
+      .accounts({
+        strategyAccount: strategyAmm.getStrategyAmmAccount(),
+        baseStrategyInfo: strategyAmm.getBaseStrategyInfoAccount(),
+        bentoboxAccount: bentobox.getBentoboxAccount(),
+        strategyProgram: strategyAmmProgram.programId,
+        strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyAmmProgram.programId),
+        bentoboxProgram: bentoboxProgram.programId,
+        strategyVault: strategyAmm.getTotalVaultPda(mint.publicKey),
+        totalData: bentobox.getTotalDataPda(mint.publicKey),
+        bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
+        authority: strategyOwner.publicKey,
+        bentoboxAuthority: bentobox.getBentoboxAuthority(),
+        strategyAuthority: strategyAmm.getStrategyAmmAuthority(),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        cpiResultAccount: null,
+        feeToBalance: null,
+        strategyData: bentobox.getStrategyDataPda(mint.publicKey),
+        strategyKeeper: bentobox.getStrategyKeeperPda(strategyOwner.publicKey, mint.publicKey),
+        keeperBalance: null,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([strategyOwner]).rpc()
+  };
+
+  const removeStrategy = async (box: Bentobox) => box.removeStrategy(
+    strategyAmmProgram.programId,
+    strategyAmm.getStrategyAmmAccount(),
+    strategyAmm.getTotalVaultPda(mint.publicKey),
+    strategyAmm.getStrategyAmmAuthority(),
+    strategyAmm.getBaseStrategyInfoAccount(),
+    mint.publicKey,
+    strategyAmm.getAmmRemainingAccounts()
+  );
+
+  const tokenAmount = async (account: PublicKey) => new BN((await connection.getTokenAccountBalance(account)).value.amount);
+
+  const assertStrategyAmmError = async (_err: any, code: string) => {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+
+    assert.strictEqual(err.error.errorCode.code, code);
+    let error = await common.getErrorInfo(strategy_amm_idl, err.error.errorCode.code);
+    assert.strictEqual(err.error.errorMessage, error.errorMsg);
+    assert.strictEqual(err.error.errorCode.number, error.errorCode);
+  };
+
+  before(async () => {
+    await common.batchAirdrop(connection, [bentoboxOwner, strategyOwner, Bob]);
+
+    mint = await common.createMintAccount(connection, bentoboxOwner, bentoboxOwner.publicKey, 0);
+    otherMint = await common.createMintAccount(connection, bentoboxOwner, bentoboxOwner.publicKey, 0);
+    rewardMint = await common.createMintAccount(connection, bentoboxOwner, bentoboxOwner.publicKey, 0);
+
+    [pool] = await createTokenSwapPool(connection, bentoboxOwner, mint, otherMint, bentoboxOwner, 1_000_000_000, 1_000_000_000);
+    [rewardPool] = await createTokenSwapPool(connection, bentoboxOwner, rewardMint, mint, bentoboxOwner, 1_000_000_000, 1_000_000_000);
+
+    BobTokenAccount = await common.createAndFundUserAccount(Bob.publicKey, bentoboxOwner, mint, 1_000_000);
+
+    // create bentobox
+    await bentobox.create(bentoboxOwner);
+    // create total vault for mint
+    await bentobox.createVault(mint.publicKey, Bob);
+    // create mint Balance account for Bob
+    await bentobox.createBalance(Bob.publicKey, mint.publicKey, Bob)
+    // create strategy data account for mint token
+    await bentobox.createStrategyData(mint.publicKey)
+    // whitelist strategy program for bentobox
+    await bentobox.createStrategyProgramWhitelist(strategyAmmProgram.programId)
+    await bentobox.createStrategyKeeper(strategyOwner.publicKey, mint.publicKey)
+    await bentobox.setStrategyDelay(new BN(0));
+
+    await bentobox.deposit(mint.publicKey, BobTokenAccount, Bob.publicKey, new BN(1_000_000), new BN(0), Bob)
+
+    await strategyAmm.initialize(mint.publicKey, rewardMint.publicKey, pool, swapperOrcaProgram.programId, TOKEN_SWAP_PROGRAM_ID,
+      bentobox.getBentoboxAccount(), bentoboxProgram.programId, strategyOwner)
+
+    // set pending strategy, then activate it
+    await bentobox.queueStrategy(strategyAmmProgram.programId, strategyAmm.getStrategyAmmAccount(), mint.publicKey)
+    await common.pause(2 * 1000);
+    await bentobox.activateStrategy(strategyAmmProgram.programId, strategyAmm.getStrategyAmmAccount(), mint.publicKey)
+    await bentobox.setStrategyTargetPercentage(new BN(50), mint.publicKey, strategyAmm.getStrategyAmmAccount())
+  });
+
+  it('Skim requires minimum pool tokens set by executor', async () => {
+    try {
+      await harvest(new BN(500_000));
+      assert.fail("skimmed without minimum pool tokens");
+    } catch (_err) {
+      await assertStrategyAmmError(_err, "SkimMinPoolTokensNotSet");
+    }
+
+    // minimum above the pool tokens minted for skimmed amount is rejected by swap program
+    await strategyAmm.setSkimMinPoolTokens(new BN(1_000_000), strategyOwner).rpc();
+    let failed = false;
+    try {
+      await harvest(new BN(500_000));
+    } catch (_err) {
+      failed = true;
+    }
+    assert.isTrue(failed);
+    assert.equal((await tokenAmount(strategyAmm.getLpVaultPda())).toString(), "0");
+  });
+
+  it('Skim deposits tokens single-sided to the pool', async () => {
+    await strategyAmm.setSkimMinPoolTokens(new BN(200_000), strategyOwner).rpc();
+    await harvest(new BN(500_000));
+
+    assert.isTrue((await tokenAmount(strategyAmm.getLpVaultPda())).gten(200_000));
+    assert.equal((await tokenAmount(strategyAmm.getTotalVaultPda(mint.publicKey))).toString(), "0");
+    assert.equal((await tokenAmount(bentobox.getTotalVaultPda(mint.publicKey))).toString(), "500000");
+
+    let strategyData = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
+    assert.equal(strategyData.balance.toString(), "500000");
+
+    // minimum pool tokens is reset after skim
+    let strategyAccount = await strategyAmmProgram.account.strategyAmm.fetch(strategyAmm.getStrategyAmmAccount());
+    assert.equal(strategyAccount.skimMinPoolTokens.toString(), "0");
+  });
+
+  it('Withdraw pulls shortfall single-sided from the pool', async () => {
+    const lpBefore = await tokenAmount(strategyAmm.getLpVaultPda());
+    const strategyAccounts = bentobox.getStrategyWithdrawAccounts(
+      strategyAmmProgram.programId,
+      strategyAmm.getStrategyAmmAccount(),
+      strategyAmm.getTotalVaultPda(mint.publicKey),
+      strategyAmm.getStrategyAmmAuthority(),
+      strategyAmm.getBaseStrategyInfoAccount(),
+      mint.publicKey,
+      strategyAmm.getAmmRemainingAccounts()
+    );
+
+    await bentobox.withdraw(mint.publicKey, Bob.publicKey, BobTokenAccount, new BN(600_000), new BN(0), Bob, strategyAccounts)
+
+    assert.equal((await tokenAmount(BobTokenAccount)).toString(), "600000");
+    assert.equal((await tokenAmount(bentobox.getTotalVaultPda(mint.publicKey))).toString(), "0");
+    assert.isTrue((await tokenAmount(strategyAmm.getLpVaultPda())).lt(lpBefore));
+
+    let strategyData = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
+    assert.equal(strategyData.balance.toString(), "400000");
+  });
+
+  it('Harvest rewards sells reward tokens for strategy token', async () => {
+    await rewardMint.mintTo(strategyAmm.getRewardVaultPda(), bentoboxOwner.publicKey, [bentoboxOwner], 100_000);
+
+    const remainingAccounts = strategyAmm.getHarvestRewardsRemainingAccounts(
+      getOrcaSwapRemainingAccounts(rewardPool, rewardMint.publicKey, mint.publicKey, true)
+    );
+
+    try {
+      await safeHarvest(new BN(1_000_000_000), remainingAccounts);
+      assert.fail("rewards were sold without minimum amount out");
+    } catch (_err) {
+      await assertStrategyAmmError(_err, "RewardMinAmountOutNotSet");
+    }
+
+    await strategyAmm.setRewardMinAmountOut(new BN(90_000), strategyOwner).rpc();
+    // harvest after reward swap can reinvest the rest of the strategy vault
+    await strategyAmm.setSkimMinPoolTokens(new BN(1), strategyOwner).rpc();
+    await safeHarvest(new BN(1_000_000_000), remainingAccounts);
+
+    assert.equal((await tokenAmount(strategyAmm.getRewardVaultPda())).toString(), "0");
+    assert.equal((await tokenAmount(strategyAmm.getTotalVaultPda(mint.publicKey))).toString(), "0");
+    // sold rewards are reported as profit and moved to Bentobox
+    assert.isTrue((await tokenAmount(bentobox.getTotalVaultPda(mint.publicKey))).gten(80_000));
+
+    let strategyAccount = await strategyAmmProgram.account.strategyAmm.fetch(strategyAmm.getStrategyAmmAccount());
+    assert.equal(strategyAccount.rewardMinAmountOut.toString(), "0");
+  });
+
+  it('Other BentoBox can not pull strategy tokens', async () => {
+    let otherBentobox = new Bentobox();
+    await otherBentobox.create(bentoboxOwner);
+    await otherBentobox.createVault(mint.publicKey, Bob);
+    await otherBentobox.createStrategyData(mint.publicKey)
+    await otherBentobox.createStrategyProgramWhitelist(strategyAmmProgram.programId)
+    await otherBentobox.setStrategyDelay(new BN(0));
+    await otherBentobox.queueStrategy(strategyAmmProgram.programId, strategyAmm.getStrategyAmmAccount(), mint.publicKey)
+    await common.pause(2 * 1000);
+    await otherBentobox.activateStrategy(strategyAmmProgram.programId, strategyAmm.getStrategyAmmAccount(), mint.publicKey)
+
+    try {
+      await removeStrategy(otherBentobox);
+      assert.fail("strategy tokens were pulled by other BentoBox");
+    } catch (_err) {
+      await assertStrategyAmmError(_err, "InvalidBentoBoxAccount");
+    }
+  });
+
+  it('Exit withdraws all liquidity from the pool', async () => {
+    await removeStrategy(bentobox);
+
+    let baseStrategyInfo = await strategyAmmProgram.account.baseStrategyInfo.fetch(strategyAmm.getBaseStrategyInfoAccount());
+    assert.isTrue(baseStrategyInfo.exited);
+
+    assert.equal((await tokenAmount(strategyAmm.getTotalVaultPda(mint.publicKey))).toString(), "0");
+
+    const bentoboxVaultAmount = await tokenAmount(bentobox.getTotalVaultPda(mint.publicKey));
+    let total = await bentoboxProgram.account.total.fetch(bentobox.getTotalDataPda(mint.publicKey));
+    assert.equal(total.amount["elastic"].toString(), bentoboxVaultAmount.toString());
+
+    let strategyData = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
+    assert.equal(strategyData.balance.toString(), "0");
+    assert.equal(strategyData.strategies.length, 0);
+  });
+});
@@ -0,0 +1,226 @@
+import * as anchor from '@coral-xyz/anchor';
+import { assert } from "chai";
+import { Blockchain } from '../common/blockchain';
+import { Bentobox as BentoboxProgram } from '../../target/types/bentobox';
+import { StrategyLending as StrategyLendingProgram } from "../../target/types/strategy_lending";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor"
+import { Bentobox } from "../common/bentobox";
+import { StrategyLending } from "../common/lending_strategy";
+import { LENDING_PROGRAM_ID, ReserveConfig, WAD_BigInt } from "../common/src";
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+import * as common from "../common/common";
+
+describe('strategy lending localnet', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+
+  const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+  const strategyLendingProgram = anchor.workspace.StrategyLending as Program<StrategyLendingProgram>;
+
+  const strategy_lending_idl = require("../../target/idl/strategy_lending.json");
+
+  const bentoboxOwner = Keypair.generate();
+  const strategyOwner = Keypair.generate();
+  const Bob = Keypair.generate();
+
+  let bentobox = new Bentobox();
+  let strategyLending = new StrategyLending();
+  let blockchain: Blockchain = null;
+  let mint: PublicKey = null;
+  let BobTokenAccount: PublicKey = null;
+
+  // high borrow rates, so interest accrued in a few slots is visible in the reserve exchange rate
+  const reserveConfig: ReserveConfig = {
+    ...Blockchain._generateStandardReserveConfig(),
+    minBorrowRate: 100,
+    optimalBorrowRate: 200,
+    maxBorrowRate: 250,
+    fees: {
+      // @ts-ignore
+      borrowFeeWad: WAD_BigInt / 1000n,
+      // @ts-ignore
+      flashLoanFeeWad: WAD_BigInt / 20n,
+      hostFeePercentage: 20,
+    },
+  };
+
+  const harvest = async (maxChangeAmount: BN) => {
+    await bentoboxProgram.methods.harvest(true, maxChangeAmount, bentobox.getBentoboxAuthorityBump())
+      .accounts({
+        strategyAccount: strategyLending.getStrategyLendingAccount(),
+        bentoboxAccount: bentobox.getBentoboxAccount(),
+        strategyProgram: strategyLendingProgram.programId,
+        strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyLendingProgram.programId),
+        bentoboxProgram: bentoboxProgram.programId,
+        strategyVault: strategyLending.getTotalVaultPda(mint),
+        totalData: bentobox.getTotalDataPda(mint),
+        bentoboxVault: bentobox.getTotalVaultPda(mint),
+        authority: strategyOwner.publicKey,
+        bentoboxAuthority: bentobox.getBentoboxAuthority(),
+        strategyAuthority: strategyLending.getStrategyLendingAuthority(),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        cpiResultAccount: null,
+        feeToBalance: null,
+        strategyData: bentobox.getStrategyDataPda(mint),
+        baseStrategyInfo: strategyLending.getBaseStrategyInfoAccount(),
+      })
+      .remainingAccounts(await strategyLending.getLendingRemainingAccounts())
+      .signers([strategyOwner]).rpc()
+  };
+
+  const strategyWithdrawAccounts = async (box: Bentobox) => box.getStrategyWithdrawAccounts(
+    strategyLendingProgram.programId,
+    strategyLending.getStrategyLendingAccount(),
+    strategyLending.getTotalVaultPda(mint),
+    strategyLending.getStrategyLendingAuthority(),
+    strategyLending.getBaseStrategyInfoAccount(),
+    mint,
+    await strategyLending.getLendingRemainingAccounts()
+  );
+
+  const tokenAmount = async (account: PublicKey) => new BN((await connection.getTokenAccountBalance(account)).value.amount);
+
+  before(async () => {
+    await common.batchAirdrop(connection, [bentoboxOwner, strategyOwner, Bob]);
+
+    blockchain = new Blockchain(connection);
+    await blockchain.initLendingMarket();
+    await blockchain.initReserve(blockchain.token, 1_000_000_000_000, 1_000_000, reserveConfig);
+    mint = blockchain.token.mint.publicKey;
+
+    BobTokenAccount = await common.createAndFundUserAccount(
+      Bob.publicKey,
+      blockchain.lendingMarketOwnerKp,
+      blockchain.token.mint,
+      1_000_000_000
+    );
+
+    // create bentobox
+    await bentobox.create(bentoboxOwner);
+    // create total vault for mint
+    await bentobox.createVault(mint, Bob);
+    // create mint Balance account for Bob
+    await bentobox.createBalance(Bob.publicKey, mint, Bob)
+    // create strategy data account for mint token
+    await bentobox.createStrategyData(mint)
+    // whitelist strategy program for bentobox
+    await bentobox.createStrategyProgramWhitelist(strategyLendingProgram.programId)
+    await bentobox.setStrategyDelay(new BN(0));
+
+    await bentobox.deposit(mint, BobTokenAccount, Bob.publicKey, new BN(1_000_000_000), new BN(0), Bob)
+    await strategyLending.initialize(mint, {
+      lendingProgram: LENDING_PROGRAM_ID,
+      reserve: blockchain.token.reserveKp.publicKey,
+      reserveLiquiditySupply: blockchain.token.protocolKp.publicKey,
+      reserveCollateralMint: blockchain.token.lpMintKp.publicKey,
+      lendingMarket: blockchain.lendingMarketKp.publicKey,
+      reserveLiquidityOracle: blockchain.token.pythPricePk,
+    }, bentobox.getBentoboxAccount(), bentoboxProgram.programId, strategyOwner)
+
+    // set pending strategy, then activate it
+    await bentobox.queueStrategy(strategyLendingProgram.programId, strategyLending.getStrategyLendingAccount(), mint)
+    await common.pause(2 * 1000);
+    await bentobox.activateStrategy(strategyLendingProgram.programId, strategyLending.getStrategyLendingAccount(), mint)
+    await bentobox.setStrategyTargetPercentage(new BN(50), mint, strategyLending.getStrategyLendingAccount())
+  });
+
+  it('Deposit supplies target percentage to the reserve', async () => {
+    await harvest(new BN(500_000_000));
+
+    let strategyData = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint));
+    assert.equal(strategyData.balance.toString(), "500000000");
+    assert.equal(strategyData.strategies[0].balance.toString(), "500000000");
+
+    assert.isTrue((await tokenAmount(strategyLending.getCollateralVaultPda())).gtn(0));
+    assert.equal((await tokenAmount(strategyLending.getTotalVaultPda(mint))).toString(), "0");
+    assert.equal((await tokenAmount(bentobox.getTotalVaultPda(mint))).toString(), "500000000");
+  });
+
+  it('Withdraw redeems shortfall from the reserve', async () => {
+    const collateralBefore = await tokenAmount(strategyLending.getCollateralVaultPda());
+
+    await bentobox.withdraw(mint, Bob.publicKey, BobTokenAccount, new BN(600_000_000), new BN(0), Bob, await strategyWithdrawAccounts(bentobox))
+
+    assert.equal((await tokenAmount(BobTokenAccount)).toString(), "600000000");
+    assert.equal((await tokenAmount(bentobox.getTotalVaultPda(mint))).toString(), "0");
+    assert.isTrue((await tokenAmount(strategyLending.getCollateralVaultPda())).lt(collateralBefore));
+
+    let strategyData = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint));
+    assert.equal(strategyData.balance.toString(), "400000000");
+  });
+
+  it('Other BentoBox can not pull strategy tokens', async () => {
+    let otherBentobox = new Bentobox();
+    await otherBentobox.create(bentoboxOwner);
+    await otherBentobox.createVault(mint, Bob);
+    await otherBentobox.createStrategyData(mint)
+    await otherBentobox.createStrategyProgramWhitelist(strategyLendingProgram.programId)
+    await otherBentobox.setStrategyDelay(new BN(0));
+    await otherBentobox.queueStrategy(strategyLendingProgram.programId, strategyLending.getStrategyLendingAccount(), mint)
+    await common.pause(2 * 1000);
+    await otherBentobox.activateStrategy(strategyLendingProgram.programId, strategyLending.getStrategyLendingAccount(), mint)
+
+    try {
+      await otherBentobox.removeStrategy(
+        strategyLendingProgram.programId,
+        strategyLending.getStrategyLendingAccount(),
+        strategyLending.getTotalVaultPda(mint),
+        strategyLending.getStrategyLendingAuthority(),
+        strategyLending.getBaseStrategyInfoAccount(),
+        mint,
+        await strategyLending.getLendingRemainingAccounts()
+      )
+      assert.fail("strategy tokens were pulled by other BentoBox");
+    } catch (_err) {
+      assert.isTrue(_err instanceof AnchorError);
+      const err: AnchorError = _err;
+
+      assert.strictEqual(err.error.errorCode.code, "InvalidBentoBoxAccount");
+      let error = await common.getErrorInfo(strategy_lending_idl, err.error.errorCode.code);
+      assert.strictEqual(err.error.errorMessage, error.errorMsg);
+      assert.strictEqual(err.error.errorCode.number, error.errorCode);
+    }
+
+    let strategyData = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint));
+    assert.equal(strategyData.balance.toString(), "400000000");
+  });
+
+  it('Exit returns supplied liquidity with profit', async () => {
+    // borrow from the reserve, so supplied liquidity earns interest
+    await blockchain.depositReserveLiquidity(blockchain.token, 100_000_000_000);
+    await blockchain.initObligation();
+    await blockchain.depositObligationCollateral(blockchain.token, 100_000_000_000);
+    await blockchain.borrowObligationLiquidity(blockchain.token, 40_000_000_000);
+    await common.pause(10 * 1000);
+
+    await bentobox.removeStrategy(
+      strategyLendingProgram.programId,
+      strategyLending.getStrategyLendingAccount(),
+      strategyLending.getTotalVaultPda(mint),
+      strategyLending.getStrategyLendingAuthority(),
+      strategyLending.getBaseStrategyInfoAccount(),
+      mint,
+      await strategyLending.getLendingRemainingAccounts()
+    )
+
+    let baseStrategyInfo = await strategyLendingProgram.account.baseStrategyInfo.fetch(strategyLending.getBaseStrategyInfoAccount());
+    assert.isTrue(baseStrategyInfo.exited);
+
+    assert.equal((await tokenAmount(strategyLending.getCollateralVaultPda())).toString(), "0");
+    assert.equal((await tokenAmount(strategyLending.getTotalVaultPda(mint))).toString(), "0");
+
+    const bentoboxVaultAmount = await tokenAmount(bentobox.getTotalVaultPda(mint));
+    assert.isTrue(bentoboxVaultAmount.gtn(400_000_000));
+
+    let total = await bentoboxProgram.account.total.fetch(bentobox.getTotalDataPda(mint));
+    assert.equal(total.amount["elastic"].toString(), bentoboxVaultAmount.toString());
+
+    let strategyData = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint));
+    assert.equal(strategyData.balance.toString(), "0");
+    assert.equal(strategyData.strategies.length, 0);
+  });
+});
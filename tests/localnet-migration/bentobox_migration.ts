@@ -0,0 +1,308 @@
+import * as anchor from '@coral-xyz/anchor';
+import { assert } from "chai";
+import { Bentobox as BentoboxProgram } from '../../target/types/bentobox';
+import { Program, BN, AnchorError } from "@coral-xyz/anchor"
+import { getBentoboxBalanceAddress, getMasterContractApprovedAddress, getMasterContractWhitelistAddress, getBentoboxStrategyDataAddress } from "../common/bentobox_pda_helper";
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+
+import * as common from "../common/common";
+
+/// Accounts loaded on validator start by `Test.toml`.
+///
+/// `bentobox.json` - `BentoBox` in current layout, authority is `tests/wallets/devnet.json`.
+/// `mint.json` - token mint with 6 decimals.
+/// `legacy_balance_empty.json` - `Balance` of `tests/wallets/devnet.json` in 16 bytes layout (discriminator, amount = 0).
+/// `legacy_balance.json` - `Balance` of `LEGACY_BALANCE_OWNER` in 16 bytes layout (discriminator, amount = 1234).
+/// `master_contract.json` - `MASTER_CONTRACT` account owned by cauldron program.
+/// `legacy_master_contract_whitelisted.json` - whitelisted `MasterContractWhitelisted` of `MASTER_CONTRACT` without `approvals_epoch`.
+/// `legacy_master_contract_approved.json` - approved `MasterContractApproved` of `tests/wallets/devnet.json` without `approvals_epoch`.
+/// `legacy_strategy_data.json` - single strategy `StrategyData` of `MINT`, active strategy `LEGACY_STRATEGY` with target percentage 50 and balance 700.
+/// `pending_mint.json` - token mint `PENDING_MINT` with 6 decimals.
+/// `legacy_pending_strategy_data.json` - single strategy `StrategyData` of `PENDING_MINT` without active strategy,
+///                                       `LEGACY_PENDING_STRATEGY` is pending since 1970.
+/// `legacy_bentobox.json` - `LEGACY_BENTOBOX_ACCOUNT` without performance fee, authority is `tests/wallets/devnet.json`,
+///                          pending authority `LEGACY_PENDING_AUTHORITY`, strategy delay 5, constants 1000 and 95.
+const BENTOBOX_ACCOUNT = new PublicKey("66AEgKeTUc2RZTKAu169MzE1RH9iTwJWEQAZ6Hb5zb7A");
+const MINT = new PublicKey("9DmhCa3sVKTMoJf5xTi3AYg6JRGSHorYckfG8jpDBKTS");
+const LEGACY_BALANCE_OWNER = new PublicKey("2xJxr2HaJBwALze2yyTYxp2eFZ9EqCEruWsWXoiFuUJJ");
+const MASTER_CONTRACT = new PublicKey("EhLGUHAb29esWEQKzR8Qz4yxUiKxcdDoZtFZPKSywHyW");
+const LEGACY_STRATEGY = new PublicKey("4bWJY3jRaJ4uC2PqbkxfmHZeH4JJxQEyRs1a75QqcbBD");
+const PENDING_MINT = new PublicKey("ECXMe4BDHPYQbkkzTFhpN9K33ybvQZxgncjUSx2TvgKR");
+const LEGACY_PENDING_STRATEGY = new PublicKey("9Go3RakZ5wG29KKbSerznoABDSAMCPyW1v1RpgHxYoju");
+const LEGACY_BENTOBOX_ACCOUNT = new PublicKey("CaNA616kxKnxHaWZ7bqpx6vaP6aMFnoix7W6UcuHHFYq");
+const LEGACY_PENDING_AUTHORITY = new PublicKey("DLYwuMA5yH6DS1zKNwK9nozLTWsqmvfGcG6agJUVAQ37");
+
+describe('bentobox legacy account migration', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+
+  const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+  const bentobox_idl = require("../../target/idl/bentobox.json");
+
+  const bentoboxOwner = Keypair.fromSecretKey(Uint8Array.from(require("../wallets/devnet.json")));
+  const payer = Keypair.generate();
+
+  const assertBentoboxError = async (_err: any, code: string) => {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+
+    assert.strictEqual(err.error.errorCode.code, code);
+    let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+    assert.strictEqual(err.error.errorMessage, error.errorMsg);
+    assert.strictEqual(err.error.errorCode.number, error.errorCode);
+  };
+
+  const migrateBalance = async (owner: PublicKey) => {
+    const [balance, _bump] = await getBentoboxBalanceAddress(owner, MINT, BENTOBOX_ACCOUNT, bentoboxProgram.programId);
+    await bentoboxProgram.methods.migrateBalance(owner)
+      .accounts({
+        balance,
+        bentoboxAccount: BENTOBOX_ACCOUNT,
+        payer: payer.publicKey,
+        mint: MINT,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+    return balance;
+  };
+
+  const migrateStrategyDataOf = async (strategyData: PublicKey, mint: PublicKey) => bentoboxProgram.methods.migrateStrategyData()
+    .accounts({
+      strategyData,
+      payer: payer.publicKey,
+      mint,
+      bentoboxAccount: BENTOBOX_ACCOUNT,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([payer])
+    .rpc();
+
+  before(async () => {
+    await common.batchAirdrop(connection, [bentoboxOwner, payer]);
+  });
+
+  it('Migrate legacy balance fills mint and owner from seeds', async () => {
+    const balance = await migrateBalance(LEGACY_BALANCE_OWNER);
+
+    const balanceInfo = await connection.getAccountInfo(balance);
+    assert.equal(balanceInfo.data.length, 8 + 8 + 32 + 32);
+
+    const balanceAccount = await bentoboxProgram.account.balance.fetch(balance);
+    assert.equal(balanceAccount.amount.toString(), "1234");
+    assert.equal(balanceAccount.mint.toBase58(), MINT.toBase58());
+    assert.equal(balanceAccount.owner.toBase58(), LEGACY_BALANCE_OWNER.toBase58());
+
+    try {
+      await migrateBalance(LEGACY_BALANCE_OWNER);
+      assert.fail("balance was migrated twice");
+    } catch (_err) {
+      await assertBentoboxError(_err, "InvalidLegacyAccount");
+    }
+  });
+
+  it('Migrated empty legacy balance can be closed', async () => {
+    const balance = await migrateBalance(bentoboxOwner.publicKey);
+
+    await bentoboxProgram.methods.closeBalance()
+      .accounts({
+        balance,
+        bentoboxAccount: BENTOBOX_ACCOUNT,
+        authority: bentoboxOwner.publicKey,
+        mint: MINT,
+      })
+      .signers([bentoboxOwner])
+      .rpc();
+
+    assert.isNull(await connection.getAccountInfo(balance));
+  });
+
+  it('Migrated legacy approval is revoked with approvals of Master Contract', async () => {
+    const [masterContractWhitelisted, _whitelistedBump] = await getMasterContractWhitelistAddress(MASTER_CONTRACT, BENTOBOX_ACCOUNT, bentoboxProgram.programId);
+    const [masterContractApproved, _approvedBump] = await getMasterContractApprovedAddress(MASTER_CONTRACT, bentoboxOwner.publicKey, BENTOBOX_ACCOUNT, bentoboxProgram.programId);
+
+    await bentoboxProgram.methods.migrateMasterContractWhitelisted()
+      .accounts({
+        masterContractWhitelisted,
+        masterContractAccount: MASTER_CONTRACT,
+        bentoboxAccount: BENTOBOX_ACCOUNT,
+        payer: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+
+    await bentoboxProgram.methods.migrateMasterContractApproved(bentoboxOwner.publicKey)
+      .accounts({
+        masterContractApproved,
+        masterContractAccount: MASTER_CONTRACT,
+        bentoboxAccount: BENTOBOX_ACCOUNT,
+        payer: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+
+    let whitelisted = await bentoboxProgram.account.masterContractWhitelisted.fetch(masterContractWhitelisted);
+    assert.equal(whitelisted.masterContractAccount.toBase58(), MASTER_CONTRACT.toBase58());
+    assert.isTrue(whitelisted.whitelisted);
+    assert.equal(whitelisted.approvalsEpoch.toString(), "0");
+
+    let approved = await bentoboxProgram.account.masterContractApproved.fetch(masterContractApproved);
+    assert.equal(approved.masterContractWhitelisted.toBase58(), masterContractWhitelisted.toBase58());
+    assert.isTrue(approved.approved);
+    assert.equal(approved.approvalsEpoch.toString(), "0");
+
+    await bentoboxProgram.methods.revokeMasterContractApprovals()
+      .accounts({
+        masterContractWhitelisted,
+        masterContractAccount: MASTER_CONTRACT,
+        authority: bentoboxOwner.publicKey,
+        bentoboxAccount: BENTOBOX_ACCOUNT,
+      })
+      .signers([bentoboxOwner])
+      .rpc();
+
+    whitelisted = await bentoboxProgram.account.masterContractWhitelisted.fetch(masterContractWhitelisted);
+    assert.equal(whitelisted.approvalsEpoch.toString(), "1");
+    approved = await bentoboxProgram.account.masterContractApproved.fetch(masterContractApproved);
+    assert.notEqual(approved.approvalsEpoch.toString(), whitelisted.approvalsEpoch.toString());
+
+    try {
+      await bentoboxProgram.methods.migrateMasterContractApproved(bentoboxOwner.publicKey)
+        .accounts({
+          masterContractApproved,
+          masterContractAccount: MASTER_CONTRACT,
+          bentoboxAccount: BENTOBOX_ACCOUNT,
+          payer: payer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([payer])
+        .rpc();
+      assert.fail("approval was migrated twice");
+    } catch (_err) {
+      await assertBentoboxError(_err, "InvalidLegacyAccount");
+    }
+  });
+
+  it('Migrate legacy strategy data keeps active strategy', async () => {
+    const [strategyData, _bump] = await getBentoboxStrategyDataAddress(BENTOBOX_ACCOUNT, MINT, bentoboxProgram.programId);
+    const migrateStrategyData = async () => migrateStrategyDataOf(strategyData, MINT);
+
+    await migrateStrategyData();
+
+    const strategyDataAccount = await bentoboxProgram.account.strategyData.fetch(strategyData);
+    assert.equal(strategyDataAccount.balance.toString(), "700");
+    assert.equal(strategyDataAccount.strategyStartDate.toString(), "0");
+    assert.equal(strategyDataAccount.pendingStrategy.toBase58(), PublicKey.default.toBase58());
+    assert.equal(strategyDataAccount.strategies.length, 1);
+    assert.equal(strategyDataAccount.strategies[0].strategy.toBase58(), LEGACY_STRATEGY.toBase58());
+    assert.equal(strategyDataAccount.strategies[0].targetPercentage.toString(), "50");
+    assert.equal(strategyDataAccount.strategies[0].balance.toString(), "700");
+    assert.equal(strategyDataAccount.strategies[0].cumulativeLoss.toString(), "0");
+    assert.equal(strategyDataAccount.withdrawOrder.length, 1);
+    assert.equal(strategyDataAccount.withdrawOrder[0].toBase58(), LEGACY_STRATEGY.toBase58());
+    assert.equal(strategyDataAccount.maxLossBps.toString(), "0");
+    assert.equal(strategyDataAccount.keeperRewardBps.toString(), "0");
+
+    try {
+      await migrateStrategyData();
+      assert.fail("strategy data was migrated twice");
+    } catch (_err) {
+      await assertBentoboxError(_err, "InvalidLegacyAccount");
+    }
+  });
+
+  it('Migrated legacy pending strategy is counted and can be cancelled', async () => {
+    const [strategyData, _bump] = await getBentoboxStrategyDataAddress(BENTOBOX_ACCOUNT, PENDING_MINT, bentoboxProgram.programId);
+
+    await migrateStrategyDataOf(strategyData, PENDING_MINT);
+
+    let strategyDataAccount = await bentoboxProgram.account.strategyData.fetch(strategyData);
+    assert.equal(strategyDataAccount.pendingStrategy.toBase58(), LEGACY_PENDING_STRATEGY.toBase58());
+    assert.equal(strategyDataAccount.strategyStartDate.toString(), "1");
+    assert.equal(strategyDataAccount.strategies.length, 0);
+
+    let bentoboxAccount = await bentoboxProgram.account.bentoBox.fetch(BENTOBOX_ACCOUNT);
+    assert.equal(bentoboxAccount.pendingStrategiesCount.toString(), "1");
+
+    const setStrategyDelay = async (delay: BN) => bentoboxProgram.methods.setStrategyDelay(delay)
+      .accounts({
+        bentoboxAccount: BENTOBOX_ACCOUNT,
+        authority: bentoboxOwner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bentoboxOwner])
+      .rpc();
+
+    // strategy delay can not be shortened while migrated strategy is pending
+    await setStrategyDelay(new BN(10));
+    try {
+      await setStrategyDelay(new BN(0));
+      assert.fail("strategy delay was shortened while strategy is pending");
+    } catch (_err) {
+      await assertBentoboxError(_err, "StrategyDelayShortenedWhilePending");
+    }
+
+    await bentoboxProgram.methods.cancelPendingStrategy()
+      .accounts({
+        strategyData,
+        bentoboxAccount: BENTOBOX_ACCOUNT,
+        mint: PENDING_MINT,
+        authority: bentoboxOwner.publicKey,
+      })
+      .signers([bentoboxOwner])
+      .rpc();
+
+    strategyDataAccount = await bentoboxProgram.account.strategyData.fetch(strategyData);
+    assert.equal(strategyDataAccount.pendingStrategy.toBase58(), PublicKey.default.toBase58());
+    bentoboxAccount = await bentoboxProgram.account.bentoBox.fetch(BENTOBOX_ACCOUNT);
+    assert.equal(bentoboxAccount.pendingStrategiesCount.toString(), "0");
+
+    await setStrategyDelay(new BN(0));
+  });
+
+  it('Migrate legacy bentobox keeps owner and constants', async () => {
+    const migrateBentobox = async () => bentoboxProgram.methods.migrateBentobox()
+      .accounts({
+        bentoboxAccount: LEGACY_BENTOBOX_ACCOUNT,
+        payer: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+
+    await migrateBentobox();
+
+    let bentoboxAccount = await bentoboxProgram.account.bentoBox.fetch(LEGACY_BENTOBOX_ACCOUNT);
+    assert.equal(bentoboxAccount.authority.toBase58(), bentoboxOwner.publicKey.toBase58());
+    assert.equal(bentoboxAccount.pendingAuthority.toBase58(), LEGACY_PENDING_AUTHORITY.toBase58());
+    assert.equal(bentoboxAccount.strategyDelay.toString(), "5");
+    assert.equal(bentoboxAccount.constants.minimumShareBalance.toString(), "1000");
+    assert.equal(bentoboxAccount.constants.maxTargetPercentage.toString(), "95");
+    assert.equal(bentoboxAccount.performanceFeeBps.toString(), "0");
+    assert.equal(bentoboxAccount.feeTo.toBase58(), bentoboxOwner.publicKey.toBase58());
+    assert.equal(bentoboxAccount.pendingStrategiesCount.toString(), "0");
+
+    // owner instructions work on migrated account
+    await bentoboxProgram.methods.setPerformanceFee(new BN(1000), LEGACY_PENDING_AUTHORITY)
+      .accounts({
+        bentoboxAccount: LEGACY_BENTOBOX_ACCOUNT,
+        authority: bentoboxOwner.publicKey,
+      })
+      .signers([bentoboxOwner])
+      .rpc();
+
+    bentoboxAccount = await bentoboxProgram.account.bentoBox.fetch(LEGACY_BENTOBOX_ACCOUNT);
+    assert.equal(bentoboxAccount.performanceFeeBps.toString(), "1000");
+    assert.equal(bentoboxAccount.feeTo.toBase58(), LEGACY_PENDING_AUTHORITY.toBase58());
+
+    try {
+      await migrateBentobox();
+      assert.fail("bentobox was migrated twice");
+    } catch (_err) {
+      await assertBentoboxError(_err, "InvalidLegacyAccount");
+    }
+  });
+});
@@ -0,0 +1,94 @@
+import * as anchor from '@coral-xyz/anchor';
+import { assert } from "chai";
+import { Cauldron as CauldronProgram } from '../../target/types/cauldron';
+import { Program, BN, AnchorError } from "@coral-xyz/anchor"
+import { getCauldronLiquidatorAccountAddress } from "../common/cauldron_pda_helper";
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+
+import * as common from "../common/common";
+
+/// Accounts loaded on validator start by `Test.toml`.
+///
+/// `cauldron.json` - `CAULDRON_ACCOUNT` in current layout, authority is `tests/wallets/devnet.json`, other fields are zero.
+/// `legacy_liquidator_account.json` - `LiquidatorAccount` of `LEGACY_LIQUIDATOR` in `CAULDRON_ACCOUNT` without `payer`, `user`,
+///                                    `borrow_part` and `distribution_amount`. Collateral is swapped by `LEGACY_SWAPPER`,
+///                                    collateral share 5000, borrow amount 300, borrow share 290, real amount 320,
+///                                    timestamp 1700000000.
+const CAULDRON_ACCOUNT = new PublicKey("DT7Z16YyyJXHXF9vQGSZZtjDmAjwfpY8Sj4bGQ2cedV4");
+const LEGACY_LIQUIDATOR = new PublicKey("F5kZvjHUbEGPhQBSY8MSxPqa7bQFvcN7Nyr9aCYHxVog");
+const LEGACY_SWAPPER = new PublicKey("9JsoUpxixdDcKCob5MxRZvHjEEX1JkyU2pkhKDkYJFd");
+
+describe('cauldron legacy account migration', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+
+  const cauldronProgram = anchor.workspace.Cauldron as Program<CauldronProgram>;
+  const cauldron_idl = require("../../target/idl/cauldron.json");
+
+  const cauldronOwner = Keypair.fromSecretKey(Uint8Array.from(require("../wallets/devnet.json")));
+  const notOwner = Keypair.generate();
+  const liquidatedUser = Keypair.generate().publicKey;
+
+  const assertCauldronError = async (_err: any, code: string) => {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+
+    assert.strictEqual(err.error.errorCode.code, code);
+    let error = await common.getErrorInfo(cauldron_idl, err.error.errorCode.code);
+    assert.strictEqual(err.error.errorMessage, error.errorMsg);
+    assert.strictEqual(err.error.errorCode.number, error.errorCode);
+  };
+
+  const migrateLiquidatorAccount = async (signer: Keypair) => {
+    const [liquidatorAccount, _bump] = await getCauldronLiquidatorAccountAddress(LEGACY_LIQUIDATOR, CAULDRON_ACCOUNT, cauldronProgram.programId);
+    await cauldronProgram.methods.migrateLiquidatorAccount(LEGACY_LIQUIDATOR, liquidatedUser, new BN(280))
+      .accounts({
+        liquidatorAccount,
+        cauldronAccount: CAULDRON_ACCOUNT,
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+    return liquidatorAccount;
+  };
+
+  before(async () => {
+    await common.batchAirdrop(connection, [cauldronOwner, notOwner]);
+  });
+
+  it('Migrate legacy liquidator account', async () => {
+    try {
+      await migrateLiquidatorAccount(notOwner);
+      assert.fail("liquidator account was migrated not by cauldron owner");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "ConstraintHasOne");
+    }
+
+    const liquidatorAccountAddress = await migrateLiquidatorAccount(cauldronOwner);
+
+    let liquidatorAccount = await cauldronProgram.account.liquidatorAccount.fetch(liquidatorAccountAddress);
+    assert.equal(liquidatorAccount.originLiquidator.toBase58(), LEGACY_SWAPPER.toBase58());
+    assert.equal(liquidatorAccount.payer.toBase58(), LEGACY_LIQUIDATOR.toBase58());
+    assert.equal(liquidatorAccount.user.toBase58(), liquidatedUser.toBase58());
+    assert.equal(liquidatorAccount.borrowPart.toString(), "280");
+    assert.equal(liquidatorAccount.distributionAmount.toString(), "0");
+    assert.equal(liquidatorAccount.collateralShare.toString(), "5000");
+    assert.equal(liquidatorAccount.borrowAmount.toString(), "300");
+    assert.equal(liquidatorAccount.borrowShare.toString(), "290");
+    assert.equal(liquidatorAccount.realAmount.toString(), "320");
+    assert.equal(liquidatorAccount.timestamp.toString(), "1700000000");
+
+    // rent of the new size is paid by cauldron owner
+    const accountInfo = await connection.getAccountInfo(liquidatorAccountAddress);
+    assert.isAtLeast(accountInfo.lamports, await connection.getMinimumBalanceForRentExemption(accountInfo.data.length));
+
+    try {
+      await migrateLiquidatorAccount(cauldronOwner);
+      assert.fail("liquidator account was migrated twice");
+    } catch (_err) {
+      await assertCauldronError(_err, "InvalidLegacyAccount");
+    }
+  });
+});
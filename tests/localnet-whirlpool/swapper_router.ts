@@ -0,0 +1,214 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { SwapperWhirlpool as SwapperWhirlpoolProgram } from "../../target/types/swapper_whirlpool";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { Token } from "@solana/spl-token";
+import { assert } from "chai";
+import * as common from "../common/common";
+import { WhirlpoolPool } from "../common/whirlpool";
+import { SwapperRouter, RouteHopAccounts } from "../common/swapper_router";
+
+// Requires Whirlpool program cloned from mainnet, see Test.toml in this folder.
+describe("Swapper Router", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const swapperWhirlpoolProgram = anchor.workspace.SwapperWhirlpool as Program<SwapperWhirlpoolProgram>;
+
+    const swapper_router_idl = require("../../target/idl/swapper_router.json");
+
+    const DECIMALS = Math.pow(10, 6);
+
+    const mintOwner = Keypair.generate();
+    // programs are deployed upgradeable by provider wallet, see Test.toml in this folder
+    const routerOwner = (provider.wallet as anchor.Wallet).payer;
+    const carol = Keypair.generate();
+
+    let mintX: Token = null;
+    let mintY: Token = null;
+    let mintZ: Token = null;
+
+    let carolXTokenAccount: PublicKey = null;
+    let carolYTokenAccount: PublicKey = null;
+    let carolZTokenAccount: PublicKey = null;
+
+    let poolXY: WhirlpoolPool = new WhirlpoolPool(provider);
+    let poolYZ: WhirlpoolPool = new WhirlpoolPool(provider);
+
+    let router: SwapperRouter = new SwapperRouter();
+    let route: PublicKey = null;
+
+    // X -> Y -> Z
+    async function getHops(): Promise<RouteHopAccounts[]> {
+        return [
+            {
+                swapperProgram: swapperWhirlpoolProgram.programId,
+                swapProgram: poolXY.getProgramId(),
+                destination: carolYTokenAccount,
+                poolAccounts: await poolXY.getSwapRemainingAccounts(mintX.publicKey),
+            },
+            {
+                swapperProgram: swapperWhirlpoolProgram.programId,
+                swapProgram: poolYZ.getProgramId(),
+                destination: carolZTokenAccount,
+                poolAccounts: await poolYZ.getSwapRemainingAccounts(mintY.publicKey),
+            },
+        ];
+    }
+
+    async function assertRouterError(_err: any, code: string) {
+        assert.isTrue(_err instanceof AnchorError);
+        const err: AnchorError = _err;
+
+        assert.strictEqual(err.error.errorCode.code, code);
+        let error = await common.getErrorInfo(swapper_router_idl, err.error.errorCode.code);
+        assert.strictEqual(err.error.errorMessage, error.errorMsg);
+        assert.strictEqual(err.error.errorCode.number, error.errorCode);
+    }
+
+    before(async () => {
+        await common.batchAirdrop(connection, [mintOwner, carol]);
+
+        mintX = await common.createMintAccount(connection, mintOwner, mintOwner.publicKey, 6);
+        mintY = await common.createMintAccount(connection, mintOwner, mintOwner.publicKey, 6);
+        mintZ = await common.createMintAccount(connection, mintOwner, mintOwner.publicKey, 6);
+
+        // liquidity provider is provider wallet
+        for (const mint of [mintX, mintY, mintZ]) {
+            const walletTokenAccount = await mint.getOrCreateAssociatedAccountInfo(provider.wallet.publicKey);
+            await mint.mintTo(walletTokenAccount.address, mintOwner.publicKey, [mintOwner], 1_000_000 * DECIMALS);
+        }
+
+        carolXTokenAccount = await common.createAndFundUserAccount(carol.publicKey, mintOwner, mintX, 1000 * DECIMALS);
+        carolYTokenAccount = await common.createAndFundUserAccount(carol.publicKey, mintOwner, mintY, 1000 * DECIMALS);
+        carolZTokenAccount = await common.createAndFundUserAccount(carol.publicKey, mintOwner, mintZ, 1000 * DECIMALS);
+
+        await poolXY.create(mintX.publicKey, mintY.publicKey, 100_000);
+        await poolYZ.create(mintY.publicKey, mintZ.publicKey, 100_000);
+
+    });
+
+    it("Initialize router", async () => {
+        try {
+            await router.initialize(carol);
+            assert.fail("only upgrade authority of router program can initialize router");
+        } catch (_err) {
+            await assertRouterError(_err, "UnauthorizedRouterInitialize");
+        }
+
+        await router.initialize(routerOwner);
+
+        const routerAccount = await router.swapperRouterProgram.account.router.fetch(router.getRouterAccount());
+        assert.strictEqual(routerAccount.authority.toBase58(), routerOwner.publicKey.toBase58());
+        assert.strictEqual(routerAccount.hopSwappers.length, 0);
+    });
+
+    it("Create route", async () => {
+        const hops = await getHops();
+
+        try {
+            await router.createRoute(hops, carol);
+            assert.fail("only router authority can create route");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+            assert.strictEqual(err.error.errorCode.code, "ConstraintHasOne");
+        }
+
+        try {
+            await router.createRoute(hops, routerOwner);
+            assert.fail("route with not whitelisted hop swapper is created");
+        } catch (_err) {
+            await assertRouterError(_err, "HopSwapperNotWhitelisted");
+        }
+
+        await router.whitelistHopSwapper(swapperWhirlpoolProgram.programId, true, routerOwner);
+
+        try {
+            await router.createRoute(hops.slice(0, 1), routerOwner);
+        } catch (_err) {
+            await assertRouterError(_err, "InvalidRouteHopsCount");
+        }
+
+        route = await router.createRoute(hops, routerOwner);
+    });
+
+    it("Swap through two hops", async () => {
+        const amountIn = new BN(10 * DECIMALS);
+
+        const xBefore = (await mintX.getAccountInfo(carolXTokenAccount)).amount;
+        const yBefore = (await mintY.getAccountInfo(carolYTokenAccount)).amount;
+        const zBefore = (await mintZ.getAccountInfo(carolZTokenAccount)).amount;
+
+        await router.swap(route, await getHops(), carolXTokenAccount, amountIn, new BN(1), carol);
+
+        const xAfter = (await mintX.getAccountInfo(carolXTokenAccount)).amount;
+        const yAfter = (await mintY.getAccountInfo(carolYTokenAccount)).amount;
+        const zAfter = (await mintZ.getAccountInfo(carolZTokenAccount)).amount;
+
+        assert.strictEqual(xBefore.sub(xAfter).toString(), amountIn.toString());
+        // whole intermediate output is swapped by the next hop
+        assert.strictEqual(yAfter.toString(), yBefore.toString());
+        // price of both pools is 1, so only two fees and small price impact are lost
+        assert.isTrue(zAfter.sub(zBefore).gt(amountIn.muln(98).divn(100)));
+        assert.isTrue(zAfter.sub(zBefore).lt(amountIn));
+    });
+
+    it("Swap with too high minimum amount out", async () => {
+        const amountIn = new BN(10 * DECIMALS);
+
+        try {
+            await router.swap(route, await getHops(), carolXTokenAccount, amountIn, amountIn, carol);
+            assert.fail("swap should fail");
+        } catch (_err) {
+            // final output is checked by last hop swap program first
+            assert.isTrue(_err instanceof anchor.web3.SendTransactionError || _err instanceof AnchorError);
+        }
+    });
+
+    it("Swap with wrong hop swapper", async () => {
+        const hops = await getHops();
+        hops[1].swapperProgram = router.getSwapperRouterProgram();
+
+        try {
+            await router.swap(route, hops, carolXTokenAccount, new BN(DECIMALS), new BN(1), carol);
+        } catch (_err) {
+            await assertRouterError(_err, "InvalidHopSwapper");
+        }
+    });
+
+    it("Swap with intermediate account of other owner", async () => {
+        const hops = await getHops();
+        hops[0].destination = (await mintY.getOrCreateAssociatedAccountInfo(provider.wallet.publicKey)).address;
+
+        try {
+            await router.swap(route, hops, carolXTokenAccount, new BN(DECIMALS), new BN(1), carol);
+        } catch (_err) {
+            await assertRouterError(_err, "InvalidIntermediateTokenAccount");
+        }
+    });
+
+    it("Swap with hop swapper removed from whitelist", async () => {
+        await router.whitelistHopSwapper(swapperWhirlpoolProgram.programId, false, routerOwner);
+
+        try {
+            await router.swap(route, await getHops(), carolXTokenAccount, new BN(DECIMALS), new BN(1), carol);
+            assert.fail("swapped through not whitelisted hop swapper");
+        } catch (_err) {
+            await assertRouterError(_err, "HopSwapperNotWhitelisted");
+        }
+
+        await router.whitelistHopSwapper(swapperWhirlpoolProgram.programId, true, routerOwner);
+    });
+
+    it("Close route", async () => {
+        await router.closeRoute(route, routerOwner);
+
+        try {
+            await router.swap(route, await getHops(), carolXTokenAccount, new BN(DECIMALS), new BN(1), carol);
+        } catch (_err) {
+            await assertRouterError(_err, "InvalidRouteAccount");
+        }
+    });
+});
@@ -0,0 +1,156 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { SwapperWhirlpool as SwapperWhirlpoolProgram } from "../../target/types/swapper_whirlpool";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { Token, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import * as common from "../common/common";
+import { WhirlpoolPool } from "../common/whirlpool";
+
+// Requires Whirlpool program fixture, see Test.toml in this folder.
+describe("Swapper Whirlpool", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const swapperWhirlpoolProgram = anchor.workspace.SwapperWhirlpool as Program<SwapperWhirlpoolProgram>;
+
+    const swapper_whirlpool_idl = require("../../target/idl/swapper_whirlpool.json");
+
+    const DECIMALS = Math.pow(10, 6);
+
+    const mintOwner = Keypair.generate();
+    const carol = Keypair.generate();
+
+    let mintX: Token = null;
+    let mintY: Token = null;
+    let mintZ: Token = null;
+
+    let carolXTokenAccount: PublicKey = null;
+    let carolYTokenAccount: PublicKey = null;
+    let carolZTokenAccount: PublicKey = null;
+
+    let pool: WhirlpoolPool = new WhirlpoolPool(provider);
+
+    before(async () => {
+        await common.batchAirdrop(connection, [mintOwner, carol]);
+
+        mintX = await common.createMintAccount(connection, mintOwner, mintOwner.publicKey, 6);
+        mintY = await common.createMintAccount(connection, mintOwner, mintOwner.publicKey, 6);
+        mintZ = await common.createMintAccount(connection, mintOwner, mintOwner.publicKey, 6);
+
+        // liquidity provider is provider wallet
+        for (const mint of [mintX, mintY]) {
+            const walletTokenAccount = await mint.getOrCreateAssociatedAccountInfo(provider.wallet.publicKey);
+            await mint.mintTo(walletTokenAccount.address, mintOwner.publicKey, [mintOwner], 1_000_000 * DECIMALS);
+        }
+
+        carolXTokenAccount = await common.createAndFundUserAccount(carol.publicKey, mintOwner, mintX, 1000 * DECIMALS);
+        carolYTokenAccount = await common.createAndFundUserAccount(carol.publicKey, mintOwner, mintY, 1000 * DECIMALS);
+        carolZTokenAccount = await common.createAndFundUserAccount(carol.publicKey, mintOwner, mintZ, 1000 * DECIMALS);
+
+        await pool.create(mintX.publicKey, mintY.publicKey, 100_000);
+    });
+
+    async function swap(source: PublicKey, sourceMint: PublicKey, destination: PublicKey, amountIn: BN, minimumAmountOut: BN, swapProgram: PublicKey = pool.getProgramId()) {
+        await swapperWhirlpoolProgram.methods.swap(amountIn, minimumAmountOut)
+            .accounts({
+                sourceTokenAccount: source,
+                destinationTokenAccount: destination,
+                swapProgram,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                authority: carol.publicKey,
+            })
+            .remainingAccounts(await pool.getSwapRemainingAccounts(sourceMint))
+            .signers([carol])
+            .rpc();
+    }
+
+    it("Swap in both directions", async () => {
+        const amountIn = new BN(10 * DECIMALS);
+
+        for (const [source, sourceMint, destination, destinationMint] of [
+            [carolXTokenAccount, mintX, carolYTokenAccount, mintY],
+            [carolYTokenAccount, mintY, carolXTokenAccount, mintX],
+        ] as [PublicKey, Token, PublicKey, Token][]) {
+            const sourceBefore = (await sourceMint.getAccountInfo(source)).amount;
+            const destinationBefore = (await destinationMint.getAccountInfo(destination)).amount;
+
+            await swap(source, sourceMint.publicKey, destination, amountIn, new BN(1));
+
+            const sourceAfter = (await sourceMint.getAccountInfo(source)).amount;
+            const destinationAfter = (await destinationMint.getAccountInfo(destination)).amount;
+
+            assert.strictEqual(sourceBefore.sub(sourceAfter).toString(), amountIn.toString());
+            // price is 1 and pool is deep, so only fee and small price impact are lost
+            assert.isTrue(destinationAfter.sub(destinationBefore).gt(amountIn.muln(99).divn(100)));
+            assert.isTrue(destinationAfter.sub(destinationBefore).lt(amountIn));
+        }
+    });
+
+    it("Swap logs verified amounts", async () => {
+        const amountIn = new BN(10 * DECIMALS);
+        const destinationBefore = (await mintY.getAccountInfo(carolYTokenAccount)).amount;
+
+        let listener = null;
+        let [event, slot] = await new Promise((resolve, _reject) => {
+            listener = swapperWhirlpoolProgram.addEventListener("LogSwap", (event, slot) => {
+                resolve([event, slot]);
+            });
+
+            swap(carolXTokenAccount, mintX.publicKey, carolYTokenAccount, amountIn, new BN(1));
+        });
+        await swapperWhirlpoolProgram.removeEventListener(listener);
+
+        const destinationAfter = (await mintY.getAccountInfo(carolYTokenAccount)).amount;
+
+        assert.isAbove(slot, 0);
+        assert.strictEqual(event.pool.toBase58(), pool.whirlpoolKey.toBase58());
+        assert.strictEqual(event.amountIn.toString(), amountIn.toString());
+        assert.strictEqual(event.amountOut.toString(), destinationAfter.sub(destinationBefore).toString());
+        // price has 1e9 precision
+        assert.strictEqual(event.price.toString(), event.amountOut.muln(1e9).div(event.amountIn).toString());
+    });
+
+    it("Swap with too high minimum amount out", async () => {
+        const amountIn = new BN(10 * DECIMALS);
+
+        try {
+            await swap(carolXTokenAccount, mintX.publicKey, carolYTokenAccount, amountIn, amountIn);
+            assert.fail("swap should fail");
+        } catch (_err) {
+            // slippage is checked by Whirlpool program
+            assert.isTrue(_err instanceof anchor.web3.SendTransactionError || _err instanceof AnchorError);
+        }
+    });
+
+    it("Swap tokens which are not in pool", async () => {
+        try {
+            await swap(carolZTokenAccount, mintZ.publicKey, carolYTokenAccount, new BN(DECIMALS), new BN(1));
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "InvalidWhirlpoolMints");
+            let error = await common.getErrorInfo(swapper_whirlpool_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+
+    it("Swap through program which is not Whirlpool", async () => {
+        // program which would receive swapper signer, pool accounts are still owned by Whirlpool
+        try {
+            await swap(carolXTokenAccount, mintX.publicKey, carolYTokenAccount, new BN(DECIMALS), new BN(1), swapperWhirlpoolProgram.programId);
+            assert.fail("swap through not Whirlpool program should fail");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "InvalidSwapProgram");
+            let error = await common.getErrorInfo(swapper_whirlpool_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+});
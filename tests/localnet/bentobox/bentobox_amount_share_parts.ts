@@ -82,17 +82,21 @@ describe("ToShare and ToAmount parts", () => {
         await bentobox.createBalance(Dan.publicKey, mint.publicKey, Dan)
         // create strategy data account for mint token
         await bentobox.createStrategyData(mint.publicKey)
+        // whitelist strategy program for bentobox
+        await bentobox.createStrategyProgramWhitelist(strategyMockProgram.programId)
         // set strategy delay
         await bentobox.setStrategyDelay(new BN(10));
+        // register strategy owner as keeper for token
+        await bentobox.createStrategyKeeper(strategyMockOwner.publicKey, mint.publicKey)
 
-        await bentobox.setStrategyTargetPercentage(new BN(10), mint.publicKey)
 
         // initialize mock_strategy
         await strategyMock.initialize(mint.publicKey, bentobox.getBentoboxAccount(), bentoboxProgram.programId, poolVault, poolOwner, strategyMockOwner)
         // try set active strategy
-        await bentoboxProgram.methods.setStrategy()
+        await bentoboxProgram.methods.queueStrategy()
             .accounts({
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -115,9 +119,10 @@ describe("ToShare and ToAmount parts", () => {
         let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
         await delay(12 * 1000);
 
-        await bentoboxProgram.methods.setStrategy()
+        await bentoboxProgram.methods.activateStrategy()
             .accounts({
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -136,6 +141,8 @@ describe("ToShare and ToAmount parts", () => {
                 { pubkey: poolVault, isWritable: true, isSigner: false },
             ])
             .signers([bentoboxOwner]).rpc()
+
+        await bentobox.setStrategyTargetPercentage(new BN(10), mint.publicKey, strategyMock.getStrategyMockAccount())
     });
 
     it("toAmount and toShare parts, two users", async () => {
@@ -180,6 +187,7 @@ describe("ToShare and ToAmount parts", () => {
                 baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -191,7 +199,7 @@ describe("ToShare and ToAmount parts", () => {
                 tokenProgram: TOKEN_PROGRAM_ID,
                 cpiResultAccount: strategyMock.getCpiResultAccount(),
                 strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                executorInfo: strategyMock.getStrategyMockExecutorInfoPda(strategyMockOwner.publicKey)
+                strategyKeeper: bentobox.getStrategyKeeperPda(strategyMockOwner.publicKey, mint.publicKey)
             })
             .remainingAccounts([
                 { pubkey: poolVault, isWritable: true, isSigner: false },
@@ -203,6 +211,7 @@ describe("ToShare and ToAmount parts", () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -241,7 +250,7 @@ describe("ToShare and ToAmount parts", () => {
         assert.equal(reader.readU64().toNumber(), 1200 * mintDecimal);
 
         // 3. Another user (Dan) deposits on bentobox
-        tx = await bentoboxProgram.methods.deposit(Dan.publicKey, new BN(depositAmount * mintDecimal), new BN(0))
+        tx = await bentoboxProgram.methods.deposit(Dan.publicKey, new BN(depositAmount * mintDecimal), new BN(0), new BN(0))
             .accounts({
                 from: danTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
@@ -291,6 +300,7 @@ describe("ToShare and ToAmount parts", () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -384,6 +394,7 @@ describe("ToShare and ToAmount parts", () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
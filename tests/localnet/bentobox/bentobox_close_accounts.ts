@@ -0,0 +1,151 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { Bentobox as BentoboxProgram } from "../../../target/types/bentobox";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { Token } from "@solana/spl-token";
+import { assert } from "chai";
+import * as common from "../../common/common";
+
+import { TEST_PRICE, COLLATERIZATION_RATE_PRECISION } from "../../common/src";
+import { Bentobox } from "../../common/bentobox"
+import { Cauldron } from "../../common/cauldron"
+
+describe("Close balance and master contract approval accounts", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+
+    const bentobox_idl = require("../../../target/idl/bentobox.json");
+
+    let bentobox = new Bentobox();
+    let cauldron = new Cauldron();
+
+    let mimMint: Token = null;
+    let mint: Token = null;
+
+    const depositAmount = 1000;
+
+    const bentoboxOwner = Keypair.generate();
+    const cauldronOwner = Keypair.generate();
+    const Bob = Keypair.generate();
+    const Carol = Keypair.generate();
+
+    let BobTokenAccount: PublicKey = null;
+
+    // rent of closed account should be returned to the user, transaction fee is paid by provider wallet
+    async function assertClosedWithRefund(user: PublicKey, account: PublicKey, close: () => Promise<any>) {
+        const rent = (await connection.getAccountInfo(account)).lamports;
+        const lamportsBefore = await connection.getBalance(user);
+
+        await close();
+
+        assert.isNull(await connection.getAccountInfo(account));
+        const lamportsAfter = await connection.getBalance(user);
+        assert.strictEqual(lamportsAfter - lamportsBefore, rent);
+    }
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, cauldronOwner, Bob, Carol]);
+
+        mimMint = await common.createMintAccount(connection, cauldronOwner, cauldronOwner.publicKey, 0);
+        mint = await common.createMintAccount(connection, bentoboxOwner, bentoboxOwner.publicKey, 0);
+
+        BobTokenAccount = await common.createAndFundUserAccount(Bob.publicKey, bentoboxOwner, mint, depositAmount);
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for mint
+        await bentobox.createVault(mint.publicKey, Bob);
+        // create strategy data account for mint token
+        await bentobox.createStrategyData(mint.publicKey)
+        // create balance account for Bob
+        await bentobox.createBalance(Bob.publicKey, mint.publicKey, Bob)
+
+        // initialize cauldron account
+        await cauldron.initialize(cauldronOwner, mimMint.publicKey, mint.publicKey, TEST_PRICE, bentobox.getBentoboxAccount(), COLLATERIZATION_RATE_PRECISION, new BN(1000000))
+        //register cauldron to bentobox as master contract
+        await bentobox.createMasterContractWhitelist(cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+        // create bentobox approval account for Bob
+        await bentobox.createMasterContractApproval(Bob, cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+    });
+
+    it("Close not empty balance", async () => {
+        await bentobox.deposit(mint.publicKey, BobTokenAccount, Bob.publicKey, new BN(depositAmount), new BN(0), Bob);
+
+        try {
+            await bentobox.closeBalance(Bob, mint.publicKey);
+            assert.fail("closed not empty balance");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "BalanceIsNotEmpty");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+
+        let balance = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(Bob.publicKey, mint.publicKey));
+        assert.strictEqual(balance.amount.toString(), depositAmount.toString());
+    });
+
+    it("Close balance not by owner", async () => {
+        await bentobox.withdraw(mint.publicKey, Bob.publicKey, BobTokenAccount, new BN(0), new BN(depositAmount), Bob);
+
+        try {
+            await bentoboxProgram.methods.closeBalance()
+                .accounts({
+                    balance: bentobox.getBalancePda(Bob.publicKey, mint.publicKey),
+                    bentoboxAccount: bentobox.getBentoboxAccount(),
+                    authority: Carol.publicKey,
+                    mint: mint.publicKey,
+                })
+                .signers([Carol])
+                .rpc();
+            assert.fail("closed balance not by owner");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "ConstraintSeeds");
+            assert.strictEqual(err.error.errorCode.number, 2006);
+        }
+
+        assert.isNotNull(await connection.getAccountInfo(bentobox.getBalancePda(Bob.publicKey, mint.publicKey)));
+    });
+
+    it("Close empty balance", async () => {
+        await assertClosedWithRefund(Bob.publicKey, bentobox.getBalancePda(Bob.publicKey, mint.publicKey),
+            () => bentobox.closeBalance(Bob, mint.publicKey));
+    });
+
+    it("Close master contract approval not by user", async () => {
+        try {
+            await bentoboxProgram.methods.closeMasterContractApproval()
+                .accounts({
+                    masterContractApproved: bentobox.getMasterContractApprovedPda(Bob.publicKey, cauldron.getCauldronAccount()),
+                    masterContractAccount: cauldron.getCauldronAccount(),
+                    authority: Carol.publicKey,
+                    bentoboxAccount: bentobox.getBentoboxAccount(),
+                }).signers([Carol])
+                .rpc();
+            assert.fail("closed master contract approval not by user");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "ConstraintSeeds");
+            assert.strictEqual(err.error.errorCode.number, 2006);
+        }
+
+        let approved = await bentoboxProgram.account.masterContractApproved.fetch(bentobox.getMasterContractApprovedPda(Bob.publicKey, cauldron.getCauldronAccount()));
+        assert.isTrue(approved.approved);
+    });
+
+    it("Close master contract approval", async () => {
+        await assertClosedWithRefund(Bob.publicKey, bentobox.getMasterContractApprovedPda(Bob.publicKey, cauldron.getCauldronAccount()),
+            () => bentobox.closeMasterContractApproval(Bob, cauldron.getCauldronAccount()));
+    });
+});
@@ -67,7 +67,7 @@ describe("BentoBox decimals operations", () => {
         let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
         await delay(5 * 1000);
 
-        tx = await bentoboxProgram.methods.deposit(Bob.publicKey, new BN(9000000 * collateralDecimal), new BN(0))
+        tx = await bentoboxProgram.methods.deposit(Bob.publicKey, new BN(9000000 * collateralDecimal), new BN(0), new BN(0))
             .accounts({
                 from: BobTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
@@ -113,7 +113,7 @@ describe("BentoBox decimals operations", () => {
         assert.ok(total.amount["elastic"].toString() == "9000000000000000");
 
 
-        tx = await bentoboxProgram.methods.deposit(Bob.publicKey, new BN(9000000 * collateralDecimal), new BN(0))
+        tx = await bentoboxProgram.methods.deposit(Bob.publicKey, new BN(9000000 * collateralDecimal), new BN(0), new BN(0))
             .accounts({
                 from: BobTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
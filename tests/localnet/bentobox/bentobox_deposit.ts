@@ -63,7 +63,7 @@ describe("Deposit to BentoBox", () => {
         let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
         await delay(5 * 1000);
 
-        tx = await bentoboxProgram.methods.deposit(Bob.publicKey, new BN(depositAmount), new BN(0))
+        tx = await bentoboxProgram.methods.deposit(Bob.publicKey, new BN(depositAmount), new BN(0), new BN(0))
             .accounts({
                 from: BobTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
@@ -109,7 +109,7 @@ describe("Deposit to BentoBox", () => {
         assert.ok(total.amount["base"].toString() == "1000");
         assert.ok(total.amount["elastic"].toString() == "1000");
 
-        tx = await bentoboxProgram.methods.deposit(Bob.publicKey, new BN(Math.round(depositAmount / 2)), new BN(0))
+        tx = await bentoboxProgram.methods.deposit(Bob.publicKey, new BN(Math.round(depositAmount / 2)), new BN(0), new BN(0))
             .accounts({
                 from: BobTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
@@ -62,11 +62,14 @@ describe('bentobox_harvest', () => {
         await bentobox.createBalance(Bob.publicKey, mint.publicKey, Bob)
         // create strategy data account for mint token
         await bentobox.createStrategyData(mint.publicKey)
+        // whitelist strategy program for bentobox
+        await bentobox.createStrategyProgramWhitelist(strategyMockProgram.programId)
         // set strategy delay
         await bentobox.setStrategyDelay(new BN(10));
+        // register strategy owner as keeper for token
+        await bentobox.createStrategyKeeper(strategyMockOwner.publicKey, mint.publicKey)
 
         await bentobox.deposit(mint.publicKey, BobTokenAccount, Bob.publicKey, new BN(2000), new BN(0), Bob)
-        await bentobox.setStrategyTargetPercentage(new BN(10), mint.publicKey)
 
         pool_vault = await common.createAndFundUserAccount(
             pool_owner.publicKey,
@@ -97,6 +100,7 @@ describe('bentobox_harvest', () => {
                     strategyAccount: strategyMock.getStrategyMockAccount(),
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                     totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -126,9 +130,10 @@ describe('bentobox_harvest', () => {
 
         // try to do harvest when set only pending strategy
         // set pending strategy
-        await bentoboxProgram.methods.setStrategy()
+        await bentoboxProgram.methods.queueStrategy()
             .accounts({
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -154,6 +159,7 @@ describe('bentobox_harvest', () => {
                     strategyAccount: strategyMock.getStrategyMockAccount(),
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                     totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -186,9 +192,10 @@ describe('bentobox_harvest', () => {
         await delay(12 * 1000);
 
         // try set active strategy
-        await bentoboxProgram.methods.setStrategy()
+        await bentoboxProgram.methods.activateStrategy()
             .accounts({
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -208,6 +215,8 @@ describe('bentobox_harvest', () => {
             ])
             .signers([bentoboxOwner]).rpc()
 
+        await bentobox.setStrategyTargetPercentage(new BN(10), mint.publicKey, strategyMock.getStrategyMockAccount())
+
 
         // try to use another than active strategy
 
@@ -217,6 +226,7 @@ describe('bentobox_harvest', () => {
                     strategyAccount: strategyMock_v2.getStrategyMockAccount(),
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     strategyVault: strategyMock_v2.getTotalVaultPda(mint.publicKey),
                     totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -251,6 +261,7 @@ describe('bentobox_harvest', () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -291,6 +302,7 @@ describe('bentobox_harvest', () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -334,6 +346,7 @@ describe('bentobox_harvest', () => {
                 baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -345,7 +358,7 @@ describe('bentobox_harvest', () => {
                 tokenProgram: TOKEN_PROGRAM_ID,
                 cpiResultAccount: strategyMock.getCpiResultAccount(),
                 strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                executorInfo: strategyMock.getStrategyMockExecutorInfoPda(strategyMockOwner.publicKey)
+                strategyKeeper: bentobox.getStrategyKeeperPda(strategyMockOwner.publicKey, mint.publicKey)
             })
             .remainingAccounts([
                 { pubkey: pool_vault, isWritable: true, isSigner: false },
@@ -402,6 +415,7 @@ describe('bentobox_harvest', () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -454,6 +468,7 @@ describe('bentobox_harvest', () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -502,6 +517,7 @@ describe('bentobox_harvest', () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -570,6 +586,7 @@ describe('bentobox_harvest', () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -628,6 +645,7 @@ describe('bentobox_harvest', () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -666,7 +684,7 @@ describe('bentobox_harvest', () => {
         let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
         await delay(5 * 1000);
         //prepare conditions
-        await bentoboxProgram.methods.deposit(Bob.publicKey, new BN(2000), new BN(0))
+        await bentoboxProgram.methods.deposit(Bob.publicKey, new BN(2000), new BN(0), new BN(0))
             .accounts({
                 from: BobTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
@@ -695,6 +713,7 @@ describe('bentobox_harvest', () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -747,6 +766,7 @@ describe('bentobox_harvest', () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -800,6 +820,7 @@ describe('bentobox_harvest', () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -0,0 +1,141 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { Bentobox as BentoboxProgram } from "../../../target/types/bentobox";
+import { PublicKey, Keypair, Ed25519Program } from "@solana/web3.js";
+import { Token } from "@solana/spl-token";
+import { assert } from "chai";
+import * as common from "../../common/common";
+
+import { TEST_PRICE, COLLATERIZATION_RATE_PRECISION } from "../../common/src";
+import { Bentobox } from "../../common/bentobox"
+import { Cauldron } from "../../common/cauldron"
+
+// Approval is signed off-chain by Alice and sent by relayer Bob, cauldron is the master contract.
+describe("Master contract approval with permit", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+
+    const bentobox_idl = require("../../../target/idl/bentobox.json");
+
+    let bentobox = new Bentobox();
+    let cauldron = new Cauldron();
+
+    let mimMint: Token = null;
+    let collateralMint: Token = null;
+
+    const bentoboxOwner = Keypair.generate();
+    const cauldronOwner = Keypair.generate();
+    const Alice = Keypair.generate();
+    const Bob = Keypair.generate();
+    const Carol = Keypair.generate();
+
+    let masterContractAccount: PublicKey = null;
+    let masterContractProgram: PublicKey = null;
+
+    async function assertError(promise: Promise<any>, code: string) {
+        try {
+            await promise;
+            assert.fail(`Expected ${code}`);
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, code);
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    }
+
+    async function getExpiry(secondsFromNow: number) {
+        const slot = await connection.getSlot();
+        const timestamp = await connection.getBlockTime(slot);
+        return new BN(timestamp + secondsFromNow);
+    }
+
+    // ed25519 verification of the message signed by `signer`
+    function signPermit(signer: Keypair, message: Buffer) {
+        return Ed25519Program.createInstructionWithPrivateKey({ privateKey: signer.secretKey, message });
+    }
+
+    // Bob sends Alice permit, `signer` and `message` are the ones verified by ed25519 program
+    async function setApprovalWithPermit(approved: boolean, nonce: BN, expiry: BN, signer: Keypair = Alice, message: Buffer = null) {
+        const permitMessage = message ?? bentobox.getApprovalPermitMessage(masterContractAccount, Alice.publicKey, approved, nonce, expiry);
+        await bentobox.setMasterContractApprovalWithPermit(Alice.publicKey, approved, nonce, expiry,
+            masterContractAccount, masterContractProgram, Bob, [signPermit(signer, permitMessage)]);
+    }
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, cauldronOwner, Bob]);
+
+        mimMint = await common.createMintAccount(connection, bentoboxOwner, bentoboxOwner.publicKey, 0);
+        collateralMint = await common.createMintAccount(connection, cauldronOwner, cauldronOwner.publicKey, 0);
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // initialize cauldron account
+        await cauldron.initialize(cauldronOwner, mimMint.publicKey, collateralMint.publicKey, TEST_PRICE, bentobox.getBentoboxAccount(), COLLATERIZATION_RATE_PRECISION, new BN(1000000))
+
+        masterContractAccount = cauldron.getCauldronAccount();
+        masterContractProgram = cauldron.getCauldronProgram();
+        //register cauldron to bentobox as master contract
+        await bentobox.createMasterContractWhitelist(masterContractAccount, masterContractProgram)
+    });
+
+    it("Approve with permit", async () => {
+        const expiry = await getExpiry(600);
+        await setApprovalWithPermit(true, new BN(0), expiry);
+
+        let approved = await bentoboxProgram.account.masterContractApproved.fetch(bentobox.getMasterContractApprovedPda(Alice.publicKey, masterContractAccount));
+        assert.isTrue(approved.approved);
+
+        let permitNonce = await bentoboxProgram.account.permitNonce.fetch(await bentobox.getPermitNoncePda(Alice.publicKey));
+        assert.equal(permitNonce.nonce.toString(), "1");
+    });
+
+    it("Approve with replayed permit nonce", async () => {
+        const expiry = await getExpiry(600);
+        await assertError(setApprovalWithPermit(true, new BN(0), expiry), "InvalidPermitNonce");
+    });
+
+    it("Approve with permit signed by other user", async () => {
+        const expiry = await getExpiry(600);
+        await assertError(setApprovalWithPermit(false, new BN(1), expiry, Carol), "InvalidPermitSignature");
+    });
+
+    it("Approve with other message in permit", async () => {
+        const expiry = await getExpiry(600);
+        // Alice signed approval, Bob sends revocation
+        const signedMessage = bentobox.getApprovalPermitMessage(masterContractAccount, Alice.publicKey, true, new BN(1), expiry);
+        await assertError(setApprovalWithPermit(false, new BN(1), expiry, Alice, signedMessage), "InvalidPermitSignature");
+    });
+
+    it("Approve with expired permit", async () => {
+        const expiry = await getExpiry(-60);
+        await assertError(setApprovalWithPermit(false, new BN(1), expiry), "PermitExpired");
+    });
+
+    it("Approve without ed25519 instruction", async () => {
+        const expiry = await getExpiry(600);
+        await assertError(bentobox.setMasterContractApprovalWithPermit(Alice.publicKey, false, new BN(1), expiry,
+            masterContractAccount, masterContractProgram, Bob, []), "InvalidPermitSignature");
+
+        // failed permits don't use the nonce
+        let permitNonce = await bentoboxProgram.account.permitNonce.fetch(await bentobox.getPermitNoncePda(Alice.publicKey));
+        assert.equal(permitNonce.nonce.toString(), "1");
+    });
+
+    it("Revoke with permit", async () => {
+        const expiry = await getExpiry(600);
+        await setApprovalWithPermit(false, new BN(1), expiry);
+
+        let approved = await bentoboxProgram.account.masterContractApproved.fetch(bentobox.getMasterContractApprovedPda(Alice.publicKey, masterContractAccount));
+        assert.isFalse(approved.approved);
+
+        let permitNonce = await bentoboxProgram.account.permitNonce.fetch(await bentobox.getPermitNoncePda(Alice.publicKey));
+        assert.equal(permitNonce.nonce.toString(), "2");
+    });
+});
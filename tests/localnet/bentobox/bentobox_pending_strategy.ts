@@ -0,0 +1,173 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, BN, AnchorError } from '@coral-xyz/anchor';
+import { Bentobox as BentoboxProgram } from "../../../target/types/bentobox";
+import { StrategyMock as StrategyMockProgram } from "../../../target/types/strategy_mock";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { Token } from "@solana/spl-token";
+import { assert } from "chai";
+
+import { Bentobox } from "../../common/bentobox"
+import { StrategyMock } from "../../common/mock_strategy"
+
+import * as common from "../../common/common";
+
+describe("Bentobox pending strategy", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const strategyMockProgram = anchor.workspace.StrategyMock as Program<StrategyMockProgram>;
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+
+    const bentobox_idl = require("../../../target/idl/bentobox.json");
+
+    let mint: Token = null;
+
+    const strategyMockOwner = Keypair.generate();
+    const bentoboxOwner = Keypair.generate();
+    const pool_owner = Keypair.generate();
+
+    let bentobox = new Bentobox();
+    let strategyMock = new StrategyMock()
+    let pool_vault: PublicKey = null;
+
+    const defaultPubKey = new PublicKey("11111111111111111111111111111111");
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, strategyMockOwner]);
+
+        mint = await common.createMintAccount(
+            connection,
+            bentoboxOwner,
+            bentoboxOwner.publicKey,
+            0
+        );
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for mint
+        await bentobox.createVault(mint.publicKey, bentoboxOwner);
+        // create strategy data account for mint token
+        await bentobox.createStrategyData(mint.publicKey)
+        // whitelist strategy program for bentobox
+        await bentobox.createStrategyProgramWhitelist(strategyMockProgram.programId)
+        // set strategy delay
+        await bentobox.setStrategyDelay(new BN(10));
+
+        pool_vault = await common.createAndFundUserAccount(
+            pool_owner.publicKey,
+            bentoboxOwner,
+            mint,
+            0
+        );
+
+        // initialize mock_strategy
+        await strategyMock.initialize(mint.publicKey, bentobox.getBentoboxAccount(), bentoboxProgram.programId, pool_vault, pool_owner, strategyMockOwner)
+    });
+
+    it("Queue strategy", async () => {
+        await bentobox.queueStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+
+        let strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
+        assert.equal(strategy_data_acc.pendingStrategy.toString(), strategyMock.getStrategyMockAccount().toString());
+
+        let bentobox_data = await bentoboxProgram.account.bentoBox.fetch(bentobox.getBentoboxAccount());
+        assert.equal(bentobox_data.pendingStrategiesCount.toString(), "1");
+
+        let pending = await bentoboxProgram.methods.getPendingStrategy()
+            .accounts({
+                strategyData: bentobox.getStrategyDataPda(mint.publicKey),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                mint: mint.publicKey,
+            }).view();
+        assert.equal(pending.strategy.toString(), strategyMock.getStrategyMockAccount().toString());
+        assert.equal(pending.strategyStartDate.toString(), strategy_data_acc.strategyStartDate.toString());
+        assert.isFalse(pending.activatable);
+
+        // only one strategy can be pending for token
+        try {
+            await bentobox.queueStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "StrategyAlreadyPending");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+
+    it("Strategy delay can not be shortened while strategy is pending", async () => {
+        try {
+            await bentobox.setStrategyDelay(new BN(0));
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "StrategyDelayShortenedWhilePending");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+
+        await bentobox.setStrategyDelay(new BN(20));
+        let bentobox_data = await bentoboxProgram.account.bentoBox.fetch(bentobox.getBentoboxAccount());
+        assert.equal(bentobox_data.strategyDelay.toString(), "20");
+    });
+
+    it("Cancel pending strategy", async () => {
+        await bentobox.cancelPendingStrategy(mint.publicKey)
+
+        let strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
+        assert.equal(strategy_data_acc.pendingStrategy.toString(), defaultPubKey.toString());
+        assert.ok(strategy_data_acc.strategyStartDate.isZero());
+
+        let bentobox_data = await bentoboxProgram.account.bentoBox.fetch(bentobox.getBentoboxAccount());
+        assert.equal(bentobox_data.pendingStrategiesCount.toString(), "0");
+
+        // cancelled strategy can not be activated
+        try {
+            await bentobox.activateStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "StrategyNotPending");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+
+        try {
+            await bentobox.cancelPendingStrategy(mint.publicKey)
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "StrategyNotPending");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+
+        // no pending strategies, delay can be shortened
+        await bentobox.setStrategyDelay(new BN(0));
+    });
+
+    it("Activate strategy", async () => {
+        await bentobox.queueStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+
+        let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
+        await delay(2 * 1000);
+
+        await bentobox.activateStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+
+        let strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
+        assert.equal(strategy_data_acc.pendingStrategy.toString(), defaultPubKey.toString());
+        assert.equal(strategy_data_acc.strategies[0].strategy.toString(), strategyMock.getStrategyMockAccount().toString());
+
+        let bentobox_data = await bentoboxProgram.account.bentoBox.fetch(bentobox.getBentoboxAccount());
+        assert.equal(bentobox_data.pendingStrategiesCount.toString(), "0");
+    });
+});
@@ -0,0 +1,176 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, BN, AnchorError } from '@coral-xyz/anchor';
+import { Bentobox as BentoboxProgram } from "../../../target/types/bentobox";
+import { StrategyMock as StrategyMockProgram } from "../../../target/types/strategy_mock";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, Token } from "@solana/spl-token";
+import { assert } from "chai";
+
+import { Bentobox } from "../../common/bentobox"
+import { StrategyMock } from "../../common/mock_strategy"
+
+import * as common from "../../common/common";
+
+describe("Bentobox performance fee", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const strategyMockProgram = anchor.workspace.StrategyMock as Program<StrategyMockProgram>;
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+
+    const bentobox_idl = require("../../../target/idl/bentobox.json");
+
+    let mint: Token = null;
+
+    const strategyMockOwner = Keypair.generate();
+    const Bob = Keypair.generate();
+    const bentoboxOwner = Keypair.generate();
+    const pool_owner = Keypair.generate();
+    const Carol = Keypair.generate();
+
+    let bentobox = new Bentobox();
+    let strategyMock = new StrategyMock()
+    let pool_vault: PublicKey = null;
+
+    let BobTokenAccount: PublicKey = null;
+
+    const harvest = async (balance: boolean, feeToBalance: PublicKey = null) => {
+        await bentoboxProgram.methods.harvest(balance, new BN(0), bentobox.getBentoboxAuthorityBump())
+            .accounts({
+                strategyAccount: strategyMock.getStrategyMockAccount(),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
+                bentoboxProgram: bentoboxProgram.programId,
+                strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
+                totalData: bentobox.getTotalDataPda(mint.publicKey),
+                bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
+                authority: strategyMockOwner.publicKey,
+                bentoboxAuthority: bentobox.getBentoboxAuthority(),
+                strategyAuthority: strategyMock.getStrategyMockAuthority(),
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                cpiResultAccount: strategyMock.getCpiResultAccount(),
+                strategyData: bentobox.getStrategyDataPda(mint.publicKey),
+                baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
+                feeToBalance,
+            })
+            .remainingAccounts([
+                { pubkey: pool_vault, isWritable: true, isSigner: false },
+            ])
+            .signers([strategyMockOwner]).rpc()
+    }
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, strategyMockOwner, Bob, Carol]);
+
+        mint = await common.createMintAccount(
+            connection,
+            bentoboxOwner,
+            bentoboxOwner.publicKey,
+            0
+        );
+
+        BobTokenAccount = await common.createAndFundUserAccount(
+            Bob.publicKey,
+            bentoboxOwner,
+            mint,
+            4000
+        );
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for mint
+        await bentobox.createVault(mint.publicKey, Bob);
+        // create mint Balance account for Bob
+        await bentobox.createBalance(Bob.publicKey, mint.publicKey, Bob)
+        // create mint Balance account for fee recipient
+        await bentobox.createBalance(Carol.publicKey, mint.publicKey, Carol)
+        // create strategy data account for mint token
+        await bentobox.createStrategyData(mint.publicKey)
+        // whitelist strategy program for bentobox
+        await bentobox.createStrategyProgramWhitelist(strategyMockProgram.programId)
+        // set strategy delay
+        await bentobox.setStrategyDelay(new BN(0));
+
+        await bentobox.deposit(mint.publicKey, BobTokenAccount, Bob.publicKey, new BN(4000), new BN(0), Bob)
+
+        pool_vault = await common.createAndFundUserAccount(
+            pool_owner.publicKey,
+            bentoboxOwner,
+            mint,
+            0
+        );
+
+        // initialize mock_strategy
+        await strategyMock.initialize(mint.publicKey, bentobox.getBentoboxAccount(), bentoboxProgram.programId, pool_vault, pool_owner, strategyMockOwner)
+
+        // set pending strategy, then activate it
+        await bentobox.queueStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+        let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
+        await delay(2 * 1000);
+        await bentobox.activateStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+
+        await bentobox.setStrategyTargetPercentage(new BN(80), mint.publicKey, strategyMock.getStrategyMockAccount())
+
+        // invest 80% of the vault into strategy
+        await harvest(true)
+
+        let bentobox_vault_acc = await mint.getAccountInfo(bentobox.getTotalVaultPda(mint.publicKey));
+        assert.equal(bentobox_vault_acc.amount.toString(), "800");
+    });
+
+    it("Performance fee can not leave less than max keeper reward of 10000 basis points", async () => {
+        try {
+            await bentobox.setPerformanceFee(new BN(9001), Carol.publicKey)
+            assert.fail("set performance fee more than 9000 basis points");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "InvalidFeeAndKeeperReward");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+
+    it("Harvest with profit requires fee_to balance account", async () => {
+        // 10% performance fee
+        await bentobox.setPerformanceFee(new BN(1000), Carol.publicKey)
+
+        let bentobox_data = await bentoboxProgram.account.bentoBox.fetch(bentobox.getBentoboxAccount());
+        assert.equal(bentobox_data.performanceFeeBps.toString(), "1000");
+        assert.equal(bentobox_data.feeTo.toString(), Carol.publicKey.toString());
+
+        await mint.mintTo(pool_vault, bentoboxOwner.publicKey, [bentoboxOwner], 100); // actually profit
+
+        try {
+            await harvest(false)
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "FeeToBalanceMissing");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+
+    it("Harvest mints performance fee shares to fee_to", async () => {
+        await harvest(false, bentobox.getBalancePda(Carol.publicKey, mint.publicKey))
+
+        // profit 100, fee 10, shares for fee: 10 * 4000 / 4090 = 9
+        let total_info_data = await bentoboxProgram.account.total.fetch(bentobox.getTotalDataPda(mint.publicKey));
+        assert.equal(total_info_data.amount["elastic"].toString(), "4100");
+        assert.equal(total_info_data.amount["base"].toString(), "4009");
+
+        let carol_balance = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(Carol.publicKey, mint.publicKey));
+        assert.equal(carol_balance.amount.toString(), "9");
+
+        let bob_balance = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(Bob.publicKey, mint.publicKey));
+        assert.equal(bob_balance.amount.toString(), "4000");
+    });
+});
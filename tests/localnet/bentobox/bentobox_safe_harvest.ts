@@ -60,8 +60,12 @@ describe("bentobox safe harvest", () => {
         await bentobox.createBalance(Bob.publicKey, mint.publicKey, Bob)
         // create strategy data account for mint token
         await bentobox.createStrategyData(mint.publicKey)
+        // whitelist strategy program for bentobox
+        await bentobox.createStrategyProgramWhitelist(strategyMockProgram.programId)
         // set strategy delay
         await bentobox.setStrategyDelay(new BN(10));
+        // register strategy owner as keeper for token
+        await bentobox.createStrategyKeeper(strategyMockOwner.publicKey, mint.publicKey)
 
         await bentobox.deposit(mint.publicKey, BobTokenAccount, Bob.publicKey, new BN(2000), new BN(0), Bob)
 
@@ -91,6 +95,7 @@ describe("bentobox safe harvest", () => {
                     baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                     totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -102,7 +107,7 @@ describe("bentobox safe harvest", () => {
                     tokenProgram: TOKEN_PROGRAM_ID,
                     cpiResultAccount: strategyMock.getCpiResultAccount(),
                     strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                    executorInfo: strategyMock.getStrategyMockExecutorInfoPda(strategyMockOwner.publicKey)
+                    strategyKeeper: bentobox.getStrategyKeeperPda(strategyMockOwner.publicKey, mint.publicKey)
                 }).remainingAccounts([{ pubkey: pool_vault, isWritable: true, isSigner: false }])
                 .signers([strategyMockOwner])
         } catch (_err) {
@@ -117,9 +122,10 @@ describe("bentobox safe harvest", () => {
 
         // try to do harvest when set only pending strategy
         // set pending strategy
-        await bentoboxProgram.methods.setStrategy()
+        await bentoboxProgram.methods.queueStrategy()
             .accounts({
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -146,6 +152,7 @@ describe("bentobox safe harvest", () => {
                     baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                     totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -157,7 +164,7 @@ describe("bentobox safe harvest", () => {
                     tokenProgram: TOKEN_PROGRAM_ID,
                     cpiResultAccount: strategyMock.getCpiResultAccount(),
                     strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                    executorInfo: strategyMock.getStrategyMockExecutorInfoPda(strategyMockOwner.publicKey)
+                    strategyKeeper: bentobox.getStrategyKeeperPda(strategyMockOwner.publicKey, mint.publicKey)
                 }).remainingAccounts([{ pubkey: pool_vault, isWritable: true, isSigner: false }])
                 .signers([strategyMockOwner])
         } catch (_err) {
@@ -174,9 +181,10 @@ describe("bentobox safe harvest", () => {
         await delay(12 * 1000);
 
         // try set active strategy
-        await bentoboxProgram.methods.setStrategy()
+        await bentoboxProgram.methods.activateStrategy()
             .accounts({
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -203,6 +211,7 @@ describe("bentobox safe harvest", () => {
                     baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                     totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -214,7 +223,7 @@ describe("bentobox safe harvest", () => {
                     tokenProgram: TOKEN_PROGRAM_ID,
                     cpiResultAccount: strategyMock.getCpiResultAccount(),
                     strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                    executorInfo: strategyMock.getStrategyMockExecutorInfoPda(strategyMockOwner.publicKey)
+                    strategyKeeper: bentobox.getStrategyKeeperPda(strategyMockOwner.publicKey, mint.publicKey)
 
                 }).remainingAccounts([
                     { pubkey: pool_vault, isWritable: true, isSigner: false }
@@ -238,6 +247,7 @@ describe("bentobox safe harvest", () => {
                     baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                     totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -249,7 +259,7 @@ describe("bentobox safe harvest", () => {
                     tokenProgram: TOKEN_PROGRAM_ID,
                     cpiResultAccount: strategyMock.getCpiResultAccount(),
                     strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                    executorInfo: strategyMock.getStrategyMockExecutorInfoPda(strategyMockOwner.publicKey)
+                    strategyKeeper: bentobox.getStrategyKeeperPda(strategyMockOwner.publicKey, mint.publicKey)
                 }).remainingAccounts([
                     { pubkey: pool_vault, isWritable: true, isSigner: false }
                 ])
@@ -269,6 +279,7 @@ describe("bentobox safe harvest", () => {
                     baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                     totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -280,7 +291,7 @@ describe("bentobox safe harvest", () => {
                     tokenProgram: TOKEN_PROGRAM_ID,
                     cpiResultAccount: strategyMock.getCpiResultAccount(),
                     strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                    executorInfo: strategyMock.getStrategyMockExecutorInfoPda(strategyMockOwner.publicKey)
+                    strategyKeeper: bentobox.getStrategyKeeperPda(strategyMockOwner.publicKey, mint.publicKey)
                 }).remainingAccounts([
                     { pubkey: pool_vault, isWritable: true, isSigner: false }
                 ])
@@ -301,6 +312,7 @@ describe("bentobox safe harvest", () => {
                     baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                     totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -312,7 +324,7 @@ describe("bentobox safe harvest", () => {
                     tokenProgram: TOKEN_PROGRAM_ID,
                     cpiResultAccount: strategyMock.getCpiResultAccount(),
                     strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                    executorInfo: strategyMock.getStrategyMockExecutorInfoPda(strategyMockOwner.publicKey)
+                    strategyKeeper: bentobox.getStrategyKeeperPda(strategyMockOwner.publicKey, mint.publicKey)
                 }).remainingAccounts([
                     { pubkey: pool_vault, isWritable: true, isSigner: false }
                 ])
@@ -334,6 +346,7 @@ describe("bentobox safe harvest", () => {
                     baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                     totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -345,7 +358,7 @@ describe("bentobox safe harvest", () => {
                     tokenProgram: TOKEN_PROGRAM_ID,
                     cpiResultAccount: strategyMock.getCpiResultAccount(),
                     strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                    executorInfo: strategyMock.getStrategyMockExecutorInfoPda(strategyMockOwner.publicKey)
+                    strategyKeeper: bentobox.getStrategyKeeperPda(strategyMockOwner.publicKey, mint.publicKey)
                 }).remainingAccounts([
                     { pubkey: pool_vault, isWritable: true, isSigner: false }
                 ])
@@ -368,6 +381,7 @@ describe("bentobox safe harvest", () => {
                     baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                     totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -379,7 +393,7 @@ describe("bentobox safe harvest", () => {
                     tokenProgram: TOKEN_PROGRAM_ID,
                     cpiResultAccount: strategyMock.getCpiResultAccount(),
                     strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                    executorInfo: strategyMock.getStrategyMockExecutorInfoPda(strategyMockOwner.publicKey)
+                    strategyKeeper: bentobox.getStrategyKeeperPda(strategyMockOwner.publicKey, mint.publicKey)
                 }).remainingAccounts([
                     { pubkey: pool_vault, isWritable: true, isSigner: false }
                 ])
@@ -402,6 +416,7 @@ describe("bentobox safe harvest", () => {
                 baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -413,7 +428,7 @@ describe("bentobox safe harvest", () => {
                 tokenProgram: TOKEN_PROGRAM_ID,
                 cpiResultAccount: strategyMock.getCpiResultAccount(),
                 strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                executorInfo: strategyMock.getStrategyMockExecutorInfoPda(strategyMockOwner.publicKey)
+                strategyKeeper: bentobox.getStrategyKeeperPda(strategyMockOwner.publicKey, mint.publicKey)
             }).remainingAccounts([
                 { pubkey: pool_vault, isWritable: true, isSigner: false }
             ])
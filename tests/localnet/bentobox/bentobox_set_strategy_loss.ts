@@ -61,11 +61,12 @@ describe("Bentobox set strategy loss", () => {
         await bentobox.createBalance(Bob.publicKey, mint.publicKey, Bob)
         // create strategy data account for mint token
         await bentobox.createStrategyData(mint.publicKey)
+        // whitelist strategy program for bentobox
+        await bentobox.createStrategyProgramWhitelist(strategyMockProgram.programId)
         // set strategy delay
         await bentobox.setStrategyDelay(new BN(10));
 
         await bentobox.deposit(mint.publicKey, BobTokenAccount, Bob.publicKey, new BN(2000), new BN(0), Bob)
-        await bentobox.setStrategyTargetPercentage(new BN(10), mint.publicKey)
 
         pool_vault = await common.createAndFundUserAccount(
             pool_owner.publicKey,
@@ -80,9 +81,10 @@ describe("Bentobox set strategy loss", () => {
 
     it('Set strategy loss!', async () => {
         // case when there will be no profit in pool
-        await bentoboxProgram.methods.setStrategy()
+        await bentoboxProgram.methods.queueStrategy()
             .accounts({
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -104,14 +106,15 @@ describe("Bentobox set strategy loss", () => {
 
         let strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
         assert.equal(strategy_data_acc.pendingStrategy.toString(), strategyMock.getStrategyMockAccount().toString());
-        assert.equal(strategy_data_acc.activeStrategy.toString(), defaultPubKey.toString());
+        assert.equal(strategy_data_acc.strategies.length, 0);
         assert.ok(strategy_data_acc.strategyStartDate > new BN(0));
 
         // try set strategy too soon
         try {
-            await bentoboxProgram.methods.setStrategy()
+            await bentoboxProgram.methods.activateStrategy()
                 .accounts({
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -143,9 +146,10 @@ describe("Bentobox set strategy loss", () => {
         let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
         await delay(12 * 1000);
 
-        await bentoboxProgram.methods.setStrategy()
+        await bentoboxProgram.methods.activateStrategy()
             .accounts({
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -167,8 +171,11 @@ describe("Bentobox set strategy loss", () => {
 
         strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
         assert.equal(strategy_data_acc.pendingStrategy.toString(), defaultPubKey.toString());
-        assert.equal(strategy_data_acc.activeStrategy.toString(), strategyMock.getStrategyMockAccount().toString());
+        assert.equal(strategy_data_acc.strategies[0].strategy.toString(), strategyMock.getStrategyMockAccount().toString());
         assert.ok(strategy_data_acc.strategyStartDate.isZero());
+        assert.equal(strategy_data_acc.withdrawOrder[0].toString(), strategyMock.getStrategyMockAccount().toString());
+
+        await bentobox.setStrategyTargetPercentage(new BN(10), mint.publicKey, strategyMock.getStrategyMockAccount())
 
         // 2. profit = 0, rebalancing -> skimming
         await bentoboxProgram.methods.harvest(true, new BN(500), bentobox.getBentoboxAuthorityBump())
@@ -176,6 +183,7 @@ describe("Bentobox set strategy loss", () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -210,32 +218,18 @@ describe("Bentobox set strategy loss", () => {
         strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
         assert.ok(strategy_data_acc.balance.toString() == "200");
 
-        await bentoboxProgram.methods.setStrategy()
-            .accounts({
-                strategyProgram: strategyMockProgram.programId,
-                bentoboxProgram: bentoboxProgram.programId,
-                bentoboxAccount: bentobox.getBentoboxAccount(),
-                strategyAccount: strategyMock.getStrategyMockAccount(),
-                strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
-                strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
-                totalData: bentobox.getTotalDataPda(mint.publicKey),
-                tokenProgram: TOKEN_PROGRAM_ID,
-                baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
-                bentoboxAuthority: bentobox.getBentoboxAuthority(),
-                authority: bentoboxOwner.publicKey,
-                strategyAuthority: strategyMock.getStrategyMockAuthority(),
-                systemProgram: SystemProgram.programId,
-            })
-            .remainingAccounts([
-                { pubkey: pool_vault, isWritable: true, isSigner: false },
-            ])
-            .signers([bentoboxOwner]).rpc()
+        // try to add strategy which is already added
+        try {
+            await bentobox.queueStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
 
-        strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
-        assert.equal(strategy_data_acc.pendingStrategy.toString(), strategyMock.getStrategyMockAccount().toString());
-        assert.equal(strategy_data_acc.activeStrategy.toString(), strategyMock.getStrategyMockAccount().toString());
-        assert.ok(strategy_data_acc.strategyStartDate > new BN(0));
+            assert.strictEqual(err.error.errorCode.code, "StrategyAlreadyAdded");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
 
         let someVault = await common.createAndFundUserAccount(
             strategyMockOwner.publicKey,
@@ -248,35 +242,19 @@ describe("Bentobox set strategy loss", () => {
         pool_vault_acc = await mint.getAccountInfo(pool_vault);
         assert.ok(pool_vault_acc.amount.toString() == "100");
 
-        delay = ms => new Promise(resolve => setTimeout(resolve, ms))
-        await delay(12 * 1000);
-
-        await bentoboxProgram.methods.setStrategy()
-            .accounts({
-                strategyProgram: strategyMockProgram.programId,
-                bentoboxProgram: bentoboxProgram.programId,
-                bentoboxAccount: bentobox.getBentoboxAccount(),
-                strategyAccount: strategyMock.getStrategyMockAccount(),
-                strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
-                strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
-                totalData: bentobox.getTotalDataPda(mint.publicKey),
-                tokenProgram: TOKEN_PROGRAM_ID,
-                baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
-                bentoboxAuthority: bentobox.getBentoboxAuthority(),
-                authority: bentoboxOwner.publicKey,
-                strategyAuthority: strategyMock.getStrategyMockAuthority(),
-                systemProgram: SystemProgram.programId,
-            })
-            .remainingAccounts([
-                { pubkey: pool_vault, isWritable: true, isSigner: false },
-            ])
-            .signers([bentoboxOwner]).rpc()
+        await bentobox.removeStrategy(
+            strategyMockProgram.programId,
+            strategyMock.getStrategyMockAccount(),
+            strategyMock.getTotalVaultPda(mint.publicKey),
+            strategyMock.getStrategyMockAuthority(),
+            strategyMock.getBaseStrategyInfoAccount(),
+            mint.publicKey,
+            [{ pubkey: pool_vault, isWritable: true, isSigner: false }]
+        )
 
         strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
-        assert.equal(strategy_data_acc.pendingStrategy.toString(), defaultPubKey.toString());
-        assert.equal(strategy_data_acc.activeStrategy.toString(), strategyMock.getStrategyMockAccount().toString());
-        assert.ok(strategy_data_acc.strategyStartDate.isZero());
+        assert.equal(strategy_data_acc.strategies.length, 0);
+        assert.equal(strategy_data_acc.withdrawOrder.length, 0);
 
         strategy_vault_acc = await mint.getAccountInfo(strategyMock.getTotalVaultPda(mint.publicKey));
         assert.equal(strategy_vault_acc.amount.toString(), "0");
@@ -60,11 +60,12 @@ describe("Bentobox set strategy profit", () => {
         await bentobox.createBalance(Bob.publicKey, mint.publicKey, Bob)
         // create strategy data account for mint token
         await bentobox.createStrategyData(mint.publicKey)
+        // whitelist strategy program for bentobox
+        await bentobox.createStrategyProgramWhitelist(strategyMockProgram.programId)
         // set strategy delay
         await bentobox.setStrategyDelay(new BN(10));
 
         await bentobox.deposit(mint.publicKey, BobTokenAccount, Bob.publicKey, new BN(2000), new BN(0), Bob)
-        await bentobox.setStrategyTargetPercentage(new BN(10), mint.publicKey)
 
         pool_vault = await common.createAndFundUserAccount(
             pool_owner.publicKey,
@@ -79,9 +80,10 @@ describe("Bentobox set strategy profit", () => {
 
     it('Set strategy profit!', async () => {
         
-        await bentoboxProgram.methods.setStrategy()
+        await bentoboxProgram.methods.queueStrategy()
             .accounts({
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -103,13 +105,14 @@ describe("Bentobox set strategy profit", () => {
 
         let strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
         assert.equal(strategy_data_acc.pendingStrategy.toString(), strategyMock.getStrategyMockAccount().toString());
-        assert.equal(strategy_data_acc.activeStrategy.toString(), defaultPubKey.toString());
+        assert.equal(strategy_data_acc.strategies.length, 0);
         assert.ok(strategy_data_acc.strategyStartDate > new BN(0));
         // try set strategy too soon
         try {
-            await bentoboxProgram.methods.setStrategy()
+            await bentoboxProgram.methods.activateStrategy()
                 .accounts({
                     strategyProgram: strategyMockProgram.programId,
+                    strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                     bentoboxProgram: bentoboxProgram.programId,
                     bentoboxAccount: bentobox.getBentoboxAccount(),
                     strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -141,9 +144,10 @@ describe("Bentobox set strategy profit", () => {
         let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
         await delay(12 * 1000);
 
-        await bentoboxProgram.methods.setStrategy()
+        await bentoboxProgram.methods.activateStrategy()
             .accounts({
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyAccount: strategyMock.getStrategyMockAccount(),
@@ -164,8 +168,11 @@ describe("Bentobox set strategy profit", () => {
             .signers([bentoboxOwner]).rpc()
         strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
         assert.equal(strategy_data_acc.pendingStrategy.toString(), defaultPubKey.toString());
-        assert.equal(strategy_data_acc.activeStrategy.toString(), strategyMock.getStrategyMockAccount().toString());
+        assert.equal(strategy_data_acc.strategies[0].strategy.toString(), strategyMock.getStrategyMockAccount().toString());
         assert.ok(strategy_data_acc.strategyStartDate.isZero());
+        assert.equal(strategy_data_acc.withdrawOrder[0].toString(), strategyMock.getStrategyMockAccount().toString());
+
+        await bentobox.setStrategyTargetPercentage(new BN(10), mint.publicKey, strategyMock.getStrategyMockAccount())
 
         // 2. profit = 0, rebalancing -> skimming
         await bentoboxProgram.methods.harvest(true, new BN(500), bentobox.getBentoboxAuthorityBump())
@@ -173,6 +180,7 @@ describe("Bentobox set strategy profit", () => {
                 strategyAccount: strategyMock.getStrategyMockAccount(),
                 bentoboxAccount: bentobox.getBentoboxAccount(),
                 strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
                 bentoboxProgram: bentoboxProgram.programId,
                 strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
                 totalData: bentobox.getTotalDataPda(mint.publicKey),
@@ -206,32 +214,18 @@ describe("Bentobox set strategy profit", () => {
         strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
         assert.ok(strategy_data_acc.balance.toString() == "200");
 
-        await bentoboxProgram.methods.setStrategy()
-            .accounts({
-                strategyProgram: strategyMockProgram.programId,
-                bentoboxProgram: bentoboxProgram.programId,
-                bentoboxAccount: bentobox.getBentoboxAccount(),
-                strategyAccount: strategyMock.getStrategyMockAccount(),
-                strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
-                strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
-                totalData: bentobox.getTotalDataPda(mint.publicKey),
-                tokenProgram: TOKEN_PROGRAM_ID,
-                baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
-                bentoboxAuthority: bentobox.getBentoboxAuthority(),
-                authority: bentoboxOwner.publicKey,
-                strategyAuthority: strategyMock.getStrategyMockAuthority(),
-                systemProgram: SystemProgram.programId,
-            })
-            .remainingAccounts([
-                { pubkey: pool_vault, isWritable: true, isSigner: false },
-            ])
-            .signers([bentoboxOwner]).rpc()
+        // try to add strategy which is already added
+        try {
+            await bentobox.queueStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
 
-        strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
-        assert.equal(strategy_data_acc.pendingStrategy.toString(), strategyMock.getStrategyMockAccount().toString());
-        assert.equal(strategy_data_acc.activeStrategy.toString(), strategyMock.getStrategyMockAccount().toString());
-        assert.ok(strategy_data_acc.strategyStartDate > new BN(0));
+            assert.strictEqual(err.error.errorCode.code, "StrategyAlreadyAdded");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
 
         let someVault = await common.createAndFundUserAccount(
             strategyMockOwner.publicKey,
@@ -244,35 +238,19 @@ describe("Bentobox set strategy profit", () => {
         pool_vault_acc = await mint.getAccountInfo(pool_vault);
         assert.ok(pool_vault_acc.amount.toString() == "300");
 
-        delay = ms => new Promise(resolve => setTimeout(resolve, ms))
-        await delay(12 * 1000);
-
-        await bentoboxProgram.methods.setStrategy()
-            .accounts({
-                strategyProgram: strategyMockProgram.programId,
-                bentoboxProgram: bentoboxProgram.programId,
-                bentoboxAccount: bentobox.getBentoboxAccount(),
-                strategyAccount: strategyMock.getStrategyMockAccount(),
-                strategyData: bentobox.getStrategyDataPda(mint.publicKey),
-                bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
-                strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
-                totalData: bentobox.getTotalDataPda(mint.publicKey),
-                tokenProgram: TOKEN_PROGRAM_ID,
-                baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
-                bentoboxAuthority: bentobox.getBentoboxAuthority(),
-                authority: bentoboxOwner.publicKey,
-                strategyAuthority: strategyMock.getStrategyMockAuthority(),
-                systemProgram: SystemProgram.programId,
-            })
-            .remainingAccounts([
-                { pubkey: pool_vault, isWritable: true, isSigner: false },
-            ])
-            .signers([bentoboxOwner]).rpc()
+        await bentobox.removeStrategy(
+            strategyMockProgram.programId,
+            strategyMock.getStrategyMockAccount(),
+            strategyMock.getTotalVaultPda(mint.publicKey),
+            strategyMock.getStrategyMockAuthority(),
+            strategyMock.getBaseStrategyInfoAccount(),
+            mint.publicKey,
+            [{ pubkey: pool_vault, isWritable: true, isSigner: false }]
+        )
 
         strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
-        assert.equal(strategy_data_acc.pendingStrategy.toString(), defaultPubKey.toString());
-        assert.equal(strategy_data_acc.activeStrategy.toString(), strategyMock.getStrategyMockAccount().toString());
-        assert.ok(strategy_data_acc.strategyStartDate.isZero());
+        assert.equal(strategy_data_acc.strategies.length, 0);
+        assert.equal(strategy_data_acc.withdrawOrder.length, 0);
 
         strategy_vault_acc = await mint.getAccountInfo(strategyMock.getTotalVaultPda(mint.publicKey));
         assert.equal(strategy_vault_acc.amount.toString(), "0");
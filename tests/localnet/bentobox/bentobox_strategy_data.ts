@@ -59,19 +59,13 @@ describe('Bentobox strategy data', () => {
     });
 
     it("Set strategy percentage", async () => {
-        await bentoboxProgram.methods.setStrategyTargetPercentage(new BN(10))
-            .accounts({
-                strategyData: strategy_data_key,
-                bentoboxAccount: bentobox.getBentoboxAccount(),
-                mint: mint.publicKey,
-                authority: bentoboxOwner.publicKey,
-            }).signers([bentoboxOwner]).rpc()
-
         let strategy_data = await bentoboxProgram.account.strategyData.fetch(strategy_data_key);
-        assert.ok(strategy_data.targetPercentage.toNumber() == 10);
+        assert.equal(strategy_data.strategies.length, 0);
+        assert.equal(strategy_data.withdrawOrder.length, 0);
 
+        // target percentage can be set only for added strategy
         try {
-            await bentoboxProgram.methods.setStrategyTargetPercentage(new BN(97))
+            await bentoboxProgram.methods.setStrategyTargetPercentage(Keypair.generate().publicKey, new BN(10))
                 .accounts({
                     strategyData: strategy_data_key,
                     bentoboxAccount: bentobox.getBentoboxAccount(),
@@ -82,7 +76,7 @@ describe('Bentobox strategy data', () => {
             assert.isTrue(_err instanceof AnchorError);
             const err: AnchorError = _err;
 
-            assert.strictEqual(err.error.errorCode.code, "StrategyTargetPercentageTooHigh");
+            assert.strictEqual(err.error.errorCode.code, "StrategyNotSet");
             let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
             assert.strictEqual(err.error.errorMessage, error.errorMsg);
             assert.strictEqual(err.error.errorCode.number, error.errorCode);
@@ -0,0 +1,244 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, BN, AnchorError } from '@coral-xyz/anchor';
+import { Bentobox as BentoboxProgram } from "../../../target/types/bentobox";
+import { StrategyMock as StrategyMockProgram } from "../../../target/types/strategy_mock";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, Token } from "@solana/spl-token";
+import { assert } from "chai";
+
+import { Bentobox } from "../../common/bentobox"
+import { StrategyMock } from "../../common/mock_strategy"
+
+import * as common from "../../common/common";
+
+describe("Bentobox strategy keepers", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const strategyMockProgram = anchor.workspace.StrategyMock as Program<StrategyMockProgram>;
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+
+    const bentobox_idl = require("../../../target/idl/bentobox.json");
+
+    let mint: Token = null;
+
+    const strategyMockOwner = Keypair.generate();
+    const Bob = Keypair.generate();
+    const bentoboxOwner = Keypair.generate();
+    const pool_owner = Keypair.generate();
+    const Carol = Keypair.generate();
+
+    let bentobox = new Bentobox();
+    let strategyMock = new StrategyMock()
+    let pool_vault: PublicKey = null;
+
+    let BobTokenAccount: PublicKey = null;
+
+    const harvest = async (balance: boolean) => {
+        await bentoboxProgram.methods.harvest(balance, new BN(0), bentobox.getBentoboxAuthorityBump())
+            .accounts({
+                strategyAccount: strategyMock.getStrategyMockAccount(),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
+                bentoboxProgram: bentoboxProgram.programId,
+                strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
+                totalData: bentobox.getTotalDataPda(mint.publicKey),
+                bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
+                authority: strategyMockOwner.publicKey,
+                bentoboxAuthority: bentobox.getBentoboxAuthority(),
+                strategyAuthority: strategyMock.getStrategyMockAuthority(),
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                cpiResultAccount: strategyMock.getCpiResultAccount(),
+                feeToBalance: null,
+                strategyData: bentobox.getStrategyDataPda(mint.publicKey),
+                baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
+            })
+            .remainingAccounts([
+                { pubkey: pool_vault, isWritable: true, isSigner: false },
+            ])
+            .signers([strategyMockOwner]).rpc()
+    }
+
+    const safeHarvest = async (keeper: Keypair, keeperBalance: PublicKey = null) => {
+        await bentoboxProgram.methods.safeHarvest(new BN(0), false, new BN(0), false, bentobox.getBentoboxAuthorityBump())
+            .accounts({
+                strategyAccount: strategyMock.getStrategyMockAccount(),
+                baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
+                bentoboxProgram: bentoboxProgram.programId,
+                strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
+                totalData: bentobox.getTotalDataPda(mint.publicKey),
+                bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
+                authority: keeper.publicKey,
+                bentoboxAuthority: bentobox.getBentoboxAuthority(),
+                strategyAuthority: strategyMock.getStrategyMockAuthority(),
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                cpiResultAccount: strategyMock.getCpiResultAccount(),
+                feeToBalance: null,
+                strategyData: bentobox.getStrategyDataPda(mint.publicKey),
+                strategyKeeper: bentobox.getStrategyKeeperPda(keeper.publicKey, mint.publicKey),
+                keeperBalance,
+            })
+            .remainingAccounts([
+                { pubkey: pool_vault, isWritable: true, isSigner: false },
+            ])
+            .signers([keeper]).rpc()
+    }
+
+    const assertBentoboxError = async (_err: any, code: string) => {
+        assert.isTrue(_err instanceof AnchorError);
+        const err: AnchorError = _err;
+
+        assert.strictEqual(err.error.errorCode.code, code);
+        let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+        assert.strictEqual(err.error.errorMessage, error.errorMsg);
+        assert.strictEqual(err.error.errorCode.number, error.errorCode);
+    };
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, strategyMockOwner, Bob, Carol]);
+
+        mint = await common.createMintAccount(
+            connection,
+            bentoboxOwner,
+            bentoboxOwner.publicKey,
+            0
+        );
+
+        BobTokenAccount = await common.createAndFundUserAccount(
+            Bob.publicKey,
+            bentoboxOwner,
+            mint,
+            4000
+        );
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for mint
+        await bentobox.createVault(mint.publicKey, Bob);
+        // create mint Balance account for Bob
+        await bentobox.createBalance(Bob.publicKey, mint.publicKey, Bob)
+        // create mint Balance account for keeper
+        await bentobox.createBalance(Carol.publicKey, mint.publicKey, Carol)
+        // create strategy data account for mint token
+        await bentobox.createStrategyData(mint.publicKey)
+        // whitelist strategy program for bentobox
+        await bentobox.createStrategyProgramWhitelist(strategyMockProgram.programId)
+        // set strategy delay
+        await bentobox.setStrategyDelay(new BN(0));
+        // register Carol as keeper for token
+        await bentobox.createStrategyKeeper(Carol.publicKey, mint.publicKey)
+
+        await bentobox.deposit(mint.publicKey, BobTokenAccount, Bob.publicKey, new BN(4000), new BN(0), Bob)
+
+        pool_vault = await common.createAndFundUserAccount(
+            pool_owner.publicKey,
+            bentoboxOwner,
+            mint,
+            0
+        );
+
+        // initialize mock_strategy
+        await strategyMock.initialize(mint.publicKey, bentobox.getBentoboxAccount(), bentoboxProgram.programId, pool_vault, pool_owner, strategyMockOwner)
+
+        // set pending strategy, then activate it
+        await bentobox.queueStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+        let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
+        await delay(2 * 1000);
+        await bentobox.activateStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+
+        await bentobox.setStrategyTargetPercentage(new BN(80), mint.publicKey, strategyMock.getStrategyMockAccount())
+
+        // invest 80% of the vault into strategy
+        await harvest(true)
+
+        let bentobox_vault_acc = await mint.getAccountInfo(bentobox.getTotalVaultPda(mint.publicKey));
+        assert.equal(bentobox_vault_acc.amount.toString(), "800");
+    });
+
+    it("Safe harvest is allowed only for keepers", async () => {
+        await bentobox.setStrategyKeeper(Carol.publicKey, mint.publicKey, false)
+
+        let strategy_keeper = await bentoboxProgram.account.strategyKeeper.fetch(bentobox.getStrategyKeeperPda(Carol.publicKey, mint.publicKey));
+        assert.equal(strategy_keeper.keeper.toString(), Carol.publicKey.toString());
+        assert.isFalse(strategy_keeper.isKeeper);
+
+        try {
+            await safeHarvest(Carol)
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "UnauthorizedSafeHarvest");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+
+        await bentobox.setStrategyKeeper(Carol.publicKey, mint.publicKey, true)
+        await safeHarvest(Carol)
+    });
+
+    it("Keeper reward can not be more than 1000 basis points", async () => {
+        try {
+            await bentobox.setKeeperReward(new BN(1001), mint.publicKey)
+            assert.fail("keeper reward is set above the limit");
+        } catch (_err) {
+            await assertBentoboxError(_err, "InvalidKeeperReward");
+        }
+    });
+
+    it("Performance fee leaves room for keeper reward", async () => {
+        try {
+            await bentobox.setPerformanceFee(new BN(9001), Carol.publicKey)
+            assert.fail("performance fee is set above the rest of keeper reward");
+        } catch (_err) {
+            await assertBentoboxError(_err, "InvalidFeeAndKeeperReward");
+        }
+    });
+
+    it("Harvest without keeper balance does not take keeper reward", async () => {
+        // 5% keeper reward
+        await bentobox.setKeeperReward(new BN(500), mint.publicKey)
+
+        let strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
+        assert.equal(strategy_data_acc.keeperRewardBps.toString(), "500");
+
+        await mint.mintTo(pool_vault, bentoboxOwner.publicKey, [bentoboxOwner], 100); // actually profit
+        await harvest(false)
+
+        let total_info_data = await bentoboxProgram.account.total.fetch(bentobox.getTotalDataPda(mint.publicKey));
+        assert.equal(total_info_data.amount["elastic"].toString(), "4100");
+        assert.equal(total_info_data.amount["base"].toString(), "4000");
+
+        await mint.mintTo(pool_vault, bentoboxOwner.publicKey, [bentoboxOwner], 100); // actually profit
+        await safeHarvest(Carol)
+
+        total_info_data = await bentoboxProgram.account.total.fetch(bentobox.getTotalDataPda(mint.publicKey));
+        assert.equal(total_info_data.amount["elastic"].toString(), "4200");
+        assert.equal(total_info_data.amount["base"].toString(), "4000");
+
+        let carol_balance = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(Carol.publicKey, mint.publicKey));
+        assert.equal(carol_balance.amount.toString(), "0");
+    });
+
+    it("Safe harvest mints keeper reward shares to keeper", async () => {
+        await mint.mintTo(pool_vault, bentoboxOwner.publicKey, [bentoboxOwner], 100); // actually profit
+
+        await safeHarvest(Carol, bentobox.getBalancePda(Carol.publicKey, mint.publicKey))
+
+        // profit 100, reward 5, shares for reward: 5 * 4000 / 4295 = 4
+        let total_info_data = await bentoboxProgram.account.total.fetch(bentobox.getTotalDataPda(mint.publicKey));
+        assert.equal(total_info_data.amount["elastic"].toString(), "4300");
+        assert.equal(total_info_data.amount["base"].toString(), "4004");
+
+        let carol_balance = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(Carol.publicKey, mint.publicKey));
+        assert.equal(carol_balance.amount.toString(), "4");
+    });
+});
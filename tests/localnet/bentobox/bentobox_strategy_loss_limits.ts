@@ -0,0 +1,185 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, BN, AnchorError } from '@coral-xyz/anchor';
+import { Bentobox as BentoboxProgram } from "../../../target/types/bentobox";
+import { StrategyMock as StrategyMockProgram } from "../../../target/types/strategy_mock";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, Token } from "@solana/spl-token";
+import { assert } from "chai";
+
+import { Bentobox } from "../../common/bentobox"
+import { StrategyMock } from "../../common/mock_strategy"
+
+import * as common from "../../common/common";
+
+describe("Bentobox strategy loss limits", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const strategyMockProgram = anchor.workspace.StrategyMock as Program<StrategyMockProgram>;
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+
+    const bentobox_idl = require("../../../target/idl/bentobox.json");
+
+    let mint: Token = null;
+
+    const strategyMockOwner = Keypair.generate();
+    const Bob = Keypair.generate();
+    const bentoboxOwner = Keypair.generate();
+    const pool_owner = Keypair.generate();
+
+    let bentobox = new Bentobox();
+    let strategyMock = new StrategyMock()
+    let pool_vault: PublicKey = null;
+
+    let BobTokenAccount: PublicKey = null;
+
+    const harvest = async (balance: boolean) => {
+        await bentoboxProgram.methods.harvest(balance, new BN(0), bentobox.getBentoboxAuthorityBump())
+            .accounts({
+                strategyAccount: strategyMock.getStrategyMockAccount(),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
+                bentoboxProgram: bentoboxProgram.programId,
+                strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
+                totalData: bentobox.getTotalDataPda(mint.publicKey),
+                bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
+                authority: strategyMockOwner.publicKey,
+                bentoboxAuthority: bentobox.getBentoboxAuthority(),
+                strategyAuthority: strategyMock.getStrategyMockAuthority(),
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                cpiResultAccount: strategyMock.getCpiResultAccount(),
+                strategyData: bentobox.getStrategyDataPda(mint.publicKey),
+                baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
+            })
+            .remainingAccounts([
+                { pubkey: pool_vault, isWritable: true, isSigner: false },
+            ])
+            .signers([strategyMockOwner]).rpc()
+    }
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, strategyMockOwner, Bob]);
+
+        mint = await common.createMintAccount(
+            connection,
+            bentoboxOwner,
+            bentoboxOwner.publicKey,
+            0
+        );
+
+        BobTokenAccount = await common.createAndFundUserAccount(
+            Bob.publicKey,
+            bentoboxOwner,
+            mint,
+            4000
+        );
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for mint
+        await bentobox.createVault(mint.publicKey, Bob);
+        // create mint Balance account for Bob
+        await bentobox.createBalance(Bob.publicKey, mint.publicKey, Bob)
+        // create strategy data account for mint token
+        await bentobox.createStrategyData(mint.publicKey)
+        // whitelist strategy program for bentobox
+        await bentobox.createStrategyProgramWhitelist(strategyMockProgram.programId)
+        // set strategy delay
+        await bentobox.setStrategyDelay(new BN(0));
+
+        await bentobox.deposit(mint.publicKey, BobTokenAccount, Bob.publicKey, new BN(4000), new BN(0), Bob)
+
+        pool_vault = await common.createAndFundUserAccount(
+            pool_owner.publicKey,
+            bentoboxOwner,
+            mint,
+            0
+        );
+
+        // initialize mock_strategy
+        await strategyMock.initialize(mint.publicKey, bentobox.getBentoboxAccount(), bentoboxProgram.programId, pool_vault, pool_owner, strategyMockOwner)
+
+        // set pending strategy, then activate it
+        await bentobox.queueStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+        let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
+        await delay(2 * 1000);
+        await bentobox.activateStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+
+        await bentobox.setStrategyTargetPercentage(new BN(80), mint.publicKey, strategyMock.getStrategyMockAccount())
+
+        // invest 80% of the vault into strategy
+        await harvest(true)
+
+        let bentobox_vault_acc = await mint.getAccountInfo(bentobox.getTotalVaultPda(mint.publicKey));
+        assert.equal(bentobox_vault_acc.amount.toString(), "800");
+    });
+
+    it("Loss limits can not be more than 10000 basis points", async () => {
+        try {
+            await bentobox.setStrategyLossLimits(new BN(10001), new BN(0), false, mint.publicKey)
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "InvalidLossLimit");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+
+    it("Harvest reverts when loss exceeds limit", async () => {
+        // 1% of 4000 total amount
+        await bentobox.setStrategyLossLimits(new BN(100), new BN(200), false, mint.publicKey)
+
+        let someVault = await common.createAndFundUserAccount(
+            strategyMockOwner.publicKey,
+            bentoboxOwner,
+            mint,
+            0
+        );
+        await mint.transfer(pool_vault, someVault, pool_owner, [pool_owner], 100) // actually loss
+
+        try {
+            await harvest(false)
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "StrategyLossTooHigh");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+
+        let total_info_data = await bentoboxProgram.account.total.fetch(bentobox.getTotalDataPda(mint.publicKey));
+        assert.equal(total_info_data.amount["elastic"].toString(), "4000");
+
+        let strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
+        assert.equal(strategy_data_acc.strategies.length, 1);
+        assert.equal(strategy_data_acc.strategies[0].cumulativeLoss.toString(), "0");
+    });
+
+    it("Harvest exits strategy when loss exceeds limit", async () => {
+        await bentobox.setStrategyLossLimits(new BN(100), new BN(200), true, mint.publicKey)
+
+        await harvest(false)
+
+        let pool_vault_acc = await mint.getAccountInfo(pool_vault);
+        assert.equal(pool_vault_acc.amount.toString(), "0");
+
+        let bentobox_vault_acc = await mint.getAccountInfo(bentobox.getTotalVaultPda(mint.publicKey));
+        assert.equal(bentobox_vault_acc.amount.toString(), "3900");
+
+        let total_info_data = await bentoboxProgram.account.total.fetch(bentobox.getTotalDataPda(mint.publicKey));
+        assert.equal(total_info_data.amount["elastic"].toString(), "3900");
+
+        let strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
+        assert.equal(strategy_data_acc.balance.toString(), "0");
+        assert.equal(strategy_data_acc.strategies.length, 0);
+        assert.equal(strategy_data_acc.withdrawOrder.length, 0);
+    });
+});
@@ -0,0 +1,196 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, BN, AnchorError } from '@coral-xyz/anchor';
+import { Bentobox as BentoboxProgram } from "../../../target/types/bentobox";
+import { StrategyMock as StrategyMockProgram } from "../../../target/types/strategy_mock";
+import { AccountMeta, PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, Token } from "@solana/spl-token";
+import { assert } from "chai";
+
+import { Bentobox } from "../../common/bentobox"
+import { StrategyMock } from "../../common/mock_strategy"
+
+import * as common from "../../common/common";
+
+describe("Bentobox withdraw from multiple strategies", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const strategyMockProgram = anchor.workspace.StrategyMock as Program<StrategyMockProgram>;
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+
+    const bentobox_idl = require("../../../target/idl/bentobox.json");
+
+    let mint: Token = null;
+
+    const strategyMockOwner = Keypair.generate();
+    const Bob = Keypair.generate();
+    const bentoboxOwner = Keypair.generate();
+    const pool_owner = Keypair.generate();
+
+    let bentobox = new Bentobox();
+    let strategyMockA = new StrategyMock()
+    let strategyMockB = new StrategyMock()
+    let pool_vault_a: PublicKey = null;
+    let pool_vault_b: PublicKey = null;
+
+    let BobTokenAccount: PublicKey = null;
+
+    const harvest = async (strategyMock: StrategyMock, poolVault: PublicKey) => {
+        await bentoboxProgram.methods.harvest(true, new BN(0), bentobox.getBentoboxAuthorityBump())
+            .accounts({
+                strategyAccount: strategyMock.getStrategyMockAccount(),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
+                bentoboxProgram: bentoboxProgram.programId,
+                strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
+                totalData: bentobox.getTotalDataPda(mint.publicKey),
+                bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
+                authority: strategyMockOwner.publicKey,
+                bentoboxAuthority: bentobox.getBentoboxAuthority(),
+                strategyAuthority: strategyMock.getStrategyMockAuthority(),
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                cpiResultAccount: strategyMock.getCpiResultAccount(),
+                feeToBalance: null,
+                strategyData: bentobox.getStrategyDataPda(mint.publicKey),
+                baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
+            })
+            .remainingAccounts([
+                { pubkey: poolVault, isWritable: true, isSigner: false },
+            ])
+            .signers([strategyMockOwner]).rpc()
+    };
+
+    // accounts of one strategy without `strategy_data` and `bentobox_program`
+    const strategyAccounts = (strategyMock: StrategyMock, poolVault: PublicKey): AccountMeta[] =>
+        bentobox.getStrategyWithdrawAccounts(
+            strategyMockProgram.programId,
+            strategyMock.getStrategyMockAccount(),
+            strategyMock.getTotalVaultPda(mint.publicKey),
+            strategyMock.getStrategyMockAuthority(),
+            strategyMock.getBaseStrategyInfoAccount(),
+            mint.publicKey,
+            [{ pubkey: poolVault, isWritable: true, isSigner: false }]
+        ).slice(2);
+
+    const withdrawAccounts = (...strategies: AccountMeta[][]): AccountMeta[] => [
+        { pubkey: bentobox.getStrategyDataPda(mint.publicKey), isWritable: true, isSigner: false },
+        { pubkey: bentoboxProgram.programId, isWritable: false, isSigner: false },
+        ...strategies.flat(),
+    ];
+
+    const assertBentoboxError = async (_err: any, code: string) => {
+        assert.isTrue(_err instanceof AnchorError);
+        const err: AnchorError = _err;
+
+        assert.strictEqual(err.error.errorCode.code, code);
+        let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+        assert.strictEqual(err.error.errorMessage, error.errorMsg);
+        assert.strictEqual(err.error.errorCode.number, error.errorCode);
+    };
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, strategyMockOwner, Bob]);
+
+        mint = await common.createMintAccount(
+            connection,
+            bentoboxOwner,
+            bentoboxOwner.publicKey,
+            0
+        );
+
+        BobTokenAccount = await common.createAndFundUserAccount(
+            Bob.publicKey,
+            bentoboxOwner,
+            mint,
+            4000
+        );
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for mint
+        await bentobox.createVault(mint.publicKey, Bob);
+        // create mint Balance account for Bob
+        await bentobox.createBalance(Bob.publicKey, mint.publicKey, Bob)
+        // create strategy data account for mint token
+        await bentobox.createStrategyData(mint.publicKey)
+        // whitelist strategy program for bentobox
+        await bentobox.createStrategyProgramWhitelist(strategyMockProgram.programId)
+        // set strategy delay
+        await bentobox.setStrategyDelay(new BN(0));
+
+        await bentobox.deposit(mint.publicKey, BobTokenAccount, Bob.publicKey, new BN(4000), new BN(0), Bob)
+
+        pool_vault_a = await common.createAndFundUserAccount(pool_owner.publicKey, bentoboxOwner, mint, 0);
+        pool_vault_b = await common.createAndFundUserAccount(pool_owner.publicKey, bentoboxOwner, mint, 0);
+
+        // initialize mock strategies
+        await strategyMockA.initialize(mint.publicKey, bentobox.getBentoboxAccount(), bentoboxProgram.programId, pool_vault_a, pool_owner, strategyMockOwner)
+        await strategyMockB.initialize(mint.publicKey, bentobox.getBentoboxAccount(), bentoboxProgram.programId, pool_vault_b, pool_owner, strategyMockOwner)
+
+        // set both strategies for token, 40% of the vault into each
+        for (const strategyMock of [strategyMockA, strategyMockB]) {
+            await bentobox.queueStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+            await common.pause(2 * 1000);
+            await bentobox.activateStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+            await bentobox.setStrategyTargetPercentage(new BN(40), mint.publicKey, strategyMock.getStrategyMockAccount())
+        }
+
+        await harvest(strategyMockA, pool_vault_a);
+        await harvest(strategyMockB, pool_vault_b);
+
+        // withdraw from strategy B first
+        await bentobox.setStrategyWithdrawOrder([strategyMockB.getStrategyMockAccount(), strategyMockA.getStrategyMockAccount()], mint.publicKey)
+
+        let bentobox_vault_acc = await mint.getAccountInfo(bentobox.getTotalVaultPda(mint.publicKey));
+        assert.equal(bentobox_vault_acc.amount.toString(), "800");
+    });
+
+    it("Withdraw with strategies out of withdraw order", async () => {
+        try {
+            await bentobox.withdraw(mint.publicKey, Bob.publicKey, BobTokenAccount, new BN(3000), new BN(0), Bob,
+                withdrawAccounts(strategyAccounts(strategyMockA, pool_vault_a), strategyAccounts(strategyMockB, pool_vault_b)))
+            assert.fail("withdrawn out of withdraw order");
+        } catch (_err) {
+            await assertBentoboxError(_err, "StrategyNotNextInWithdrawOrder");
+        }
+    });
+
+    it("Withdraw more than first strategy holds with accounts of one strategy", async () => {
+        try {
+            await bentobox.withdraw(mint.publicKey, Bob.publicKey, BobTokenAccount, new BN(3000), new BN(0), Bob,
+                withdrawAccounts(strategyAccounts(strategyMockB, pool_vault_b)))
+            assert.fail("withdrawn more than vault and strategy hold");
+        } catch (_err) {
+            await assertBentoboxError(_err, "WithdrawAmountToHigh");
+        }
+    });
+
+    it("Withdraw pulls shortfall from strategies in withdraw order", async () => {
+        await bentobox.withdraw(mint.publicKey, Bob.publicKey, BobTokenAccount, new BN(3000), new BN(0), Bob,
+            withdrawAccounts(strategyAccounts(strategyMockB, pool_vault_b), strategyAccounts(strategyMockA, pool_vault_a)))
+
+        let bob_token_acc = await mint.getAccountInfo(BobTokenAccount);
+        assert.equal(bob_token_acc.amount.toString(), "3000");
+
+        let bentobox_vault_acc = await mint.getAccountInfo(bentobox.getTotalVaultPda(mint.publicKey));
+        assert.equal(bentobox_vault_acc.amount.toString(), "0");
+
+        // strategy B is emptied first, the rest of the shortfall is pulled from strategy A
+        let pool_vault_b_acc = await mint.getAccountInfo(pool_vault_b);
+        assert.equal(pool_vault_b_acc.amount.toString(), "0");
+        let pool_vault_a_acc = await mint.getAccountInfo(pool_vault_a);
+        assert.equal(pool_vault_a_acc.amount.toString(), "1000");
+
+        let total_info_data = await bentoboxProgram.account.total.fetch(bentobox.getTotalDataPda(mint.publicKey));
+        assert.equal(total_info_data.amount["elastic"].toString(), "1000");
+
+        let strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
+        assert.equal(strategy_data_acc.balance.toString(), "1000");
+        assert.equal(strategy_data_acc.strategies[0].strategy.toBase58(), strategyMockA.getStrategyMockAccount().toBase58());
+        assert.equal(strategy_data_acc.strategies[0].balance.toString(), "1000");
+        assert.equal(strategy_data_acc.strategies[1].balance.toString(), "0");
+    });
+});
@@ -0,0 +1,186 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, BN, AnchorError } from '@coral-xyz/anchor';
+import { Bentobox as BentoboxProgram } from "../../../target/types/bentobox";
+import { StrategyMock as StrategyMockProgram } from "../../../target/types/strategy_mock";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, Token } from "@solana/spl-token";
+import { assert } from "chai";
+
+import { Bentobox } from "../../common/bentobox"
+import { StrategyMock } from "../../common/mock_strategy"
+
+import * as common from "../../common/common";
+
+describe("Bentobox withdraw from strategy", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const strategyMockProgram = anchor.workspace.StrategyMock as Program<StrategyMockProgram>;
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+
+    const bentobox_idl = require("../../../target/idl/bentobox.json");
+
+    let mint: Token = null;
+
+    const strategyMockOwner = Keypair.generate();
+    const Bob = Keypair.generate();
+    const bentoboxOwner = Keypair.generate();
+    const pool_owner = Keypair.generate();
+
+    let bentobox = new Bentobox();
+    let strategyMock = new StrategyMock()
+    let pool_vault: PublicKey = null;
+
+    let BobTokenAccount: PublicKey = null;
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, strategyMockOwner, Bob]);
+
+        mint = await common.createMintAccount(
+            connection,
+            bentoboxOwner,
+            bentoboxOwner.publicKey,
+            0
+        );
+
+        BobTokenAccount = await common.createAndFundUserAccount(
+            Bob.publicKey,
+            bentoboxOwner,
+            mint,
+            4000
+        );
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for mint
+        await bentobox.createVault(mint.publicKey, Bob);
+        // create mint Balance account for Bob
+        await bentobox.createBalance(Bob.publicKey, mint.publicKey, Bob)
+        // create strategy data account for mint token
+        await bentobox.createStrategyData(mint.publicKey)
+        // whitelist strategy program for bentobox
+        await bentobox.createStrategyProgramWhitelist(strategyMockProgram.programId)
+        // set strategy delay
+        await bentobox.setStrategyDelay(new BN(0));
+
+        await bentobox.deposit(mint.publicKey, BobTokenAccount, Bob.publicKey, new BN(4000), new BN(0), Bob)
+
+        pool_vault = await common.createAndFundUserAccount(
+            pool_owner.publicKey,
+            bentoboxOwner,
+            mint,
+            0
+        );
+
+        // initialize mock_strategy
+        await strategyMock.initialize(mint.publicKey, bentobox.getBentoboxAccount(), bentoboxProgram.programId, pool_vault, pool_owner, strategyMockOwner)
+
+        // set pending strategy, then activate it
+        await bentobox.queueStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+        let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
+        await delay(2 * 1000);
+        await bentobox.activateStrategy(strategyMockProgram.programId, strategyMock.getStrategyMockAccount(), mint.publicKey)
+
+        await bentobox.setStrategyTargetPercentage(new BN(80), mint.publicKey, strategyMock.getStrategyMockAccount())
+
+        // invest 80% of the vault into strategy
+        await bentoboxProgram.methods.harvest(true, new BN(0), bentobox.getBentoboxAuthorityBump())
+            .accounts({
+                strategyAccount: strategyMock.getStrategyMockAccount(),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                strategyProgram: strategyMockProgram.programId,
+                strategyProgramWhitelisted: bentobox.getStrategyProgramWhitelistedPda(strategyMockProgram.programId),
+                bentoboxProgram: bentoboxProgram.programId,
+                strategyVault: strategyMock.getTotalVaultPda(mint.publicKey),
+                totalData: bentobox.getTotalDataPda(mint.publicKey),
+                bentoboxVault: bentobox.getTotalVaultPda(mint.publicKey),
+                authority: strategyMockOwner.publicKey,
+                bentoboxAuthority: bentobox.getBentoboxAuthority(),
+                strategyAuthority: strategyMock.getStrategyMockAuthority(),
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                cpiResultAccount: strategyMock.getCpiResultAccount(),
+                strategyData: bentobox.getStrategyDataPda(mint.publicKey),
+                baseStrategyInfo: strategyMock.getBaseStrategyInfoAccount(),
+            })
+            .remainingAccounts([
+                { pubkey: pool_vault, isWritable: true, isSigner: false },
+            ])
+            .signers([strategyMockOwner]).rpc()
+
+        let bentobox_vault_acc = await mint.getAccountInfo(bentobox.getTotalVaultPda(mint.publicKey));
+        assert.equal(bentobox_vault_acc.amount.toString(), "800");
+    });
+
+    it("Withdraw more than vault holds without strategy accounts", async () => {
+        try {
+            await bentobox.withdraw(mint.publicKey, Bob.publicKey, BobTokenAccount, new BN(1500), new BN(0), Bob)
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "WithdrawAmountToHigh");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+
+    it("Withdraw from strategy without strategy program whitelisted account", async () => {
+        const strategyAccounts = bentobox.getStrategyWithdrawAccounts(
+            strategyMockProgram.programId,
+            strategyMock.getStrategyMockAccount(),
+            strategyMock.getTotalVaultPda(mint.publicKey),
+            strategyMock.getStrategyMockAuthority(),
+            strategyMock.getBaseStrategyInfoAccount(),
+            mint.publicKey,
+            [{ pubkey: pool_vault, isWritable: true, isSigner: false }]
+        );
+        // replace strategy program whitelisted account with strategy data account
+        strategyAccounts[7] = { pubkey: bentobox.getStrategyDataPda(mint.publicKey), isWritable: false, isSigner: false };
+
+        try {
+            await bentobox.withdraw(mint.publicKey, Bob.publicKey, BobTokenAccount, new BN(1500), new BN(0), Bob, strategyAccounts)
+            assert.fail("withdrew from strategy without strategy program whitelisted account");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "StrategyProgramNotWhitelisted");
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    });
+
+    it("Withdraw pulls shortfall from strategy", async () => {
+        const strategyAccounts = bentobox.getStrategyWithdrawAccounts(
+            strategyMockProgram.programId,
+            strategyMock.getStrategyMockAccount(),
+            strategyMock.getTotalVaultPda(mint.publicKey),
+            strategyMock.getStrategyMockAuthority(),
+            strategyMock.getBaseStrategyInfoAccount(),
+            mint.publicKey,
+            [{ pubkey: pool_vault, isWritable: true, isSigner: false }]
+        );
+
+        await bentobox.withdraw(mint.publicKey, Bob.publicKey, BobTokenAccount, new BN(1500), new BN(0), Bob, strategyAccounts)
+
+        let bob_token_acc = await mint.getAccountInfo(BobTokenAccount);
+        assert.equal(bob_token_acc.amount.toString(), "1500");
+
+        let bentobox_vault_acc = await mint.getAccountInfo(bentobox.getTotalVaultPda(mint.publicKey));
+        assert.equal(bentobox_vault_acc.amount.toString(), "0");
+
+        let pool_vault_acc = await mint.getAccountInfo(pool_vault);
+        assert.equal(pool_vault_acc.amount.toString(), "2500");
+
+        let total_info_data = await bentoboxProgram.account.total.fetch(bentobox.getTotalDataPda(mint.publicKey));
+        assert.equal(total_info_data.amount["elastic"].toString(), "2500");
+
+        let strategy_data_acc = await bentoboxProgram.account.strategyData.fetch(bentobox.getStrategyDataPda(mint.publicKey));
+        assert.equal(strategy_data_acc.balance.toString(), "2500");
+        assert.equal(strategy_data_acc.strategies[0].balance.toString(), "2500");
+    });
+});
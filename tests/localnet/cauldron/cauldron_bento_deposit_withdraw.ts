@@ -102,7 +102,7 @@ describe("Bento deposit / bento withdraw through cauldron", () => {
     it("Bento deposit", async () => {
         // try to bento deposit with incorrect bentobox account
         try {
-            await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(1000), new BN(0))
+            await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(1000), new BN(0), new BN(0))
                 .accounts({
                     fromVault: BobTokenAccount,
                     bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
@@ -132,7 +132,7 @@ describe("Bento deposit / bento withdraw through cauldron", () => {
         }
         // try to bento deposit with incorrect bentobox program
         try {
-            await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(1000), new BN(0))
+            await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(1000), new BN(0), new BN(0))
                 .accounts({
                     fromVault: BobTokenAccount,
                     bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
@@ -162,7 +162,7 @@ describe("Bento deposit / bento withdraw through cauldron", () => {
 
         try {
             // deposited to bentobox from Bob using wrong master contract approved account
-            await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(2000), new BN(0))
+            await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(2000), new BN(0), new BN(0))
                 .accounts({
                     fromVault: BobTokenAccount,
                     bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
@@ -193,7 +193,7 @@ describe("Bento deposit / bento withdraw through cauldron", () => {
 
         try {
             // deposited to bentobox from Bob using wrong master contract approved account
-            await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(2000), new BN(0))
+            await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(2000), new BN(0), new BN(0))
                 .accounts({
                     fromVault: BobTokenAccount,
                     bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
@@ -224,7 +224,7 @@ describe("Bento deposit / bento withdraw through cauldron", () => {
 
         try {
             // deposited to bentobox from Bob using wrong cauldron_v2.getCauldronAuthority()
-            await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(2000), new BN(0))
+            await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(2000), new BN(0), new BN(0))
                 .accounts({
                     fromVault: BobTokenAccount,
                     bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
@@ -255,7 +255,7 @@ describe("Bento deposit / bento withdraw through cauldron", () => {
         await cauldron.approveToCauldron(BobTokenAccount, Bob)
 
         // deposited to bentobox from Bob
-        await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(2000), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(Bob.publicKey, new BN(2000), new BN(0), new BN(0))
             .accounts({
                 fromVault: BobTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
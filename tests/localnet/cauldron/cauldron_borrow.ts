@@ -129,7 +129,7 @@ describe("Borrow Call", () => {
         await cauldron.approveToCauldron(stanTokenAccount, borrowerStan);
 
         // deposited to bentobox from Stan
-        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(20000), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(20000), new BN(0), new BN(0))
             .accounts({
                 fromVault: stanTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
@@ -213,6 +213,7 @@ describe("Borrow Call", () => {
                 bentoboxProgram: bentoboxProgram.programId,
                 magicInternetMoneyMint: mimMint.publicKey,
                 switchboardDataFeed: TEST_PRICE,
+                poolOracle: cauldron.getPoolOraclePda(),
                 authority: borrowerDan.publicKey,
             }).signers([borrowerDan]).rpc();
         } catch (_err) {
@@ -235,6 +236,7 @@ describe("Borrow Call", () => {
             bentoboxProgram: bentoboxProgram.programId,
             magicInternetMoneyMint: mimMint.publicKey,
             switchboardDataFeed: TEST_PRICE,
+            poolOracle: cauldron.getPoolOraclePda(),
             authority: borrowerStan.publicKey,
         }).signers([borrowerStan]).rpc();
 
@@ -130,7 +130,7 @@ describe("Get Repay part", () => {
         await cauldron.approveToCauldron(stanTokenAccount, borrowerStan);
 
         // deposited to bentobox from Stan
-        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(20000), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(20000), new BN(0), new BN(0))
             .accounts({
                 fromVault: stanTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
@@ -0,0 +1,170 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { Cauldron as CauldronProgram } from "../../../target/types/cauldron";
+import { Bentobox as BentoboxProgram } from "../../../target/types/bentobox";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, Token } from "@solana/spl-token";
+import { assert } from "chai";
+import * as common from "../../common/common";
+
+import { TEST_PRICE, COLLATERIZATION_RATE_PRECISION, STALE_AFTER_SLOTS_ELAPSED } from "../../common/src";
+
+import { Bentobox } from "../../common/bentobox"
+import { Cauldron } from "../../common/cauldron"
+
+describe("Bento deposit through de-whitelisted / revoked master contract", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+    const connection = provider.connection;
+
+    const bentoboxProgram = anchor.workspace.Bentobox as Program<BentoboxProgram>;
+    const cauldronProgram = anchor.workspace.Cauldron as Program<CauldronProgram>;
+
+    const bentobox_idl = require("../../../target/idl/bentobox.json");
+
+    let mimMint: Token = null;
+    let collateralMint: Token = null;
+
+    let cauldron = new Cauldron();
+    let bentobox = new Bentobox();
+
+    const cauldronOwner = Keypair.generate();
+    const bentoboxOwner = Keypair.generate();
+    const Bob = Keypair.generate();
+
+    let BobTokenAccount: PublicKey = null;
+
+    async function assertError(promise: Promise<any>, code: string) {
+        try {
+            await promise;
+            assert.fail(`Expected ${code}`);
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, code);
+            let error = await common.getErrorInfo(bentobox_idl, err.error.errorCode.code);
+            assert.strictEqual(err.error.errorMessage, error.errorMsg);
+            assert.strictEqual(err.error.errorCode.number, error.errorCode);
+        }
+    }
+
+    // deposit of Bob tokens to Bob balance by cauldron, checked with `allowed`
+    async function bentoDeposit(amount: BN) {
+        await cauldronProgram.methods.bentoDeposit(Bob.publicKey, amount, new BN(0), new BN(0))
+            .accounts({
+                fromVault: BobTokenAccount,
+                bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
+                bentoboxToBalance: bentobox.getBalancePda(Bob.publicKey, collateralMint.publicKey),
+                bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
+                bentoboxAccount: bentobox.getBentoboxAccount(),
+                bentoboxProgram: bentoboxProgram.programId,
+                cauldronAccount: cauldron.getCauldronAccount(),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                bentoboxStrategyData: bentobox.getStrategyDataPda(collateralMint.publicKey),
+                mint: collateralMint.publicKey,
+                cauldronAuthority: cauldron.getCauldronAuthority(),
+                masterContractApproved: bentobox.getMasterContractApprovedPda(Bob.publicKey, cauldron.getCauldronAccount()),
+                masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()),
+                authority: Bob.publicKey,
+            })
+            .signers([Bob])
+            .rpc();
+    }
+
+    async function assertBobBalance(amount: string) {
+        let balance = await bentoboxProgram.account.balance.fetch(bentobox.getBalancePda(Bob.publicKey, collateralMint.publicKey));
+        assert.strictEqual(balance.amount.toString(), amount);
+    }
+
+    before(async () => {
+        await common.batchAirdrop(connection, [bentoboxOwner, cauldronOwner, Bob]);
+
+        mimMint = await common.createMintAccount(connection, cauldronOwner, cauldronOwner.publicKey, 0);
+        collateralMint = await common.createMintAccount(connection, bentoboxOwner, bentoboxOwner.publicKey, 0);
+
+        //create token account for Bob with some collateral tokens
+        BobTokenAccount = await common.createAndFundUserAccount(Bob.publicKey, bentoboxOwner, collateralMint, 5000);
+
+        // create bentobox
+        await bentobox.create(bentoboxOwner);
+        // create total vault for collateral
+        await bentobox.createVault(collateralMint.publicKey, cauldronOwner);
+        // create collateral Balance account for Bob on Bentobox
+        await bentobox.createBalance(Bob.publicKey, collateralMint.publicKey, Bob)
+        // create strategy data account for collateral token
+        await bentobox.createStrategyData(collateralMint.publicKey)
+
+        // initialize cauldron account
+        await cauldron.initialize(cauldronOwner, mimMint.publicKey, collateralMint.publicKey, TEST_PRICE, bentobox.getBentoboxAccount(), COLLATERIZATION_RATE_PRECISION, STALE_AFTER_SLOTS_ELAPSED)
+
+        //register cauldron to bentobox as master contract
+        await bentobox.createMasterContractWhitelist(cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+        // create bentobox approval account for Bob
+        await bentobox.createMasterContractApproval(Bob, cauldron.getCauldronAccount(), cauldron.getCauldronProgram())
+        // approve cauldron authority to transfer Bob tokens
+        await cauldron.approveToCauldron(BobTokenAccount, Bob)
+
+        await bentoDeposit(new BN(1000));
+        await assertBobBalance("1000");
+    });
+
+    it("Bento deposit through de-whitelisted master contract", async () => {
+        await bentobox.whitelistMasterContract(false, cauldron.getCauldronAccount(), cauldron.getCauldronProgram());
+
+        let whitelisted = await bentoboxProgram.account.masterContractWhitelisted.fetch(bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()));
+        assert.isFalse(whitelisted.whitelisted);
+
+        await assertError(bentoDeposit(new BN(1000)), "MasterContractNotWhitelisted");
+        await assertBobBalance("1000");
+
+        // whitelisting again restores access with existing approval
+        await bentobox.whitelistMasterContract(true, cauldron.getCauldronAccount(), cauldron.getCauldronProgram());
+
+        await bentoDeposit(new BN(1000));
+        await assertBobBalance("2000");
+    });
+
+    it("Bento deposit after master contract approvals are revoked", async () => {
+        await bentobox.revokeMasterContractApprovals(cauldron.getCauldronAccount());
+
+        let whitelisted = await bentoboxProgram.account.masterContractWhitelisted.fetch(bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()));
+        assert.strictEqual(whitelisted.approvalsEpoch.toString(), "1");
+        let approved = await bentoboxProgram.account.masterContractApproved.fetch(bentobox.getMasterContractApprovedPda(Bob.publicKey, cauldron.getCauldronAccount()));
+        assert.isTrue(approved.approved);
+        assert.strictEqual(approved.approvalsEpoch.toString(), "0");
+
+        await assertError(bentoDeposit(new BN(1000)), "MasterContractApprovalRevoked");
+        await assertBobBalance("2000");
+
+        // approval in the new epoch restores access
+        await bentobox.setMasterContractApproval(Bob, true, cauldron.getCauldronAccount(), cauldron.getCauldronProgram());
+
+        approved = await bentoboxProgram.account.masterContractApproved.fetch(bentobox.getMasterContractApprovedPda(Bob.publicKey, cauldron.getCauldronAccount()));
+        assert.strictEqual(approved.approvalsEpoch.toString(), "1");
+
+        await bentoDeposit(new BN(1000));
+        await assertBobBalance("3000");
+    });
+
+    it("Revoke master contract approvals not by bentobox owner", async () => {
+        try {
+            await bentoboxProgram.methods.revokeMasterContractApprovals()
+                .accounts({
+                    masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount()),
+                    masterContractAccount: cauldron.getCauldronAccount(),
+                    authority: Bob.publicKey,
+                    bentoboxAccount: bentobox.getBentoboxAccount(),
+                })
+                .signers([Bob])
+                .rpc();
+            assert.fail("revoked approvals not by bentobox owner");
+        } catch (_err) {
+            assert.isTrue(_err instanceof AnchorError);
+            const err: AnchorError = _err;
+
+            assert.strictEqual(err.error.errorCode.code, "ConstraintHasOne");
+            assert.strictEqual(err.error.errorCode.number, 2001);
+        }
+    });
+});
@@ -96,7 +96,7 @@ describe("Reduce supply", () => {
         await bentobox.createBalance(cauldron.getCauldronAuthority(), mimMint.publicKey, cauldronOwner)
 
         // deposited to bentobox from Bob
-        await cauldronProgram.methods.bentoDeposit(cauldron.getCauldronAuthority(), new BN(2000), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(cauldron.getCauldronAuthority(), new BN(2000), new BN(0), new BN(0))
             .accounts({
                 fromVault: BobTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(mimMint.publicKey),
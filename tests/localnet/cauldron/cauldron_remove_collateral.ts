@@ -111,6 +111,7 @@ describe("Remove collateral", () => {
                     cauldronAuthority: cauldron.getCauldronAuthority(),
                     bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                     switchboardDataFeed: TEST_PRICE,
+                    poolOracle: cauldron.getPoolOraclePda(),
                     authority: Bob.publicKey,
                     masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                     masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
@@ -140,6 +141,7 @@ describe("Remove collateral", () => {
                     cauldronAuthority: cauldron.getCauldronAuthority(),
                     bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                     switchboardDataFeed: TEST_PRICE,
+                    poolOracle: cauldron.getPoolOraclePda(),
                     authority: Bob.publicKey,
                     masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                     masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
@@ -171,6 +173,7 @@ describe("Remove collateral", () => {
                     cauldronAuthority: cauldron.getCauldronAuthority(),
                     bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                     switchboardDataFeed: TEST_PRICE,
+                    poolOracle: cauldron.getPoolOraclePda(),
                     authority: Bob.publicKey,
                     masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                     masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
@@ -202,6 +205,7 @@ describe("Remove collateral", () => {
                     cauldronAuthority: cauldron.getCauldronAuthority(),
                     bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                     switchboardDataFeed: TEST_PRICE,
+                    poolOracle: cauldron.getPoolOraclePda(),
                     authority: Bob.publicKey,
                     masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                     masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
@@ -272,6 +276,7 @@ describe("Remove collateral", () => {
                     cauldronAuthority: cauldron.getCauldronAuthority(),
                     bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                     switchboardDataFeed: TEST_PRICE,
+                    poolOracle: cauldron.getPoolOraclePda(),
                     authority: Bob.publicKey,
                     masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                     masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
@@ -301,6 +306,7 @@ describe("Remove collateral", () => {
                 bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                 cauldronAuthority: cauldron.getCauldronAuthority(),
                 switchboardDataFeed: TEST_PRICE,
+                poolOracle: cauldron.getPoolOraclePda(),
                 authority: Bob.publicKey,
                 masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                 masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
@@ -135,7 +135,7 @@ describe("Repay test flow", () => {
 
 
         // deposited to bentobox from Stan
-        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(200000), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(200000), new BN(0), new BN(0))
             .accounts({
                 fromVault: stanTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
@@ -277,7 +277,7 @@ describe("Repay test flow", () => {
         await cauldron.approveToCauldron(stanMimTokenAccount, borrowerStan);
 
         // deposited to bentobox from Stan
-        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(10), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(10), new BN(0), new BN(0))
             .accounts({
                 fromVault: stanMimTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(mimMint.publicKey),
@@ -360,6 +360,7 @@ describe("Repay test flow", () => {
                     bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                     cauldronAuthority: cauldron.getCauldronAuthority(),
                     switchboardDataFeed: TEST_PRICE,
+                    poolOracle: cauldron.getPoolOraclePda(),
                     authority: borrowerStan.publicKey,
                     masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                     masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
@@ -395,6 +396,7 @@ describe("Repay test flow", () => {
                     bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                     cauldronAuthority: cauldron.getCauldronAuthority(),
                     switchboardDataFeed: TEST_PRICE,
+                    poolOracle: cauldron.getPoolOraclePda(),
                     authority: borrowerStan.publicKey,
                     masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                     masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
@@ -445,6 +447,7 @@ describe("Repay test flow", () => {
                 bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                 cauldronAuthority: cauldron.getCauldronAuthority(),
                 switchboardDataFeed: TEST_PRICE,
+                poolOracle: cauldron.getPoolOraclePda(),
                 authority: borrowerStan.publicKey,
                 masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                 masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
@@ -453,7 +456,7 @@ describe("Repay test flow", () => {
             .rpc();
 
         // deposited to bentobox from Stan
-        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(11), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(11), new BN(0), new BN(0))
             .accounts({
                 fromVault: stanMimTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(mimMint.publicKey),
@@ -532,6 +535,7 @@ describe("Repay test flow", () => {
                 bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                 cauldronAuthority: cauldron.getCauldronAuthority(),
                 switchboardDataFeed: TEST_PRICE,
+                poolOracle: cauldron.getPoolOraclePda(),
                 authority: borrowerStan.publicKey,
                 masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                 masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
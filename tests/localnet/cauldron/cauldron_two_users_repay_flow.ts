@@ -164,7 +164,7 @@ describe("Two users repay test flow", () => {
         await cauldron.approveToCauldron(stanTokenAccount, borrowerStan);
 
         // deposited to bentobox from Stan
-        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(3000000 * collateralDecimal), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(3000000 * collateralDecimal), new BN(0), new BN(0))
             .accounts({
                 fromVault: stanTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
@@ -188,7 +188,7 @@ describe("Two users repay test flow", () => {
         await cauldron.approveToCauldron(danTokenAccount, borrowerDan);
 
         // deposited to bentobox from Dan
-        await cauldronProgram.methods.bentoDeposit(borrowerDan.publicKey, new BN(2000000 * collateralDecimal), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(borrowerDan.publicKey, new BN(2000000 * collateralDecimal), new BN(0), new BN(0))
             .accounts({
                 fromVault: danTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(collateralMint.publicKey),
@@ -375,7 +375,7 @@ describe("Two users repay test flow", () => {
         await cauldron.approveToCauldron(stanMimTokenAccount, borrowerStan);
 
         // deposited to bentobox from Stan
-        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(1010 * mimDecimal), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(borrowerStan.publicKey, new BN(1010 * mimDecimal), new BN(0), new BN(0))
             .accounts({
                 fromVault: stanMimTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(mimMint.publicKey),
@@ -398,7 +398,7 @@ describe("Two users repay test flow", () => {
         await cauldron.approveToCauldron(danMimTokenAccount, borrowerDan);
 
         // deposited to bentobox from Dan
-        await cauldronProgram.methods.bentoDeposit(borrowerDan.publicKey, new BN(505 * mimDecimal), new BN(0))
+        await cauldronProgram.methods.bentoDeposit(borrowerDan.publicKey, new BN(505 * mimDecimal), new BN(0), new BN(0))
             .accounts({
                 fromVault: danMimTokenAccount,
                 bentoboxVault: bentobox.getTotalVaultPda(mimMint.publicKey),
@@ -516,6 +516,7 @@ describe("Two users repay test flow", () => {
                 bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                 cauldronAuthority: cauldron.getCauldronAuthority(),
                 switchboardDataFeed: TEST_PRICE,
+                poolOracle: cauldron.getPoolOraclePda(),
                 authority: borrowerStan.publicKey,
                 masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                 masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
@@ -537,6 +538,7 @@ describe("Two users repay test flow", () => {
                 bentoboxTotalData: bentobox.getTotalDataPda(collateralMint.publicKey),
                 cauldronAuthority: cauldron.getCauldronAuthority(),
                 switchboardDataFeed: TEST_PRICE,
+                poolOracle: cauldron.getPoolOraclePda(),
                 authority: borrowerDan.publicKey,
                 masterContractApproved: cauldron.getCauldronAuthorityApprovedPda(),
                 masterContractWhitelisted: bentobox.getMasterContractWhitelistedPda(cauldron.getCauldronAccount())
@@ -11,7 +11,7 @@ import {
     createMintAccount, requestAirdrop, getErrorInfo, createAndFundUserAccount,
 } from "../../common/common";
 
-import { getBentoBoxAddress, getBentoboxTotalAddress, getBentoboxStrategyDataAddress, getBentoboxTotalVaultAddress } from "../../common/bentobox_pda_helper";
+import { getBentoBoxAddress, getBentoboxTotalAddress, getBentoboxStrategyDataAddress, getBentoboxTotalVaultAddress, getStrategyProgramWhitelistAddress } from "../../common/bentobox_pda_helper";
 import { getStrategyExecutorInfoAddress, getStrategyAddress, getStrategyVaultAddress } from "../../common/mock_strategy_pda_helper";
 
 describe('strategy-mock', () => {
@@ -318,6 +318,24 @@ describe('strategy-mock', () => {
             signers: [bentoboxOwner],
         });
 
+        const [_strategy_program_whitelisted_key, _strategy_program_whitelisted_nonce] =
+            await getStrategyProgramWhitelistAddress(
+                strategy_mock_program.programId,
+                bentobox.publicKey,
+                bentobox_program.programId
+            );
+
+        await bentobox_program.rpc.createStrategyProgramWhitelist(true, {
+            accounts: {
+                strategyProgramWhitelisted: _strategy_program_whitelisted_key,
+                strategyProgram: strategy_mock_program.programId,
+                bentoboxAccount: bentobox.publicKey,
+                authority: bentoboxOwner.publicKey,
+                systemProgram: SystemProgram.programId,
+            },
+            signers: [bentoboxOwner],
+        });
+
         let someVault = await createAndFundUserAccount(
             strategy_mock_owner.publicKey,
             bentoboxOwner,
@@ -326,9 +344,10 @@ describe('strategy-mock', () => {
         );
 
         // set exited = true  
-        await bentobox_program.rpc.setStrategy({
+        await bentobox_program.rpc.queueStrategy({
             accounts: {
                 strategyProgram: strategy_mock_program.programId,
+                strategyProgramWhitelisted: _strategy_program_whitelisted_key,
                 bentoboxProgram: bentobox_program.programId,
                 bentoboxAccount: bentobox.publicKey,
                 strategyAccount: strategy_mock.publicKey,
@@ -352,9 +371,10 @@ describe('strategy-mock', () => {
         let delay = ms => new Promise(resolve => setTimeout(resolve, ms))
         await delay(2000);
 
-        await bentobox_program.rpc.setStrategy({
+        await bentobox_program.rpc.activateStrategy({
             accounts: {
                 strategyProgram: strategy_mock_program.programId,
+                strategyProgramWhitelisted: _strategy_program_whitelisted_key,
                 bentoboxProgram: bentobox_program.programId,
                 bentoboxAccount: bentobox.publicKey,
                 strategyAccount: strategy_mock.publicKey,
@@ -375,9 +395,10 @@ describe('strategy-mock', () => {
             signers: [bentoboxOwner]
         });
 
-        await bentobox_program.rpc.setStrategy({
+        await bentobox_program.rpc.queueStrategy({
             accounts: {
                 strategyProgram: strategy_mock_program.programId,
+                strategyProgramWhitelisted: _strategy_program_whitelisted_key,
                 bentoboxProgram: bentobox_program.programId,
                 bentoboxAccount: bentobox.publicKey,
                 strategyAccount: strategy_mock.publicKey,
@@ -401,9 +422,10 @@ describe('strategy-mock', () => {
         delay = ms => new Promise(resolve => setTimeout(resolve, ms))
         await delay(2000);
 
-        await bentobox_program.rpc.setStrategy({
+        await bentobox_program.rpc.activateStrategy({
             accounts: {
                 strategyProgram: strategy_mock_program.programId,
+                strategyProgramWhitelisted: _strategy_program_whitelisted_key,
                 bentoboxProgram: bentobox_program.programId,
                 bentoboxAccount: bentobox.publicKey,
                 strategyAccount: strategy_mock.publicKey,